@@ -7,7 +7,7 @@ use bevy_ecs::{
 };
 
 use naia_client::{
-    shared::{ChannelIndex, Protocolize, ReplicateSafe},
+    shared::{ChannelIndex, Protocolize, ReplicateSafe, Tick},
     Client as NaiaClient, EntityRef,
 };
 
@@ -96,11 +96,64 @@ impl<'a, P: Protocolize, C: ChannelIndex> Client<'a, P, C> {
         return self.client.entities(&self.world.proxy());
     }
 
+    //// Resources ////
+
+    pub fn has_resource<R: ReplicateSafe<P>>(&self) -> bool {
+        self.client.has_resource::<R, WorldRef>(&self.world.proxy())
+    }
+
+    pub fn resource<R: ReplicateSafe<P>>(&self) -> Option<&R> {
+        self.world.get_resource::<R>()
+    }
+
+    pub fn resource_entity(&self) -> Option<Entity> {
+        self.client.resource_entity()
+    }
+
+    pub fn entity_has_authority(&self, entity: &Entity) -> bool {
+        self.client.entity_has_authority(entity)
+    }
+
+    //// Prediction ////
+
+    pub fn predict_entity(&mut self, confirmed_entity: &Entity, predicted_entity: &Entity) {
+        self.client
+            .predict_entity(confirmed_entity, predicted_entity);
+    }
+
+    pub fn unpredict_entity(&mut self, confirmed_entity: &Entity) -> Option<Entity> {
+        self.client.unpredict_entity(confirmed_entity)
+    }
+
+    pub fn predicted_entity(&self, confirmed_entity: &Entity) -> Option<Entity> {
+        self.client.predicted_entity(confirmed_entity)
+    }
+
+    pub fn record_prediction(&mut self, confirmed_entity: &Entity, tick: Tick) {
+        self.client
+            .record_prediction(&self.world.proxy(), confirmed_entity, tick);
+    }
+
+    pub fn set_prediction_comparer<R: ReplicateSafe<P>>(
+        &mut self,
+        comparer: Option<fn(&R, &R) -> bool>,
+    ) {
+        self.client.set_prediction_comparer::<R>(comparer);
+    }
+
     //// Ticks ////
 
     pub fn client_tick(&self) -> Option<u16> {
         self.client.client_tick()
     }
+
+    pub fn receiving_tick(&self) -> Option<u16> {
+        self.client.receiving_tick()
+    }
+
+    pub fn received_states<R: ReplicateSafe<P>>(&self, entity: &Entity) -> Vec<(Tick, &R)> {
+        self.client.received_states::<R>(entity)
+    }
 }
 
 impl<'a, P: Protocolize, C: ChannelIndex> SystemParam for Client<'a, P, C> {
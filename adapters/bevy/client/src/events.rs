@@ -1,10 +1,18 @@
 use bevy_ecs::entity::Entity;
 
-use naia_client::shared::{ChannelIndex, ProtocolKindType, Protocolize, Tick};
+use naia_client::shared::{ChannelIndex, ProtocolKindType, Protocolize, RejectReason, Tick};
 
+pub struct RejectionEvent(pub RejectReason);
 pub struct SpawnEntityEvent(pub Entity);
 pub struct DespawnEntityEvent(pub Entity);
+pub struct EntityRejectedEvent(pub Entity);
 pub struct InsertComponentEvent<K: ProtocolKindType>(pub Entity, pub K);
 pub struct UpdateComponentEvent<K: ProtocolKindType>(pub Tick, pub Entity, pub K);
 pub struct RemoveComponentEvent<P: Protocolize>(pub Entity, pub P);
+pub struct InsertResourceEvent<K: ProtocolKindType>(pub K);
+pub struct UpdateResourceEvent<K: ProtocolKindType>(pub Tick, pub K);
+pub struct RemoveResourceEvent<P: Protocolize>(pub P);
+pub struct AuthorityGrantedEvent(pub Entity);
+pub struct AuthorityRevokedEvent(pub Entity);
+pub struct RollbackEvent(pub Tick, pub Entity);
 pub struct MessageEvent<P: Protocolize, C: ChannelIndex>(pub C, pub P);
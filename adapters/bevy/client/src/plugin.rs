@@ -14,8 +14,10 @@ use crate::systems::should_receive;
 
 use super::{
     events::{
-        DespawnEntityEvent, InsertComponentEvent, MessageEvent, RemoveComponentEvent,
-        SpawnEntityEvent, UpdateComponentEvent,
+        AuthorityGrantedEvent, AuthorityRevokedEvent, DespawnEntityEvent, EntityRejectedEvent,
+        InsertComponentEvent, InsertResourceEvent, MessageEvent, RejectionEvent,
+        RemoveComponentEvent, RemoveResourceEvent, RollbackEvent, SpawnEntityEvent,
+        UpdateComponentEvent, UpdateResourceEvent,
     },
     resource::ClientResource,
     stage::{PrivateStage, Stage},
@@ -65,11 +67,19 @@ impl<P: Protocolize, C: ChannelIndex> PluginType for Plugin<P, C> {
             .init_resource::<ClientResource>()
             .init_resource::<WorldData<P>>()
             // EVENTS //
+            .add_event::<RejectionEvent>()
             .add_event::<SpawnEntityEvent>()
             .add_event::<DespawnEntityEvent>()
+            .add_event::<EntityRejectedEvent>()
             .add_event::<InsertComponentEvent<P::Kind>>()
             .add_event::<UpdateComponentEvent<P::Kind>>()
             .add_event::<RemoveComponentEvent<P>>()
+            .add_event::<InsertResourceEvent<P::Kind>>()
+            .add_event::<UpdateResourceEvent<P::Kind>>()
+            .add_event::<RemoveResourceEvent<P>>()
+            .add_event::<AuthorityGrantedEvent>()
+            .add_event::<AuthorityRevokedEvent>()
+            .add_event::<RollbackEvent>()
             .add_event::<MessageEvent<P, C>>()
             // STAGES //
             // events //
@@ -14,8 +14,10 @@ use naia_client::{
 use naia_bevy_shared::WorldProxyMut;
 
 use crate::events::{
-    DespawnEntityEvent, InsertComponentEvent, MessageEvent, RemoveComponentEvent, SpawnEntityEvent,
-    UpdateComponentEvent,
+    AuthorityGrantedEvent, AuthorityRevokedEvent, DespawnEntityEvent, EntityRejectedEvent,
+    InsertComponentEvent, InsertResourceEvent, MessageEvent, RejectionEvent, RemoveComponentEvent,
+    RemoveResourceEvent, RollbackEvent, SpawnEntityEvent, UpdateComponentEvent,
+    UpdateResourceEvent,
 };
 
 use super::resource::ClientResource;
@@ -26,12 +28,18 @@ pub fn before_receive_events<P: Protocolize, C: ChannelIndex>(world: &mut World)
             let event_results = client.receive(world.proxy_mut());
 
             unsafe {
+                let mut rejection_event_writer = world
+                    .get_resource_unchecked_mut::<Events<RejectionEvent>>()
+                    .unwrap();
                 let mut spawn_entity_event_writer = world
                     .get_resource_unchecked_mut::<Events<SpawnEntityEvent>>()
                     .unwrap();
                 let mut despawn_entity_event_writer = world
                     .get_resource_unchecked_mut::<Events<DespawnEntityEvent>>()
                     .unwrap();
+                let mut entity_rejected_event_writer = world
+                    .get_resource_unchecked_mut::<Events<EntityRejectedEvent>>()
+                    .unwrap();
                 let mut insert_component_event_writer = world
                     .get_resource_unchecked_mut::<Events<InsertComponentEvent<P::Kind>>>()
                     .unwrap();
@@ -41,9 +49,27 @@ pub fn before_receive_events<P: Protocolize, C: ChannelIndex>(world: &mut World)
                 let mut remove_component_event_writer = world
                     .get_resource_unchecked_mut::<Events<RemoveComponentEvent<P>>>()
                     .unwrap();
+                let mut insert_resource_event_writer = world
+                    .get_resource_unchecked_mut::<Events<InsertResourceEvent<P::Kind>>>()
+                    .unwrap();
+                let mut update_resource_event_writer = world
+                    .get_resource_unchecked_mut::<Events<UpdateResourceEvent<P::Kind>>>()
+                    .unwrap();
+                let mut remove_resource_event_writer = world
+                    .get_resource_unchecked_mut::<Events<RemoveResourceEvent<P>>>()
+                    .unwrap();
                 let mut message_event_writer = world
                     .get_resource_unchecked_mut::<Events<MessageEvent<P, C>>>()
                     .unwrap();
+                let mut authority_granted_event_writer = world
+                    .get_resource_unchecked_mut::<Events<AuthorityGrantedEvent>>()
+                    .unwrap();
+                let mut authority_revoked_event_writer = world
+                    .get_resource_unchecked_mut::<Events<AuthorityRevokedEvent>>()
+                    .unwrap();
+                let mut rollback_event_writer = world
+                    .get_resource_unchecked_mut::<Events<RollbackEvent>>()
+                    .unwrap();
 
                 for event_result in event_results {
                     match event_result {
@@ -55,6 +81,9 @@ pub fn before_receive_events<P: Protocolize, C: ChannelIndex>(world: &mut World)
                             client_resource.disconnector.set();
                             continue;
                         }
+                        Ok(Event::Rejection(_, reason)) => {
+                            rejection_event_writer.send(RejectionEvent(reason));
+                        }
                         Ok(Event::Tick) => {
                             client_resource.ticker.set();
                             continue;
@@ -65,6 +94,9 @@ pub fn before_receive_events<P: Protocolize, C: ChannelIndex>(world: &mut World)
                         Ok(Event::DespawnEntity(entity)) => {
                             despawn_entity_event_writer.send(DespawnEntityEvent(entity));
                         }
+                        Ok(Event::EntityRejected(entity)) => {
+                            entity_rejected_event_writer.send(EntityRejectedEvent(entity));
+                        }
                         Ok(Event::InsertComponent(entity, component)) => {
                             insert_component_event_writer
                                 .send(InsertComponentEvent(entity, component));
@@ -80,6 +112,24 @@ pub fn before_receive_events<P: Protocolize, C: ChannelIndex>(world: &mut World)
                             update_component_event_writer
                                 .send(UpdateComponentEvent(tick, entity, component));
                         }
+                        Ok(Event::InsertResource(resource)) => {
+                            insert_resource_event_writer.send(InsertResourceEvent(resource));
+                        }
+                        Ok(Event::UpdateResource(tick, resource)) => {
+                            update_resource_event_writer.send(UpdateResourceEvent(tick, resource));
+                        }
+                        Ok(Event::RemoveResource(resource)) => {
+                            remove_resource_event_writer.send(RemoveResourceEvent(resource));
+                        }
+                        Ok(Event::AuthorityGranted(entity)) => {
+                            authority_granted_event_writer.send(AuthorityGrantedEvent(entity));
+                        }
+                        Ok(Event::AuthorityRevoked(entity)) => {
+                            authority_revoked_event_writer.send(AuthorityRevokedEvent(entity));
+                        }
+                        Ok(Event::Rollback(tick, entity)) => {
+                            rollback_event_writer.send(RollbackEvent(tick, entity));
+                        }
                         Err(_) => {}
                     }
                 }
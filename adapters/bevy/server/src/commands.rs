@@ -4,7 +4,7 @@ use bevy_ecs::entity::Entity;
 
 use naia_server::{
     shared::{ChannelIndex, Protocolize, Replicate, ReplicateSafe},
-    Server,
+    ClientEntityKey, Server, UserKey,
 };
 
 use naia_bevy_shared::WorldMut;
@@ -84,3 +84,197 @@ impl<P: Protocolize, R: Replicate<P>, C: ChannelIndex> Command<P, C> for RemoveC
             .remove_component::<R>();
     }
 }
+
+//// Accept Client Entity Action ////
+
+pub(crate) struct AcceptClientEntityAction {
+    client_entity_key: ClientEntityKey,
+}
+
+impl AcceptClientEntityAction {
+    pub fn new(client_entity_key: &ClientEntityKey) -> Self {
+        AcceptClientEntityAction {
+            client_entity_key: *client_entity_key,
+        }
+    }
+}
+
+impl<P: Protocolize, C: ChannelIndex> Command<P, C> for AcceptClientEntityAction {
+    fn write(self: Box<Self>, server: &mut Server<P, Entity, C>, world: WorldMut) {
+        server.accept_client_entity_action(world, &self.client_entity_key);
+    }
+}
+
+//// Reject Client Entity Action ////
+
+pub(crate) struct RejectClientEntityAction {
+    client_entity_key: ClientEntityKey,
+}
+
+impl RejectClientEntityAction {
+    pub fn new(client_entity_key: &ClientEntityKey) -> Self {
+        RejectClientEntityAction {
+            client_entity_key: *client_entity_key,
+        }
+    }
+}
+
+impl<P: Protocolize, C: ChannelIndex> Command<P, C> for RejectClientEntityAction {
+    fn write(self: Box<Self>, server: &mut Server<P, Entity, C>, _: WorldMut) {
+        server.reject_client_entity_action(&self.client_entity_key);
+    }
+}
+
+//// Give Authority ////
+
+pub(crate) struct GiveAuthority {
+    entity: Entity,
+    user_key: UserKey,
+}
+
+impl GiveAuthority {
+    pub fn new(entity: &Entity, user_key: &UserKey) -> Self {
+        GiveAuthority {
+            entity: *entity,
+            user_key: *user_key,
+        }
+    }
+}
+
+impl<P: Protocolize, C: ChannelIndex> Command<P, C> for GiveAuthority {
+    fn write(self: Box<Self>, server: &mut Server<P, Entity, C>, world: WorldMut) {
+        server
+            .entity_mut(world, &self.entity)
+            .give_authority(&self.user_key);
+    }
+}
+
+//// Take Authority ////
+
+pub(crate) struct TakeAuthority {
+    entity: Entity,
+}
+
+impl TakeAuthority {
+    pub fn new(entity: &Entity) -> Self {
+        TakeAuthority { entity: *entity }
+    }
+}
+
+impl<P: Protocolize, C: ChannelIndex> Command<P, C> for TakeAuthority {
+    fn write(self: Box<Self>, server: &mut Server<P, Entity, C>, world: WorldMut) {
+        server.entity_mut(world, &self.entity).take_authority();
+    }
+}
+
+//// Set Position ////
+
+pub(crate) struct SetPosition {
+    entity: Entity,
+    x: f32,
+    y: f32,
+}
+
+impl SetPosition {
+    pub fn new(entity: &Entity, x: f32, y: f32) -> Self {
+        SetPosition {
+            entity: *entity,
+            x,
+            y,
+        }
+    }
+}
+
+impl<P: Protocolize, C: ChannelIndex> Command<P, C> for SetPosition {
+    fn write(self: Box<Self>, server: &mut Server<P, Entity, C>, world: WorldMut) {
+        server
+            .entity_mut(world, &self.entity)
+            .set_position(self.x, self.y);
+    }
+}
+
+//// Mark Scope Dirty ////
+
+pub(crate) struct MarkScopeDirty {
+    entity: Entity,
+}
+
+impl MarkScopeDirty {
+    pub fn new(entity: &Entity) -> Self {
+        MarkScopeDirty { entity: *entity }
+    }
+}
+
+impl<P: Protocolize, C: ChannelIndex> Command<P, C> for MarkScopeDirty {
+    fn write(self: Box<Self>, server: &mut Server<P, Entity, C>, world: WorldMut) {
+        server.entity_mut(world, &self.entity).mark_scope_dirty();
+    }
+}
+
+//// Set Global ////
+
+pub(crate) struct SetGlobal {
+    entity: Entity,
+    is_global: bool,
+}
+
+impl SetGlobal {
+    pub fn new(entity: &Entity, is_global: bool) -> Self {
+        SetGlobal {
+            entity: *entity,
+            is_global,
+        }
+    }
+}
+
+impl<P: Protocolize, C: ChannelIndex> Command<P, C> for SetGlobal {
+    fn write(self: Box<Self>, server: &mut Server<P, Entity, C>, world: WorldMut) {
+        server
+            .entity_mut(world, &self.entity)
+            .set_global(self.is_global);
+    }
+}
+
+//// Insert Resource ////
+
+pub(crate) struct InsertResource<P: Protocolize, R: ReplicateSafe<P>> {
+    resource: R,
+    phantom_p: PhantomData<P>,
+}
+
+impl<P: Protocolize, R: ReplicateSafe<P>> InsertResource<P, R> {
+    pub fn new(resource: R) -> Self {
+        InsertResource {
+            resource,
+            phantom_p: PhantomData,
+        }
+    }
+}
+
+impl<P: Protocolize, R: ReplicateSafe<P>, C: ChannelIndex> Command<P, C> for InsertResource<P, R> {
+    fn write(self: Box<Self>, server: &mut Server<P, Entity, C>, world: WorldMut) {
+        server.insert_resource(world, self.resource);
+    }
+}
+
+//// Remove Resource ////
+
+pub(crate) struct RemoveResource<P: Protocolize, R: Replicate<P>> {
+    phantom_p: PhantomData<P>,
+    phantom_r: PhantomData<R>,
+}
+
+impl<P: Protocolize, R: Replicate<P>> RemoveResource<P, R> {
+    pub fn new() -> Self {
+        RemoveResource {
+            phantom_p: PhantomData,
+            phantom_r: PhantomData,
+        }
+    }
+}
+
+impl<P: Protocolize, R: Replicate<P>, C: ChannelIndex> Command<P, C> for RemoveResource<P, R> {
+    fn write(self: Box<Self>, server: &mut Server<P, Entity, C>, world: WorldMut) {
+        server.remove_resource::<R, WorldMut>(world);
+    }
+}
@@ -2,11 +2,14 @@ use bevy_ecs::entity::Entity;
 
 use naia_server::{
     shared::{ChannelIndex, Protocolize, Replicate, ReplicateSafe},
-    RoomKey,
+    RoomKey, UserKey,
 };
 
 use super::{
-    commands::{DespawnEntity, InsertComponent, RemoveComponent},
+    commands::{
+        DespawnEntity, GiveAuthority, InsertComponent, MarkScopeDirty, RemoveComponent, SetGlobal,
+        SetPosition, TakeAuthority,
+    },
     server::Server,
 };
 
@@ -47,6 +50,40 @@ impl<'s, 'world, 'state, P: Protocolize, C: ChannelIndex> EntityMut<'s, 'world,
         self
     }
 
+    // Authority
+
+    pub fn give_authority(&mut self, user_key: &UserKey) -> &mut Self {
+        self.server
+            .queue_command(GiveAuthority::new(&self.entity, user_key));
+        self
+    }
+
+    pub fn take_authority(&mut self) -> &mut Self {
+        self.server.queue_command(TakeAuthority::new(&self.entity));
+        self
+    }
+
+    // Scope
+
+    pub fn set_global(&mut self, is_global: bool) -> &mut Self {
+        self.server
+            .queue_command(SetGlobal::new(&self.entity, is_global));
+        self
+    }
+
+    pub fn mark_scope_dirty(&mut self) -> &mut Self {
+        self.server.queue_command(MarkScopeDirty::new(&self.entity));
+        self
+    }
+
+    // Spatial Scope
+
+    pub fn set_position(&mut self, x: f32, y: f32) -> &mut Self {
+        self.server
+            .queue_command(SetPosition::new(&self.entity, x, y));
+        self
+    }
+
     // Rooms
 
     pub fn enter_room(&mut self, room_key: &RoomKey) -> &mut Self {
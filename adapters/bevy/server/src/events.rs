@@ -1,9 +1,14 @@
 use naia_server::{
-    shared::{ChannelIndex, Protocolize},
-    User, UserKey,
+    shared::{ChannelIndex, ProtocolKindType, Protocolize},
+    ClientEntityKey, User, UserKey,
 };
 
 pub struct AuthorizationEvent<P: Protocolize>(pub UserKey, pub P);
 pub struct ConnectionEvent(pub UserKey);
 pub struct DisconnectionEvent(pub UserKey, pub User);
 pub struct MessageEvent<P: Protocolize, C: ChannelIndex>(pub UserKey, pub C, pub P);
+pub struct SpawnEntityEvent(pub UserKey, pub ClientEntityKey);
+pub struct DespawnEntityEvent(pub UserKey, pub ClientEntityKey);
+pub struct InsertComponentEvent<K: ProtocolKindType>(pub UserKey, pub ClientEntityKey, pub K);
+pub struct RemoveComponentEvent<K: ProtocolKindType>(pub UserKey, pub ClientEntityKey, pub K);
+pub struct UpdateComponentEvent<K: ProtocolKindType>(pub UserKey, pub ClientEntityKey, pub K);
@@ -11,7 +11,11 @@ use naia_server::{
 use naia_bevy_shared::WorldData;
 
 use super::{
-    events::{AuthorizationEvent, ConnectionEvent, DisconnectionEvent, MessageEvent},
+    events::{
+        AuthorizationEvent, ConnectionEvent, DespawnEntityEvent, DisconnectionEvent,
+        InsertComponentEvent, MessageEvent, RemoveComponentEvent, SpawnEntityEvent,
+        UpdateComponentEvent,
+    },
     resource::ServerResource,
     stage::{PrivateStage, Stage},
     systems::{before_receive_events, finish_tick, should_receive, should_tick},
@@ -64,6 +68,11 @@ impl<P: Protocolize, C: ChannelIndex> PluginType for Plugin<P, C> {
             .add_event::<ConnectionEvent>()
             .add_event::<DisconnectionEvent>()
             .add_event::<MessageEvent<P, C>>()
+            .add_event::<SpawnEntityEvent>()
+            .add_event::<DespawnEntityEvent>()
+            .add_event::<InsertComponentEvent<P::Kind>>()
+            .add_event::<RemoveComponentEvent<P::Kind>>()
+            .add_event::<UpdateComponentEvent<P::Kind>>()
             // STAGES //
             .add_stage_before(
                 CoreStage::PreUpdate,
@@ -7,15 +7,21 @@ use bevy_ecs::{
 };
 
 use naia_server::{
-    shared::{ChannelIndex, EntityHandleConverter, Protocolize, ReplicateSafe},
-    EntityRef, Event, NaiaServerError, RoomKey, RoomMut, RoomRef, Server as NaiaServer,
-    ServerAddrs, UserKey, UserMut, UserRef, UserScopeMut,
+    shared::{ChannelIndex, EntityHandleConverter, Protocolize, Replicate, ReplicateSafe},
+    ClientEntityKey, EntityRef, Event, NaiaServerError, RoomKey, RoomMut, RoomRef,
+    Server as NaiaServer, ServerAddrs, UserKey, UserMut, UserRef, UserScopeMut,
 };
 
 use crate::shared::EntityHandle;
 use naia_bevy_shared::{WorldProxy, WorldRef};
 
-use super::{commands::Command, entity_mut::EntityMut, state::State};
+use super::{
+    commands::{
+        AcceptClientEntityAction, Command, InsertResource, RejectClientEntityAction, RemoveResource,
+    },
+    entity_mut::EntityMut,
+    state::State,
+};
 
 // Server
 
@@ -74,10 +80,18 @@ impl<'world, 'state, P: Protocolize, C: ChannelIndex> Server<'world, 'state, P,
 
     //// Updates ////
 
+    #[deprecated(
+        note = "use `pending_scope_checks`, which returns only the Scope Sets that have changed"
+    )]
+    #[allow(deprecated)]
     pub fn scope_checks(&self) -> Vec<(RoomKey, UserKey, Entity)> {
         self.server.scope_checks()
     }
 
+    pub fn pending_scope_checks(&mut self) -> Vec<(RoomKey, UserKey, Entity)> {
+        self.server.pending_scope_checks()
+    }
+
     pub fn send_all_updates(&mut self) {
         return self.server.send_all_updates(self.world.proxy());
     }
@@ -102,6 +116,46 @@ impl<'world, 'state, P: Protocolize, C: ChannelIndex> Server<'world, 'state, P,
         return self.server.entities(self.world.proxy());
     }
 
+    //// Resources ////
+
+    pub fn insert_resource<R: ReplicateSafe<P>>(&mut self, resource: R) {
+        self.queue_command(InsertResource::new(resource));
+    }
+
+    pub fn remove_resource<R: Replicate<P>>(&mut self) {
+        self.queue_command(RemoveResource::<P, R>::new());
+    }
+
+    pub fn has_resource<R: ReplicateSafe<P>>(&self) -> bool {
+        self.server.has_resource::<R, WorldRef>(self.world.proxy())
+    }
+
+    pub fn resource<R: ReplicateSafe<P>>(&self) -> Option<&R> {
+        self.world.get_resource::<R>()
+    }
+
+    pub fn resource_entity(&self) -> Option<Entity> {
+        self.server.resource_entity()
+    }
+
+    //// Client-owned Entities ////
+
+    pub fn accept_client_entity_action(&mut self, client_entity_key: &ClientEntityKey) {
+        self.queue_command(AcceptClientEntityAction::new(client_entity_key));
+    }
+
+    pub fn reject_client_entity_action(&mut self, client_entity_key: &ClientEntityKey) {
+        self.queue_command(RejectClientEntityAction::new(client_entity_key));
+    }
+
+    pub fn client_entity(&self, client_entity_key: &ClientEntityKey) -> Option<Entity> {
+        self.server.client_entity(client_entity_key)
+    }
+
+    pub fn client_entity_owner(&self, client_entity_key: &ClientEntityKey) -> Option<UserKey> {
+        self.server.client_entity_owner(client_entity_key)
+    }
+
     //// Users ////
 
     pub fn user_exists(&self, user_key: &UserKey) -> bool {
@@ -164,6 +218,28 @@ impl<'world, 'state, P: Protocolize, C: ChannelIndex> Server<'world, 'state, P,
         self.server.server_tick()
     }
 
+    //// Lag Compensation ////
+
+    pub fn record_component_history<R: ReplicateSafe<P>>(&mut self) {
+        self.server.record_component_history::<R>();
+    }
+
+    pub fn component_at<R: Replicate<P>>(&self, entity: &Entity, tick: u16) -> Option<R> {
+        self.server.component_at::<R>(entity, tick)
+    }
+
+    pub fn rewind_component<R: Replicate<P>>(
+        &self,
+        user_key: &UserKey,
+        entity: &Entity,
+    ) -> Option<R> {
+        self.server.rewind_component::<R>(user_key, entity)
+    }
+
+    pub fn user_view_tick(&self, user_key: &UserKey) -> Option<u16> {
+        self.server.user_view_tick(user_key)
+    }
+
     // Crate-public methods
 
     pub(crate) fn queue_command<COMMAND: Command<P, C>>(&mut self, command: COMMAND) {
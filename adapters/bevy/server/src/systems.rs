@@ -10,8 +10,14 @@ use naia_server::{
     Event, Server,
 };
 
+use naia_bevy_shared::WorldProxyMut;
+
 use super::{
-    events::{AuthorizationEvent, ConnectionEvent, DisconnectionEvent, MessageEvent},
+    events::{
+        AuthorizationEvent, ConnectionEvent, DespawnEntityEvent, DisconnectionEvent,
+        InsertComponentEvent, MessageEvent, RemoveComponentEvent, SpawnEntityEvent,
+        UpdateComponentEvent,
+    },
     resource::ServerResource,
 };
 
@@ -20,6 +26,9 @@ pub fn before_receive_events<P: Protocolize, C: ChannelIndex>(world: &mut World)
         world.resource_scope(|world, mut server_resource: Mut<ServerResource>| {
             let event_results = server.receive();
 
+            // apply changes made by Users with authority over an Entity
+            server.apply_authority_updates(world.proxy_mut());
+
             unsafe {
                 let mut authorize_event_writer = world
                     .get_resource_unchecked_mut::<Events<AuthorizationEvent<P>>>()
@@ -33,6 +42,21 @@ pub fn before_receive_events<P: Protocolize, C: ChannelIndex>(world: &mut World)
                 let mut message_event_writer = world
                     .get_resource_unchecked_mut::<Events<MessageEvent<P, C>>>()
                     .unwrap();
+                let mut spawn_entity_event_writer = world
+                    .get_resource_unchecked_mut::<Events<SpawnEntityEvent>>()
+                    .unwrap();
+                let mut despawn_entity_event_writer = world
+                    .get_resource_unchecked_mut::<Events<DespawnEntityEvent>>()
+                    .unwrap();
+                let mut insert_component_event_writer = world
+                    .get_resource_unchecked_mut::<Events<InsertComponentEvent<P::Kind>>>()
+                    .unwrap();
+                let mut remove_component_event_writer = world
+                    .get_resource_unchecked_mut::<Events<RemoveComponentEvent<P::Kind>>>()
+                    .unwrap();
+                let mut update_component_event_writer = world
+                    .get_resource_unchecked_mut::<Events<UpdateComponentEvent<P::Kind>>>()
+                    .unwrap();
 
                 for event_result in event_results {
                     match event_result {
@@ -52,6 +76,35 @@ pub fn before_receive_events<P: Protocolize, C: ChannelIndex>(world: &mut World)
                         Ok(Event::Message(user_key, channel, message)) => {
                             message_event_writer.send(MessageEvent(user_key, channel, message));
                         }
+                        Ok(Event::SpawnEntity(user_key, client_entity_key)) => {
+                            spawn_entity_event_writer
+                                .send(SpawnEntityEvent(user_key, client_entity_key));
+                        }
+                        Ok(Event::DespawnEntity(user_key, client_entity_key)) => {
+                            despawn_entity_event_writer
+                                .send(DespawnEntityEvent(user_key, client_entity_key));
+                        }
+                        Ok(Event::InsertComponent(user_key, client_entity_key, component_kind)) => {
+                            insert_component_event_writer.send(InsertComponentEvent(
+                                user_key,
+                                client_entity_key,
+                                component_kind,
+                            ));
+                        }
+                        Ok(Event::RemoveComponent(user_key, client_entity_key, component_kind)) => {
+                            remove_component_event_writer.send(RemoveComponentEvent(
+                                user_key,
+                                client_entity_key,
+                                component_kind,
+                            ));
+                        }
+                        Ok(Event::UpdateComponent(user_key, client_entity_key, component_kind)) => {
+                            update_component_event_writer.send(UpdateComponentEvent(
+                                user_key,
+                                client_entity_key,
+                                component_kind,
+                            ));
+                        }
                         Err(_) => {}
                     }
                 }
@@ -4,7 +4,10 @@ use bevy_ecs::{entity::Entity, world::World};
 
 use naia_shared::{Protocolize, ReplicaDynMutWrapper, ReplicaDynRefWrapper, ReplicateSafe};
 
-use super::component_ref::{ComponentDynMut, ComponentDynRef};
+use super::{
+    component_ref::{ComponentDynMut, ComponentDynRef},
+    resource_entity::is_resource_entity,
+};
 
 pub trait ComponentAccess<P: Protocolize>: Send + Sync {
     fn component<'w>(
@@ -47,7 +50,12 @@ impl<P: Protocolize, R: ReplicateSafe<P>> ComponentAccess<P> for ComponentAccess
         world: &'w World,
         entity: &Entity,
     ) -> Option<ReplicaDynRefWrapper<'w, P>> {
-        if let Some(component_ref) = world.get::<R>(*entity) {
+        let component_ref = if is_resource_entity(world, entity) {
+            world.get_resource::<R>()
+        } else {
+            world.get::<R>(*entity)
+        };
+        if let Some(component_ref) = component_ref {
             let wrapper = ComponentDynRef(component_ref);
             let component_dyn_ref = ReplicaDynRefWrapper::new(wrapper);
             return Some(component_dyn_ref);
@@ -60,7 +68,12 @@ impl<P: Protocolize, R: ReplicateSafe<P>> ComponentAccess<P> for ComponentAccess
         world: &'w mut World,
         entity: &Entity,
     ) -> Option<ReplicaDynMutWrapper<'w, P>> {
-        if let Some(component_mut) = world.get_mut::<R>(*entity) {
+        let component_mut = if is_resource_entity(world, entity) {
+            world.get_resource_mut::<R>()
+        } else {
+            world.get_mut::<R>(*entity)
+        };
+        if let Some(component_mut) = component_mut {
             let wrapper = ComponentDynMut(component_mut);
             let component_dyn_mut = ReplicaDynMutWrapper::new(wrapper);
             return Some(component_dyn_mut);
@@ -69,6 +82,9 @@ impl<P: Protocolize, R: ReplicateSafe<P>> ComponentAccess<P> for ComponentAccess
     }
 
     fn remove_component(&self, world: &mut World, entity: &Entity) -> Option<P> {
+        if is_resource_entity(world, entity) {
+            return world.remove_resource::<R>().map(|v| v.into_protocol());
+        }
         return world
             .entity_mut(*entity)
             .remove::<R>()
@@ -1,10 +1,12 @@
 mod component_access;
 mod component_ref;
 mod flag;
+mod resource_entity;
 mod world_data;
 mod world_proxy;
 
 pub use component_access::{ComponentAccess, ComponentAccessor};
 pub use flag::Flag;
+pub use resource_entity::ResourceEntity;
 pub use world_data::WorldData;
 pub use world_proxy::{WorldMut, WorldProxy, WorldProxyMut, WorldRef};
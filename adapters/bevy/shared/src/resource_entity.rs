@@ -0,0 +1,11 @@
+use bevy_ecs::{component::Component, entity::Entity, world::World};
+
+/// Marks the Entity whose Components are the Server's Resources. Those
+/// Components are stored as Bevy Resources instead, so they are read with
+/// `Res<R>` rather than matched by queries
+#[derive(Component)]
+pub struct ResourceEntity;
+
+pub(crate) fn is_resource_entity(world: &World, entity: &Entity) -> bool {
+    world.get::<ResourceEntity>(*entity).is_some()
+}
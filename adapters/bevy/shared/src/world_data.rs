@@ -64,6 +64,10 @@ impl<P: Protocolize> WorldData<P> {
         None
     }
 
+    pub(crate) fn kinds(&self) -> Vec<P::Kind> {
+        self.kind_to_accessor_map.keys().copied().collect()
+    }
+
     pub(crate) fn has_kind(&self, component_kind: &P::Kind) -> bool {
         self.kind_to_accessor_map.contains_key(component_kind)
     }
@@ -4,13 +4,14 @@ use bevy_ecs::{
 };
 
 use naia_shared::{
-    ComponentUpdate, NetEntityHandleConverter, ProtocolInserter, ProtocolKindType, Protocolize,
-    ReplicaDynRefWrapper, ReplicaMutWrapper, ReplicaRefWrapper, ReplicateSafe, WorldMutType,
-    WorldRefType,
+    ComponentUpdate, NetEntityHandleConverter, PropertyMutator, ProtocolInserter, ProtocolKindType,
+    Protocolize, ReplicaDynRefWrapper, ReplicaMutWrapper, ReplicaRefWrapper, ReplicateSafe,
+    WorldMutType, WorldRefType,
 };
 
 use super::{
     component_ref::{ComponentMut, ComponentRef},
+    resource_entity::{is_resource_entity, ResourceEntity},
     world_data::WorldData,
 };
 
@@ -132,6 +133,12 @@ impl<'w, P: Protocolize> WorldMutType<P, Entity> for WorldMut<'w> {
         entity
     }
 
+    fn spawn_resource_entity(&mut self) -> Entity {
+        // left out of WorldData's entities, & its Components are stored as
+        // Resources, see `ResourceEntity`
+        self.world.spawn().insert(ResourceEntity).id()
+    }
+
     fn duplicate_entity(&mut self, entity: &Entity) -> Entity {
         let new_entity = WorldMutType::<P, Entity>::spawn_entity(self);
 
@@ -160,6 +167,15 @@ impl<'w, P: Protocolize> WorldMutType<P, Entity> for WorldMut<'w> {
     }
 
     fn component_kinds(&mut self, entity: &Entity) -> Vec<P::Kind> {
+        if is_resource_entity(self.world, entity) {
+            let world_data = world_data::<P>(self.world);
+            return world_data
+                .kinds()
+                .into_iter()
+                .filter(|kind| has_component_of_kind::<P>(self.world, entity, kind))
+                .collect();
+        }
+
         let mut kinds = Vec::new();
 
         let components = self.world.components();
@@ -183,7 +199,12 @@ impl<'w, P: Protocolize> WorldMutType<P, Entity> for WorldMut<'w> {
         &mut self,
         entity: &Entity,
     ) -> Option<ReplicaMutWrapper<P, R>> {
-        if let Some(bevy_mut) = self.world.get_mut::<R>(*entity) {
+        let bevy_mut = if is_resource_entity(self.world, entity) {
+            self.world.get_resource_mut::<R>()
+        } else {
+            self.world.get_mut::<R>(*entity)
+        };
+        if let Some(bevy_mut) = bevy_mut {
             let wrapper = ComponentMut(bevy_mut);
             let component_mut = ReplicaMutWrapper::new(wrapper);
             return Some(component_mut);
@@ -208,6 +229,22 @@ impl<'w, P: Protocolize> WorldMutType<P, Entity> for WorldMut<'w> {
             });
     }
 
+    fn component_set_mutator(
+        &mut self,
+        entity: &Entity,
+        component_kind: &P::Kind,
+        mutator: &PropertyMutator,
+    ) {
+        self.world
+            .resource_scope(|world: &mut World, data: Mut<WorldData<P>>| {
+                if let Some(accessor) = data.component_access(component_kind) {
+                    if let Some(mut component) = accessor.component_mut(world, entity) {
+                        component.set_mutator(mutator);
+                    }
+                }
+            });
+    }
+
     fn mirror_entities(&mut self, new_entity: &Entity, old_entity: &Entity) {
         for component_kind in WorldMutType::<P, Entity>::component_kinds(self, old_entity) {
             WorldMutType::<P, Entity>::mirror_components(
@@ -243,10 +280,17 @@ impl<'w, P: Protocolize> WorldMutType<P, Entity> for WorldMut<'w> {
         }
 
         // insert into ecs
-        self.world.entity_mut(*entity).insert(component_ref);
+        if is_resource_entity(self.world, entity) {
+            self.world.insert_resource(component_ref);
+        } else {
+            self.world.entity_mut(*entity).insert(component_ref);
+        }
     }
 
     fn remove_component<R: ReplicateSafe<P>>(&mut self, entity: &Entity) -> Option<R> {
+        if is_resource_entity(self.world, entity) {
+            return self.world.remove_resource::<R>();
+        }
         return self.world.entity_mut(*entity).remove::<R>();
     }
 
@@ -280,6 +324,9 @@ fn entities<P: Protocolize>(world: &World) -> Vec<Entity> {
 }
 
 fn has_component<P: Protocolize, R: ReplicateSafe<P>>(world: &World, entity: &Entity) -> bool {
+    if is_resource_entity(world, entity) {
+        return world.get_resource::<R>().is_some();
+    }
     return world.get::<R>(*entity).is_some();
 }
 
@@ -288,6 +335,9 @@ fn has_component_of_kind<P: Protocolize>(
     entity: &Entity,
     component_kind: &P::Kind,
 ) -> bool {
+    if is_resource_entity(world, entity) {
+        return component_of_kind::<P>(world, entity, component_kind).is_some();
+    }
     return world
         .entity(*entity)
         .contains_type_id(component_kind.to_type_id());
@@ -297,7 +347,12 @@ fn component<'a, P: Protocolize, R: ReplicateSafe<P>>(
     world: &'a World,
     entity: &Entity,
 ) -> Option<ReplicaRefWrapper<'a, P, R>> {
-    if let Some(bevy_ref) = world.get::<R>(*entity) {
+    let bevy_ref = if is_resource_entity(world, entity) {
+        world.get_resource::<R>()
+    } else {
+        world.get::<R>(*entity)
+    };
+    if let Some(bevy_ref) = bevy_ref {
         let wrapper = ComponentRef(bevy_ref);
         let component_ref = ReplicaRefWrapper::new(wrapper);
         return Some(component_ref);
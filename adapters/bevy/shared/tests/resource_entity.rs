@@ -0,0 +1,116 @@
+mod some_protocol {
+    use super::score::Score;
+    use naia_shared::Protocolize;
+
+    #[derive(Protocolize)]
+    pub enum SomeProtocol {
+        Score(Score),
+    }
+}
+
+mod score {
+    use bevy_ecs::prelude::Component;
+
+    use naia_shared::{Property, Replicate};
+
+    #[derive(Component, Replicate)]
+    #[protocol_path = "crate::some_protocol::SomeProtocol"]
+    pub struct Score {
+        pub points: Property<u32>,
+    }
+}
+
+use bevy_ecs::{entity::Entity, world::World};
+
+use naia_bevy_shared::{WorldData, WorldProxyMut};
+use naia_shared::{Protocolize, WorldMutType, WorldRefType};
+
+use score::Score;
+use some_protocol::SomeProtocol;
+
+fn new_world() -> World {
+    let mut world = World::new();
+    world.init_resource::<WorldData<SomeProtocol>>();
+    world
+}
+
+// Spawns an Entity with a Score, along with the Resource Entity holding
+// another, returning both
+fn spawn_scores<W: WorldMutType<SomeProtocol, Entity>>(mut world: W) -> (Entity, Entity) {
+    let entity = world.spawn_entity();
+    world.insert_component(&entity, Score::new_complete(1));
+
+    let resource_entity = world.spawn_resource_entity();
+    world.insert_component(&resource_entity, Score::new_complete(2));
+
+    (entity, resource_entity)
+}
+
+fn resource_points(world: &World) -> Option<u32> {
+    world.get_resource::<Score>().map(|score| *score.points)
+}
+
+#[test]
+fn resource_entity_components_are_bevy_resources() {
+    let mut world = new_world();
+    let (entity, resource_entity) = spawn_scores(world.proxy_mut());
+
+    assert_eq!(resource_points(&world), Some(2));
+
+    // queries only match the Entity
+    let queried: Vec<Entity> = world
+        .query::<(Entity, &Score)>()
+        .iter(&world)
+        .map(|(entity, _)| entity)
+        .collect();
+    assert_eq!(queried, vec![entity]);
+
+    let mut proxy = world.proxy_mut();
+    let score_kind = SomeProtocol::kind_of::<Score>();
+    assert!(WorldRefType::<SomeProtocol, Entity>::has_component::<Score>(&proxy, &resource_entity));
+    assert!(WorldRefType::<SomeProtocol, Entity>::has_component_of_kind(
+        &proxy,
+        &resource_entity,
+        &score_kind
+    ));
+    assert!(
+        WorldMutType::<SomeProtocol, Entity>::component_kinds(&mut proxy, &resource_entity)
+            == vec![score_kind]
+    );
+}
+
+#[test]
+fn resource_entity_is_left_out_of_entities() {
+    let mut world = new_world();
+    let (entity, _) = spawn_scores(world.proxy_mut());
+
+    let proxy = world.proxy_mut();
+    assert_eq!(
+        WorldRefType::<SomeProtocol, Entity>::entities(&proxy),
+        vec![entity]
+    );
+}
+
+#[test]
+fn resource_entity_components_are_changed_and_removed_as_resources() {
+    let mut world = new_world();
+    let (_, resource_entity) = spawn_scores(world.proxy_mut());
+
+    {
+        let mut proxy = world.proxy_mut();
+        *WorldMutType::<SomeProtocol, Entity>::component_mut::<Score>(
+            &mut proxy,
+            &resource_entity,
+        )
+        .unwrap()
+        .points = 5;
+    }
+    assert_eq!(resource_points(&world), Some(5));
+
+    let removed = WorldMutType::<SomeProtocol, Entity>::remove_component::<Score>(
+        &mut world.proxy_mut(),
+        &resource_entity,
+    );
+    assert_eq!(removed.map(|score| *score.points), Some(5));
+    assert_eq!(resource_points(&world), None);
+}
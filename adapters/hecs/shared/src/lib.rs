@@ -1,9 +1,11 @@
 mod component_access;
 mod component_ref;
+mod resource_entity;
 mod world_data;
 mod world_proxy;
 mod world_wrapper;
 
+pub use resource_entity::ResourceEntity;
 pub use world_data::WorldData;
 pub use world_proxy::{WorldProxy, WorldProxyMut};
 pub use world_wrapper::WorldWrapper;
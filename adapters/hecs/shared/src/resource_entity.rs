@@ -0,0 +1,4 @@
+/// Marks the Entity whose Components are the Server's Resources. hecs has no
+/// Resources of its own, so queries for a Resource's type still match this
+/// Entity unless they are filtered with `hecs::Without<ResourceEntity, _>`
+pub struct ResourceEntity;
@@ -1,12 +1,14 @@
 use hecs::{Entity, World};
 
 use naia_shared::{
-    ComponentUpdate, NetEntityHandleConverter, ProtocolInserter, Protocolize, ReplicaDynRefWrapper,
-    ReplicaMutWrapper, ReplicaRefWrapper, Replicate, ReplicateSafe, WorldMutType, WorldRefType,
+    ComponentUpdate, NetEntityHandleConverter, PropertyMutator, ProtocolInserter, Protocolize,
+    ReplicaDynRefWrapper, ReplicaMutWrapper, ReplicaRefWrapper, Replicate, ReplicateSafe,
+    WorldMutType, WorldRefType,
 };
 
 use super::{
     component_ref::{ComponentMut, ComponentRef},
+    resource_entity::ResourceEntity,
     world_data::WorldData,
 };
 
@@ -131,6 +133,10 @@ impl<'w, 'd, P: Protocolize> WorldMutType<P, Entity> for WorldMut<'w, 'd, P> {
         self.world.spawn(())
     }
 
+    fn spawn_resource_entity(&mut self) -> Entity {
+        self.world.spawn((ResourceEntity,))
+    }
+
     fn duplicate_entity(&mut self, entity: &Entity) -> Entity {
         let new_entity = WorldMutType::<P, Entity>::spawn_entity(self);
 
@@ -197,6 +203,19 @@ impl<'w, 'd, P: Protocolize> WorldMutType<P, Entity> for WorldMut<'w, 'd, P> {
         }
     }
 
+    fn component_set_mutator(
+        &mut self,
+        entity: &Entity,
+        component_kind: &P::Kind,
+        mutator: &PropertyMutator,
+    ) {
+        if let Some(access) = self.world_data.component_access(component_kind) {
+            if let Some(mut component) = access.component_mut(self.world, entity) {
+                component.set_mutator(mutator);
+            }
+        }
+    }
+
     fn mirror_entities(&mut self, new_entity: &Entity, old_entity: &Entity) {
         for component_kind in WorldMutType::<P, Entity>::component_kinds(self, old_entity) {
             WorldMutType::<P, Entity>::mirror_components(
@@ -259,7 +278,9 @@ fn entities(world: &World) -> Vec<Entity> {
     let mut output = Vec::new();
 
     for entity in world.iter() {
-        output.push(entity.entity());
+        if !entity.has::<ResourceEntity>() {
+            output.push(entity.entity());
+        }
     }
 
     output
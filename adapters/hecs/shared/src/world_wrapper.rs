@@ -6,13 +6,14 @@ use std::{
 use hecs::{Entity, World};
 
 use naia_shared::{
-    ComponentUpdate, NetEntityHandleConverter, ProtocolInserter, Protocolize, ReplicaDynRefWrapper,
-    ReplicaMutWrapper, ReplicaRefWrapper, Replicate, ReplicateSafe, WorldMutType, WorldRefType,
+    ComponentUpdate, NetEntityHandleConverter, PropertyMutator, ProtocolInserter, Protocolize,
+    ReplicaDynRefWrapper, ReplicaMutWrapper, ReplicaRefWrapper, Replicate, ReplicateSafe,
+    WorldMutType, WorldRefType,
 };
 
 use crate::{
     component_ref::{ComponentMut, ComponentRef},
-    WorldData,
+    ResourceEntity, WorldData,
 };
 
 #[derive(Default)]
@@ -116,6 +117,10 @@ impl<P: Protocolize> WorldMutType<P, Entity> for &mut WorldWrapper<P> {
         self.inner.spawn(())
     }
 
+    fn spawn_resource_entity(&mut self) -> Entity {
+        self.inner.spawn((ResourceEntity,))
+    }
+
     fn duplicate_entity(&mut self, entity: &Entity) -> Entity {
         let new_entity = WorldMutType::<P, Entity>::spawn_entity(self);
 
@@ -182,6 +187,19 @@ impl<P: Protocolize> WorldMutType<P, Entity> for &mut WorldWrapper<P> {
         }
     }
 
+    fn component_set_mutator(
+        &mut self,
+        entity: &Entity,
+        component_kind: &P::Kind,
+        mutator: &PropertyMutator,
+    ) {
+        if let Some(access) = self.data.component_access(component_kind) {
+            if let Some(mut component) = access.component_mut(&mut self.inner, entity) {
+                component.set_mutator(mutator);
+            }
+        }
+    }
+
     fn mirror_entities(&mut self, new_entity: &Entity, old_entity: &Entity) {
         for component_kind in WorldMutType::<P, Entity>::component_kinds(self, old_entity) {
             WorldMutType::<P, Entity>::mirror_components(
@@ -245,7 +263,9 @@ fn entities(world: &World) -> Vec<Entity> {
     let mut output = Vec::new();
 
     for entity in world.iter() {
-        output.push(entity.entity());
+        if !entity.has::<ResourceEntity>() {
+            output.push(entity.entity());
+        }
     }
 
     output
@@ -1,17 +1,32 @@
-use std::{collections::VecDeque, hash::Hash, marker::PhantomData, net::SocketAddr};
+use std::{
+    collections::{HashMap, VecDeque},
+    hash::Hash,
+    marker::PhantomData,
+    net::SocketAddr,
+};
 
 use naia_client_socket::Socket;
 
 pub use naia_shared::{
-    serde::{BitReader, BitWriter, Serde},
+    serde::{BitReader, BitWriter, Serde, SerdeErr},
     ChannelIndex, ConnectionConfig, EntityHandle, EntityHandleConverter, PacketType, PingConfig,
-    PingIndex, ProtocolKindType, Protocolize, ReplicateSafe, SharedConfig, SocketConfig,
-    StandardHeader, Tick, Timer, Timestamp, WorldMutType, WorldRefType,
+    PingIndex, PropertyMutator, ProtocolIo, ProtocolKindType, Protocolize, ReplicaRefWrapper,
+    Replicate, ReplicateSafe, SharedConfig, SocketConfig, StandardHeader, Tick, Timer, Timestamp,
+    WorldMutType, WorldRefType,
 };
 
 use crate::{
-    connection::{connection::Connection, handshake_manager::HandshakeManager, io::Io},
-    protocol::entity_ref::EntityRef,
+    command_history::CommandHistory,
+    connection::{
+        connection::Connection,
+        handshake_manager::{HandshakeManager, HandshakeResult},
+        io::Io,
+    },
+    protocol::{
+        entity_ref::{EntityMut, EntityRef},
+        mut_channel::MutChannel,
+        prediction_manager::PredictionComparers,
+    },
     tick::tick_manager::TickManager,
 };
 
@@ -31,6 +46,10 @@ pub struct Client<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> {
     incoming_events: VecDeque<Result<Event<P, E, C>, NaiaClientError>>,
     // Ticks
     tick_manager: Option<TickManager>,
+    // Malformed packets which have been dropped
+    invalid_packet_count: u64,
+    // Prediction
+    prediction_comparers: PredictionComparers<P>,
     // Phantom
     phantom_k: PhantomData<E>,
 }
@@ -38,7 +57,10 @@ pub struct Client<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> {
 impl<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> Client<P, E, C> {
     /// Create a new Client
     pub fn new(client_config: &ClientConfig, shared_config: &SharedConfig<C>) -> Self {
-        let handshake_manager = HandshakeManager::new(client_config.send_handshake_interval);
+        let handshake_manager = HandshakeManager::new(
+            client_config.send_handshake_interval,
+            shared_config.fingerprint::<P>(),
+        );
 
         let tick_manager = shared_config
             .tick_interval
@@ -59,6 +81,9 @@ impl<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> Client<P, E, C> {
             incoming_events: VecDeque::new(),
             // Ticks
             tick_manager,
+            invalid_packet_count: 0,
+            // Prediction
+            prediction_comparers: HashMap::new(),
             // Phantom
             phantom_k: PhantomData,
         }
@@ -138,6 +163,9 @@ impl<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> Client<P, E, C> {
         if let Some(server_connection) = self.server_connection.as_mut() {
             let mut did_tick = false;
 
+            // the previous events have been handled
+            server_connection.received_states.clear();
+
             // update current tick
             if let Some(tick_manager) = &mut self.tick_manager {
                 if tick_manager.recv_client_tick() {
@@ -145,16 +173,18 @@ impl<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> Client<P, E, C> {
 
                     // apply updates on tick boundary
                     let receiving_tick = tick_manager.client_receiving_tick();
-                    server_connection.process_buffered_packets(
+                    self.invalid_packet_count += server_connection.process_buffered_packets(
                         &mut world,
                         receiving_tick,
+                        &self.prediction_comparers,
                         &mut self.incoming_events,
                     );
                 }
             } else {
-                server_connection.process_buffered_packets(
+                self.invalid_packet_count += server_connection.process_buffered_packets(
                     &mut world,
                     0,
+                    &self.prediction_comparers,
                     &mut self.incoming_events,
                 );
             }
@@ -167,7 +197,7 @@ impl<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> Client<P, E, C> {
             }
 
             // send outgoing packets
-            server_connection.send_outgoing_packets(&mut self.io, &self.tick_manager);
+            server_connection.send_outgoing_packets(&mut self.io, &world, &self.tick_manager);
 
             // tick event
             if did_tick {
@@ -205,10 +235,12 @@ impl<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> Client<P, E, C> {
                 );
             }
         } else if let Some(connection) = &mut self.server_connection {
-            connection
-                .base
-                .message_manager
-                .send_message(channel, message.protocol_copy());
+            let channel_writer = ProtocolIo::new(&connection.entity_manager);
+            connection.base.message_manager.send_message(
+                &channel_writer,
+                channel,
+                message.protocol_copy(),
+            );
         }
     }
 
@@ -238,23 +270,212 @@ impl<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> Client<P, E, C> {
         EntityRef::new(world, entity)
     }
 
-    // /// Retrieves an EntityMut that exposes read and write operations for the
-    // /// Entity.
-    // /// Panics if the Entity does not exist.
-    // pub fn entity_mut<W: WorldMutType<P, E>>(
-    //     &mut self,
-    //     world: W,
-    //     entity: &E,
-    // ) -> EntityMut<P, E, W> {
-    //     if world.has_entity(entity) {
-    //         return EntityMut::new(world, &entity);
-    //     }
-    //     panic!("No Entity exists for given Key!");
-    // }
+    /// Creates a new Entity owned by the Client, which will be replicated to
+    /// the Server, and returns an EntityMut which can be used for further
+    /// operations on the Entity.
+    /// The Server may accept or reject the Entity, and each change made to it.
+    /// Panics if the Client is not connected.
+    pub fn spawn_entity<W: WorldMutType<P, E>>(
+        &mut self,
+        mut world: W,
+    ) -> EntityMut<'_, P, E, W, C> {
+        let connection = self
+            .server_connection
+            .as_mut()
+            .expect("Client must be connected to spawn Entities");
+
+        let entity = world.spawn_entity();
+        connection.host_entity_manager.spawn_entity(&entity);
+
+        EntityMut::new(self, world, &entity)
+    }
+
+    /// Retrieves an EntityMut that exposes read and write operations for an
+    /// Entity spawned by the Client.
+    /// Panics if the Entity does not exist, or is not owned by the Client.
+    pub fn entity_mut<W: WorldMutType<P, E>>(
+        &mut self,
+        world: W,
+        entity: &E,
+    ) -> EntityMut<'_, P, E, W, C> {
+        if world.has_entity(entity) && self.entity_is_owned(entity) {
+            return EntityMut::new(self, world, entity);
+        }
+        panic!("No Client-owned Entity exists for given Key!");
+    }
+
+    /// Returns whether the given Entity was spawned by the Client
+    pub fn entity_is_owned(&self, entity: &E) -> bool {
+        self.server_connection
+            .as_ref()
+            .map(|connection| connection.host_entity_manager.has_entity(entity))
+            .unwrap_or(false)
+    }
+
+    /// Returns whether the Server has given the Client authority over the
+    /// given Entity. Changes made to its Components are sent to the Server.
+    pub fn entity_has_authority(&self, entity: &E) -> bool {
+        self.server_connection
+            .as_ref()
+            .map(|connection| connection.authority_manager.has_authority(entity))
+            .unwrap_or(false)
+    }
+
+    // Prediction
+
+    /// Marks a local Entity as the prediction of an Entity replicated from the
+    /// Server. Each Tick's predicted state should then be stored with
+    /// `record_prediction`, and a Rollback event will be emitted when the
+    /// Server's state for that Tick differs from the prediction.
+    /// Panics if the Client is not connected.
+    pub fn predict_entity(&mut self, confirmed_entity: &E, predicted_entity: &E) {
+        self.server_connection
+            .as_mut()
+            .expect("Client must be connected to predict Entities")
+            .prediction_manager
+            .predict(confirmed_entity, predicted_entity);
+    }
+
+    /// Stops predicting the given Entity, returning the local Entity which held
+    /// its prediction
+    pub fn unpredict_entity(&mut self, confirmed_entity: &E) -> Option<E> {
+        self.server_connection
+            .as_mut()
+            .and_then(|connection| connection.prediction_manager.unpredict(confirmed_entity))
+    }
+
+    /// Returns the local Entity which holds the prediction of the given
+    /// Entity, if it is being predicted
+    pub fn predicted_entity(&self, confirmed_entity: &E) -> Option<E> {
+        self.server_connection.as_ref().and_then(|connection| {
+            connection
+                .prediction_manager
+                .predicted_entity(confirmed_entity)
+        })
+    }
+
+    /// Stores the current state of the given Entity's prediction as the state
+    /// predicted for the given Tick. Ticks must be recorded in increasing
+    /// order.
+    pub fn record_prediction<W: WorldRefType<P, E>>(
+        &mut self,
+        world: &W,
+        confirmed_entity: &E,
+        tick: Tick,
+    ) {
+        if let Some(connection) = self.server_connection.as_mut() {
+            if let Some(component_kinds) =
+                connection.entity_manager.component_kinds(confirmed_entity)
+            {
+                connection.prediction_manager.record(
+                    world,
+                    confirmed_entity,
+                    component_kinds,
+                    tick,
+                );
+            }
+        }
+    }
+
+    /// Sets how predicted Components of the given type are compared with the
+    /// state received from the Server, for example to tolerate small
+    /// differences. The comparer is given the predicted Component, then the
+    /// confirmed one, and returns whether the prediction was right.
+    /// Pass None to require an exact match again, which is the default.
+    pub fn set_prediction_comparer<R: ReplicateSafe<P>>(
+        &mut self,
+        comparer: Option<fn(&R, &R) -> bool>,
+    ) {
+        let component_kind = P::kind_of::<R>();
+        match comparer {
+            Some(comparer) => {
+                self.prediction_comparers.insert(
+                    component_kind,
+                    Box::new(move |predicted: &P, confirmed: &P| {
+                        match (predicted.cast_ref::<R>(), confirmed.cast_ref::<R>()) {
+                            (Some(predicted), Some(confirmed)) => comparer(predicted, confirmed),
+                            _ => false,
+                        }
+                    }),
+                );
+            }
+            None => {
+                self.prediction_comparers.remove(&component_kind);
+            }
+        }
+    }
+
+    /// Resets the prediction of the given Entity to the state last received
+    /// from the Server, and returns the commands issued after the given Tick,
+    /// which should be applied to the prediction again in order
+    pub fn rollback_entity<W: WorldMutType<P, E>, T: Clone>(
+        &mut self,
+        mut world: W,
+        confirmed_entity: &E,
+        tick: &Tick,
+        command_history: &mut CommandHistory<T>,
+    ) -> Vec<(Tick, T)> {
+        if let Some(predicted_entity) = self.predicted_entity(confirmed_entity) {
+            world.mirror_entities(&predicted_entity, confirmed_entity);
+        }
+
+        command_history.replays(tick)
+    }
+
+    /// Returns each state the Entity's Component of the given type was
+    /// inserted or updated to during the last call to `receive`, oldest
+    /// first, along with the Tick of the state. The Component in the World
+    /// only holds the latest of these.
+    pub fn received_states<R: ReplicateSafe<P>>(&self, entity: &E) -> Vec<(Tick, &R)> {
+        let received_states = self.server_connection.as_ref().and_then(|connection| {
+            connection
+                .received_states
+                .get(&(*entity, P::kind_of::<R>()))
+        });
+        match received_states {
+            Some(received_states) => received_states
+                .iter()
+                .filter_map(|(tick, state)| state.cast_ref::<R>().map(|state| (*tick, state)))
+                .collect(),
+            None => Vec::new(),
+        }
+    }
 
     /// Return a list of all Entities
     pub fn entities<W: WorldRefType<P, E>>(&self, world: &W) -> Vec<E> {
-        world.entities()
+        let mut entities = world.entities();
+        if let Some(resource_entity) = self.resource_entity() {
+            entities.retain(|entity| *entity != resource_entity);
+        }
+        entities
+    }
+
+    // Resources
+
+    /// Gets a reference to the Resource of the given type replicated from the
+    /// Server, if it exists
+    pub fn resource<'w, R: ReplicateSafe<P>, W: WorldRefType<P, E>>(
+        &self,
+        world: &'w W,
+    ) -> Option<ReplicaRefWrapper<'w, P, R>> {
+        world.component::<R>(&self.resource_entity()?)
+    }
+
+    /// Returns whether a Resource of the given type has been replicated from
+    /// the Server
+    pub fn has_resource<R: ReplicateSafe<P>, W: WorldRefType<P, E>>(&self, world: &W) -> bool {
+        match self.resource_entity() {
+            Some(resource_entity) => world.has_component::<R>(&resource_entity),
+            None => false,
+        }
+    }
+
+    /// Gets the Entity whose Components are the Resources replicated from the
+    /// Server, if any have been received
+    pub fn resource_entity(&self) -> Option<E> {
+        self.server_connection
+            .as_ref()
+            .and_then(|connection| connection.entity_manager.resource_entity())
     }
 
     // Connection
@@ -284,6 +505,14 @@ impl<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> Client<P, E, C> {
             .map(|tick_manager| tick_manager.client_sending_tick());
     }
 
+    /// Gets the Tick at which updates received from the Server are currently
+    /// being applied
+    pub fn receiving_tick(&self) -> Option<Tick> {
+        self.tick_manager
+            .as_ref()
+            .map(|tick_manager| tick_manager.client_receiving_tick())
+    }
+
     // Interpolation
 
     /// Gets the interpolation tween amount for the current frame
@@ -302,6 +531,80 @@ impl<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> Client<P, E, C> {
         self.io.incoming_bandwidth()
     }
 
+    /// Gets the number of incoming packets which have been dropped because
+    /// they could not be read
+    pub fn invalid_packet_count(&self) -> u64 {
+        self.invalid_packet_count
+    }
+
+    // Crate-Public methods
+
+    //// Entities
+
+    /// Despawns a Client-owned Entity, along with all of its Components
+    pub(crate) fn despawn_entity<W: WorldMutType<P, E>>(&mut self, world: &mut W, entity: &E) {
+        if !world.has_entity(entity) {
+            panic!("attempted to de-spawn nonexistent entity");
+        }
+
+        if let Some(connection) = self.server_connection.as_mut() {
+            connection.host_entity_manager.despawn_entity(entity);
+        }
+
+        world.despawn_entity(entity);
+    }
+
+    //// Components
+
+    /// Adds a Component to a Client-owned Entity
+    pub(crate) fn insert_component<R: ReplicateSafe<P>, W: WorldMutType<P, E>>(
+        &mut self,
+        world: &mut W,
+        entity: &E,
+        mut component_ref: R,
+    ) {
+        if !world.has_entity(entity) {
+            panic!("attempted to add component to non-existent entity");
+        }
+
+        let component_kind = component_ref.kind();
+
+        if world.has_component_of_kind(entity, &component_kind) {
+            panic!(
+                "attempted to add component to entity which already has one of that type! \
+                   an entity is not allowed to have more than 1 type of component at a time."
+            )
+        }
+
+        // track changes to the Component's Properties
+        let mut_channel = MutChannel::new(component_ref.diff_mask_size());
+        component_ref.set_mutator(&PropertyMutator::new(mut_channel.clone()));
+
+        // actually insert component into world
+        world.insert_component(entity, component_ref);
+
+        if let Some(connection) = self.server_connection.as_mut() {
+            connection
+                .host_entity_manager
+                .insert_component(entity, &component_kind, mut_channel);
+        }
+    }
+
+    /// Removes a Component from a Client-owned Entity
+    pub(crate) fn remove_component<R: Replicate<P>, W: WorldMutType<P, E>>(
+        &mut self,
+        world: &mut W,
+        entity: &E,
+    ) -> Option<R> {
+        if let Some(connection) = self.server_connection.as_mut() {
+            connection
+                .host_entity_manager
+                .remove_component(entity, &P::kind_of::<R>());
+        }
+
+        world.remove_component::<R>(entity)
+    }
+
     // internal functions
 
     fn maintain_socket(&mut self) {
@@ -356,75 +659,20 @@ impl<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> Client<P, E, C> {
                     Ok(Some(mut reader)) => {
                         server_connection.base.mark_heard();
 
-                        let header = StandardHeader::de(&mut reader).unwrap();
-
-                        match header.packet_type {
-                            PacketType::Data
-                            | PacketType::Heartbeat
-                            | PacketType::Ping
-                            | PacketType::Pong => {
-                                // continue, these packet types are allowed when
-                                // connection is established
-                            }
-                            _ => {
-                                // short-circuit, do not need to handle other packet types at this
-                                // point
-                                continue;
-                            }
-                        }
-
-                        // Read incoming header
-                        server_connection.process_incoming_header(&header);
-
-                        // Record incoming tick
-                        let mut incoming_tick = 0;
-
-                        if let Some(tick_manager) = self.tick_manager.as_mut() {
-                            incoming_tick = tick_manager.read_server_tick(
-                                &mut reader,
-                                server_connection.ping_manager.rtt,
-                                server_connection.ping_manager.jitter,
-                            );
-                        }
-
-                        // Handle based on PacketType
-                        match header.packet_type {
-                            PacketType::Data => {
-                                server_connection.buffer_data_packet(incoming_tick, &mut reader);
-                            }
-                            PacketType::Heartbeat => {
-                                // already marked as heard, job done
-                            }
-                            PacketType::Ping => {
-                                // read incoming ping index
-                                let ping_index = PingIndex::de(&mut reader).unwrap();
-
-                                // write pong payload
-                                let mut writer = BitWriter::default();
-
-                                // write header
-                                server_connection
-                                    .base
-                                    .write_outgoing_header(PacketType::Pong, &mut writer);
-
-                                // write server tick
-                                if let Some(tick_manager) = self.tick_manager.as_ref() {
-                                    tick_manager.write_client_tick(&mut writer);
-                                }
-
-                                // write index
-                                ping_index.ser(&mut writer);
-
+                        match Self::read_packet(
+                            server_connection,
+                            &mut self.tick_manager,
+                            &mut reader,
+                        ) {
+                            Ok(Some(mut pong_writer)) => {
                                 // send packet
-                                self.io.send_writer(&mut writer);
+                                self.io.send_writer(&mut pong_writer);
                                 server_connection.base.mark_sent();
                             }
-                            PacketType::Pong => {
-                                server_connection.ping_manager.process_pong(&mut reader);
-                            }
-                            _ => {
-                                // no other packet types matter when connection
-                                // is established
+                            Ok(None) => {}
+                            Err(_) => {
+                                // drop the malformed packet
+                                self.invalid_packet_count += 1;
                             }
                         }
                     }
@@ -444,17 +692,29 @@ impl<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> Client<P, E, C> {
                 loop {
                     match self.io.recv_reader() {
                         Ok(Some(mut reader)) => {
-                            if self.handshake_manager.recv(&mut reader) {
-                                // new connect!
-                                let server_addr = self.server_address_unwrapped();
-                                self.server_connection = Some(Connection::new(
-                                    server_addr,
-                                    &self.client_config.connection,
-                                    &self.shared_config.channel,
-                                    &self.shared_config.tick_interval,
-                                ));
-                                self.incoming_events
-                                    .push_back(Ok(Event::Connection(server_addr)));
+                            match self.handshake_manager.recv(&mut reader) {
+                                Ok(HandshakeResult::Connected) => {
+                                    // new connect!
+                                    let server_addr = self.server_address_unwrapped();
+                                    self.server_connection = Some(Connection::new(
+                                        server_addr,
+                                        &self.client_config.connection,
+                                        &self.shared_config.channel,
+                                        &self.shared_config.tick_interval,
+                                    ));
+                                    self.incoming_events
+                                        .push_back(Ok(Event::Connection(server_addr)));
+                                }
+                                Ok(HandshakeResult::Rejected(reason)) => {
+                                    let server_addr = self.server_address_unwrapped();
+                                    self.incoming_events
+                                        .push_back(Ok(Event::Rejection(server_addr, reason)));
+                                }
+                                Ok(HandshakeResult::Pending) => {}
+                                Err(_) => {
+                                    // drop the malformed packet
+                                    self.invalid_packet_count += 1;
+                                }
                             }
                         }
                         Ok(None) => {
@@ -470,6 +730,90 @@ impl<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> Client<P, E, C> {
         }
     }
 
+    /// Reads an incoming packet from the Server, returning an error if it is
+    /// malformed, or a Pong packet to send back if it was a Ping
+    fn read_packet(
+        server_connection: &mut Connection<P, E, C>,
+        tick_manager_opt: &mut Option<TickManager>,
+        reader: &mut BitReader,
+    ) -> Result<Option<BitWriter>, SerdeErr> {
+        let header = StandardHeader::de(reader)?;
+
+        match header.packet_type {
+            PacketType::Data | PacketType::Heartbeat | PacketType::Ping | PacketType::Pong => {
+                // continue, these packet types are allowed when
+                // connection is established
+            }
+            _ => {
+                // short-circuit, do not need to handle other packet types at this
+                // point
+                return Ok(None);
+            }
+        }
+
+        // Process acks in the incoming header
+        server_connection.process_incoming_header(&header);
+
+        // Record incoming tick
+        let mut incoming_tick = 0;
+
+        if let Some(tick_manager) = tick_manager_opt.as_mut() {
+            incoming_tick = tick_manager.read_server_tick(
+                reader,
+                server_connection.ping_manager.rtt,
+                server_connection.ping_manager.jitter,
+            )?;
+        }
+
+        // Handle based on PacketType
+        match header.packet_type {
+            PacketType::Data => {
+                // acked once read from the jitter buffer
+                server_connection.buffer_data_packet(incoming_tick, &header, reader);
+                return Ok(None);
+            }
+            PacketType::Heartbeat => {
+                // already marked as heard, job done
+            }
+            PacketType::Ping => {
+                // read incoming ping index
+                let ping_index = PingIndex::de(reader)?;
+
+                // write pong payload
+                let mut writer = BitWriter::default();
+
+                // write header
+                server_connection
+                    .base
+                    .write_outgoing_header(PacketType::Pong, &mut writer);
+
+                // write server tick
+                if let Some(tick_manager) = tick_manager_opt.as_ref() {
+                    tick_manager.write_client_tick(&mut writer);
+                }
+
+                // write index
+                ping_index.ser(&mut writer);
+
+                server_connection.mark_packet_received(&header);
+                return Ok(Some(writer));
+            }
+            PacketType::Pong => {
+                server_connection.ping_manager.process_pong(reader)?;
+            }
+            _ => {
+                // no other packet types matter when connection
+                // is established
+            }
+        }
+
+        // only ack the packet once its payload has been read without error,
+        // so that the Server resends anything in a malformed packet
+        server_connection.mark_packet_received(&header);
+
+        Ok(None)
+    }
+
     fn disconnect_internal(&mut self) {
         let server_addr = self.server_address_unwrapped();
         self.disconnect_cleanup();
@@ -499,7 +843,10 @@ impl<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> Client<P, E, C> {
             &self.shared_config.compression,
         );
         self.server_connection = None;
-        self.handshake_manager = HandshakeManager::new(self.client_config.send_handshake_interval);
+        self.handshake_manager = HandshakeManager::new(
+            self.client_config.send_handshake_interval,
+            self.shared_config.fingerprint::<P>(),
+        );
         self.tick_manager = tick_manager;
     }
 
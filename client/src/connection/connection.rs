@@ -1,15 +1,26 @@
-use std::{collections::VecDeque, hash::Hash, net::SocketAddr, time::Duration};
+use std::{
+    collections::{HashMap, VecDeque},
+    hash::Hash,
+    net::SocketAddr,
+    time::Duration,
+};
 
 use naia_shared::{
-    serde::{BitReader, BitWriter, OwnedBitReader},
-    BaseConnection, ChannelConfig, ChannelIndex, ConnectionConfig, HostType, Instant, PacketType,
-    PingManager, ProtocolIo, Protocolize, StandardHeader, Tick, WorldMutType,
+    serde::{BitReader, BitWriter, OwnedBitReader, SerdeErr},
+    BaseConnection, ChannelConfig, ChannelIndex, ConnectionConfig, HostType, Instant, PacketIndex,
+    PacketNotifiable, PacketType, PingManager, ProtocolIo, Protocolize, StandardHeader, Tick,
+    WorldMutType, WorldRefType,
 };
 
 use crate::{
     error::NaiaClientError,
     event::Event,
-    protocol::entity_manager::EntityManager,
+    protocol::{
+        authority_manager::AuthorityManager,
+        entity_manager::EntityManager,
+        host_entity_manager::HostEntityManager,
+        prediction_manager::{PredictionComparers, PredictionManager},
+    },
     tick::{
         tick_buffer_sender::TickBufferSender, tick_manager::TickManager, tick_queue::TickQueue,
     },
@@ -20,9 +31,16 @@ use super::io::Io;
 pub struct Connection<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> {
     pub base: BaseConnection<P, C>,
     pub entity_manager: EntityManager<P, E>,
+    pub host_entity_manager: HostEntityManager<P, E>,
+    pub authority_manager: AuthorityManager<P, E>,
+    pub prediction_manager: PredictionManager<P, E>,
     pub ping_manager: PingManager,
     pub tick_buffer: Option<TickBufferSender<P, C>>,
-    jitter_buffer: TickQueue<OwnedBitReader>,
+    // the state each Component was inserted or updated to by the packets
+    // read since the last call to Client::receive, oldest first
+    #[allow(clippy::type_complexity)]
+    pub received_states: HashMap<(E, P::Kind), Vec<(Tick, P)>>,
+    jitter_buffer: TickQueue<(StandardHeader, OwnedBitReader)>,
 }
 
 impl<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> Connection<P, E, C> {
@@ -39,8 +57,12 @@ impl<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> Connection<P, E, C> {
         Connection {
             base: BaseConnection::new(address, HostType::Client, connection_config, channel_config),
             entity_manager: EntityManager::default(),
+            host_entity_manager: HostEntityManager::default(),
+            authority_manager: AuthorityManager::default(),
+            prediction_manager: PredictionManager::default(),
             ping_manager: PingManager::new(&connection_config.ping),
             tick_buffer,
+            received_states: HashMap::new(),
             jitter_buffer: TickQueue::new(),
         }
     }
@@ -48,49 +70,153 @@ impl<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> Connection<P, E, C> {
     // Incoming data
 
     pub fn process_incoming_header(&mut self, header: &StandardHeader) {
-        match &mut self.tick_buffer {
-            Some(tick_buffer) => self
-                .base
-                .process_incoming_header(header, &mut Some(tick_buffer)),
-            None => self.base.process_incoming_header(header, &mut None),
-        }
+        let mut notifiables = PacketNotifiables {
+            tick_buffer: self.tick_buffer.as_mut(),
+            host_entity_manager: &mut self.host_entity_manager,
+            authority_manager: &mut self.authority_manager,
+        };
+        self.base
+            .process_incoming_header(header, &mut Some(&mut notifiables));
+    }
+
+    /// Records that the packet has been read in full, so that it is acked to
+    /// the Server
+    pub fn mark_packet_received(&mut self, header: &StandardHeader) {
+        self.base.mark_packet_received(header);
     }
 
-    pub fn buffer_data_packet(&mut self, incoming_tick: Tick, reader: &mut BitReader) {
+    pub fn buffer_data_packet(
+        &mut self,
+        incoming_tick: Tick,
+        header: &StandardHeader,
+        reader: &mut BitReader,
+    ) {
         self.jitter_buffer
-            .add_item(incoming_tick, reader.to_owned());
+            .add_item(incoming_tick, (*header, reader.to_owned()));
     }
 
     pub fn process_buffered_packets<W: WorldMutType<P, E>>(
         &mut self,
         world: &mut W,
         receiving_tick: Tick,
+        prediction_comparers: &PredictionComparers<P>,
         incoming_events: &mut VecDeque<Result<Event<P, E, C>, NaiaClientError>>,
-    ) {
-        while let Some((server_tick, owned_reader)) = self.jitter_buffer.pop_item(receiving_tick) {
+    ) -> u64 {
+        let mut invalid_packet_count = 0;
+
+        while let Some((server_tick, (header, owned_reader))) =
+            self.jitter_buffer.pop_item(receiving_tick)
+        {
             let mut bit_reader = owned_reader.borrow();
+            let first_event = incoming_events.len();
 
-            let channel_reader = ProtocolIo::new(&self.entity_manager);
+            if self
+                .read_buffered_packet(
+                    world,
+                    server_tick,
+                    header.sender_packet_index,
+                    &mut bit_reader,
+                    incoming_events,
+                )
+                .is_err()
+            {
+                // drop the malformed packet without acking it, so that the
+                // Server resends its contents
+                invalid_packet_count += 1;
+            } else {
+                self.mark_packet_received(&header);
+            }
 
-            // Read Messages
-            self.base
-                .message_manager
-                .read_messages(&channel_reader, &mut bit_reader);
+            // check the received updates against the Client's predictions
+            self.prediction_manager.process_updates(
+                world,
+                &self.entity_manager,
+                prediction_comparers,
+                first_event,
+                incoming_events,
+            );
 
-            // Read Entity Actions
-            self.entity_manager
-                .read_all(world, server_tick, &mut bit_reader, incoming_events);
+            // later packets may change the Components again before the events
+            // are handled
+            for event in incoming_events.iter().skip(first_event) {
+                let (tick, entity, component_kind) = match event {
+                    Ok(Event::UpdateComponent(tick, entity, component_kind)) => {
+                        (*tick, *entity, *component_kind)
+                    }
+                    Ok(Event::InsertComponent(entity, component_kind)) => {
+                        (server_tick, *entity, *component_kind)
+                    }
+                    _ => continue,
+                };
+                if let Some(component) = world.component_of_kind(&entity, &component_kind) {
+                    self.received_states
+                        .entry((entity, component_kind))
+                        .or_default()
+                        .push((tick, component.protocol_copy()));
+                }
+            }
         }
+
+        invalid_packet_count
+    }
+
+    fn read_buffered_packet<W: WorldMutType<P, E>>(
+        &mut self,
+        world: &mut W,
+        server_tick: Tick,
+        packet_index: PacketIndex,
+        bit_reader: &mut BitReader,
+        incoming_events: &mut VecDeque<Result<Event<P, E, C>, NaiaClientError>>,
+    ) -> Result<(), SerdeErr> {
+        let channel_reader = ProtocolIo::new(&self.entity_manager);
+
+        // Read Messages
+        self.base
+            .message_manager
+            .read_messages(&channel_reader, bit_reader)?;
+
+        // Read Entity Actions
+        self.entity_manager.read_all(
+            world,
+            server_tick,
+            packet_index,
+            bit_reader,
+            &mut self.authority_manager,
+            incoming_events,
+        )?;
+
+        // Read Entities the Server has rejected
+        for entity in self.host_entity_manager.read_rejections(bit_reader)? {
+            if world.has_entity(&entity) {
+                world.despawn_entity(&entity);
+            }
+            incoming_events.push_back(Ok(Event::EntityRejected(entity)));
+        }
+
+        Ok(())
     }
 
     // Outgoing data
 
-    pub fn send_outgoing_packets(&mut self, io: &mut Io, tick_manager_opt: &Option<TickManager>) {
-        self.collect_outgoing_messages(tick_manager_opt);
+    pub fn send_outgoing_packets<W: WorldRefType<P, E>>(
+        &mut self,
+        io: &mut Io,
+        world: &W,
+        tick_manager_opt: &Option<TickManager>,
+    ) {
+        let now = Instant::now();
+
+        self.collect_outgoing_messages(&now, tick_manager_opt);
+
+        self.base.update_send_budget(&self.ping_manager.rtt);
 
         let mut any_sent = false;
         loop {
-            if self.send_outgoing_packet(io, tick_manager_opt) {
+            if !self.base.can_send_packet() {
+                // out of send budget, defer the rest until the next call
+                break;
+            }
+            if self.send_outgoing_packet(&now, io, world, tick_manager_opt) {
                 any_sent = true;
             } else {
                 break;
@@ -101,12 +227,16 @@ impl<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> Connection<P, E, C> {
         }
     }
 
-    fn collect_outgoing_messages(&mut self, tick_manager_opt: &Option<TickManager>) {
-        let now = Instant::now();
-
+    fn collect_outgoing_messages(&mut self, now: &Instant, tick_manager_opt: &Option<TickManager>) {
         self.base
             .message_manager
-            .collect_outgoing_messages(&now, &self.ping_manager.rtt);
+            .collect_outgoing_messages(now, &self.ping_manager.rtt);
+
+        self.host_entity_manager
+            .collect_outgoing_messages(now, &self.ping_manager.rtt);
+
+        self.authority_manager
+            .collect_outgoing_messages(&self.ping_manager.rtt);
 
         if let Some(tick_manager) = tick_manager_opt {
             self.tick_buffer
@@ -120,9 +250,11 @@ impl<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> Connection<P, E, C> {
     }
 
     // Sends packet and returns whether or not a packet was sent
-    fn send_outgoing_packet(
+    fn send_outgoing_packet<W: WorldRefType<P, E>>(
         &mut self,
+        now: &Instant,
         io: &mut Io,
+        world: &W,
         tick_manager_opt: &Option<TickManager>,
     ) -> bool {
         let tick_buffer_has_outgoing_messages = match &self.tick_buffer {
@@ -130,7 +262,11 @@ impl<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> Connection<P, E, C> {
             None => false,
         };
 
-        if self.base.message_manager.has_outgoing_messages() || tick_buffer_has_outgoing_messages {
+        if self.base.message_manager.has_outgoing_messages()
+            || tick_buffer_has_outgoing_messages
+            || self.host_entity_manager.has_outgoing_messages()
+            || self.authority_manager.has_outgoing_messages()
+        {
             let next_packet_index = self.base.next_packet_index();
 
             let mut bit_writer = BitWriter::default();
@@ -162,7 +298,26 @@ impl<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> Connection<P, E, C> {
                 next_packet_index,
             );
 
+            // write entity actions
+            self.host_entity_manager.write_all(
+                now,
+                &mut bit_writer,
+                &next_packet_index,
+                world,
+                &self.entity_manager,
+            );
+
+            // write changes to Entities the Client has authority over
+            self.authority_manager.write_updates(
+                now,
+                &mut bit_writer,
+                &next_packet_index,
+                world,
+                &self.entity_manager,
+            );
+
             // send packet
+            self.base.record_sent_packet(&bit_writer);
             io.send_writer(&mut bit_writer);
 
             return true;
@@ -171,3 +326,23 @@ impl<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> Connection<P, E, C> {
         false
     }
 }
+
+// Notifies every manager which tracks the delivery of outgoing packets
+struct PacketNotifiables<'a, P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> {
+    tick_buffer: Option<&'a mut TickBufferSender<P, C>>,
+    host_entity_manager: &'a mut HostEntityManager<P, E>,
+    authority_manager: &'a mut AuthorityManager<P, E>,
+}
+
+impl<'a, P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> PacketNotifiable
+    for PacketNotifiables<'a, P, E, C>
+{
+    fn notify_packet_delivered(&mut self, packet_index: PacketIndex) {
+        if let Some(tick_buffer) = self.tick_buffer.as_mut() {
+            tick_buffer.notify_packet_delivered(packet_index);
+        }
+        self.host_entity_manager
+            .notify_packet_delivered(packet_index);
+        self.authority_manager.notify_packet_delivered(packet_index);
+    }
+}
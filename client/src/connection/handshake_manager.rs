@@ -1,12 +1,12 @@
 use std::time::Duration;
 
 use naia_shared::{
-    serde::{BitReader, BitWriter, Serde},
+    serde::{BitReader, BitWriter, Serde, SerdeErr},
     FakeEntityConverter,
 };
 pub use naia_shared::{
-    ConnectionConfig, PacketType, ProtocolKindType, Protocolize, ReplicateSafe, SharedConfig,
-    StandardHeader, Timer, Timestamp as stamp_time, WorldMutType, WorldRefType,
+    ConnectionConfig, PacketType, ProtocolKindType, Protocolize, RejectReason, ReplicateSafe,
+    SharedConfig, StandardHeader, Timer, Timestamp as stamp_time, WorldMutType, WorldRefType,
 };
 
 use super::io::Io;
@@ -18,18 +18,26 @@ pub enum HandshakeState {
     AwaitingChallengeResponse,
     AwaitingConnectResponse,
     Connected,
+    Rejected(RejectReason),
+}
+
+pub enum HandshakeResult {
+    Pending,
+    Connected,
+    Rejected(RejectReason),
 }
 
 pub struct HandshakeManager<P: Protocolize> {
     handshake_timer: Timer,
     pre_connection_timestamp: Timestamp,
     pre_connection_digest: Option<Vec<u8>>,
+    protocol_fingerprint: u64,
     pub connection_state: HandshakeState,
     auth_message: Option<P>,
 }
 
 impl<P: Protocolize> HandshakeManager<P> {
-    pub fn new(send_interval: Duration) -> Self {
+    pub fn new(send_interval: Duration, protocol_fingerprint: u64) -> Self {
         let mut handshake_timer = Timer::new(send_interval);
         handshake_timer.ring_manual();
 
@@ -39,6 +47,7 @@ impl<P: Protocolize> HandshakeManager<P> {
             handshake_timer,
             pre_connection_timestamp,
             pre_connection_digest: None,
+            protocol_fingerprint,
             connection_state: HandshakeState::AwaitingChallengeResponse,
             auth_message: None,
         }
@@ -62,7 +71,7 @@ impl<P: Protocolize> HandshakeManager<P> {
             self.handshake_timer.reset();
 
             match self.connection_state {
-                HandshakeState::Connected => {
+                HandshakeState::Connected | HandshakeState::Rejected(_) => {
                     // do nothing, not necessary
                 }
                 HandshakeState::AwaitingChallengeResponse => {
@@ -78,15 +87,22 @@ impl<P: Protocolize> HandshakeManager<P> {
     }
 
     // Call this regularly so handshake manager can process incoming requests
-    pub fn recv(&mut self, reader: &mut BitReader) -> bool {
-        let header = StandardHeader::de(reader).unwrap();
+    pub fn recv(&mut self, reader: &mut BitReader) -> Result<HandshakeResult, SerdeErr> {
+        let header = StandardHeader::de(reader)?;
         match header.packet_type {
             PacketType::ServerChallengeResponse => {
-                self.recv_challenge_response(reader);
-                false
+                self.recv_challenge_response(reader)?;
+                Ok(HandshakeResult::Pending)
             }
-            PacketType::ServerConnectResponse => self.recv_connect_response(),
-            _ => false,
+            PacketType::ServerConnectResponse => {
+                if self.recv_connect_response() {
+                    Ok(HandshakeResult::Connected)
+                } else {
+                    Ok(HandshakeResult::Pending)
+                }
+            }
+            PacketType::ServerRejectResponse => self.recv_reject_response(reader),
+            _ => Ok(HandshakeResult::Pending),
         }
     }
 
@@ -101,17 +117,19 @@ impl<P: Protocolize> HandshakeManager<P> {
     }
 
     // Step 2 of Handshake
-    pub fn recv_challenge_response(&mut self, reader: &mut BitReader) {
+    pub fn recv_challenge_response(&mut self, reader: &mut BitReader) -> Result<(), SerdeErr> {
         if self.connection_state == HandshakeState::AwaitingChallengeResponse {
-            let payload_timestamp = Timestamp::de(reader).unwrap();
+            let payload_timestamp = Timestamp::de(reader)?;
 
             if self.pre_connection_timestamp == payload_timestamp {
-                let digest_bytes: Vec<u8> = Vec::<u8>::de(reader).unwrap();
+                let digest_bytes: Vec<u8> = Vec::<u8>::de(reader)?;
                 self.pre_connection_digest = Some(digest_bytes);
 
                 self.connection_state = HandshakeState::AwaitingConnectResponse;
             }
         }
+
+        Ok(())
     }
 
     // Step 3 of Handshake
@@ -123,6 +141,10 @@ impl<P: Protocolize> HandshakeManager<P> {
         // write timestamp & digest into payload
         self.write_signed_timestamp(&mut writer);
 
+        // write protocol fingerprint, so the Server can refuse a mismatched
+        // build
+        self.protocol_fingerprint.ser(&mut writer);
+
         // write auth message if there is one
         if let Some(auth_message) = &self.auth_message {
             // write that we have auth
@@ -144,6 +166,21 @@ impl<P: Protocolize> HandshakeManager<P> {
         was_not_connected
     }
 
+    // Step 4 of Handshake, if the Server refused the connect request
+    pub fn recv_reject_response(
+        &mut self,
+        reader: &mut BitReader,
+    ) -> Result<HandshakeResult, SerdeErr> {
+        let reason = RejectReason::de(reader)?;
+
+        if self.connection_state != HandshakeState::AwaitingConnectResponse {
+            return Ok(HandshakeResult::Pending);
+        }
+
+        self.connection_state = HandshakeState::Rejected(reason);
+        Ok(HandshakeResult::Rejected(reason))
+    }
+
     // Send 10 disconnect packets
     pub fn write_disconnect(&self) -> BitWriter {
         let mut writer = BitWriter::default();
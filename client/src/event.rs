@@ -1,6 +1,6 @@
 use std::net::SocketAddr;
 
-use naia_shared::{ChannelIndex, Protocolize, Tick};
+use naia_shared::{ChannelIndex, Protocolize, RejectReason, Tick};
 
 /// An Event that is be emitted by the Client, usually as a result of some
 /// communication with the Server
@@ -8,6 +8,9 @@ pub enum Event<P: Protocolize, E: Copy, C: ChannelIndex> {
     /// Occurs when the Client has successfully established a connection with
     /// the Server
     Connection(SocketAddr),
+    /// Occurs when the Server has refused the Client's connect request, for
+    /// example because they were built with different Protocols
+    Rejection(SocketAddr, RejectReason),
     /// Occurs when the Client has lost connection with the Server, usually as a
     /// result of a timeout
     Disconnection(SocketAddr),
@@ -19,6 +22,9 @@ pub enum Event<P: Protocolize, E: Copy, C: ChannelIndex> {
     /// Occurs when an Entity on the Server has been destroyed, or left the
     /// Client's scope
     DespawnEntity(E),
+    /// Occurs when the Server has refused an Entity spawned by the Client. The
+    /// Entity has already been despawned from the Client's World
+    EntityRejected(E),
     /// Occurs when a Component should be added to a given Entity
     InsertComponent(E, P::Kind),
     /// Occurs when a Component has had a state change on the Server while
@@ -26,6 +32,25 @@ pub enum Event<P: Protocolize, E: Copy, C: ChannelIndex> {
     UpdateComponent(Tick, E, P::Kind),
     /// Occurs when a Component should be removed from the given Entity
     RemoveComponent(E, P),
+    /// Occurs when a Resource has been inserted on the Server, or the Client
+    /// has connected while it exists
+    InsertResource(P::Kind),
+    /// Occurs when a Resource has had a state change on the Server
+    UpdateResource(Tick, P::Kind),
+    /// Occurs when a Resource has been removed on the Server
+    RemoveResource(P),
+    /// Occurs when the Server has given the Client authority over an Entity.
+    /// Changes the Client makes to the Entity's Components are sent to the
+    /// Server from then on
+    AuthorityGranted(E),
+    /// Occurs when the Server has taken away the Client's authority over an
+    /// Entity
+    AuthorityRevoked(E),
+    /// Occurs when the Server's state for a predicted Entity at the given Tick
+    /// differs from the state the Client predicted for it. The predicted
+    /// Entity should be reset to the confirmed state, and the commands issued
+    /// since that Tick replayed
+    Rollback(Tick, E),
     /// A Message emitted to the Client from the Server
     Message(C, P),
 }
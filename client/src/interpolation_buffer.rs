@@ -0,0 +1,207 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    hash::Hash,
+};
+
+use naia_shared::{
+    sequence_greater_than, wrapping_diff, ChannelIndex, Lerp, Protocolize, Replicate, Tick,
+};
+
+use crate::{client::Client, event::Event};
+
+/// Keeps the last few states of a Component received from the Server on each
+/// Entity, stamped with the Tick they were sent at, and blends between them to
+/// give a smooth value at any point in time.
+///
+/// Render a little behind the latest received state, for example at
+/// `client.receiving_tick() - 1` with `client.interpolation()` as the fraction,
+/// so that there is usually a newer state to blend towards.
+pub struct InterpolationBuffer<E: Copy + Eq + Hash, R: Clone + Lerp> {
+    capacity: usize,
+    max_extrapolation: f32,
+    // oldest Tick at the front
+    entities: HashMap<E, VecDeque<(Tick, R)>>,
+}
+
+impl<E: Copy + Eq + Hash, R: Clone + Lerp> InterpolationBuffer<E, R> {
+    /// Create a new InterpolationBuffer, which keeps up to `capacity` states
+    /// for each Entity, and will extrapolate at most `max_extrapolation` Ticks
+    /// past the latest state received
+    pub fn new(capacity: usize, max_extrapolation: f32) -> Self {
+        if capacity == 0 {
+            panic!("InterpolationBuffer must be able to hold at least 1 state");
+        }
+
+        Self {
+            capacity,
+            max_extrapolation: max_extrapolation.max(0.0),
+            entities: HashMap::new(),
+        }
+    }
+
+    /// Stores each state the Component was given by an Insert or Update
+    /// event, and forgets an Entity once the Component has been removed or
+    /// the Entity despawned. Other events are ignored. Must be given the
+    /// events of the latest call to `Client::receive`.
+    pub fn receive<P: Protocolize, C: ChannelIndex>(
+        &mut self,
+        client: &Client<P, E, C>,
+        event: &Event<P, E, C>,
+    ) where
+        R: Replicate<P>,
+    {
+        match event {
+            Event::InsertComponent(entity, component_kind)
+            | Event::UpdateComponent(_, entity, component_kind) => {
+                if *component_kind != P::kind_of::<R>() {
+                    return;
+                }
+                for (tick, state) in client.received_states::<R>(entity) {
+                    self.insert(entity, tick, state.clone());
+                }
+            }
+            Event::RemoveComponent(entity, component)
+                if component.dyn_ref().kind() == P::kind_of::<R>() =>
+            {
+                self.remove(entity);
+            }
+            Event::DespawnEntity(entity) => {
+                self.remove(entity);
+            }
+            _ => {}
+        }
+    }
+
+    /// Stores the state of an Entity's Component at the given Tick. A state
+    /// older than every state held, or for a Tick already held, is ignored.
+    pub fn insert(&mut self, entity: &E, tick: Tick, state: R) {
+        let states = self.entities.entry(*entity).or_default();
+
+        // find the position which keeps the states sorted by Tick
+        let mut index = states.len();
+        for (state_tick, _) in states.iter().rev() {
+            if *state_tick == tick {
+                return;
+            }
+            if sequence_greater_than(tick, *state_tick) {
+                break;
+            }
+            index -= 1;
+        }
+        if index == 0 && states.len() >= self.capacity {
+            return;
+        }
+
+        states.insert(index, (tick, state));
+        while states.len() > self.capacity {
+            states.pop_front();
+        }
+    }
+
+    /// Forgets every state held for the given Entity
+    pub fn remove(&mut self, entity: &E) {
+        self.entities.remove(entity);
+    }
+
+    /// Clears every state held
+    pub fn clear(&mut self) {
+        self.entities.clear();
+    }
+
+    /// Returns the state of the Entity's Component at a point `fraction` of
+    /// the way between `tick` and the next Tick. Blends across Ticks for which
+    /// no state was received, holds the oldest state before it, and
+    /// extrapolates past the latest state up to the configured limit.
+    pub fn value_at(&self, entity: &E, tick: Tick, fraction: f32) -> Option<R> {
+        let states = self.entities.get(entity)?;
+
+        // the first state after the given Tick
+        let next_index = states
+            .iter()
+            .position(|(state_tick, _)| sequence_greater_than(*state_tick, tick));
+
+        match next_index {
+            // before the oldest state
+            Some(0) => states.front().map(|(_, state)| state.clone()),
+            // between two states
+            Some(next_index) => {
+                let (prev_tick, prev_state) = &states[next_index - 1];
+                let (next_tick, next_state) = &states[next_index];
+                let elapsed = f32::from(wrapping_diff(*prev_tick, tick)) + fraction;
+                let span = f32::from(wrapping_diff(*prev_tick, *next_tick));
+                Some(prev_state.lerp(next_state, elapsed / span))
+            }
+            // past the latest state
+            None => {
+                let (last_tick, last_state) = states.back()?;
+                if states.len() < 2 {
+                    return Some(last_state.clone());
+                }
+                let (prev_tick, prev_state) = &states[states.len() - 2];
+                let elapsed = f32::from(wrapping_diff(*last_tick, tick)) + fraction;
+                let span = f32::from(wrapping_diff(*prev_tick, *last_tick));
+                let extrapolation = elapsed.min(self.max_extrapolation);
+                Some(prev_state.lerp(last_state, 1.0 + (extrapolation / span)))
+            }
+        }
+    }
+}
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::InterpolationBuffer;
+
+    fn buffer() -> InterpolationBuffer<u8, f32> {
+        let mut buffer = InterpolationBuffer::new(4, 1.0);
+        buffer.insert(&0, 10, 0.0);
+        buffer.insert(&0, 12, 20.0);
+        buffer
+    }
+
+    #[test]
+    fn interpolates_across_missing_ticks() {
+        let buffer = buffer();
+
+        assert_eq!(buffer.value_at(&0, 10, 0.0), Some(0.0));
+        assert_eq!(buffer.value_at(&0, 10, 0.5), Some(5.0));
+        assert_eq!(buffer.value_at(&0, 11, 0.5), Some(15.0));
+    }
+
+    #[test]
+    fn extrapolation_is_limited() {
+        let buffer = buffer();
+
+        assert_eq!(buffer.value_at(&0, 12, 0.5), Some(25.0));
+        assert_eq!(buffer.value_at(&0, 15, 0.0), Some(30.0));
+        assert_eq!(buffer.value_at(&0, 9, 0.0), Some(0.0));
+    }
+
+    #[test]
+    fn insert_keeps_ticks_sorted_and_bounded() {
+        let mut buffer = buffer();
+        buffer.insert(&0, 11, 100.0);
+        buffer.insert(&0, 11, 200.0);
+        buffer.insert(&0, 13, 30.0);
+        buffer.insert(&0, 14, 40.0);
+
+        // tick 10 was dropped to stay within capacity
+        assert_eq!(buffer.value_at(&0, 10, 0.0), Some(100.0));
+        assert_eq!(buffer.value_at(&0, 11, 0.5), Some(60.0));
+        assert_eq!(buffer.value_at(&0, 14, 0.0), Some(40.0));
+
+        // older than every state held
+        buffer.insert(&0, 9, 0.0);
+        assert_eq!(buffer.value_at(&0, 9, 0.0), Some(100.0));
+    }
+
+    #[test]
+    fn handles_tick_wrap_around() {
+        let mut buffer = InterpolationBuffer::new(4, 0.0);
+        buffer.insert(&0, u16::MAX, 0.0);
+        buffer.insert(&0, 1, 20.0);
+
+        assert_eq!(buffer.value_at(&0, 0, 0.0), Some(10.0));
+        assert_eq!(buffer.value_at(&0, 2, 0.0), Some(20.0));
+    }
+}
@@ -19,6 +19,7 @@ mod connection;
 mod constants;
 mod error;
 mod event;
+mod interpolation_buffer;
 mod protocol;
 mod tick;
 
@@ -27,8 +28,11 @@ pub use client_config::ClientConfig;
 pub use command_history::CommandHistory;
 pub use error::NaiaClientError;
 pub use event::Event;
-pub use protocol::entity_ref::EntityRef;
+pub use interpolation_buffer::InterpolationBuffer;
+pub use protocol::entity_ref::{EntityMut, EntityRef};
 
 pub mod internal {
-    pub use crate::connection::handshake_manager::{HandshakeManager, HandshakeState};
+    pub use crate::connection::handshake_manager::{
+        HandshakeManager, HandshakeResult, HandshakeState,
+    };
 }
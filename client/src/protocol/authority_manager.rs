@@ -0,0 +1,277 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    time::Duration,
+};
+
+use naia_shared::{
+    message_list_header,
+    serde::{BitCounter, BitWrite, BitWriter, Serde, UnsignedVariableInteger},
+    DiffMask, Instant, NetEntity, NetEntityHandleConverter, PacketIndex, PacketNotifiable,
+    PropertyMutator, Protocolize, WorldMutType, WorldRefType, MTU_SIZE_BITS,
+};
+
+use super::mut_channel::MutChannel;
+
+const DROP_UPDATE_RTT_FACTOR: f32 = 1.5;
+
+struct AuthorityRecord<K> {
+    net_entity: NetEntity,
+    components: HashMap<K, MutChannel>,
+}
+
+/// Tracks the Server Entities the Client has been given authority over, and
+/// sends the changes made to them back to the Server
+pub struct AuthorityManager<P: Protocolize, E: Copy + Eq + Hash> {
+    records: HashMap<E, AuthorityRecord<P::Kind>>,
+    next_send_updates: HashMap<E, HashSet<P::Kind>>,
+    #[allow(clippy::type_complexity)]
+    sent_updates: HashMap<PacketIndex, (Instant, HashMap<(E, P::Kind), DiffMask>)>,
+}
+
+impl<P: Protocolize, E: Copy + Eq + Hash> Default for AuthorityManager<P, E> {
+    fn default() -> Self {
+        Self {
+            records: HashMap::new(),
+            next_send_updates: HashMap::new(),
+            sent_updates: HashMap::new(),
+        }
+    }
+}
+
+impl<P: Protocolize, E: Copy + Eq + Hash> AuthorityManager<P, E> {
+    // Authority
+
+    pub fn has_authority(&self, entity: &E) -> bool {
+        self.records.contains_key(entity)
+    }
+
+    pub fn grant<W: WorldMutType<P, E>>(
+        &mut self,
+        world: &mut W,
+        entity: &E,
+        net_entity: &NetEntity,
+        component_kinds: &HashSet<P::Kind>,
+    ) {
+        if self.records.contains_key(entity) {
+            return;
+        }
+
+        self.records.insert(
+            *entity,
+            AuthorityRecord {
+                net_entity: *net_entity,
+                components: HashMap::new(),
+            },
+        );
+
+        for component_kind in component_kinds {
+            self.insert_component(world, entity, component_kind);
+        }
+    }
+
+    /// Returns whether the Client had authority over the Entity
+    pub fn revoke(&mut self, entity: &E) -> bool {
+        self.next_send_updates.remove(entity);
+        self.records.remove(entity).is_some()
+    }
+
+    pub fn insert_component<W: WorldMutType<P, E>>(
+        &mut self,
+        world: &mut W,
+        entity: &E,
+        component_kind: &P::Kind,
+    ) {
+        if let Some(record) = self.records.get_mut(entity) {
+            let diff_mask_size = match world.component_of_kind(entity, component_kind) {
+                Some(component) => component.diff_mask_size(),
+                None => return,
+            };
+
+            // track changes made to the Component from now on
+            let mut_channel = MutChannel::new(diff_mask_size);
+            world.component_set_mutator(
+                entity,
+                component_kind,
+                &PropertyMutator::new(mut_channel.clone()),
+            );
+            record.components.insert(*component_kind, mut_channel);
+        }
+    }
+
+    pub fn remove_component(&mut self, entity: &E, component_kind: &P::Kind) {
+        if let Some(record) = self.records.get_mut(entity) {
+            record.components.remove(component_kind);
+        }
+        if let Some(update_kinds) = self.next_send_updates.get_mut(entity) {
+            update_kinds.remove(component_kind);
+        }
+    }
+
+    // Writer
+
+    pub fn collect_outgoing_messages(&mut self, rtt_millis: &f32) {
+        self.collect_dropped_update_packets(rtt_millis);
+        self.collect_component_updates();
+    }
+
+    pub fn has_outgoing_messages(&self) -> bool {
+        !self.next_send_updates.is_empty()
+    }
+
+    // Collecting
+
+    fn collect_dropped_update_packets(&mut self, rtt_millis: &f32) {
+        let drop_duration = Duration::from_millis((DROP_UPDATE_RTT_FACTOR * rtt_millis) as u64);
+
+        let dropped_packets: Vec<PacketIndex> = self
+            .sent_updates
+            .iter()
+            .filter(|(_, (time_sent, _))| time_sent.elapsed() > drop_duration)
+            .map(|(packet_index, _)| *packet_index)
+            .collect();
+
+        for packet_index in dropped_packets {
+            let (_, diff_mask_map) = self.sent_updates.remove(&packet_index).unwrap();
+
+            // queue the dropped Properties up to be sent again
+            for ((entity, component_kind), diff_mask) in diff_mask_map {
+                if let Some(record) = self.records.get(&entity) {
+                    if let Some(mut_channel) = record.components.get(&component_kind) {
+                        mut_channel.or_mask(&diff_mask);
+                    }
+                }
+            }
+        }
+    }
+
+    fn collect_component_updates(&mut self) {
+        self.next_send_updates.clear();
+
+        for (entity, record) in &self.records {
+            for (component_kind, mut_channel) in &record.components {
+                if !mut_channel.diff_mask_is_clear() {
+                    self.next_send_updates
+                        .entry(*entity)
+                        .or_default()
+                        .insert(*component_kind);
+                }
+            }
+        }
+    }
+
+    // Writing updates
+
+    pub fn write_updates<W: WorldRefType<P, E>>(
+        &mut self,
+        now: &Instant,
+        writer: &mut BitWriter,
+        packet_index: &PacketIndex,
+        world: &W,
+        converter: &dyn NetEntityHandleConverter,
+    ) {
+        let mut update_entities: Vec<E> = Vec::new();
+
+        // Header
+        {
+            // Measure
+            let current_packet_size = writer.bit_count();
+            if current_packet_size > MTU_SIZE_BITS {
+                message_list_header::write(writer, 0);
+                return;
+            }
+
+            let mut counter = BitCounter::default();
+            message_list_header::write(&mut counter, 123);
+
+            // Check for overflow
+            if current_packet_size + counter.bit_count() > MTU_SIZE_BITS {
+                message_list_header::write(writer, 0);
+                return;
+            }
+
+            // Find how many updates will fit into the packet
+            for entity in self.next_send_updates.keys() {
+                self.write_update(world, converter, &mut counter, entity);
+                if current_packet_size + counter.bit_count() <= MTU_SIZE_BITS {
+                    update_entities.push(*entity);
+                } else {
+                    break;
+                }
+            }
+        }
+
+        // Write header
+        message_list_header::write(writer, update_entities.len() as u16);
+
+        // Updates
+        let mut sent_updates_map = HashMap::new();
+        for entity in update_entities {
+            let diff_masks = self.write_update(world, converter, writer, &entity);
+            let record = self.records.get(&entity).unwrap();
+
+            // having copied the diff masks for this update, clear the components
+            for (component_kind, diff_mask) in diff_masks {
+                record.components.get(&component_kind).unwrap().clear_mask();
+                sent_updates_map.insert((entity, component_kind), diff_mask);
+            }
+
+            self.next_send_updates.remove(&entity);
+        }
+
+        if !sent_updates_map.is_empty() {
+            self.sent_updates
+                .insert(*packet_index, (now.clone(), sent_updates_map));
+        }
+    }
+
+    // Writes the update for the given Entity, and returns the diff masks
+    // that were written
+    fn write_update<W: WorldRefType<P, E>>(
+        &self,
+        world: &W,
+        converter: &dyn NetEntityHandleConverter,
+        bit_writer: &mut dyn BitWrite,
+        entity: &E,
+    ) -> Vec<(P::Kind, DiffMask)> {
+        let component_set = self.next_send_updates.get(entity).unwrap();
+        let record = self.records.get(entity).unwrap();
+        let mut diff_masks = Vec::new();
+
+        // write net entity
+        record.net_entity.ser(bit_writer);
+
+        // write number of components
+        UnsignedVariableInteger::<3>::new(component_set.len() as u64).ser(bit_writer);
+
+        for component_kind in component_set {
+            // write component kind
+            component_kind.ser(bit_writer);
+
+            // get diff mask
+            let diff_mask = record
+                .components
+                .get(component_kind)
+                .unwrap()
+                .diff_mask()
+                .expect("DiffMask should be readable");
+
+            // write payload
+            world
+                .component_of_kind(entity, component_kind)
+                .expect("Component does not exist in World")
+                .write_update(&diff_mask, bit_writer, converter);
+
+            diff_masks.push((*component_kind, diff_mask));
+        }
+
+        diff_masks
+    }
+}
+
+// PacketNotifiable
+impl<P: Protocolize, E: Copy + Eq + Hash> PacketNotifiable for AuthorityManager<P, E> {
+    fn notify_packet_delivered(&mut self, packet_index: PacketIndex) {
+        self.sent_updates.remove(&packet_index);
+    }
+}
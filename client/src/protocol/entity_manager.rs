@@ -1,19 +1,24 @@
 use std::{
-    collections::{HashMap, VecDeque},
+    collections::{HashMap, HashSet, VecDeque},
     hash::Hash,
 };
 
 use naia_shared::{
     message_list_header,
-    serde::{BitReader, Serde, UnsignedVariableInteger},
+    serde::{BitReader, Serde, SerdeErr, UnsignedVariableInteger},
     BigMap, ChannelIndex, EntityAction, EntityActionReceiver, EntityActionType, EntityHandle,
-    EntityHandleConverter, MessageId, NetEntity, NetEntityHandleConverter, Protocolize, Tick,
-    WorldMutType,
+    EntityHandleConverter, MessageId, NetEntity, NetEntityHandleConverter, PacketIndex,
+    Protocolize, Tick, WorldMutType,
 };
 
+use log::warn;
+
 use crate::{error::NaiaClientError, event::Event};
 
-use super::entity_record::EntityRecord;
+use super::{
+    authority_manager::AuthorityManager, entity_record::EntityRecord,
+    received_baselines::ReceivedBaselines,
+};
 
 pub struct EntityManager<P: Protocolize, E: Copy + Eq + Hash> {
     entity_records: HashMap<E, EntityRecord<P::Kind>>,
@@ -21,6 +26,12 @@ pub struct EntityManager<P: Protocolize, E: Copy + Eq + Hash> {
     pub handle_entity_map: BigMap<EntityHandle, E>,
     receiver: EntityActionReceiver<NetEntity, P::Kind>,
     received_components: HashMap<(NetEntity, P::Kind), P>,
+    // Entities which have been read, but not yet spawned, which hold the
+    // Server's Resources
+    received_resource_entities: HashSet<NetEntity>,
+    resource_entity: Option<E>,
+    // the values of delta-encoded Properties received in recent packets
+    received_baselines: HashMap<NetEntity, HashMap<P::Kind, ReceivedBaselines>>,
 }
 
 impl<P: Protocolize, E: Copy + Eq + Hash> Default for EntityManager<P, E> {
@@ -31,6 +42,9 @@ impl<P: Protocolize, E: Copy + Eq + Hash> Default for EntityManager<P, E> {
             handle_entity_map: BigMap::default(),
             receiver: EntityActionReceiver::default(),
             received_components: HashMap::default(),
+            received_resource_entities: HashSet::default(),
+            resource_entity: None,
+            received_baselines: HashMap::default(),
         }
     }
 }
@@ -42,74 +56,109 @@ impl<P: Protocolize, E: Copy + Eq + Hash> EntityManager<P, E> {
         &mut self,
         world: &mut W,
         server_tick: Tick,
+        packet_index: PacketIndex,
         reader: &mut BitReader,
+        authority_manager: &mut AuthorityManager<P, E>,
         event_stream: &mut VecDeque<Result<Event<P, E, C>, NaiaClientError>>,
-    ) {
-        self.read_updates(world, server_tick, reader, event_stream);
-        self.read_actions(world, reader, event_stream);
+    ) -> Result<(), SerdeErr> {
+        self.read_updates(world, server_tick, packet_index, reader, event_stream)?;
+        self.read_actions(world, reader, authority_manager, event_stream)?;
+        Ok(())
+    }
+
+    /// Returns the kinds of Components the Server has inserted into the given
+    /// Entity
+    pub fn component_kinds(&self, entity: &E) -> Option<&HashSet<P::Kind>> {
+        self.entity_records
+            .get(entity)
+            .map(|entity_record| &entity_record.component_kinds)
+    }
+
+    /// Returns the Entity whose Components are the Server's Resources, if
+    /// any Resources have been received
+    pub fn resource_entity(&self) -> Option<E> {
+        self.resource_entity
     }
 
     fn read_message_id(
         bit_reader: &mut BitReader,
         last_id_opt: &mut Option<MessageId>,
-    ) -> MessageId {
+    ) -> Result<MessageId, SerdeErr> {
         let current_id = if let Some(last_id) = last_id_opt {
             // read diff
-            let id_diff = UnsignedVariableInteger::<3>::de(bit_reader).unwrap().get() as MessageId;
+            let id_diff = UnsignedVariableInteger::<3>::de(bit_reader)?.get() as MessageId;
             last_id.wrapping_add(id_diff)
         } else {
             // read message id
-            MessageId::de(bit_reader).unwrap()
+            MessageId::de(bit_reader)?
         };
         *last_id_opt = Some(current_id);
-        current_id
+        Ok(current_id)
     }
 
     fn read_actions<W: WorldMutType<P, E>, C: ChannelIndex>(
         &mut self,
         world: &mut W,
         reader: &mut BitReader,
+        authority_manager: &mut AuthorityManager<P, E>,
         event_stream: &mut VecDeque<Result<Event<P, E, C>, NaiaClientError>>,
-    ) {
+    ) -> Result<(), SerdeErr> {
         let mut last_read_id: Option<MessageId> = None;
-        let action_count = message_list_header::read(reader);
+        let action_count = message_list_header::read(reader)?;
         for _ in 0..action_count {
-            self.read_action(reader, &mut last_read_id);
+            self.read_action(reader, &mut last_read_id)?;
         }
-        self.process_incoming_actions(world, event_stream);
+        self.process_incoming_actions(world, authority_manager, event_stream);
+        Ok(())
     }
 
-    fn read_action(&mut self, reader: &mut BitReader, last_read_id: &mut Option<MessageId>) {
-        let action_id = Self::read_message_id(reader, last_read_id);
+    fn read_action(
+        &mut self,
+        reader: &mut BitReader,
+        last_read_id: &mut Option<MessageId>,
+    ) -> Result<(), SerdeErr> {
+        let action_id = Self::read_message_id(reader, last_read_id)?;
 
-        let action_type = EntityActionType::de(reader).unwrap();
+        let action_type = EntityActionType::de(reader)?;
 
         match action_type {
             // Entity Creation
             EntityActionType::SpawnEntity => {
                 // read entity
-                let net_entity = NetEntity::de(reader).unwrap();
+                let net_entity = NetEntity::de(reader)?;
+
+                // read whether the entity holds the Server's Resources
+                if bool::de(reader)? {
+                    self.received_resource_entities.insert(net_entity);
+                }
 
                 // read components
-                let components_num = UnsignedVariableInteger::<3>::de(reader).unwrap().get();
-                let mut component_kinds = Vec::new();
+                let components_num = UnsignedVariableInteger::<3>::de(reader)?.get();
+                let mut components = Vec::new();
                 for _ in 0..components_num {
-                    let new_component = P::read(reader, self);
-                    let new_component_kind = new_component.dyn_ref().kind();
-                    self.received_components
-                        .insert((net_entity, new_component_kind), new_component);
-                    component_kinds.push(new_component_kind);
+                    components.push(P::read(reader, self)?);
                 }
+                let component_kinds = components
+                    .iter()
+                    .map(|component| component.dyn_ref().kind())
+                    .collect();
 
-                self.receiver.buffer_action(
+                // a retransmitted spawn's Components have been received already
+                if self.receiver.buffer_action(
                     action_id,
                     EntityAction::SpawnEntity(net_entity, component_kinds),
-                );
+                ) {
+                    for component in components {
+                        let component_kind = component.dyn_ref().kind();
+                        self.received_components
+                            .insert((net_entity, component_kind), component);
+                    }
+                }
             }
             // Entity Deletion
             EntityActionType::DespawnEntity => {
                 // read all data
-                let net_entity = NetEntity::de(reader).unwrap();
+                let net_entity = NetEntity::de(reader)?;
 
                 self.receiver
                     .buffer_action(action_id, EntityAction::DespawnEntity(net_entity));
@@ -117,37 +166,55 @@ impl<P: Protocolize, E: Copy + Eq + Hash> EntityManager<P, E> {
             // Add Component to Entity
             EntityActionType::InsertComponent => {
                 // read all data
-                let net_entity = NetEntity::de(reader).unwrap();
-                let new_component = P::read(reader, self);
+                let net_entity = NetEntity::de(reader)?;
+                let new_component = P::read(reader, self)?;
                 let new_component_kind = new_component.dyn_ref().kind();
 
-                self.receiver.buffer_action(
+                if self.receiver.buffer_action(
                     action_id,
                     EntityAction::InsertComponent(net_entity, new_component_kind),
-                );
-                self.received_components
-                    .insert((net_entity, new_component_kind), new_component);
+                ) {
+                    self.received_components
+                        .insert((net_entity, new_component_kind), new_component);
+                }
             }
             // Component Removal
             EntityActionType::RemoveComponent => {
                 // read all data
-                let net_entity = NetEntity::de(reader).unwrap();
-                let component_kind = P::Kind::de(reader).unwrap();
+                let net_entity = NetEntity::de(reader)?;
+                let component_kind = P::Kind::de(reader)?;
 
                 self.receiver.buffer_action(
                     action_id,
                     EntityAction::RemoveComponent(net_entity, component_kind),
                 );
             }
+            // Authority given to the Client
+            EntityActionType::GrantAuthority => {
+                let net_entity = NetEntity::de(reader)?;
+
+                self.receiver
+                    .buffer_action(action_id, EntityAction::GrantAuthority(net_entity));
+            }
+            // Authority taken from the Client
+            EntityActionType::RevokeAuthority => {
+                let net_entity = NetEntity::de(reader)?;
+
+                self.receiver
+                    .buffer_action(action_id, EntityAction::RevokeAuthority(net_entity));
+            }
             EntityActionType::Noop => {
                 self.receiver.buffer_action(action_id, EntityAction::Noop);
             }
         }
+
+        Ok(())
     }
 
     fn process_incoming_actions<W: WorldMutType<P, E>, C: ChannelIndex>(
         &mut self,
         world: &mut W,
+        authority_manager: &mut AuthorityManager<P, E>,
         event_stream: &mut VecDeque<Result<Event<P, E, C>, NaiaClientError>>,
     ) {
         let incoming_actions = self.receiver.receive_actions();
@@ -159,30 +226,55 @@ impl<P: Protocolize, E: Copy + Eq + Hash> EntityManager<P, E> {
                     //info!("spawn entity: {}", e_u16);
 
                     if self.local_to_world_entity.contains_key(&net_entity) {
-                        panic!("attempted to insert duplicate entity");
+                        warn!("received message attempting to spawn duplicate entity");
+                        for component_kind in components {
+                            self.received_components
+                                .remove(&(net_entity, component_kind));
+                        }
+                        continue;
                     }
 
                     // set up entity
-                    let world_entity = world.spawn_entity();
+                    let is_resource_entity = self.received_resource_entities.remove(&net_entity);
+                    let world_entity = if is_resource_entity {
+                        world.spawn_resource_entity()
+                    } else {
+                        world.spawn_entity()
+                    };
                     self.local_to_world_entity.insert(net_entity, world_entity);
                     let entity_handle = self.handle_entity_map.insert(world_entity);
                     let mut entity_record = EntityRecord::new(net_entity, entity_handle);
 
-                    event_stream.push_back(Ok(Event::SpawnEntity(world_entity)));
+                    if is_resource_entity {
+                        self.resource_entity = Some(world_entity);
+                    } else {
+                        event_stream.push_back(Ok(Event::SpawnEntity(world_entity)));
+                    }
 
                     // read component list
                     for component_kind in components {
-                        let component = self
+                        let component = if let Some(component) = self
                             .received_components
                             .remove(&(net_entity, component_kind))
-                            .unwrap();
+                        {
+                            component
+                        } else {
+                            // a component kind was listed twice
+                            continue;
+                        };
 
                         entity_record.component_kinds.insert(component_kind);
 
                         component.extract_and_insert(&world_entity, world);
 
-                        event_stream
-                            .push_back(Ok(Event::InsertComponent(world_entity, component_kind)));
+                        if is_resource_entity {
+                            event_stream.push_back(Ok(Event::InsertResource(component_kind)));
+                        } else {
+                            event_stream.push_back(Ok(Event::InsertComponent(
+                                world_entity,
+                                component_kind,
+                            )));
+                        }
                     }
                     //
 
@@ -192,9 +284,19 @@ impl<P: Protocolize, E: Copy + Eq + Hash> EntityManager<P, E> {
                     //let e_u16: u16 = net_entity.into();
                     //info!("despawn entity: {}", e_u16);
 
+                    self.received_baselines.remove(&net_entity);
+
                     if let Some(world_entity) = self.local_to_world_entity.remove(&net_entity) {
                         if self.entity_records.remove(&world_entity).is_none() {
-                            panic!("despawning an uninitialized entity");
+                            warn!("received message attempting to despawn uninitialized entity");
+                            continue;
+                        }
+
+                        authority_manager.revoke(&world_entity);
+
+                        let is_resource_entity = self.resource_entity == Some(world_entity);
+                        if is_resource_entity {
+                            self.resource_entity = None;
                         }
 
                         // Generate event for each component, handing references off just in
@@ -203,68 +305,133 @@ impl<P: Protocolize, E: Copy + Eq + Hash> EntityManager<P, E> {
                             if let Some(component) =
                                 world.remove_component_of_kind(&world_entity, &component_kind)
                             {
-                                event_stream
-                                    .push_back(Ok(Event::RemoveComponent(world_entity, component)));
+                                if is_resource_entity {
+                                    event_stream.push_back(Ok(Event::RemoveResource(component)));
+                                } else {
+                                    event_stream.push_back(Ok(Event::RemoveComponent(
+                                        world_entity,
+                                        component,
+                                    )));
+                                }
                             }
                         }
 
                         world.despawn_entity(&world_entity);
 
-                        event_stream.push_back(Ok(Event::DespawnEntity(world_entity)));
+                        if !is_resource_entity {
+                            event_stream.push_back(Ok(Event::DespawnEntity(world_entity)));
+                        }
                     } else {
-                        panic!("received message attempting to delete nonexistent entity");
+                        warn!("received message attempting to despawn nonexistent entity");
                     }
                 }
                 EntityAction::InsertComponent(net_entity, component_kind) => {
                     //let e_u16: u16 = net_entity.into();
                     //info!("insert component for: {}", e_u16);
 
-                    let component = self
+                    let component = if let Some(component) = self
                         .received_components
                         .remove(&(net_entity, component_kind))
-                        .unwrap();
-
-                    if !self.local_to_world_entity.contains_key(&net_entity) {
-                        panic!(
-                            "attempting to add a component to nonexistent entity: {}",
-                            Into::<u16>::into(net_entity)
-                        );
+                    {
+                        component
                     } else {
-                        let world_entity = self.local_to_world_entity.get(&net_entity).unwrap();
-
-                        let entity_record = self.entity_records.get_mut(world_entity).unwrap();
+                        continue;
+                    };
+
+                    if let Some(world_entity) = self.local_to_world_entity.get(&net_entity) {
+                        let entity_record = if let Some(entity_record) =
+                            self.entity_records.get_mut(world_entity)
+                        {
+                            entity_record
+                        } else {
+                            continue;
+                        };
 
                         entity_record.component_kinds.insert(component_kind);
 
                         component.extract_and_insert(world_entity, world);
 
-                        event_stream
-                            .push_back(Ok(Event::InsertComponent(*world_entity, component_kind)));
+                        authority_manager.insert_component(world, world_entity, &component_kind);
+
+                        if self.resource_entity == Some(*world_entity) {
+                            event_stream.push_back(Ok(Event::InsertResource(component_kind)));
+                        } else {
+                            event_stream.push_back(Ok(Event::InsertComponent(
+                                *world_entity,
+                                component_kind,
+                            )));
+                        }
+                    } else {
+                        warn!(
+                            "received message attempting to add a component to nonexistent entity: {}",
+                            Into::<u16>::into(net_entity)
+                        );
                     }
                 }
                 EntityAction::RemoveComponent(net_entity, component_kind) => {
                     //let e_u16: u16 = net_entity.into();
                     //info!("remove component for: {}", e_u16);
 
-                    let world_entity = self
-                        .local_to_world_entity
-                        .get_mut(&net_entity)
-                        .expect("attempting to delete component of nonexistent entity");
-                    let entity_record = self
-                        .entity_records
-                        .get_mut(world_entity)
-                        .expect("attempting to delete component of nonexistent entity");
+                    let (world_entity, entity_record) =
+                        if let Some(world_entity) = self.local_to_world_entity.get(&net_entity) {
+                            if let Some(entity_record) = self.entity_records.get_mut(world_entity) {
+                                (world_entity, entity_record)
+                            } else {
+                                continue;
+                            }
+                        } else {
+                            warn!(
+                            "received message attempting to remove component of nonexistent entity"
+                        );
+                            continue;
+                        };
                     if entity_record.component_kinds.remove(&component_kind) {
+                        authority_manager.remove_component(world_entity, &component_kind);
+
                         // Get component for last change
-                        let component = world
-                            .remove_component_of_kind(world_entity, &component_kind)
-                            .expect("Component already removed?");
+                        let component = if let Some(component) =
+                            world.remove_component_of_kind(world_entity, &component_kind)
+                        {
+                            component
+                        } else {
+                            continue;
+                        };
 
                         // Generate event
-                        event_stream
-                            .push_back(Ok(Event::RemoveComponent(*world_entity, component)));
+                        if self.resource_entity == Some(*world_entity) {
+                            event_stream.push_back(Ok(Event::RemoveResource(component)));
+                        } else {
+                            event_stream
+                                .push_back(Ok(Event::RemoveComponent(*world_entity, component)));
+                        }
                     } else {
-                        panic!("attempting to delete nonexistent component of entity");
+                        warn!("received message attempting to remove nonexistent component");
+                    }
+                }
+                EntityAction::GrantAuthority(net_entity) => {
+                    if let Some(world_entity) = self.local_to_world_entity.get(&net_entity) {
+                        let entity_record =
+                            if let Some(entity_record) = self.entity_records.get(world_entity) {
+                                entity_record
+                            } else {
+                                continue;
+                            };
+
+                        authority_manager.grant(
+                            world,
+                            world_entity,
+                            &net_entity,
+                            &entity_record.component_kinds,
+                        );
+
+                        event_stream.push_back(Ok(Event::AuthorityGranted(*world_entity)));
+                    }
+                }
+                EntityAction::RevokeAuthority(net_entity) => {
+                    if let Some(world_entity) = self.local_to_world_entity.get(&net_entity) {
+                        if authority_manager.revoke(world_entity) {
+                            event_stream.push_back(Ok(Event::AuthorityRevoked(*world_entity)));
+                        }
                     }
                 }
                 EntityAction::Noop => {
@@ -278,41 +445,58 @@ impl<P: Protocolize, E: Copy + Eq + Hash> EntityManager<P, E> {
         &mut self,
         world: &mut W,
         server_tick: Tick,
+        packet_index: PacketIndex,
         reader: &mut BitReader,
         event_stream: &mut VecDeque<Result<Event<P, E, C>, NaiaClientError>>,
-    ) {
-        let update_count = message_list_header::read(reader);
+    ) -> Result<(), SerdeErr> {
+        let update_count = message_list_header::read(reader)?;
         for _ in 0..update_count {
-            self.read_update(world, server_tick, reader, event_stream);
+            self.read_update(world, server_tick, packet_index, reader, event_stream)?;
         }
+        Ok(())
     }
 
     fn read_update<W: WorldMutType<P, E>, C: ChannelIndex>(
         &mut self,
         world: &mut W,
         server_tick: Tick,
+        packet_index: PacketIndex,
         reader: &mut BitReader,
         event_stream: &mut VecDeque<Result<Event<P, E, C>, NaiaClientError>>,
-    ) {
-        let net_entity = NetEntity::de(reader).unwrap();
+    ) -> Result<(), SerdeErr> {
+        let net_entity = NetEntity::de(reader)?;
 
-        let components_number = UnsignedVariableInteger::<3>::de(reader).unwrap().get();
+        let components_number = UnsignedVariableInteger::<3>::de(reader)?.get();
 
         for _ in 0..components_number {
             // read incoming update
-            let component_update = P::read_create_update(reader);
-            let component_kind = component_update.kind;
+            let component_kind = P::Kind::de(reader)?;
+            let mut baselines = self
+                .received_baselines
+                .entry(net_entity)
+                .or_default()
+                .entry(component_kind)
+                .or_default()
+                .for_packet(packet_index);
+            let component_update =
+                P::read_create_update_of(&component_kind, reader, &mut baselines)?;
 
             if let Some(world_entity) = self.local_to_world_entity.get(&net_entity) {
                 world.component_apply_update(self, world_entity, &component_kind, component_update);
 
-                event_stream.push_back(Ok(Event::UpdateComponent(
-                    server_tick,
-                    *world_entity,
-                    component_kind,
-                )));
+                if self.resource_entity == Some(*world_entity) {
+                    event_stream.push_back(Ok(Event::UpdateResource(server_tick, component_kind)));
+                } else {
+                    event_stream.push_back(Ok(Event::UpdateComponent(
+                        server_tick,
+                        *world_entity,
+                        component_kind,
+                    )));
+                }
             }
         }
+
+        Ok(())
     }
 }
 
@@ -342,11 +526,10 @@ impl<P: Protocolize, E: Copy + Eq + Hash> NetEntityHandleConverter for EntityMan
         entity_record.net_entity
     }
 
-    fn net_entity_to_handle(&self, net_entity: &NetEntity) -> EntityHandle {
-        let entity = self
-            .local_to_world_entity
-            .get(net_entity)
-            .expect("no entity exists associated with given net entity");
-        self.entity_to_handle(entity)
+    fn net_entity_to_handle(&self, net_entity: &NetEntity) -> Option<EntityHandle> {
+        let entity = self.local_to_world_entity.get(net_entity)?;
+        self.entity_records
+            .get(entity)
+            .map(|entity_record| entity_record.entity_handle)
     }
 }
@@ -0,0 +1,626 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    hash::Hash,
+    time::Duration,
+};
+
+use naia_shared::{
+    message_list_header,
+    serde::{BitCounter, BitReader, BitWrite, BitWriter, Serde, SerdeErr, UnsignedVariableInteger},
+    wrapping_diff, ChannelSender, DiffMask, EntityAction, EntityActionEvent, EntityActionType,
+    Instant, KeyGenerator, MessageId, NetEntity, NetEntityHandleConverter, PacketIndex,
+    PacketNotifiable, Protocolize, ReliableSender, WorldRefType, MTU_SIZE_BITS,
+};
+
+use super::mut_channel::MutChannel;
+
+const RESEND_ACTION_RTT_FACTOR: f32 = 1.5;
+const DROP_UPDATE_RTT_FACTOR: f32 = 1.5;
+const ACTION_RECORD_TTL: Duration = Duration::from_secs(60);
+
+pub type ActionId = MessageId;
+
+struct HostComponentRecord {
+    mut_channel: MutChannel,
+    delivered: bool,
+}
+
+struct HostEntityRecord<E: Copy, K> {
+    entity: E,
+    spawned: bool,
+    despawned: bool,
+    components: HashMap<K, HostComponentRecord>,
+}
+
+/// Manages the Entities spawned by the Client, and keeps them in sync on the
+/// Server
+pub struct HostEntityManager<P: Protocolize, E: Copy + Eq + Hash> {
+    // World
+    entity_to_net_entity: HashMap<E, NetEntity>,
+    entity_records: HashMap<NetEntity, HostEntityRecord<E, P::Kind>>,
+    net_entity_generator: KeyGenerator<NetEntity>,
+
+    // Actions
+    outgoing_actions: ReliableSender<EntityActionEvent<NetEntity, P::Kind>>,
+    next_send_actions: VecDeque<(ActionId, EntityActionEvent<NetEntity, P::Kind>)>,
+    #[allow(clippy::type_complexity)]
+    sent_action_packets:
+        HashMap<PacketIndex, (Instant, Vec<(ActionId, EntityAction<NetEntity, P::Kind>)>)>,
+
+    // Updates
+    next_send_updates: HashMap<NetEntity, HashSet<P::Kind>>,
+    #[allow(clippy::type_complexity)]
+    sent_updates: HashMap<PacketIndex, (Instant, HashMap<(NetEntity, P::Kind), DiffMask>)>,
+}
+
+impl<P: Protocolize, E: Copy + Eq + Hash> Default for HostEntityManager<P, E> {
+    fn default() -> Self {
+        Self {
+            entity_to_net_entity: HashMap::new(),
+            entity_records: HashMap::new(),
+            net_entity_generator: KeyGenerator::default(),
+            outgoing_actions: ReliableSender::new(RESEND_ACTION_RTT_FACTOR),
+            next_send_actions: VecDeque::new(),
+            sent_action_packets: HashMap::new(),
+            next_send_updates: HashMap::new(),
+            sent_updates: HashMap::new(),
+        }
+    }
+}
+
+impl<P: Protocolize, E: Copy + Eq + Hash> HostEntityManager<P, E> {
+    // World
+
+    pub fn has_entity(&self, entity: &E) -> bool {
+        self.entity_to_net_entity.contains_key(entity)
+    }
+
+    pub fn spawn_entity(&mut self, entity: &E) {
+        if self.entity_to_net_entity.contains_key(entity) {
+            return;
+        }
+
+        let net_entity = self.net_entity_generator.generate();
+        self.entity_to_net_entity.insert(*entity, net_entity);
+        self.entity_records.insert(
+            net_entity,
+            HostEntityRecord {
+                entity: *entity,
+                spawned: false,
+                despawned: false,
+                components: HashMap::new(),
+            },
+        );
+
+        self.outgoing_actions
+            .send_message(EntityActionEvent::SpawnEntity(net_entity));
+    }
+
+    pub fn despawn_entity(&mut self, entity: &E) {
+        if let Some(net_entity) = self.entity_to_net_entity.remove(entity) {
+            let record = self.entity_records.get_mut(&net_entity).unwrap();
+            record.despawned = true;
+            record.components.clear();
+
+            self.next_send_updates.remove(&net_entity);
+            self.outgoing_actions
+                .send_message(EntityActionEvent::DespawnEntity(net_entity));
+        }
+    }
+
+    pub fn insert_component(&mut self, entity: &E, component: &P::Kind, mut_channel: MutChannel) {
+        if let Some(net_entity) = self.entity_to_net_entity.get(entity) {
+            let record = self.entity_records.get_mut(net_entity).unwrap();
+            record.components.insert(
+                *component,
+                HostComponentRecord {
+                    mut_channel,
+                    delivered: false,
+                },
+            );
+
+            // Components inserted before the spawn is delivered are sent along with it
+            if record.spawned {
+                self.outgoing_actions
+                    .send_message(EntityActionEvent::InsertComponent(*net_entity, *component));
+            }
+        }
+    }
+
+    pub fn remove_component(&mut self, entity: &E, component: &P::Kind) {
+        if let Some(net_entity) = self.entity_to_net_entity.get(entity) {
+            let record = self.entity_records.get_mut(net_entity).unwrap();
+            if record.components.remove(component).is_none() {
+                return;
+            }
+
+            if let Some(update_kinds) = self.next_send_updates.get_mut(net_entity) {
+                update_kinds.remove(component);
+            }
+
+            if record.spawned {
+                self.outgoing_actions
+                    .send_message(EntityActionEvent::RemoveComponent(*net_entity, *component));
+            }
+        }
+    }
+
+    // Reader
+
+    /// Reads the Entities the Server has refused, and returns those which are
+    /// still alive. The Server is told of their despawn as usual
+    pub fn read_rejections(&mut self, reader: &mut BitReader) -> Result<Vec<E>, SerdeErr> {
+        let rejection_count = message_list_header::read(reader)?;
+
+        let mut rejected_entities = Vec::new();
+        for _ in 0..rejection_count {
+            let net_entity = NetEntity::de(reader)?;
+
+            // a retransmitted rejection may arrive after the Entity is gone
+            if let Some(record) = self.entity_records.get(&net_entity) {
+                if record.spawned && !record.despawned {
+                    let entity = record.entity;
+                    self.despawn_entity(&entity);
+                    rejected_entities.push(entity);
+                }
+            }
+        }
+
+        Ok(rejected_entities)
+    }
+
+    // Writer
+
+    pub fn collect_outgoing_messages(&mut self, now: &Instant, rtt_millis: &f32) {
+        self.collect_dropped_update_packets(rtt_millis);
+        self.collect_dropped_action_packets();
+
+        self.outgoing_actions.collect_messages(now, rtt_millis);
+        self.next_send_actions = self.outgoing_actions.take_next_messages();
+
+        self.collect_component_updates();
+    }
+
+    pub fn has_outgoing_messages(&self) -> bool {
+        !self.next_send_actions.is_empty() || !self.next_send_updates.is_empty()
+    }
+
+    pub fn write_all<W: WorldRefType<P, E>>(
+        &mut self,
+        now: &Instant,
+        writer: &mut BitWriter,
+        packet_index: &PacketIndex,
+        world: &W,
+        converter: &dyn NetEntityHandleConverter,
+    ) {
+        self.write_updates(now, writer, packet_index, world, converter);
+        self.write_actions(now, writer, packet_index, world, converter);
+    }
+
+    // Collecting
+
+    fn collect_dropped_action_packets(&mut self) {
+        self.sent_action_packets
+            .retain(|_, (time_sent, _)| time_sent.elapsed() <= ACTION_RECORD_TTL);
+    }
+
+    fn collect_dropped_update_packets(&mut self, rtt_millis: &f32) {
+        let drop_duration = Duration::from_millis((DROP_UPDATE_RTT_FACTOR * rtt_millis) as u64);
+
+        let dropped_packets: Vec<PacketIndex> = self
+            .sent_updates
+            .iter()
+            .filter(|(_, (time_sent, _))| time_sent.elapsed() > drop_duration)
+            .map(|(packet_index, _)| *packet_index)
+            .collect();
+
+        for packet_index in dropped_packets {
+            let (_, diff_mask_map) = self.sent_updates.remove(&packet_index).unwrap();
+
+            // queue the dropped Properties up to be sent again
+            for ((net_entity, component_kind), diff_mask) in diff_mask_map {
+                if let Some(record) = self.entity_records.get(&net_entity) {
+                    if let Some(component_record) = record.components.get(&component_kind) {
+                        component_record.mut_channel.or_mask(&diff_mask);
+                    }
+                }
+            }
+        }
+    }
+
+    fn collect_component_updates(&mut self) {
+        self.next_send_updates.clear();
+
+        for (net_entity, record) in &self.entity_records {
+            if !record.spawned || record.despawned {
+                continue;
+            }
+            for (component_kind, component_record) in &record.components {
+                if component_record.delivered && !component_record.mut_channel.diff_mask_is_clear()
+                {
+                    self.next_send_updates
+                        .entry(*net_entity)
+                        .or_default()
+                        .insert(*component_kind);
+                }
+            }
+        }
+    }
+
+    fn action_delivered(&mut self, action: EntityAction<NetEntity, P::Kind>) {
+        match action {
+            EntityAction::SpawnEntity(net_entity, spawned_components) => {
+                let mut next_actions = Vec::new();
+
+                if let Some(record) = self.entity_records.get_mut(&net_entity) {
+                    record.spawned = true;
+
+                    if !record.despawned {
+                        // reconcile Components changed while the spawn was in flight
+                        for (component_kind, component_record) in record.components.iter_mut() {
+                            if spawned_components.contains(component_kind) {
+                                component_record.delivered = true;
+                            } else {
+                                next_actions.push(EntityActionEvent::InsertComponent(
+                                    net_entity,
+                                    *component_kind,
+                                ));
+                            }
+                        }
+                        for component_kind in spawned_components {
+                            if !record.components.contains_key(&component_kind) {
+                                next_actions.push(EntityActionEvent::RemoveComponent(
+                                    net_entity,
+                                    component_kind,
+                                ));
+                            }
+                        }
+                    }
+                }
+
+                for next_action in next_actions {
+                    self.outgoing_actions.send_message(next_action);
+                }
+            }
+            EntityAction::DespawnEntity(net_entity) => {
+                if self.entity_records.remove(&net_entity).is_some() {
+                    self.net_entity_generator.recycle_key(&net_entity);
+                }
+            }
+            EntityAction::InsertComponent(net_entity, component_kind) => {
+                if let Some(record) = self.entity_records.get_mut(&net_entity) {
+                    if let Some(component_record) = record.components.get_mut(&component_kind) {
+                        component_record.delivered = true;
+                    }
+                }
+            }
+            EntityAction::RemoveComponent(_, _)
+            | EntityAction::GrantAuthority(_)
+            | EntityAction::RevokeAuthority(_)
+            | EntityAction::Noop => {}
+        }
+    }
+
+    // Writing actions
+
+    fn write_action_id(
+        bit_writer: &mut dyn BitWrite,
+        last_id_opt: &mut Option<ActionId>,
+        current_id: &ActionId,
+    ) {
+        if let Some(last_id) = last_id_opt {
+            // write diff
+            let id_diff = wrapping_diff(*last_id, *current_id);
+            let id_diff_encoded = UnsignedVariableInteger::<3>::new(id_diff);
+            id_diff_encoded.ser(bit_writer);
+        } else {
+            // write message id
+            current_id.ser(bit_writer);
+        }
+        *last_id_opt = Some(*current_id);
+    }
+
+    fn write_actions<W: WorldRefType<P, E>>(
+        &mut self,
+        now: &Instant,
+        writer: &mut BitWriter,
+        packet_index: &PacketIndex,
+        world: &W,
+        converter: &dyn NetEntityHandleConverter,
+    ) {
+        let mut message_count = 0;
+
+        // Header
+        {
+            // Measure
+            let current_packet_size = writer.bit_count();
+            if current_packet_size > MTU_SIZE_BITS {
+                message_list_header::write(writer, 0);
+                return;
+            }
+
+            let mut counter = BitCounter::default();
+            message_list_header::write(&mut counter, 123);
+
+            // Check for overflow
+            if current_packet_size + counter.bit_count() > MTU_SIZE_BITS {
+                message_list_header::write(writer, 0);
+                return;
+            }
+
+            // Find how many messages will fit into the packet
+            let mut last_written_id: Option<ActionId> = None;
+
+            for action_index in 0..self.next_send_actions.len() {
+                self.write_action(
+                    world,
+                    converter,
+                    &mut counter,
+                    action_index,
+                    &mut last_written_id,
+                );
+                if current_packet_size + counter.bit_count() <= MTU_SIZE_BITS {
+                    message_count += 1;
+                } else {
+                    break;
+                }
+            }
+        }
+
+        // Write header
+        message_list_header::write(writer, message_count as u64);
+
+        // Actions
+        {
+            let mut last_written_id: Option<ActionId> = None;
+            let mut written_actions = Vec::new();
+
+            // Write messages
+            for action_index in 0..message_count {
+                let written_action =
+                    self.write_action(world, converter, writer, action_index, &mut last_written_id);
+                written_actions.push(written_action);
+            }
+
+            // Pop messages
+            self.next_send_actions.drain(..message_count);
+
+            if !written_actions.is_empty() {
+                self.sent_action_packets
+                    .insert(*packet_index, (now.clone(), written_actions));
+            }
+        }
+    }
+
+    // Writes the action at the given index, and returns a record of what was
+    // actually written
+    fn write_action<W: WorldRefType<P, E>>(
+        &self,
+        world: &W,
+        converter: &dyn NetEntityHandleConverter,
+        bit_writer: &mut dyn BitWrite,
+        action_index: usize,
+        last_written_id: &mut Option<ActionId>,
+    ) -> (ActionId, EntityAction<NetEntity, P::Kind>) {
+        let (action_id, action) = self.next_send_actions.get(action_index).unwrap();
+
+        // write message id
+        Self::write_action_id(bit_writer, last_written_id, action_id);
+
+        let action_record = match action {
+            EntityActionEvent::SpawnEntity(net_entity) => {
+                EntityActionType::SpawnEntity.ser(bit_writer);
+
+                // write net entity
+                net_entity.ser(bit_writer);
+
+                // get component list
+                let record = self.entity_records.get(net_entity).unwrap();
+                let component_kinds: Vec<P::Kind> = record
+                    .components
+                    .keys()
+                    .filter(|component_kind| {
+                        !record.despawned
+                            && world.has_component_of_kind(&record.entity, component_kind)
+                    })
+                    .copied()
+                    .collect();
+
+                // write number of components
+                UnsignedVariableInteger::<3>::new(component_kinds.len() as i128).ser(bit_writer);
+
+                for component_kind in &component_kinds {
+                    // write component payload
+                    world
+                        .component_of_kind(&record.entity, component_kind)
+                        .unwrap()
+                        .write(bit_writer, converter);
+                }
+
+                EntityAction::SpawnEntity(*net_entity, component_kinds)
+            }
+            EntityActionEvent::DespawnEntity(net_entity) => {
+                EntityActionType::DespawnEntity.ser(bit_writer);
+
+                // write net entity
+                net_entity.ser(bit_writer);
+
+                EntityAction::DespawnEntity(*net_entity)
+            }
+            EntityActionEvent::InsertComponent(net_entity, component_kind) => {
+                let record = self.entity_records.get(net_entity).unwrap();
+                if record.despawned
+                    || !record.components.contains_key(component_kind)
+                    || !world.has_component_of_kind(&record.entity, component_kind)
+                {
+                    EntityActionType::Noop.ser(bit_writer);
+
+                    EntityAction::Noop
+                } else {
+                    EntityActionType::InsertComponent.ser(bit_writer);
+
+                    // write net entity
+                    net_entity.ser(bit_writer);
+
+                    // write component payload
+                    world
+                        .component_of_kind(&record.entity, component_kind)
+                        .unwrap()
+                        .write(bit_writer, converter);
+
+                    EntityAction::InsertComponent(*net_entity, *component_kind)
+                }
+            }
+            EntityActionEvent::RemoveComponent(net_entity, component_kind) => {
+                EntityActionType::RemoveComponent.ser(bit_writer);
+
+                // write net entity
+                net_entity.ser(bit_writer);
+
+                // write component kind
+                component_kind.ser(bit_writer);
+
+                EntityAction::RemoveComponent(*net_entity, *component_kind)
+            }
+            // authority is only ever handed out by the Server
+            EntityActionEvent::GrantAuthority(_) | EntityActionEvent::RevokeAuthority(_) => {
+                EntityActionType::Noop.ser(bit_writer);
+
+                EntityAction::Noop
+            }
+        };
+
+        (*action_id, action_record)
+    }
+
+    // Writing updates
+
+    fn write_updates<W: WorldRefType<P, E>>(
+        &mut self,
+        now: &Instant,
+        writer: &mut BitWriter,
+        packet_index: &PacketIndex,
+        world: &W,
+        converter: &dyn NetEntityHandleConverter,
+    ) {
+        let mut update_entities: Vec<NetEntity> = Vec::new();
+
+        // Header
+        {
+            // Measure
+            let current_packet_size = writer.bit_count();
+            if current_packet_size > MTU_SIZE_BITS {
+                message_list_header::write(writer, 0);
+                return;
+            }
+
+            let mut counter = BitCounter::default();
+            message_list_header::write(&mut counter, 123);
+
+            // Check for overflow
+            if current_packet_size + counter.bit_count() > MTU_SIZE_BITS {
+                message_list_header::write(writer, 0);
+                return;
+            }
+
+            // Find how many updates will fit into the packet
+            for net_entity in self.next_send_updates.keys() {
+                self.write_update(world, converter, &mut counter, net_entity);
+                if current_packet_size + counter.bit_count() <= MTU_SIZE_BITS {
+                    update_entities.push(*net_entity);
+                } else {
+                    break;
+                }
+            }
+        }
+
+        // Write header
+        message_list_header::write(writer, update_entities.len() as u16);
+
+        // Updates
+        let mut sent_updates_map = HashMap::new();
+        for net_entity in update_entities {
+            let diff_masks = self.write_update(world, converter, writer, &net_entity);
+            let record = self.entity_records.get(&net_entity).unwrap();
+
+            // having copied the diff masks for this update, clear the components
+            for (component_kind, diff_mask) in diff_masks {
+                record
+                    .components
+                    .get(&component_kind)
+                    .unwrap()
+                    .mut_channel
+                    .clear_mask();
+                sent_updates_map.insert((net_entity, component_kind), diff_mask);
+            }
+
+            self.next_send_updates.remove(&net_entity);
+        }
+
+        if !sent_updates_map.is_empty() {
+            self.sent_updates
+                .insert(*packet_index, (now.clone(), sent_updates_map));
+        }
+    }
+
+    // Writes the update for the given Entity, and returns the diff masks
+    // that were written
+    fn write_update<W: WorldRefType<P, E>>(
+        &self,
+        world: &W,
+        converter: &dyn NetEntityHandleConverter,
+        bit_writer: &mut dyn BitWrite,
+        net_entity: &NetEntity,
+    ) -> Vec<(P::Kind, DiffMask)> {
+        let component_set = self.next_send_updates.get(net_entity).unwrap();
+        let record = self.entity_records.get(net_entity).unwrap();
+        let mut diff_masks = Vec::new();
+
+        // write net entity
+        net_entity.ser(bit_writer);
+
+        // write number of components
+        UnsignedVariableInteger::<3>::new(component_set.len() as u64).ser(bit_writer);
+
+        for component_kind in component_set {
+            // write component kind
+            component_kind.ser(bit_writer);
+
+            // get diff mask
+            let diff_mask = record
+                .components
+                .get(component_kind)
+                .unwrap()
+                .mut_channel
+                .diff_mask()
+                .expect("DiffMask should be readable");
+
+            // write payload
+            world
+                .component_of_kind(&record.entity, component_kind)
+                .expect("Component does not exist in World")
+                .write_update(&diff_mask, bit_writer, converter);
+
+            diff_masks.push((*component_kind, diff_mask));
+        }
+
+        diff_masks
+    }
+}
+
+// PacketNotifiable
+impl<P: Protocolize, E: Copy + Eq + Hash> PacketNotifiable for HostEntityManager<P, E> {
+    fn notify_packet_delivered(&mut self, packet_index: PacketIndex) {
+        // Updates
+        self.sent_updates.remove(&packet_index);
+
+        // Actions
+        if let Some((_, action_list)) = self.sent_action_packets.remove(&packet_index) {
+            for (action_id, action) in action_list {
+                if self.outgoing_actions.deliver_message(&action_id).is_some() {
+                    self.action_delivered(action);
+                }
+            }
+        }
+    }
+}
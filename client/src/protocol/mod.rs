@@ -1,3 +1,8 @@
+pub mod authority_manager;
 pub mod entity_manager;
 pub mod entity_record;
 pub mod entity_ref;
+pub mod host_entity_manager;
+pub mod mut_channel;
+pub mod prediction_manager;
+pub mod received_baselines;
@@ -0,0 +1,50 @@
+use std::sync::{Arc, RwLock};
+
+use naia_shared::{DiffMask, PropertyMutate};
+
+// MutChannel
+// Shared between a Client-owned Component's PropertyMutator and the
+// HostEntityManager, so that Property changes can be found & sent to the Server
+#[derive(Clone)]
+pub struct MutChannel {
+    mask: Arc<RwLock<DiffMask>>,
+}
+
+impl MutChannel {
+    pub fn new(diff_mask_length: u8) -> Self {
+        Self {
+            mask: Arc::new(RwLock::new(DiffMask::new(diff_mask_length))),
+        }
+    }
+
+    pub fn diff_mask(&self) -> Option<DiffMask> {
+        self.mask.as_ref().read().ok().map(|mask| mask.clone())
+    }
+
+    pub fn diff_mask_is_clear(&self) -> bool {
+        if let Ok(mask) = self.mask.as_ref().read() {
+            return mask.is_clear();
+        }
+        true
+    }
+
+    pub fn or_mask(&self, other_mask: &DiffMask) {
+        if let Ok(mut mask) = self.mask.as_ref().write() {
+            mask.or(other_mask);
+        }
+    }
+
+    pub fn clear_mask(&self) {
+        if let Ok(mut mask) = self.mask.as_ref().write() {
+            mask.clear();
+        }
+    }
+}
+
+impl PropertyMutate for MutChannel {
+    fn mutate(&mut self, property_index: u8) {
+        if let Ok(mut mask) = self.mask.as_ref().write() {
+            mask.set_bit(property_index, true);
+        }
+    }
+}
@@ -0,0 +1,202 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    hash::Hash,
+};
+
+use naia_shared::{
+    sequence_greater_than, serde::BitWriter, ChannelIndex, NetEntityHandleConverter, Protocolize,
+    Tick, WorldRefType,
+};
+
+use crate::{error::NaiaClientError, event::Event};
+
+// The most Ticks of predicted state kept for a single Entity
+const PREDICTION_HISTORY_SIZE: usize = 64;
+
+/// Decides whether a predicted Component (the first argument) is close
+/// enough to the state confirmed by the Server (the second argument)
+pub type PredictionComparer<P> = Box<dyn Fn(&P, &P) -> bool + Send + Sync>;
+
+/// The PredictionComparer of each Component kind which isn't compared exactly
+pub type PredictionComparers<P> = HashMap<<P as Protocolize>::Kind, PredictionComparer<P>>;
+
+struct PredictionRecord<E, P: Protocolize> {
+    predicted_entity: E,
+    // most recent Tick at the front, each holding a copy of the predicted
+    // Entity's Components
+    #[allow(clippy::type_complexity)]
+    history: VecDeque<(Tick, HashMap<P::Kind, P>)>,
+}
+
+/// Tracks Entities which the Client is predicting locally, and compares the
+/// state it predicted at each Tick with the authoritative state received from
+/// the Server for that Tick
+pub struct PredictionManager<P: Protocolize, E: Copy + Eq + Hash> {
+    records: HashMap<E, PredictionRecord<E, P>>,
+}
+
+impl<P: Protocolize, E: Copy + Eq + Hash> Default for PredictionManager<P, E> {
+    fn default() -> Self {
+        Self {
+            records: HashMap::new(),
+        }
+    }
+}
+
+impl<P: Protocolize, E: Copy + Eq + Hash> PredictionManager<P, E> {
+    pub fn predict(&mut self, confirmed_entity: &E, predicted_entity: &E) {
+        self.records.insert(
+            *confirmed_entity,
+            PredictionRecord {
+                predicted_entity: *predicted_entity,
+                history: VecDeque::new(),
+            },
+        );
+    }
+
+    /// Returns the predicted Entity, if the given Entity was being predicted
+    pub fn unpredict(&mut self, confirmed_entity: &E) -> Option<E> {
+        self.records
+            .remove(confirmed_entity)
+            .map(|record| record.predicted_entity)
+    }
+
+    pub fn predicted_entity(&self, confirmed_entity: &E) -> Option<E> {
+        self.records
+            .get(confirmed_entity)
+            .map(|record| record.predicted_entity)
+    }
+
+    /// Stores the state of the predicted Entity's Components at the given
+    /// Tick. Ticks must be recorded in increasing order, any older Tick is
+    /// ignored.
+    pub fn record<W: WorldRefType<P, E>>(
+        &mut self,
+        world: &W,
+        confirmed_entity: &E,
+        component_kinds: &HashSet<P::Kind>,
+        tick: Tick,
+    ) {
+        let record = match self.records.get_mut(confirmed_entity) {
+            Some(record) => record,
+            None => return,
+        };
+
+        if let Some((last_tick, _)) = record.history.front() {
+            if !sequence_greater_than(tick, *last_tick) {
+                return;
+            }
+        }
+
+        let mut states = HashMap::new();
+        for component_kind in component_kinds {
+            if let Some(component) =
+                world.component_of_kind(&record.predicted_entity, component_kind)
+            {
+                states.insert(*component_kind, component.protocol_copy());
+            }
+        }
+
+        record.history.push_front((tick, states));
+        record.history.truncate(PREDICTION_HISTORY_SIZE);
+    }
+
+    /// Checks the Component updates received since `first_event` against the
+    /// predictions made for their Tick, and emits a Rollback event for each
+    /// predicted Entity whose prediction turned out to be wrong. Components
+    /// without a PredictionComparer must match exactly
+    pub fn process_updates<W: WorldRefType<P, E>, C: ChannelIndex>(
+        &mut self,
+        world: &W,
+        converter: &dyn NetEntityHandleConverter,
+        comparers: &PredictionComparers<P>,
+        first_event: usize,
+        event_stream: &mut VecDeque<Result<Event<P, E, C>, NaiaClientError>>,
+    ) {
+        // the latest updated Tick of each predicted Entity, and whether any
+        // update for that Tick did not match the prediction
+        let mut corrections: Vec<(E, Tick, bool)> = Vec::new();
+
+        for event in event_stream.iter().skip(first_event) {
+            let (tick, entity, component_kind) = match event {
+                Ok(Event::UpdateComponent(tick, entity, component_kind)) => {
+                    (*tick, *entity, *component_kind)
+                }
+                Ok(Event::DespawnEntity(entity)) => {
+                    self.records.remove(entity);
+                    continue;
+                }
+                _ => continue,
+            };
+
+            let record = match self.records.get(&entity) {
+                Some(record) => record,
+                None => continue,
+            };
+            let predicted_state = match record
+                .history
+                .iter()
+                .find(|(predicted_tick, _)| *predicted_tick == tick)
+            {
+                Some((_, states)) => states.get(&component_kind),
+                None => continue,
+            };
+
+            let confirmed_state = world
+                .component_of_kind(&entity, &component_kind)
+                .map(|component| component.protocol_copy());
+            let mispredicted = match (predicted_state, confirmed_state) {
+                (Some(predicted), Some(confirmed)) => match comparers.get(&component_kind) {
+                    Some(comparer) => !comparer(predicted, &confirmed),
+                    None => {
+                        Self::write_component(predicted, converter)
+                            != Self::write_component(&confirmed, converter)
+                    }
+                },
+                (None, None) => false,
+                _ => true,
+            };
+
+            match corrections
+                .iter_mut()
+                .find(|(corrected_entity, _, _)| *corrected_entity == entity)
+            {
+                Some((_, last_tick, last_mispredicted)) => {
+                    if sequence_greater_than(tick, *last_tick) {
+                        *last_tick = tick;
+                        *last_mispredicted = mispredicted;
+                    } else if tick == *last_tick {
+                        *last_mispredicted |= mispredicted;
+                    }
+                }
+                None => corrections.push((entity, tick, mispredicted)),
+            }
+        }
+
+        for (entity, tick, mispredicted) in corrections {
+            let record = match self.records.get_mut(&entity) {
+                Some(record) => record,
+                // despawned later in the same packet
+                None => continue,
+            };
+
+            if mispredicted {
+                // every later prediction was built on a wrong state, and will be
+                // recorded again as the pending commands are replayed
+                record.history.clear();
+                event_stream.push_back(Ok(Event::Rollback(tick, entity)));
+            } else {
+                // the Server has confirmed the prediction, so it is no longer needed
+                record
+                    .history
+                    .retain(|(predicted_tick, _)| sequence_greater_than(*predicted_tick, tick));
+            }
+        }
+    }
+
+    fn write_component(component: &P, converter: &dyn NetEntityHandleConverter) -> Box<[u8]> {
+        let mut writer = BitWriter::default();
+        component.write(&mut writer, converter);
+        writer.to_bytes()
+    }
+}
@@ -0,0 +1,54 @@
+use std::collections::HashMap;
+
+use naia_shared::{sequence_less_than, PacketIndex, PropertyBaselines, MAX_BASELINE_DISTANCE};
+
+type PropertyValues = HashMap<u8, Vec<(PacketIndex, Box<[u8]>)>>;
+
+/// The values of a Component's delta-encoded Properties received in recent
+/// packets, which the Server may write the Component's updates against
+#[derive(Default)]
+pub struct ReceivedBaselines {
+    values: PropertyValues,
+}
+
+impl ReceivedBaselines {
+    /// Gets the PropertyBaselines to read an update from the given packet with
+    pub fn for_packet(&mut self, packet_index: PacketIndex) -> PacketBaselines<'_> {
+        PacketBaselines {
+            packet_index,
+            values: &mut self.values,
+        }
+    }
+}
+
+/// The PropertyBaselines of a Component update read from a given packet
+pub struct PacketBaselines<'a> {
+    packet_index: PacketIndex,
+    values: &'a mut PropertyValues,
+}
+
+impl<'a> PropertyBaselines for PacketBaselines<'a> {
+    fn baseline(&self, _: u8) -> Option<(u16, &[u8])> {
+        None
+    }
+
+    fn baseline_at(&self, property_index: u8, distance: u16) -> Option<&[u8]> {
+        let baseline_index = self.packet_index.wrapping_sub(distance);
+        self.values
+            .get(&property_index)?
+            .iter()
+            .find(|(index, _)| *index == baseline_index)
+            .map(|(_, value)| &value[..])
+    }
+
+    fn record(&mut self, property_index: u8, value: Box<[u8]>) {
+        // packets may be read out of order, so values are kept for longer than
+        // the Server writes against them
+        let oldest_index = self.packet_index.wrapping_sub(MAX_BASELINE_DISTANCE * 2);
+        let values = self.values.entry(property_index).or_default();
+        values.retain(|(index, _)| {
+            *index != self.packet_index && !sequence_less_than(*index, oldest_index)
+        });
+        values.push((self.packet_index, value));
+    }
+}
@@ -1,9 +1,18 @@
 use std::time::Duration;
 
-use naia_shared::{serde::Serde, wrapping_diff, Instant, Tick};
+use naia_shared::{
+    serde::{Serde, SerdeErr},
+    wrapping_diff, Instant, Tick, JITTER_BUFFER_FACTOR,
+};
 
 use crate::client::{BitReader, BitWriter};
 
+// How far the Client's ticking may be sped up or slowed down to catch up
+// with the Server. Unbounded, a run of bad Server ticks could stop the
+// Client's tick interval from being positive
+const TICK_SPEED_FACTOR_MIN: f32 = 0.5;
+const TICK_SPEED_FACTOR_MAX: f32 = 2.0;
+
 /// Manages the current tick for the host
 pub struct TickManager {
     tick_interval_millis: f32,
@@ -61,12 +70,17 @@ impl TickManager {
         client_tick
     }
 
-    pub fn read_server_tick(&mut self, reader: &mut BitReader, rtt: f32, jitter: f32) -> Tick {
-        let server_tick = Tick::de(reader).unwrap();
+    pub fn read_server_tick(
+        &mut self,
+        reader: &mut BitReader,
+        rtt: f32,
+        jitter: f32,
+    ) -> Result<Tick, SerdeErr> {
+        let server_tick = Tick::de(reader)?;
 
         self.record_server_tick(server_tick, rtt, jitter);
 
-        server_tick
+        Ok(server_tick)
     }
 
     pub fn recv_client_tick(&mut self) -> bool {
@@ -113,7 +127,7 @@ impl TickManager {
             self.ticks_recorded += 1;
         } else {
             self.tick_offset_avg = (0.9 * self.tick_offset_avg) + (0.1 * (tick_offset as f32));
-            let tick_offset_speed = (tick_offset - self.last_tick_offset) as f32;
+            let tick_offset_speed = tick_offset.wrapping_sub(self.last_tick_offset) as f32;
             self.tick_offset_speed_avg =
                 (0.9 * self.tick_offset_speed_avg) + (0.1 * tick_offset_speed);
         }
@@ -128,10 +142,13 @@ impl TickManager {
             self.tick_speed_factor += 0.1;
             self.tick_offset_speed_avg = 0.0;
         }
+        self.tick_speed_factor = self
+            .tick_speed_factor
+            .clamp(TICK_SPEED_FACTOR_MIN, TICK_SPEED_FACTOR_MAX);
 
         // Calculate incoming & outgoing jitter buffer tick offsets
 
-        let jitter_limit = jitter_average * 4.0;
+        let jitter_limit = jitter_average * JITTER_BUFFER_FACTOR;
         self.client_receiving_tick_adjust = jitter_limit / self.tick_interval_millis;
 
         // NOTE: I've struggled multiple times with why rtt_average instead of
@@ -133,20 +133,20 @@ impl App {
 
                     // Iterate through Characters, marching them from (0,0) to (20, N)
                     for entity in self.server.entities(self.world.proxy()) {
-                        if let Some(mut character) = self
-                            .server
-                            .entity_mut(self.world.proxy_mut(), &entity)
-                            .component::<Character>()
-                        {
+                        let mut entity_mut =
+                            self.server.entity_mut(self.world.proxy_mut(), &entity);
+                        if let Some(mut character) = entity_mut.component::<Character>() {
                             character.step();
                         }
+                        // the Character has moved, so check its scope again
+                        entity_mut.mark_scope_dirty();
                     }
 
                     // Update scopes of entities
                     {
                         let server = &mut self.server;
                         let world = &self.world;
-                        for (_, user_key, entity) in server.scope_checks() {
+                        for (_, user_key, entity) in server.pending_scope_checks() {
                             if let Some(character) = world.proxy().component::<Character>(&entity) {
                                 let x = *character.x;
                                 if (5..=15).contains(&x) {
@@ -19,7 +19,7 @@ pub fn tick(
     //info!("tick");
 
     // Update scopes of entities
-    for (_, user_key, entity) in server.scope_checks() {
+    for (_, user_key, entity) in server.pending_scope_checks() {
         // You'd normally do whatever checks you need to in here..
         // to determine whether each Entity should be in scope or not.
 
@@ -1,9 +1,9 @@
 use std::collections::HashMap;
 
 use naia_shared::{
-    BigMap, ComponentUpdate, NetEntityHandleConverter, ProtocolInserter, Protocolize,
-    ReplicaDynMutWrapper, ReplicaDynRefWrapper, ReplicaMutWrapper, ReplicaRefWrapper, Replicate,
-    ReplicateSafe, WorldMutType, WorldRefType,
+    BigMap, ComponentUpdate, NetEntityHandleConverter, PropertyMutator, ProtocolInserter,
+    Protocolize, ReplicaDynMutWrapper, ReplicaDynRefWrapper, ReplicaMutWrapper, ReplicaRefWrapper,
+    Replicate, ReplicateSafe, WorldMutType, WorldRefType,
 };
 
 use super::{
@@ -19,12 +19,15 @@ use super::{
 /// own World available.
 pub struct World<P: Protocolize> {
     pub entities: BigMap<Entity, HashMap<P::Kind, P>>,
+    // the Entity holding the Server's Resources, left out of `entities()`
+    resource_entity: Option<Entity>,
 }
 
 impl<P: Protocolize> Default for World<P> {
     fn default() -> Self {
         Self {
             entities: BigMap::default(),
+            resource_entity: None,
         }
     }
 }
@@ -133,6 +136,12 @@ impl<'w, P: Protocolize> WorldMutType<P, Entity> for WorldMut<'w, P> {
         self.world.entities.insert(component_map)
     }
 
+    fn spawn_resource_entity(&mut self) -> Entity {
+        let entity = self.spawn_entity();
+        self.world.resource_entity = Some(entity);
+        entity
+    }
+
     fn duplicate_entity(&mut self, entity: &Entity) -> Entity {
         let new_entity = self.spawn_entity();
 
@@ -155,6 +164,9 @@ impl<'w, P: Protocolize> WorldMutType<P, Entity> for WorldMut<'w, P> {
 
     fn despawn_entity(&mut self, entity: &Entity) {
         self.world.entities.remove(entity);
+        if self.world.resource_entity == Some(*entity) {
+            self.world.resource_entity = None;
+        }
     }
 
     fn component_kinds(&mut self, entity: &Entity) -> Vec<P::Kind> {
@@ -198,6 +210,17 @@ impl<'w, P: Protocolize> WorldMutType<P, Entity> for WorldMut<'w, P> {
         }
     }
 
+    fn component_set_mutator(
+        &mut self,
+        entity: &Entity,
+        component_kind: &P::Kind,
+        mutator: &PropertyMutator,
+    ) {
+        if let Some(mut component) = component_mut_of_kind(self.world, entity, component_kind) {
+            component.set_mutator(mutator);
+        }
+    }
+
     fn mirror_entities(&mut self, new_entity: &Entity, old_entity: &Entity) {
         for component_kind in self.component_kinds(old_entity) {
             self.mirror_components(new_entity, old_entity, &component_kind);
@@ -276,7 +299,9 @@ fn entities<P: Protocolize>(world: &World<P>) -> Vec<Entity> {
     let mut output = Vec::new();
 
     for (key, _) in world.entities.iter() {
-        output.push(key);
+        if world.resource_entity != Some(key) {
+            output.push(key);
+        }
     }
 
     output
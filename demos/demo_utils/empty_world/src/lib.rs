@@ -4,7 +4,7 @@ mod inner {
     use std::marker::PhantomData;
 
     use naia_shared::{
-        ComponentUpdate, NetEntityHandleConverter, ProtocolInserter, Protocolize,
+        ComponentUpdate, NetEntityHandleConverter, PropertyMutator, ProtocolInserter, Protocolize,
         ReplicaDynRefWrapper, ReplicaMutWrapper, ReplicaRefWrapper, Replicate, ReplicateSafe,
         WorldMutType, WorldRefType,
     };
@@ -112,6 +112,10 @@ mod inner {
             unimplemented!()
         }
 
+        fn spawn_resource_entity(&mut self) -> EmptyEntity {
+            unimplemented!()
+        }
+
         fn duplicate_entity(&mut self, _entity: &EmptyEntity) -> EmptyEntity {
             unimplemented!()
         }
@@ -149,6 +153,15 @@ mod inner {
             unimplemented!()
         }
 
+        fn component_set_mutator(
+            &mut self,
+            _entity: &EmptyEntity,
+            _component_kind: &P::Kind,
+            _mutator: &PropertyMutator,
+        ) {
+            unimplemented!()
+        }
+
         fn mirror_entities(
             &mut self,
             _mutable_entity: &EmptyEntity,
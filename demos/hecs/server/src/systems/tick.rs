@@ -15,10 +15,15 @@ pub fn march_and_mark(app: &mut App) {
     let mut entities_to_remove: Vec<Entity> = Vec::new();
     let mut entities_to_delete: Vec<Entity> = Vec::new();
     let mut entities_to_respawn: Vec<Entity> = Vec::new();
+    let mut entities_to_rescope: Vec<Entity> = Vec::new();
 
     for (entity, position) in app.world.query_mut::<&mut Position>() {
         *position.x += 1;
 
+        // entities entering or leaving the scoped range need their scope checked
+        if *position.x == 50 || *position.x == 201 {
+            entities_to_rescope.push(entity);
+        }
         if *position.x == 100 {
             entities_to_add.push(entity);
         }
@@ -64,6 +69,12 @@ pub fn march_and_mark(app: &mut App) {
         }
     }
 
+    while let Some(entity) = entities_to_rescope.pop() {
+        app.server
+            .entity_mut(&mut app.world, &entity)
+            .mark_scope_dirty();
+    }
+
     while let Some(entity) = entities_to_delete.pop() {
         app.server.entity_mut(&mut app.world, &entity).despawn();
     }
@@ -99,7 +110,7 @@ pub fn check_scopes(app: &mut App) {
     // Update scopes of entities
     let server = &mut app.server;
     let world = &app.world;
-    for (_, user_key, entity) in server.scope_checks() {
+    for (_, user_key, entity) in server.pending_scope_checks() {
         if let Ok(entity_ref) = world.entity(entity) {
             if let Some(position) = entity_ref.get::<Position>() {
                 let x = *position.x;
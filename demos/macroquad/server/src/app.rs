@@ -147,7 +147,7 @@ impl App {
                     // All game logic should happen here, on a tick event
 
                     // Check whether Entities are in/out of all possible Scopes
-                    for (_, user_key, entity) in self.server.scope_checks() {
+                    for (_, user_key, entity) in self.server.pending_scope_checks() {
                         // You'd normally do whatever checks you need to in here..
                         // to determine whether each Entity should be in scope or not.
 
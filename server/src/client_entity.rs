@@ -0,0 +1,56 @@
+use std::collections::{HashSet, VecDeque};
+
+use naia_shared::{BigMapKey, ComponentUpdate, NetEntity, Protocolize};
+
+use super::user::UserKey;
+
+// The most changes a Client can make to one of its Entities before the
+// Server decides on them. Beyond this, the Entity is rejected
+pub const CLIENT_ENTITY_PENDING_ACTION_LIMIT: usize = 256;
+
+// ClientEntityKey
+#[derive(Clone, Copy, Eq, PartialEq, Hash)]
+pub struct ClientEntityKey(u64);
+
+impl BigMapKey for ClientEntityKey {
+    fn to_u64(&self) -> u64 {
+        self.0
+    }
+
+    fn from_u64(value: u64) -> Self {
+        ClientEntityKey(value)
+    }
+}
+
+// ClientEntityAction
+// A change made by a Client to one of its Entities, awaiting acceptance
+pub enum ClientEntityAction<P: Protocolize> {
+    SpawnEntity,
+    DespawnEntity,
+    InsertComponent(P),
+    RemoveComponent(P::Kind),
+    UpdateComponent(ComponentUpdate<P::Kind>),
+}
+
+// ClientEntity
+pub struct ClientEntity<P: Protocolize, E: Copy> {
+    pub user_key: UserKey,
+    pub net_entity: NetEntity,
+    pub entity: Option<E>,
+    pub rejected: bool,
+    pub component_kinds: HashSet<P::Kind>,
+    pub pending_actions: VecDeque<ClientEntityAction<P>>,
+}
+
+impl<P: Protocolize, E: Copy> ClientEntity<P, E> {
+    pub fn new(user_key: &UserKey, net_entity: &NetEntity) -> Self {
+        Self {
+            user_key: *user_key,
+            net_entity: *net_entity,
+            entity: None,
+            rejected: false,
+            component_kinds: HashSet::new(),
+            pending_actions: VecDeque::new(),
+        }
+    }
+}
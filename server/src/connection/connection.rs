@@ -6,7 +6,7 @@ use std::{
 
 use naia_shared::{
     sequence_greater_than,
-    serde::{BitReader, BitWriter},
+    serde::{BitReader, BitWriter, SerdeErr},
     BaseConnection, ChannelConfig, ChannelIndex, ConnectionConfig, EntityConverter, HostType,
     Instant, PacketType, PingManager, ProtocolIo, Protocolize, StandardHeader, Tick, WorldRefType,
 };
@@ -14,8 +14,9 @@ use naia_shared::{
 use crate::{
     protocol::{
         entity_manager::EntityManager, global_diff_handler::GlobalDiffHandler,
-        world_record::WorldRecord,
+        remote_entity_manager::RemoteEntityManager, world_record::WorldRecord,
     },
+    server_config::DespawnGracePeriod,
     tick::{tick_buffer_receiver::TickBufferReceiver, tick_manager::TickManager},
     user::UserKey,
 };
@@ -26,6 +27,7 @@ pub struct Connection<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: Chan
     pub user_key: UserKey,
     pub base: BaseConnection<P, C>,
     pub entity_manager: EntityManager<P, E, C>,
+    pub remote_entity_manager: RemoteEntityManager<P>,
     pub tick_buffer: TickBufferReceiver<P, C>,
     pub last_received_tick: Tick,
     pub ping_manager: PingManager,
@@ -38,6 +40,7 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Connect
         user_address: SocketAddr,
         user_key: &UserKey,
         diff_handler: &Arc<RwLock<GlobalDiffHandler<E, P::Kind>>>,
+        despawn_grace_period: DespawnGracePeriod,
     ) -> Self {
         Connection {
             user_key: *user_key,
@@ -47,7 +50,8 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Connect
                 connection_config,
                 channel_config,
             ),
-            entity_manager: EntityManager::new(user_address, diff_handler),
+            entity_manager: EntityManager::new(user_address, diff_handler, despawn_grace_period),
+            remote_entity_manager: RemoteEntityManager::default(),
             tick_buffer: TickBufferReceiver::new(channel_config),
             ping_manager: PingManager::new(&connection_config.ping),
             last_received_tick: 0,
@@ -72,13 +76,17 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Connect
         server_and_client_tick_opt: Option<(Tick, Tick)>,
         bit_reader: &mut BitReader,
         world_record: &WorldRecord<E, P::Kind>,
-    ) {
+    ) -> Result<(), SerdeErr> {
         // Read Tick Buffered Messages
         if let Some((server_tick, client_tick)) = server_and_client_tick_opt {
             let converter = EntityConverter::new(world_record, &self.entity_manager);
             let channel_reader = ProtocolIo::new(&converter);
-            self.tick_buffer
-                .read_messages(&server_tick, &client_tick, &channel_reader, bit_reader);
+            self.tick_buffer.read_messages(
+                &server_tick,
+                &client_tick,
+                &channel_reader,
+                bit_reader,
+            )?;
         }
 
         // Read Messages
@@ -87,8 +95,17 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Connect
             let channel_reader = ProtocolIo::new(&converter);
             self.base
                 .message_manager
-                .read_messages(&channel_reader, bit_reader);
+                .read_messages(&channel_reader, bit_reader)?;
+        }
+
+        // Read Entity Actions
+        {
+            let converter = EntityConverter::new(world_record, &self.entity_manager);
+            self.remote_entity_manager
+                .read_all(bit_reader, &converter)?;
         }
+
+        Ok(())
     }
 
     // Outgoing data
@@ -101,10 +118,19 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Connect
         tick_manager_opt: &Option<TickManager>,
         rtt_millis: &f32,
     ) {
-        self.collect_outgoing_messages(now, rtt_millis);
+        let server_tick = tick_manager_opt
+            .as_ref()
+            .map(|tick_manager| tick_manager.server_tick());
+        self.collect_outgoing_messages(now, rtt_millis, server_tick, world, world_record);
+
+        self.base.update_send_budget(rtt_millis);
 
         let mut any_sent = false;
         loop {
+            if !self.base.can_send_packet() {
+                // out of send budget, defer the rest until the next call
+                break;
+            }
             if self.send_outgoing_packet(now, io, world, world_record, tick_manager_opt) {
                 any_sent = true;
             } else {
@@ -116,10 +142,20 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Connect
         }
     }
 
-    fn collect_outgoing_messages(&mut self, now: &Instant, rtt_millis: &f32) {
+    fn collect_outgoing_messages<W: WorldRefType<P, E>>(
+        &mut self,
+        now: &Instant,
+        rtt_millis: &f32,
+        server_tick: Option<Tick>,
+        world: &W,
+        world_record: &WorldRecord<E, P::Kind>,
+    ) {
         self.entity_manager.collect_outgoing_messages(
             now,
             rtt_millis,
+            server_tick,
+            world,
+            world_record,
             &mut self.base.message_manager,
         );
         self.base
@@ -179,6 +215,7 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Connect
             //info!("--------------\n");
 
             // send packet
+            self.base.record_sent_packet(&bit_writer);
             io.send_writer(&self.base.address, &mut bit_writer);
 
             return true;
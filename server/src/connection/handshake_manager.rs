@@ -3,10 +3,11 @@ use std::{collections::HashMap, hash::Hash, marker::PhantomData, net::SocketAddr
 use ring::{hmac, rand};
 
 pub use naia_shared::{
-    serde::{BitReader, BitWriter, Serde},
+    serde::{BitReader, BitWriter, Serde, SerdeErr},
     wrapping_diff, BaseConnection, ChannelIndex, ConnectionConfig, FakeEntityConverter, Instant,
     KeyGenerator, PacketType, PropertyMutate, PropertyMutator, ProtocolKindType, Protocolize,
-    Replicate, ReplicateSafe, SharedConfig, StandardHeader, Timer, WorldMutType, WorldRefType,
+    RejectReason, Replicate, ReplicateSafe, SharedConfig, StandardHeader, Timer, WorldMutType,
+    WorldRefType,
 };
 
 use crate::cache_map::CacheMap;
@@ -17,25 +18,28 @@ pub type Timestamp = u64;
 
 pub enum HandshakeResult<P: Protocolize> {
     Invalid,
+    Rejected(RejectReason),
     Success(Option<P>),
 }
 
 pub struct HandshakeManager<P: Protocolize> {
     connection_hash_key: hmac::Key,
     require_auth: bool,
+    protocol_fingerprint: u64,
     address_to_timestamp_map: HashMap<SocketAddr, Timestamp>,
     timestamp_digest_map: CacheMap<Timestamp, Vec<u8>>,
     phantom: PhantomData<P>,
 }
 
 impl<P: Protocolize> HandshakeManager<P> {
-    pub fn new(require_auth: bool) -> Self {
+    pub fn new(require_auth: bool, protocol_fingerprint: u64) -> Self {
         let connection_hash_key =
             hmac::Key::generate(hmac::HMAC_SHA256, &rand::SystemRandom::new()).unwrap();
 
         Self {
             connection_hash_key,
             require_auth,
+            protocol_fingerprint,
             address_to_timestamp_map: HashMap::new(),
             timestamp_digest_map: CacheMap::with_capacity(64),
             phantom: PhantomData,
@@ -43,10 +47,13 @@ impl<P: Protocolize> HandshakeManager<P> {
     }
 
     // Step 1 of Handshake
-    pub fn recv_challenge_request(&mut self, reader: &mut BitReader) -> BitWriter {
-        let timestamp = Timestamp::de(reader).unwrap();
+    pub fn recv_challenge_request(
+        &mut self,
+        reader: &mut BitReader,
+    ) -> Result<BitWriter, SerdeErr> {
+        let timestamp = Timestamp::de(reader)?;
 
-        self.write_challenge_response(&timestamp)
+        Ok(self.write_challenge_response(&timestamp))
     }
 
     // Step 2 of Handshake
@@ -70,25 +77,35 @@ impl<P: Protocolize> HandshakeManager<P> {
     }
 
     // Step 3 of Handshake
-    pub fn recv_connect_request(&mut self, reader: &mut BitReader) -> HandshakeResult<P> {
+    pub fn recv_connect_request(
+        &mut self,
+        reader: &mut BitReader,
+    ) -> Result<HandshakeResult<P>, SerdeErr> {
         // Verify that timestamp hash has been written by this
         // server instance
-        if self.timestamp_validate(reader).is_some() {
-            // Timestamp hash is validated, now start configured auth process
-            let has_auth = bool::de(reader).unwrap();
+        if self.timestamp_validate(reader)?.is_some() {
+            // Timestamp hash is validated, now make sure the Client was built
+            // with the same Protocol & Channels
+            let protocol_fingerprint = u64::de(reader)?;
+            if protocol_fingerprint != self.protocol_fingerprint {
+                return Ok(HandshakeResult::Rejected(RejectReason::ProtocolMismatch));
+            }
+
+            // Now start configured auth process
+            let has_auth = bool::de(reader)?;
 
             if has_auth != self.require_auth {
-                return HandshakeResult::Invalid;
+                return Ok(HandshakeResult::Invalid);
             }
 
             if has_auth {
-                let auth_message = P::read(reader, &FakeEntityConverter);
-                HandshakeResult::Success(Some(auth_message))
+                let auth_message = P::read(reader, &FakeEntityConverter)?;
+                Ok(HandshakeResult::Success(Some(auth_message)))
             } else {
-                HandshakeResult::Success(None)
+                Ok(HandshakeResult::Success(None))
             }
         } else {
-            HandshakeResult::Invalid
+            Ok(HandshakeResult::Invalid)
         }
     }
 
@@ -99,33 +116,41 @@ impl<P: Protocolize> HandshakeManager<P> {
         writer
     }
 
+    // Step 3 of Handshake, if the connect request was refused
+    pub fn write_reject_response(&self, reason: &RejectReason) -> BitWriter {
+        let mut writer = BitWriter::default();
+        StandardHeader::new(PacketType::ServerRejectResponse, 0, 0, 0).ser(&mut writer);
+        reason.ser(&mut writer);
+        writer
+    }
+
     pub fn verify_disconnect_request<E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex>(
         &mut self,
         connection: &Connection<P, E, C>,
         reader: &mut BitReader,
-    ) -> bool {
+    ) -> Result<bool, SerdeErr> {
         // Verify that timestamp hash has been written by this
         // server instance
-        if let Some(new_timestamp) = self.timestamp_validate(reader) {
+        if let Some(new_timestamp) = self.timestamp_validate(reader)? {
             if let Some(old_timestamp) = self.address_to_timestamp_map.get(&connection.base.address)
             {
                 if *old_timestamp == new_timestamp {
-                    return true;
+                    return Ok(true);
                 }
             }
         }
 
-        false
+        Ok(false)
     }
 
     pub fn delete_user(&mut self, address: &SocketAddr) {
         self.address_to_timestamp_map.remove(address);
     }
 
-    fn timestamp_validate(&self, reader: &mut BitReader) -> Option<Timestamp> {
+    fn timestamp_validate(&self, reader: &mut BitReader) -> Result<Option<Timestamp>, SerdeErr> {
         // Read timestamp
-        let timestamp = Timestamp::de(reader).unwrap();
-        let digest_bytes: Vec<u8> = Vec::<u8>::de(reader).unwrap();
+        let timestamp = Timestamp::de(reader)?;
+        let digest_bytes: Vec<u8> = Vec::<u8>::de(reader)?;
 
         // Verify that timestamp hash has been written by this
         // server instance
@@ -135,9 +160,9 @@ impl<P: Protocolize> HandshakeManager<P> {
             &digest_bytes,
         );
         if validation_result.is_err() {
-            None
+            Ok(None)
         } else {
-            Some(timestamp)
+            Ok(Some(timestamp))
         }
     }
 }
@@ -1,6 +1,9 @@
 use naia_shared::{ChannelIndex, Protocolize};
 
-use super::user::{User, UserKey};
+use super::{
+    client_entity::ClientEntityKey,
+    user::{User, UserKey},
+};
 
 /// An Event that is emitted as a result of some communication with a Client, or
 /// a Tick event
@@ -20,4 +23,15 @@ pub enum Event<P: Protocolize, C: ChannelIndex> {
     Tick,
     /// A Message emitted to the Server from a Client
     Message(UserKey, C, P),
+    /// Occurs when a Client has spawned an Entity of its own.
+    /// Must be accepted or rejected, like every other change to the Entity
+    SpawnEntity(UserKey, ClientEntityKey),
+    /// Occurs when a Client has despawned one of its Entities
+    DespawnEntity(UserKey, ClientEntityKey),
+    /// Occurs when a Client has added a Component to one of its Entities
+    InsertComponent(UserKey, ClientEntityKey, P::Kind),
+    /// Occurs when a Client has removed a Component from one of its Entities
+    RemoveComponent(UserKey, ClientEntityKey, P::Kind),
+    /// Occurs when a Client has changed a Component of one of its Entities
+    UpdateComponent(UserKey, ClientEntityKey, P::Kind),
 }
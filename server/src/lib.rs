@@ -25,6 +25,7 @@ pub use naia_server_socket::ServerAddrs;
 pub use naia_shared as shared;
 
 mod cache_map;
+mod client_entity;
 mod connection;
 mod error;
 mod event;
@@ -37,12 +38,13 @@ mod tick;
 mod user;
 mod user_scope;
 
+pub use client_entity::ClientEntityKey;
 pub use error::NaiaServerError;
 pub use event::Event;
 pub use protocol::entity_ref::EntityRef;
 pub use room::{RoomKey, RoomMut, RoomRef};
 pub use server::Server;
-pub use server_config::ServerConfig;
+pub use server_config::{DespawnGracePeriod, ServerConfig};
 pub use user::{User, UserKey, UserMut, UserRef};
 pub use user_scope::UserScopeMut;
 
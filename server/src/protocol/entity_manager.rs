@@ -10,20 +10,23 @@ use std::{
 use naia_shared::{
     message_list_header,
     serde::{BitCounter, BitWrite, BitWriter, Serde, UnsignedVariableInteger},
-    wrapping_diff, ChannelIndex, DiffMask, EntityAction, EntityActionType, EntityConverter,
-    Instant, MessageId, MessageManager, NetEntity, NetEntityConverter, PacketIndex,
-    PacketNotifiable, Protocolize, ReplicateSafe, WorldRefType, MTU_SIZE_BITS,
+    wrapping_diff, ChannelIndex, ChannelSender, DiffMask, EntityAction, EntityActionEvent,
+    EntityActionType, EntityConverter, Instant, MessageId, MessageManager, NetEntity,
+    NetEntityConverter, NetEntityHandleConverter, PacketIndex, PacketNotifiable, ProtocolIo,
+    Protocolize, ReliableSender, ReplicaDynRefWrapper, ReplicateSafe, Tick, WorldRefType,
+    MTU_SIZE_BITS,
 };
 
-use crate::sequence_list::SequenceList;
+use crate::{sequence_list::SequenceList, server_config::DespawnGracePeriod};
 
 use super::{
-    entity_action_event::EntityActionEvent, global_diff_handler::GlobalDiffHandler,
-    world_channel::WorldChannel, world_record::WorldRecord,
+    global_diff_handler::GlobalDiffHandler, priority_accumulator::PriorityAccumulator,
+    sent_baselines::SentBaselines, world_channel::WorldChannel, world_record::WorldRecord,
 };
 
 const DROP_UPDATE_RTT_FACTOR: f32 = 1.5;
 const ACTION_RECORD_TTL: Duration = Duration::from_secs(60);
+const RESEND_REJECTION_RTT_FACTOR: f32 = 1.5;
 
 pub type ActionId = MessageId;
 
@@ -41,6 +44,19 @@ pub struct EntityManager<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: C
     #[allow(clippy::type_complexity)]
     sent_updates: HashMap<PacketIndex, (Instant, HashMap<(E, P::Kind), DiffMask>)>,
     last_update_packet_index: PacketIndex,
+    update_priorities: PriorityAccumulator<E>,
+    last_update_times: HashMap<E, HashMap<P::Kind, Instant>>,
+    // the last written value of each Property, for Components whose updates
+    // are compared before sending
+    #[allow(clippy::type_complexity)]
+    sent_properties: HashMap<E, HashMap<P::Kind, HashMap<u8, Box<[u8]>>>>,
+    // the values of delta-encoded Properties, which updates are written against
+    sent_baselines: SentBaselines<E, P::Kind>,
+
+    // Rejections of Entities spawned by the Client
+    rejections: ReliableSender<NetEntity>,
+    next_send_rejections: VecDeque<(MessageId, NetEntity)>,
+    sent_rejection_packets: HashMap<PacketIndex, (Instant, Vec<MessageId>)>,
 }
 
 impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> EntityManager<P, E, C> {
@@ -48,10 +64,11 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> EntityM
     pub fn new(
         address: SocketAddr,
         diff_handler: &Arc<RwLock<GlobalDiffHandler<E, P::Kind>>>,
+        despawn_grace_period: DespawnGracePeriod,
     ) -> Self {
         EntityManager {
             // World
-            world_channel: WorldChannel::new(address, diff_handler),
+            world_channel: WorldChannel::new(address, diff_handler, despawn_grace_period),
             next_send_actions: VecDeque::new(),
             sent_action_packets: SequenceList::new(),
 
@@ -59,6 +76,15 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> EntityM
             next_send_updates: HashMap::new(),
             sent_updates: HashMap::new(),
             last_update_packet_index: 0,
+            update_priorities: PriorityAccumulator::default(),
+            last_update_times: HashMap::new(),
+            sent_properties: HashMap::new(),
+            sent_baselines: SentBaselines::default(),
+
+            // Rejections
+            rejections: ReliableSender::new(RESEND_REJECTION_RTT_FACTOR),
+            next_send_rejections: VecDeque::new(),
+            sent_rejection_packets: HashMap::new(),
         }
     }
 
@@ -70,16 +96,57 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> EntityM
 
     pub fn despawn_entity(&mut self, entity: &E) {
         self.world_channel.host_despawn_entity(entity);
+        self.update_priorities.remove_entity(entity);
+        self.last_update_times.remove(entity);
+        self.sent_properties.remove(entity);
+        self.sent_baselines.forget_entity(entity);
+    }
+
+    /// Removes the Entity from the User's scope, despawning it once the
+    /// despawn grace period has passed
+    pub fn exclude_entity(&mut self, entity: &E, server_tick: Option<Tick>) {
+        self.world_channel.host_exclude_entity(entity, server_tick);
     }
 
     pub fn insert_component(&mut self, entity: &E, component: &P::Kind) {
         self.world_channel.host_insert_component(entity, component);
+        self.forget_sent_properties(entity, component);
+        self.sent_baselines.forget_component(entity, component);
     }
 
     pub fn remove_component(&mut self, entity: &E, component: &P::Kind) {
+        if !self.world_channel.host_is_replicating(entity) {
+            // do nothing
+            return;
+        }
         self.world_channel.host_remove_component(entity, component);
+        if let Some(update_times) = self.last_update_times.get_mut(entity) {
+            update_times.remove(component);
+        }
+        self.forget_sent_properties(entity, component);
+        self.sent_baselines.forget_component(entity, component);
+    }
+
+    fn forget_sent_properties(&mut self, entity: &E, component: &P::Kind) {
+        if let Some(component_properties) = self.sent_properties.get_mut(entity) {
+            component_properties.remove(component);
+            if component_properties.is_empty() {
+                self.sent_properties.remove(entity);
+            }
+        }
+    }
+
+    pub fn grant_authority(&mut self, entity: &E) {
+        self.world_channel.host_grant_authority(entity);
+    }
+
+    pub fn revoke_authority(&mut self, entity: &E) {
+        self.world_channel.host_revoke_authority(entity);
     }
 
+    /// Whether the Entity is in the User's scope, which an Entity lingering
+    /// through its despawn grace period is not, although it is still
+    /// replicated to them
     pub fn scope_has_entity(&self, entity: &E) -> bool {
         self.world_channel.host_has_entity(entity)
     }
@@ -88,6 +155,20 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> EntityM
         self.world_channel.entity_channel_is_open(entity)
     }
 
+    /// Gets the Entity associated with a NetEntity of this connection, if any
+    pub fn world_entity(&self, net_entity: &NetEntity) -> Option<E> {
+        self.world_channel.net_entity_to_entity(net_entity).copied()
+    }
+
+    /// Tells the Client that the Server will not accept an Entity it spawned
+    pub fn reject_client_entity(&mut self, net_entity: &NetEntity) {
+        self.rejections.send_message(*net_entity);
+    }
+
+    pub fn set_entity_priority(&mut self, entity: &E, priority: f32) {
+        self.update_priorities.set_user_priority(entity, priority);
+    }
+
     // Messages
 
     pub fn queue_entity_message<R: ReplicateSafe<P>>(
@@ -105,26 +186,46 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> EntityM
 
     // Writer
 
-    pub fn collect_outgoing_messages(
+    pub fn collect_outgoing_messages<W: WorldRefType<P, E>>(
         &mut self,
         now: &Instant,
         rtt_millis: &f32,
+        server_tick: Option<Tick>,
+        world: &W,
+        world_record: &WorldRecord<E, P::Kind>,
         message_manager: &mut MessageManager<P, C>,
     ) {
-        self.world_channel
+        let ready_messages = self
+            .world_channel
             .delayed_entity_messages
-            .collect_ready_messages(message_manager);
+            .collect_ready_messages();
+        if !ready_messages.is_empty() {
+            let converter = EntityConverter::new(world_record, self);
+            let channel_writer = ProtocolIo::new(&converter);
+            for (channel, message) in ready_messages {
+                message_manager.send_message(&channel_writer, channel, message);
+            }
+        }
 
         self.collect_dropped_update_packets(rtt_millis);
 
+        self.collect_lingering_entities(server_tick);
+
         self.collect_dropped_action_packets();
         self.collect_next_actions(now, rtt_millis);
 
-        self.collect_component_updates();
+        self.collect_component_updates(world, world_record);
+
+        self.sent_rejection_packets
+            .retain(|_, (time_sent, _)| time_sent.elapsed() <= ACTION_RECORD_TTL);
+        self.rejections.collect_messages(now, rtt_millis);
+        self.next_send_rejections = self.rejections.take_next_messages();
     }
 
     pub fn has_outgoing_messages(&self) -> bool {
-        !self.next_send_actions.is_empty() || !self.next_send_updates.is_empty()
+        !self.next_send_actions.is_empty()
+            || !self.next_send_updates.is_empty()
+            || !self.next_send_rejections.is_empty()
     }
 
     pub fn write_all<W: WorldRefType<P, E>>(
@@ -137,6 +238,7 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> EntityM
     ) {
         self.write_updates(now, writer, packet_index, world, world_record);
         self.write_actions(now, writer, packet_index, world, world_record);
+        self.write_rejections(now, writer, packet_index);
     }
 
     // Collecting
@@ -160,6 +262,12 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> EntityM
         }
     }
 
+    fn collect_lingering_entities(&mut self, server_tick: Option<Tick>) {
+        for entity in self.world_channel.expired_lingering_entities(server_tick) {
+            self.despawn_entity(&entity);
+        }
+    }
+
     fn collect_next_actions(&mut self, now: &Instant, rtt_millis: &f32) {
         self.next_send_actions = self.world_channel.take_next_actions(now, rtt_millis);
     }
@@ -210,6 +318,15 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> EntityM
                     packet_index = packet_index.wrapping_add(1);
                 }
 
+                // the User may not have the values which were sent
+                if let Some(sent_properties) = self
+                    .sent_properties
+                    .get_mut(entity)
+                    .and_then(|component_properties| component_properties.get_mut(component))
+                {
+                    sent_properties.retain(|index, _| new_diff_mask.bit(*index) != Some(true));
+                }
+
                 self.world_channel
                     .diff_handler
                     .or_diff_mask(entity, component, &new_diff_mask);
@@ -217,8 +334,202 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> EntityM
         }
     }
 
-    fn collect_component_updates(&mut self) {
+    fn collect_component_updates<W: WorldRefType<P, E>>(
+        &mut self,
+        world: &W,
+        world_record: &WorldRecord<E, P::Kind>,
+    ) {
         self.next_send_updates = self.world_channel.collect_next_updates();
+
+        // Hold back Components which were updated more recently than their
+        // update interval allows. Their diff masks are left untouched, so
+        // changes keep accumulating until the interval has elapsed.
+        let last_update_times = &self.last_update_times;
+        self.next_send_updates.retain(|entity, component_kinds| {
+            component_kinds.retain(|component_kind| {
+                match (
+                    world_record.update_interval(entity, component_kind),
+                    last_update_times
+                        .get(entity)
+                        .and_then(|update_times| update_times.get(component_kind)),
+                ) {
+                    (Some(interval), Some(last_update)) => last_update.elapsed() >= interval,
+                    _ => true,
+                }
+            });
+            !component_kinds.is_empty()
+        });
+
+        self.drop_unchanged_properties(world, world_record);
+
+        // Entities which keep waiting to be sent rise in priority
+        for (entity, component_kinds) in &self.next_send_updates {
+            self.update_priorities.accumulate(
+                entity,
+                world_record.updates_priority(entity, component_kinds),
+            );
+        }
+    }
+
+    /// For Components whose updates are compared, clears the changed
+    /// Properties which have ended up with the value last sent to the User,
+    /// and holds back Components which are left with nothing to send
+    fn drop_unchanged_properties<W: WorldRefType<P, E>>(
+        &mut self,
+        world: &W,
+        world_record: &WorldRecord<E, P::Kind>,
+    ) {
+        let mut unchanged_masks = Vec::new();
+        {
+            let converter = EntityConverter::new(world_record, self);
+            for (entity, component_kinds) in &self.next_send_updates {
+                for component_kind in component_kinds {
+                    if !world_record.compares_updates(component_kind) {
+                        continue;
+                    }
+                    let sent_properties = match self
+                        .sent_properties
+                        .get(entity)
+                        .and_then(|component_properties| component_properties.get(component_kind))
+                    {
+                        Some(sent_properties) => sent_properties,
+                        None => continue,
+                    };
+                    let component = match world.component_of_kind(entity, component_kind) {
+                        Some(component) => component,
+                        None => continue,
+                    };
+                    let diff_mask = self
+                        .world_channel
+                        .diff_handler
+                        .diff_mask(entity, component_kind)
+                        .expect("DiffHandler does not have registered Component!")
+                        .clone();
+
+                    let mut unchanged_mask = DiffMask::new(diff_mask.byte_number());
+                    Self::for_each_property(&component, &diff_mask, &converter, |index, bytes| {
+                        if sent_properties.get(&index).map(|sent| &sent[..]) == Some(bytes) {
+                            unchanged_mask.set_bit(index, true);
+                        }
+                    });
+                    if !unchanged_mask.is_clear() {
+                        unchanged_masks.push((*entity, *component_kind, unchanged_mask));
+                    }
+                }
+            }
+        }
+
+        for (entity, component_kind, unchanged_mask) in unchanged_masks {
+            let diff_handler = &mut self.world_channel.diff_handler;
+            diff_handler.nand_diff_mask(&entity, &component_kind, &unchanged_mask);
+            if diff_handler.diff_mask_is_clear(&entity, &component_kind) != Some(true) {
+                continue;
+            }
+            if let Some(component_kinds) = self.next_send_updates.get_mut(&entity) {
+                component_kinds.remove(&component_kind);
+                if component_kinds.is_empty() {
+                    self.next_send_updates.remove(&entity);
+                }
+            }
+        }
+    }
+
+    /// Writes each Property marked in the DiffMask on its own, passing the
+    /// written bytes to `on_property` to be compared with later values. These
+    /// are never sent, so a Property may be larger than a packet
+    fn for_each_property<F: FnMut(u8, &[u8])>(
+        component: &ReplicaDynRefWrapper<'_, P>,
+        diff_mask: &DiffMask,
+        converter: &dyn NetEntityHandleConverter,
+        mut on_property: F,
+    ) {
+        let mut property_mask = DiffMask::new(diff_mask.byte_number());
+        for index in 0..(diff_mask.byte_number() * 8) {
+            if diff_mask.bit(index) != Some(true) {
+                continue;
+            }
+            let mut writer = BitWriter::default();
+            property_mask.set_bit(index, true);
+            component.write_update(&property_mask, &mut writer, converter);
+            property_mask.set_bit(index, false);
+            on_property(index, &writer.to_bytes());
+        }
+    }
+
+    /// Stores the value of each Property marked in the DiffMask as the one
+    /// last sent to the User
+    fn record_sent_properties(
+        sent_properties: &mut HashMap<u8, Box<[u8]>>,
+        component: &ReplicaDynRefWrapper<'_, P>,
+        diff_mask: &DiffMask,
+        converter: &dyn NetEntityHandleConverter,
+    ) {
+        Self::for_each_property(component, diff_mask, converter, |index, bytes| {
+            match sent_properties.get_mut(&index) {
+                Some(sent) if sent[..] == *bytes => {}
+                Some(sent) => *sent = bytes.into(),
+                None => {
+                    sent_properties.insert(index, bytes.into());
+                }
+            }
+        });
+    }
+
+    /// Returns the value of every Property of a Component which is written in
+    /// full, to be stored so that its first update only holds what has
+    /// changed since
+    fn written_properties(
+        &self,
+        world_record: &WorldRecord<E, P::Kind>,
+        component: &ReplicaDynRefWrapper<'_, P>,
+    ) -> HashMap<u8, Box<[u8]>> {
+        let mut full_mask = DiffMask::new(component.diff_mask_size());
+        for index in 0..(full_mask.byte_number() * 8) {
+            full_mask.set_bit(index, true);
+        }
+        let mut sent_properties = HashMap::new();
+        let converter = EntityConverter::new(world_record, self);
+        Self::record_sent_properties(&mut sent_properties, component, &full_mask, &converter);
+        sent_properties
+    }
+
+    // Writing rejections
+
+    fn write_rejections(
+        &mut self,
+        now: &Instant,
+        writer: &mut BitWriter,
+        packet_index: &PacketIndex,
+    ) {
+        let current_packet_size = writer.bit_count();
+        let mut counter = BitCounter::default();
+        message_list_header::write(&mut counter, 123);
+
+        // Find how many rejections will fit into the packet
+        let mut rejection_count = 0;
+        if current_packet_size + counter.bit_count() <= MTU_SIZE_BITS {
+            for (_, net_entity) in &self.next_send_rejections {
+                net_entity.ser(&mut counter);
+                if current_packet_size + counter.bit_count() <= MTU_SIZE_BITS {
+                    rejection_count += 1;
+                } else {
+                    break;
+                }
+            }
+        }
+
+        message_list_header::write(writer, rejection_count as u64);
+
+        let mut written_ids = Vec::new();
+        for (rejection_id, net_entity) in self.next_send_rejections.drain(..rejection_count) {
+            net_entity.ser(writer);
+            written_ids.push(rejection_id);
+        }
+
+        if !written_ids.is_empty() {
+            self.sent_rejection_packets
+                .insert(*packet_index, (now.clone(), written_ids));
+        }
     }
 
     // Writing actions
@@ -349,6 +660,9 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> EntityM
                     .unwrap()
                     .ser(bit_writer);
 
+                // write whether the entity holds the Server's Resources
+                world_record.is_resource_entity(entity).ser(bit_writer);
+
                 // get component list
                 let component_kinds = match world_record.component_kinds(entity) {
                     Some(kind_list) => kind_list,
@@ -361,13 +675,28 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> EntityM
                 components_num.ser(bit_writer);
 
                 for component_kind in &component_kinds {
-                    let converter = EntityConverter::new(world_record, self);
-
-                    // write component payload
-                    world
+                    let component = world
                         .component_of_kind(entity, component_kind)
-                        .expect("Component does not exist in World")
-                        .write(bit_writer, &converter);
+                        .expect("Component does not exist in World");
+                    {
+                        let converter = EntityConverter::new(world_record, self);
+
+                        // write component payload
+                        component.write(bit_writer, &converter);
+                    }
+
+                    if is_writing {
+                        self.world_channel
+                            .on_component_written(entity, component_kind);
+                    }
+
+                    if is_writing && world_record.compares_updates(component_kind) {
+                        let sent_properties = self.written_properties(world_record, &component);
+                        self.sent_properties
+                            .entry(*entity)
+                            .or_default()
+                            .insert(*component_kind, sent_properties);
+                    }
                 }
 
                 // if we are writing to this packet, add it to record
@@ -430,18 +759,31 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> EntityM
                         .unwrap()
                         .ser(bit_writer);
 
-                    let converter = EntityConverter::new(world_record, self);
-
-                    // write component payload
-                    world
+                    let component_ref = world
                         .component_of_kind(entity, component)
-                        .expect("Component does not exist in World")
-                        .write(bit_writer, &converter);
+                        .expect("Component does not exist in World");
+                    {
+                        let converter = EntityConverter::new(world_record, self);
+
+                        // write component payload
+                        component_ref.write(bit_writer, &converter);
+                    }
 
                     // if we are actually writing this packet
                     if is_writing {
                         //info!("write InsertComponent({})", action_id);
 
+                        self.world_channel.on_component_written(entity, component);
+
+                        if world_record.compares_updates(component) {
+                            let sent_properties =
+                                self.written_properties(world_record, &component_ref);
+                            self.sent_properties
+                                .entry(*entity)
+                                .or_default()
+                                .insert(*component, sent_properties);
+                        }
+
                         // add it to action record
                         Self::record_action_written(
                             &mut self.sent_action_packets,
@@ -493,6 +835,74 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> EntityM
                     }
                 }
             }
+            EntityActionEvent::GrantAuthority(entity) => {
+                if !self.world_channel.entity_channel_is_open(entity) {
+                    EntityActionType::Noop.ser(bit_writer);
+
+                    // if we are actually writing this packet
+                    if is_writing {
+                        // add it to action record
+                        Self::record_action_written(
+                            &mut self.sent_action_packets,
+                            packet_index,
+                            action_id,
+                            EntityAction::Noop,
+                        );
+                    }
+                } else {
+                    EntityActionType::GrantAuthority.ser(bit_writer);
+
+                    // write net entity
+                    self.world_channel
+                        .entity_to_net_entity(entity)
+                        .unwrap()
+                        .ser(bit_writer);
+
+                    // if we are writing to this packet, add it to record
+                    if is_writing {
+                        Self::record_action_written(
+                            &mut self.sent_action_packets,
+                            packet_index,
+                            action_id,
+                            EntityAction::GrantAuthority(*entity),
+                        );
+                    }
+                }
+            }
+            EntityActionEvent::RevokeAuthority(entity) => {
+                if !self.world_channel.entity_channel_is_open(entity) {
+                    EntityActionType::Noop.ser(bit_writer);
+
+                    // if we are actually writing this packet
+                    if is_writing {
+                        // add it to action record
+                        Self::record_action_written(
+                            &mut self.sent_action_packets,
+                            packet_index,
+                            action_id,
+                            EntityAction::Noop,
+                        );
+                    }
+                } else {
+                    EntityActionType::RevokeAuthority.ser(bit_writer);
+
+                    // write net entity
+                    self.world_channel
+                        .entity_to_net_entity(entity)
+                        .unwrap()
+                        .ser(bit_writer);
+
+                    // if we are writing to this packet, add it to record
+                    if is_writing {
+                        Self::record_action_written(
+                            &mut self.sent_action_packets,
+                            packet_index,
+                            action_id,
+                            EntityAction::RevokeAuthority(*entity),
+                        );
+                    }
+                }
+            }
         }
     }
 
@@ -535,8 +945,9 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> EntityM
                 return;
             }
 
-            // Find how many messages will fit into the packet
-            let all_update_entities: Vec<E> = self.next_send_updates.keys().copied().collect();
+            // Find how many messages will fit into the packet, highest priority first
+            let mut all_update_entities: Vec<E> = self.next_send_updates.keys().copied().collect();
+            self.update_priorities.sort(&mut all_update_entities);
 
             for update_entity in all_update_entities {
                 self.write_update(
@@ -585,6 +996,7 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> EntityM
     ) {
         let mut update_holder: Option<HashSet<P::Kind>> = None;
         if is_writing {
+            self.update_priorities.reset(entity);
             update_holder = Some(
                 self.next_send_updates
                     .remove(entity)
@@ -620,14 +1032,41 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> EntityM
                 .expect("DiffHandler does not have registered Component!")
                 .clone();
 
+            // taken out while the Properties are written, to be put back below
+            let mut sent_properties = None;
+            if is_writing && world_record.compares_updates(component_kind) {
+                sent_properties = Some(
+                    self.sent_properties
+                        .get_mut(entity)
+                        .and_then(|component_properties| {
+                            component_properties.remove(component_kind)
+                        })
+                        .unwrap_or_default(),
+                );
+            }
+
             // write payload
-            {
+            let baselines = {
                 let converter = EntityConverter::new(world_record, self);
-                world
+                let component = world
                     .component_of_kind(entity, component_kind)
-                    .expect("Component does not exist in World")
-                    .write_update(&diff_mask, bit_writer, &converter);
-            }
+                    .expect("Component does not exist in World");
+                let mut baselines =
+                    self.sent_baselines
+                        .for_update(entity, component_kind, *packet_index);
+                component.write_update_against(&diff_mask, bit_writer, &converter, &mut baselines);
+
+                if let Some(sent_properties) = &mut sent_properties {
+                    Self::record_sent_properties(
+                        sent_properties,
+                        &component,
+                        &diff_mask,
+                        &converter,
+                    );
+                }
+
+                baselines.into_written()
+            };
 
             ////////
             if is_writing {
@@ -643,6 +1082,21 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> EntityM
                 self.world_channel
                     .diff_handler
                     .clear_diff_mask(entity, component_kind);
+
+                self.last_update_times
+                    .entry(*entity)
+                    .or_default()
+                    .insert(*component_kind, Instant::now());
+
+                if let Some(sent_properties) = sent_properties {
+                    self.sent_properties
+                        .entry(*entity)
+                        .or_default()
+                        .insert(*component_kind, sent_properties);
+                }
+
+                self.sent_baselines
+                    .update_sent(entity, component_kind, *packet_index, baselines);
             }
         }
     }
@@ -655,6 +1109,7 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> PacketN
     fn notify_packet_delivered(&mut self, packet_index: PacketIndex) {
         // Updates
         self.sent_updates.remove(&packet_index);
+        self.sent_baselines.packet_delivered(packet_index);
 
         // Actions
         if let Some((_, action_list)) = self
@@ -665,6 +1120,13 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> PacketN
                 self.world_channel.action_delivered(action_id, action);
             }
         }
+
+        // Rejections
+        if let Some((_, rejection_ids)) = self.sent_rejection_packets.remove(&packet_index) {
+            for rejection_id in rejection_ids {
+                self.rejections.deliver_message(&rejection_id);
+            }
+        }
     }
 }
 
@@ -679,10 +1141,7 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> NetEnti
             .expect("entity does not exist for this connection!");
     }
 
-    fn net_entity_to_entity(&self, net_entity: &NetEntity) -> E {
-        return *self
-            .world_channel
-            .net_entity_to_entity(net_entity)
-            .expect("entity does not exist for this connection!");
+    fn net_entity_to_entity(&self, net_entity: &NetEntity) -> Option<E> {
+        self.world_entity(net_entity)
     }
 }
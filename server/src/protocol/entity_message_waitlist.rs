@@ -1,7 +1,8 @@
-use naia_shared::{ChannelIndex, KeyGenerator, MessageManager, Protocolize};
+use naia_shared::{ChannelIndex, KeyGenerator, Protocolize};
 use std::{
     collections::{HashMap, HashSet},
     hash::Hash,
+    mem,
 };
 
 type MessageHandle = u16;
@@ -97,9 +98,7 @@ impl<P: Protocolize, E: Copy + Eq + Hash, C: ChannelIndex> EntityMessageWaitlist
         self.in_scope_entities.remove(entity);
     }
 
-    pub fn collect_ready_messages(&mut self, message_manager: &mut MessageManager<P, C>) {
-        for (channel, message) in self.ready_messages.drain(..) {
-            message_manager.send_message(channel, message);
-        }
+    pub fn collect_ready_messages(&mut self) -> Vec<(C, P)> {
+        mem::take(&mut self.ready_messages)
     }
 }
@@ -1,11 +1,11 @@
-use std::{hash::Hash, marker::PhantomData};
+use std::{hash::Hash, marker::PhantomData, time::Duration};
 
 use naia_shared::{
     ChannelIndex, Protocolize, ReplicaMutWrapper, ReplicaRefWrapper, Replicate, ReplicateSafe,
     WorldMutType, WorldRefType,
 };
 
-use crate::{room::RoomKey, server::Server};
+use crate::{room::RoomKey, server::Server, user::UserKey};
 
 // EntityRef
 
@@ -114,6 +114,94 @@ impl<
             .remove_component::<R, W>(&mut self.world, &self.entity)
     }
 
+    // Scope
+
+    /// Sets whether the Entity is global, in which case it is replicated to
+    /// every connected User regardless of Rooms or `UserScopeMut`
+    pub fn set_global(&mut self, is_global: bool) -> &mut Self {
+        self.server.entity_set_global(&self.entity, is_global);
+
+        self
+    }
+
+    /// Marks the Entity as needing its scope evaluated again, so that each
+    /// User it shares a Room with is returned by
+    /// `Server::pending_scope_checks`
+    pub fn mark_scope_dirty(&mut self) -> &mut Self {
+        self.server.entity_mark_scope_dirty(&self.entity);
+
+        self
+    }
+
+    // Spatial Scope
+
+    /// Sets the Entity's position, which scopes it to every User with a view
+    /// that reaches it, see `UserMut::set_view`. The Entity needn't be in a
+    /// Room, as the spatial grid decides its scope instead. Panics if
+    /// `ServerConfig.spatial_cell_size` is not set
+    pub fn set_position(&mut self, x: f32, y: f32) -> &mut Self {
+        self.server.entity_set_position(&self.entity, x, y);
+
+        self
+    }
+
+    // Priority
+
+    /// Sets the base priority of the Entity's updates for every User. When
+    /// there isn't enough room to send all pending updates, Entities with a
+    /// higher priority, or which have gone unsent for longer, are sent first
+    pub fn set_priority(&mut self, priority: f32) -> &mut Self {
+        self.server.entity_set_priority(&self.entity, priority);
+
+        self
+    }
+
+    /// Sets the priority of updates to one of the Entity's Components,
+    /// overriding the Entity's priority whenever that Component has changed
+    pub fn set_component_priority<R: ReplicateSafe<P>>(&mut self, priority: f32) -> &mut Self {
+        self.server
+            .entity_set_component_priority::<R>(&self.entity, priority);
+
+        self
+    }
+
+    /// Sets the minimum duration between updates of one of the Entity's
+    /// Components, overriding any interval set for the Component's kind.
+    /// Pass None to fall back to the Component kind's interval again
+    pub fn set_component_update_interval<R: ReplicateSafe<P>>(
+        &mut self,
+        interval: Option<Duration>,
+    ) -> &mut Self {
+        self.server
+            .entity_set_component_update_interval::<R>(&self.entity, interval);
+
+        self
+    }
+
+    // Authority
+
+    /// Gives a User authority over the Entity, letting them change its
+    /// Components. Their changes are applied by
+    /// Server::apply_authority_updates, and passed on to every other User
+    /// the Entity is in scope for
+    pub fn give_authority(&mut self, user_key: &UserKey) -> &mut Self {
+        self.server.entity_give_authority(&self.entity, user_key);
+
+        self
+    }
+
+    /// Takes authority over the Entity away from whichever User holds it
+    pub fn take_authority(&mut self) -> &mut Self {
+        self.server.entity_take_authority(&self.entity);
+
+        self
+    }
+
+    /// Gets the User which holds authority over the Entity, if any
+    pub fn authority(&self) -> Option<UserKey> {
+        self.server.entity_authority(&self.entity)
+    }
+
     // Rooms
 
     pub fn enter_room(&mut self, room_key: &RoomKey) -> &mut Self {
@@ -26,7 +26,9 @@ impl<E: Copy + Eq + Hash> EntityScopeMap<E> {
         self.main_map.get(&key)
     }
 
-    pub fn insert(&mut self, user_key: UserKey, entity: E, in_scope: bool) {
+    /// Returns whether the Entity was previously in the User's scope, if it
+    /// had been set
+    pub fn insert(&mut self, user_key: UserKey, entity: E, in_scope: bool) -> Option<bool> {
         self.entities_of_user
             .entry(user_key)
             .or_insert_with(|| HashSet::new());
@@ -43,7 +45,7 @@ impl<E: Copy + Eq + Hash> EntityScopeMap<E> {
             .unwrap()
             .insert(user_key);
 
-        self.main_map.insert((user_key, entity), in_scope);
+        self.main_map.insert((user_key, entity), in_scope)
     }
 
     pub fn remove_user(&mut self, user_key: &UserKey) {
@@ -1,6 +1,6 @@
 use std::{collections::HashMap, hash::Hash, net::SocketAddr};
 
-use naia_shared::ProtocolKindType;
+use naia_shared::{DiffMask, ProtocolKindType};
 
 use super::mut_channel::{MutChannel, MutReceiver, MutReceiverBuilder, MutSender};
 
@@ -44,6 +44,20 @@ impl<E: Copy + Eq + Hash, K: ProtocolKindType> GlobalDiffHandler<E, K> {
             .remove(&(*entity, *component_kind));
     }
 
+    /// Marks the given Properties of a Component as changed for every User,
+    /// other than the one at the given address
+    pub fn mutate(
+        &self,
+        entity: &E,
+        component_kind: &K,
+        diff_mask: &DiffMask,
+        except_addr: Option<&SocketAddr>,
+    ) {
+        if let Some(builder) = self.mut_receiver_builders.get(&(*entity, *component_kind)) {
+            builder.mutate(diff_mask, except_addr);
+        }
+    }
+
     pub fn receiver(
         &self,
         addr: &SocketAddr,
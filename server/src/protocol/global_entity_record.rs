@@ -1,13 +1,22 @@
-use std::collections::HashSet;
+use std::{
+    collections::{HashMap, HashSet},
+    time::Duration,
+};
 
 use naia_shared::{EntityHandle, ProtocolKindType};
 
 use crate::room::RoomKey;
 
+/// The priority given to the updates of an Entity, unless otherwise specified
+pub const DEFAULT_ENTITY_PRIORITY: f32 = 1.0;
+
 pub struct GlobalEntityRecord<K: ProtocolKindType> {
     pub room_key: Option<RoomKey>,
     pub entity_handle: EntityHandle,
     pub component_kinds: HashSet<K>,
+    pub priority: f32,
+    pub component_priorities: HashMap<K, f32>,
+    pub update_intervals: HashMap<K, Duration>,
 }
 
 impl<K: ProtocolKindType> GlobalEntityRecord<K> {
@@ -16,6 +25,9 @@ impl<K: ProtocolKindType> GlobalEntityRecord<K> {
             room_key: None,
             entity_handle,
             component_kinds: HashSet::new(),
+            priority: DEFAULT_ENTITY_PRIORITY,
+            component_priorities: HashMap::new(),
+            update_intervals: HashMap::new(),
         }
     }
 }
@@ -1,4 +1,3 @@
-pub mod entity_action_event;
 pub mod entity_manager;
 pub mod entity_message_waitlist;
 pub mod entity_ref;
@@ -6,6 +5,12 @@ pub mod entity_scope_map;
 pub mod global_diff_handler;
 pub mod global_entity_record;
 pub mod mut_channel;
+pub mod priority_accumulator;
+pub mod remote_entity_manager;
+pub mod scope_checks;
+pub mod sent_baselines;
+pub mod spatial_grid;
 pub mod user_diff_handler;
 pub mod world_channel;
+pub mod world_history;
 pub mod world_record;
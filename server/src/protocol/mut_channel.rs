@@ -47,6 +47,14 @@ impl MutChannel {
         }
         false
     }
+
+    pub fn send_except(&self, diff: u8, except_addr: &SocketAddr) -> bool {
+        if let Ok(data) = self.data.as_ref().read() {
+            data.send_except(diff, except_addr);
+            return true;
+        }
+        false
+    }
 }
 
 struct MutChannelData {
@@ -78,6 +86,14 @@ impl MutChannelData {
             receiver.mutate(diff);
         }
     }
+
+    pub fn send_except(&self, diff: u8, except_addr: &SocketAddr) {
+        for (addr, receiver) in self.recv_map.iter() {
+            if addr != except_addr {
+                receiver.mutate(diff);
+            }
+        }
+    }
 }
 
 // MutReceiver
@@ -116,6 +132,12 @@ impl MutReceiver {
         }
     }
 
+    pub fn nand_mask(&self, other_mask: &DiffMask) {
+        if let Ok(mut mask) = self.mask.as_ref().write() {
+            mask.nand(other_mask);
+        }
+    }
+
     pub fn clear_mask(&self) {
         if let Ok(mut mask) = self.mask.as_ref().write() {
             mask.clear();
@@ -158,4 +180,15 @@ impl MutReceiverBuilder {
     pub fn build(&self, addr: &SocketAddr) -> Option<MutReceiver> {
         self.channel.new_receiver(addr)
     }
+
+    pub fn mutate(&self, diff_mask: &DiffMask, except_addr: Option<&SocketAddr>) {
+        for index in 0..(diff_mask.byte_number() * 8) {
+            if let Some(true) = diff_mask.bit(index) {
+                match except_addr {
+                    Some(except_addr) => self.channel.send_except(index, except_addr),
+                    None => self.channel.send(index),
+                };
+            }
+        }
+    }
 }
@@ -0,0 +1,95 @@
+use std::{cmp::Ordering, collections::HashMap, hash::Hash};
+
+/// Tracks, for a single User, how long each Entity with pending updates has
+/// gone unsent, so that the most starved and highest priority Entities are
+/// written into outgoing packets first
+pub struct PriorityAccumulator<E: Copy + Eq + Hash> {
+    user_priorities: HashMap<E, f32>,
+    accumulated: HashMap<E, f32>,
+}
+
+impl<E: Copy + Eq + Hash> Default for PriorityAccumulator<E> {
+    fn default() -> Self {
+        Self {
+            user_priorities: HashMap::default(),
+            accumulated: HashMap::default(),
+        }
+    }
+}
+
+impl<E: Copy + Eq + Hash> PriorityAccumulator<E> {
+    /// Overrides the base priority of an Entity for this User only
+    pub fn set_user_priority(&mut self, entity: &E, priority: f32) {
+        self.user_priorities.insert(*entity, priority);
+    }
+
+    /// Adds the Entity's priority to its accumulated priority. Should be
+    /// called once per update for every Entity with pending updates
+    pub fn accumulate(&mut self, entity: &E, base_priority: f32) {
+        let priority = self
+            .user_priorities
+            .get(entity)
+            .copied()
+            .unwrap_or(base_priority);
+        *self.accumulated.entry(*entity).or_insert(0.0) += priority;
+    }
+
+    /// Sorts the given Entities from highest to lowest accumulated priority
+    pub fn sort(&self, entities: &mut [E]) {
+        entities.sort_by(|a, b| {
+            let a_priority = self.accumulated.get(a).copied().unwrap_or(0.0);
+            let b_priority = self.accumulated.get(b).copied().unwrap_or(0.0);
+            b_priority
+                .partial_cmp(&a_priority)
+                .unwrap_or(Ordering::Equal)
+        });
+    }
+
+    /// Resets the accumulated priority of an Entity after it has been sent
+    pub fn reset(&mut self, entity: &E) {
+        self.accumulated.remove(entity);
+    }
+
+    /// Stops tracking the Entity entirely
+    pub fn remove_entity(&mut self, entity: &E) {
+        self.user_priorities.remove(entity);
+        self.accumulated.remove(entity);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PriorityAccumulator;
+
+    #[test]
+    fn starved_entities_rise() {
+        let mut accumulator = PriorityAccumulator::<u32>::default();
+
+        // entity 1 is sent every update, entity 2 starves
+        for _ in 0..3 {
+            accumulator.accumulate(&1, 2.0);
+            accumulator.accumulate(&2, 1.0);
+            accumulator.reset(&1);
+        }
+        accumulator.accumulate(&1, 2.0);
+
+        let mut entities = vec![1, 2];
+        accumulator.sort(&mut entities);
+
+        assert_eq!(entities, vec![2, 1]);
+    }
+
+    #[test]
+    fn user_priority_overrides_base() {
+        let mut accumulator = PriorityAccumulator::<u32>::default();
+        accumulator.set_user_priority(&1, 10.0);
+
+        accumulator.accumulate(&1, 1.0);
+        accumulator.accumulate(&2, 5.0);
+
+        let mut entities = vec![2, 1];
+        accumulator.sort(&mut entities);
+
+        assert_eq!(entities, vec![1, 2]);
+    }
+}
@@ -0,0 +1,262 @@
+use std::collections::HashMap;
+
+use naia_shared::{
+    message_list_header,
+    serde::{BitReader, Serde, SerdeErr, SerdeErrReason, UnsignedVariableInteger},
+    ComponentUpdate, EntityAction, EntityActionReceiver, EntityActionType, MessageId, NetEntity,
+    NetEntityHandleConverter, Protocolize,
+};
+
+use crate::client_entity::ClientEntityAction;
+
+/// Receives the Entities spawned by a Client, and the changes it makes to them
+pub struct RemoteEntityManager<P: Protocolize> {
+    receiver: EntityActionReceiver<NetEntity, P::Kind>,
+    received_components: HashMap<(NetEntity, P::Kind), P>,
+    incoming_actions: Vec<(NetEntity, ClientEntityAction<P>)>,
+    incoming_authority_updates: Vec<(NetEntity, ComponentUpdate<P::Kind>)>,
+}
+
+impl<P: Protocolize> Default for RemoteEntityManager<P> {
+    fn default() -> Self {
+        Self {
+            receiver: EntityActionReceiver::default(),
+            received_components: HashMap::default(),
+            incoming_actions: Vec::new(),
+            incoming_authority_updates: Vec::new(),
+        }
+    }
+}
+
+impl<P: Protocolize> RemoteEntityManager<P> {
+    // Action Reader
+
+    pub fn read_all(
+        &mut self,
+        reader: &mut BitReader,
+        converter: &dyn NetEntityHandleConverter,
+    ) -> Result<(), SerdeErr> {
+        self.read_updates(reader)?;
+        self.read_actions(reader, converter)?;
+        self.read_authority_updates(reader)?;
+        Ok(())
+    }
+
+    /// Take all the actions received since the last call, in order
+    pub fn take_incoming_actions(&mut self) -> Vec<(NetEntity, ClientEntityAction<P>)> {
+        std::mem::take(&mut self.incoming_actions)
+    }
+
+    /// Take all the updates received since the last call, made to Server
+    /// Entities the Client has been given authority over
+    pub fn take_authority_updates(&mut self) -> Vec<(NetEntity, ComponentUpdate<P::Kind>)> {
+        std::mem::take(&mut self.incoming_authority_updates)
+    }
+
+    fn read_message_id(
+        bit_reader: &mut BitReader,
+        last_id_opt: &mut Option<MessageId>,
+    ) -> Result<MessageId, SerdeErr> {
+        let current_id = if let Some(last_id) = last_id_opt {
+            // read diff
+            let id_diff = UnsignedVariableInteger::<3>::de(bit_reader)?.get() as MessageId;
+            last_id.wrapping_add(id_diff)
+        } else {
+            // read message id
+            MessageId::de(bit_reader)?
+        };
+        *last_id_opt = Some(current_id);
+        Ok(current_id)
+    }
+
+    fn read_actions(
+        &mut self,
+        reader: &mut BitReader,
+        converter: &dyn NetEntityHandleConverter,
+    ) -> Result<(), SerdeErr> {
+        let mut last_read_id: Option<MessageId> = None;
+        let action_count = message_list_header::read(reader)?;
+        for _ in 0..action_count {
+            self.read_action(reader, converter, &mut last_read_id)?;
+        }
+        self.process_incoming_actions();
+        Ok(())
+    }
+
+    fn read_action(
+        &mut self,
+        reader: &mut BitReader,
+        converter: &dyn NetEntityHandleConverter,
+        last_read_id: &mut Option<MessageId>,
+    ) -> Result<(), SerdeErr> {
+        let action_id = Self::read_message_id(reader, last_read_id)?;
+
+        let action_type = EntityActionType::de(reader)?;
+
+        match action_type {
+            // Entity Creation
+            EntityActionType::SpawnEntity => {
+                // read entity
+                let net_entity = NetEntity::de(reader)?;
+
+                // read components
+                let components_num = UnsignedVariableInteger::<3>::de(reader)?.get();
+                let mut components = Vec::new();
+                for _ in 0..components_num {
+                    components.push(P::read(reader, converter)?);
+                }
+                let component_kinds = components
+                    .iter()
+                    .map(|component| component.dyn_ref().kind())
+                    .collect();
+
+                // a retransmitted spawn's Components have been received already
+                if self.receiver.buffer_action(
+                    action_id,
+                    EntityAction::SpawnEntity(net_entity, component_kinds),
+                ) {
+                    for component in components {
+                        let component_kind = component.dyn_ref().kind();
+                        self.received_components
+                            .insert((net_entity, component_kind), component);
+                    }
+                }
+            }
+            // Entity Deletion
+            EntityActionType::DespawnEntity => {
+                let net_entity = NetEntity::de(reader)?;
+
+                self.receiver
+                    .buffer_action(action_id, EntityAction::DespawnEntity(net_entity));
+            }
+            // Add Component to Entity
+            EntityActionType::InsertComponent => {
+                let net_entity = NetEntity::de(reader)?;
+                let new_component = P::read(reader, converter)?;
+                let new_component_kind = new_component.dyn_ref().kind();
+
+                if self.receiver.buffer_action(
+                    action_id,
+                    EntityAction::InsertComponent(net_entity, new_component_kind),
+                ) {
+                    self.received_components
+                        .insert((net_entity, new_component_kind), new_component);
+                }
+            }
+            // Component Removal
+            EntityActionType::RemoveComponent => {
+                let net_entity = NetEntity::de(reader)?;
+                let component_kind = P::Kind::de(reader)?;
+
+                self.receiver.buffer_action(
+                    action_id,
+                    EntityAction::RemoveComponent(net_entity, component_kind),
+                );
+            }
+            EntityActionType::Noop => {
+                self.receiver.buffer_action(action_id, EntityAction::Noop);
+            }
+            // Only the Server can hand out authority
+            EntityActionType::GrantAuthority | EntityActionType::RevokeAuthority => {
+                return Err(SerdeErr::new(
+                    reader.bit_offset(),
+                    "EntityActionType",
+                    SerdeErrReason::InvalidValue,
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn process_incoming_actions(&mut self) {
+        for action in self.receiver.receive_actions() {
+            match action {
+                EntityAction::SpawnEntity(net_entity, component_kinds) => {
+                    self.incoming_actions
+                        .push((net_entity, ClientEntityAction::SpawnEntity));
+
+                    for component_kind in component_kinds {
+                        if let Some(component) = self
+                            .received_components
+                            .remove(&(net_entity, component_kind))
+                        {
+                            self.incoming_actions
+                                .push((net_entity, ClientEntityAction::InsertComponent(component)));
+                        }
+                    }
+                }
+                EntityAction::DespawnEntity(net_entity) => {
+                    // drop any Components whose actions were superseded
+                    self.received_components
+                        .retain(|(component_entity, _), _| *component_entity != net_entity);
+
+                    self.incoming_actions
+                        .push((net_entity, ClientEntityAction::DespawnEntity));
+                }
+                EntityAction::InsertComponent(net_entity, component_kind) => {
+                    if let Some(component) = self
+                        .received_components
+                        .remove(&(net_entity, component_kind))
+                    {
+                        self.incoming_actions
+                            .push((net_entity, ClientEntityAction::InsertComponent(component)));
+                    }
+                }
+                EntityAction::RemoveComponent(net_entity, component_kind) => {
+                    self.incoming_actions.push((
+                        net_entity,
+                        ClientEntityAction::RemoveComponent(component_kind),
+                    ));
+                }
+                EntityAction::GrantAuthority(_)
+                | EntityAction::RevokeAuthority(_)
+                | EntityAction::Noop => {}
+            }
+        }
+    }
+
+    fn read_updates(&mut self, reader: &mut BitReader) -> Result<(), SerdeErr> {
+        let update_count = message_list_header::read(reader)?;
+        for _ in 0..update_count {
+            self.read_update(reader)?;
+        }
+        Ok(())
+    }
+
+    fn read_update(&mut self, reader: &mut BitReader) -> Result<(), SerdeErr> {
+        let net_entity = NetEntity::de(reader)?;
+
+        let components_number = UnsignedVariableInteger::<3>::de(reader)?.get();
+
+        for _ in 0..components_number {
+            // read incoming update
+            let component_update = P::read_create_update(reader)?;
+
+            self.incoming_actions.push((
+                net_entity,
+                ClientEntityAction::UpdateComponent(component_update),
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn read_authority_updates(&mut self, reader: &mut BitReader) -> Result<(), SerdeErr> {
+        let update_count = message_list_header::read(reader)?;
+        for _ in 0..update_count {
+            let net_entity = NetEntity::de(reader)?;
+
+            let components_number = UnsignedVariableInteger::<3>::de(reader)?.get();
+
+            for _ in 0..components_number {
+                // read incoming update
+                let component_update = P::read_create_update(reader)?;
+
+                self.incoming_authority_updates
+                    .push((net_entity, component_update));
+            }
+        }
+        Ok(())
+    }
+}
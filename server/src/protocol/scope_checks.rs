@@ -0,0 +1,144 @@
+use std::{collections::HashSet, hash::Hash};
+
+use crate::{room::RoomKey, user::UserKey};
+
+/// Keeps track of which Users & Entities need their scope evaluated, as they
+/// come to share a Room, leave one, or are otherwise changed, so that the
+/// cost of scoping scales with the number of changes rather than the number
+/// of Users & Entities sharing a Room.
+pub struct ScopeChecks<E: Copy + Eq + Hash> {
+    // Scope Sets for the Server's user to evaluate, see
+    // `Server::pending_scope_checks`
+    pending_checks: HashSet<(RoomKey, UserKey, E)>,
+    // Users & Entities whose scope must be updated on the next send
+    dirty_pairs: HashSet<(UserKey, E)>,
+}
+
+impl<E: Copy + Eq + Hash> ScopeChecks<E> {
+    pub fn new() -> Self {
+        Self {
+            pending_checks: HashSet::new(),
+            dirty_pairs: HashSet::new(),
+        }
+    }
+
+    /// A User & Entity have come to share a Room
+    pub fn add_scope_set(&mut self, room_key: &RoomKey, user_key: &UserKey, entity: &E) {
+        self.pending_checks.insert((*room_key, *user_key, *entity));
+        self.dirty_pairs.insert((*user_key, *entity));
+    }
+
+    /// A User & Entity no longer share a Room
+    pub fn remove_scope_set(&mut self, room_key: &RoomKey, user_key: &UserKey, entity: &E) {
+        self.pending_checks.remove(&(*room_key, *user_key, *entity));
+        self.dirty_pairs.insert((*user_key, *entity));
+    }
+
+    /// The Server's user needs to evaluate the Scope Set again
+    pub fn mark_pending(&mut self, room_key: &RoomKey, user_key: &UserKey, entity: &E) {
+        self.pending_checks.insert((*room_key, *user_key, *entity));
+    }
+
+    /// Whether the Entity should be in the User's scope has changed
+    pub fn mark_dirty(&mut self, user_key: &UserKey, entity: &E) {
+        self.dirty_pairs.insert((*user_key, *entity));
+    }
+
+    pub fn take_pending(&mut self) -> Vec<(RoomKey, UserKey, E)> {
+        self.pending_checks.drain().collect()
+    }
+
+    pub fn take_dirty(&mut self) -> Vec<(UserKey, E)> {
+        self.dirty_pairs.drain().collect()
+    }
+
+    pub fn remove_user(&mut self, user_key: &UserKey) {
+        self.pending_checks
+            .retain(|(_, pending_user_key, _)| pending_user_key != user_key);
+        self.dirty_pairs
+            .retain(|(dirty_user_key, _)| dirty_user_key != user_key);
+    }
+
+    pub fn remove_entity(&mut self, entity: &E) {
+        self.pending_checks
+            .retain(|(_, _, pending_entity)| pending_entity != entity);
+        self.dirty_pairs
+            .retain(|(_, dirty_entity)| dirty_entity != entity);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use naia_shared::BigMapKey;
+
+    use super::ScopeChecks;
+    use crate::{room::RoomKey, user::UserKey};
+
+    fn sorted_pending(checks: &mut ScopeChecks<u32>) -> Vec<(u64, u64, u32)> {
+        let mut output: Vec<(u64, u64, u32)> = checks
+            .take_pending()
+            .drain(..)
+            .map(|(room_key, user_key, entity)| (room_key.to_u64(), user_key.to_u64(), entity))
+            .collect();
+        output.sort();
+        output
+    }
+
+    fn sorted_dirty(checks: &mut ScopeChecks<u32>) -> Vec<(u64, u32)> {
+        let mut output: Vec<(u64, u32)> = checks
+            .take_dirty()
+            .drain(..)
+            .map(|(user_key, entity)| (user_key.to_u64(), entity))
+            .collect();
+        output.sort();
+        output
+    }
+
+    #[test]
+    fn only_changes_are_pending() {
+        let mut checks = ScopeChecks::new();
+        let room = RoomKey::from_u64(0);
+        let user = UserKey::from_u64(0);
+        checks.add_scope_set(&room, &user, &1);
+        checks.add_scope_set(&room, &user, &2);
+
+        assert_eq!(sorted_pending(&mut checks), vec![(0, 0, 1), (0, 0, 2)]);
+        assert!(checks.take_pending().is_empty());
+
+        checks.mark_pending(&room, &user, &2);
+
+        assert_eq!(sorted_pending(&mut checks), vec![(0, 0, 2)]);
+    }
+
+    #[test]
+    fn removed_scope_set_is_dirty_but_not_pending() {
+        let mut checks = ScopeChecks::new();
+        let room = RoomKey::from_u64(0);
+        let user = UserKey::from_u64(0);
+        checks.add_scope_set(&room, &user, &1);
+        checks.take_dirty();
+
+        checks.remove_scope_set(&room, &user, &1);
+
+        assert!(checks.take_pending().is_empty());
+        assert_eq!(sorted_dirty(&mut checks), vec![(0, 1)]);
+    }
+
+    #[test]
+    fn removed_users_and_entities_are_forgotten() {
+        let mut checks = ScopeChecks::new();
+        let room = RoomKey::from_u64(0);
+        let user_a = UserKey::from_u64(0);
+        let user_b = UserKey::from_u64(1);
+        checks.add_scope_set(&room, &user_a, &1);
+        checks.add_scope_set(&room, &user_a, &2);
+        checks.add_scope_set(&room, &user_b, &1);
+        checks.add_scope_set(&room, &user_b, &2);
+
+        checks.remove_user(&user_a);
+        checks.remove_entity(&1);
+
+        assert_eq!(sorted_pending(&mut checks), vec![(0, 1, 2)]);
+        assert_eq!(sorted_dirty(&mut checks), vec![(1, 2)]);
+    }
+}
@@ -0,0 +1,135 @@
+use std::{collections::HashMap, hash::Hash};
+
+use naia_shared::{
+    sequence_greater_than, sequence_less_than, PacketIndex, PropertyBaselines,
+    MAX_BASELINE_DISTANCE,
+};
+
+type PropertyValues = HashMap<u8, (PacketIndex, Box<[u8]>)>;
+
+/// Keeps the values of delta-encoded Properties sent to a User, so that
+/// updates can be written against the last value the User has acked
+pub struct SentBaselines<E: Copy + Eq + Hash, K: Copy + Eq + Hash> {
+    // the last acked value of each Property, and the packet it was sent in
+    acked: HashMap<(E, K), PropertyValues>,
+    // values which have been sent, but not yet acked
+    #[allow(clippy::type_complexity)]
+    sent: HashMap<PacketIndex, Vec<(E, K, u8, Box<[u8]>)>>,
+}
+
+impl<E: Copy + Eq + Hash, K: Copy + Eq + Hash> Default for SentBaselines<E, K> {
+    fn default() -> Self {
+        Self {
+            acked: HashMap::new(),
+            sent: HashMap::new(),
+        }
+    }
+}
+
+impl<E: Copy + Eq + Hash, K: Copy + Eq + Hash> SentBaselines<E, K> {
+    /// Gets the PropertyBaselines to write an update of a Component into the
+    /// given packet with
+    pub fn for_update(
+        &self,
+        entity: &E,
+        component: &K,
+        packet_index: PacketIndex,
+    ) -> UpdateBaselines<'_> {
+        UpdateBaselines {
+            packet_index,
+            acked: self.acked.get(&(*entity, *component)),
+            written: Vec::new(),
+        }
+    }
+
+    /// Stores the values written by an update, until the packet holding it
+    /// is acked
+    pub fn update_sent(
+        &mut self,
+        entity: &E,
+        component: &K,
+        packet_index: PacketIndex,
+        written: Vec<(u8, Box<[u8]>)>,
+    ) {
+        if written.is_empty() {
+            return;
+        }
+
+        // values older than this can no longer be written against
+        let oldest_index = packet_index.wrapping_sub(MAX_BASELINE_DISTANCE);
+        self.sent
+            .retain(|index, _| !sequence_less_than(*index, oldest_index));
+
+        let sent = self.sent.entry(packet_index).or_default();
+        for (property_index, value) in written {
+            sent.push((*entity, *component, property_index, value));
+        }
+    }
+
+    pub fn packet_delivered(&mut self, packet_index: PacketIndex) {
+        if let Some(values) = self.sent.remove(&packet_index) {
+            for (entity, component, property_index, value) in values {
+                let acked = self.acked.entry((entity, component)).or_default();
+                if let Some((acked_index, _)) = acked.get(&property_index) {
+                    if sequence_greater_than(*acked_index, packet_index) {
+                        continue;
+                    }
+                }
+                acked.insert(property_index, (packet_index, value));
+            }
+        }
+    }
+
+    /// Forgets every value of a Component, which the User will no longer hold
+    /// once it is written again in full
+    pub fn forget_component(&mut self, entity: &E, component: &K) {
+        self.acked.remove(&(*entity, *component));
+        for values in self.sent.values_mut() {
+            values.retain(|(sent_entity, sent_component, _, _)| {
+                sent_entity != entity || sent_component != component
+            });
+        }
+    }
+
+    /// Forgets every value of an Entity's Components
+    pub fn forget_entity(&mut self, entity: &E) {
+        self.acked
+            .retain(|(acked_entity, _), _| acked_entity != entity);
+        for values in self.sent.values_mut() {
+            values.retain(|(sent_entity, _, _, _)| sent_entity != entity);
+        }
+    }
+}
+
+/// The PropertyBaselines of a Component update written into a given packet
+pub struct UpdateBaselines<'a> {
+    packet_index: PacketIndex,
+    acked: Option<&'a PropertyValues>,
+    written: Vec<(u8, Box<[u8]>)>,
+}
+
+impl<'a> UpdateBaselines<'a> {
+    /// Returns the values which were written by the update
+    pub fn into_written(self) -> Vec<(u8, Box<[u8]>)> {
+        self.written
+    }
+}
+
+impl<'a> PropertyBaselines for UpdateBaselines<'a> {
+    fn baseline(&self, property_index: u8) -> Option<(u16, &[u8])> {
+        let (acked_index, value) = self.acked?.get(&property_index)?;
+        let distance = self.packet_index.wrapping_sub(*acked_index);
+        if distance == 0 || distance > MAX_BASELINE_DISTANCE {
+            return None;
+        }
+        Some((distance, value))
+    }
+
+    fn baseline_at(&self, _: u8, _: u16) -> Option<&[u8]> {
+        None
+    }
+
+    fn record(&mut self, property_index: u8, value: Box<[u8]>) {
+        self.written.push((property_index, value));
+    }
+}
@@ -0,0 +1,308 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+use crate::user::UserKey;
+
+type Cell = (i32, i32);
+
+// The furthest a view reaches from its center, in cells. Wider views are
+// clamped to this, so that the cells within a view can't grow without bound
+const MAX_VIEW_RADIUS_CELLS: f32 = 32.0;
+
+struct View {
+    cells: HashSet<Cell>,
+}
+
+/// Indexes positioned Entities by the cell of a uniform grid they're in, so
+/// that each User with a view can be scoped to the Entities in the cells
+/// within their view radius. Only the cells which enter or leave a User's
+/// view, and Entities which move between cells, result in scope changes.
+pub struct SpatialGrid<E: Copy + Eq + Hash> {
+    cell_size: f32,
+    entities_of_cell: HashMap<Cell, HashSet<E>>,
+    cell_of_entity: HashMap<E, Cell>,
+    users_of_cell: HashMap<Cell, HashSet<UserKey>>,
+    views: HashMap<UserKey, View>,
+}
+
+impl<E: Copy + Eq + Hash> SpatialGrid<E> {
+    pub fn new(cell_size: f32) -> Self {
+        if cell_size <= 0.0 {
+            panic!("the cell size of a SpatialGrid must be greater than 0");
+        }
+        Self {
+            cell_size,
+            entities_of_cell: HashMap::new(),
+            cell_of_entity: HashMap::new(),
+            users_of_cell: HashMap::new(),
+            views: HashMap::new(),
+        }
+    }
+
+    /// Moves an Entity to the given position, returning the resulting scope
+    /// changes as (User, Entity, is in scope)
+    pub fn set_entity_position(&mut self, entity: &E, x: f32, y: f32) -> Vec<(UserKey, E, bool)> {
+        if !x.is_finite() || !y.is_finite() {
+            panic!("the position of an Entity in a SpatialGrid must be finite");
+        }
+        let new_cell = self.cell_at(x, y);
+        let old_cell = self.cell_of_entity.insert(*entity, new_cell);
+        if old_cell == Some(new_cell) {
+            return Vec::new();
+        }
+
+        if let Some(old_cell) = old_cell {
+            self.remove_from_cell(entity, &old_cell);
+        }
+        self.entities_of_cell
+            .entry(new_cell)
+            .or_default()
+            .insert(*entity);
+
+        let mut changes = Vec::new();
+        let old_users = old_cell.and_then(|cell| self.users_of_cell.get(&cell));
+        let new_users = self.users_of_cell.get(&new_cell);
+        if let Some(old_users) = old_users {
+            for user_key in old_users {
+                if !matches!(new_users, Some(users) if users.contains(user_key)) {
+                    changes.push((*user_key, *entity, false));
+                }
+            }
+        }
+        if let Some(new_users) = new_users {
+            for user_key in new_users {
+                if !matches!(old_users, Some(users) if users.contains(user_key)) {
+                    changes.push((*user_key, *entity, true));
+                }
+            }
+        }
+        changes
+    }
+
+    /// Returns whether the Entity has been given a position
+    pub fn has_entity(&self, entity: &E) -> bool {
+        self.cell_of_entity.contains_key(entity)
+    }
+
+    /// Removes an Entity from the grid
+    pub fn remove_entity(&mut self, entity: &E) {
+        if let Some(cell) = self.cell_of_entity.remove(entity) {
+            self.remove_from_cell(entity, &cell);
+        }
+    }
+
+    /// Moves a User's view to the given position & radius, returning the
+    /// resulting scope changes as (User, Entity, is in scope). A radius
+    /// reaching further than `MAX_VIEW_RADIUS_CELLS` cells is clamped to it
+    pub fn set_user_view(
+        &mut self,
+        user_key: &UserKey,
+        x: f32,
+        y: f32,
+        radius: f32,
+    ) -> Vec<(UserKey, E, bool)> {
+        if !x.is_finite() || !y.is_finite() {
+            panic!("the position of a view in a SpatialGrid must be finite");
+        }
+        if !radius.is_finite() {
+            panic!("the radius of a view in a SpatialGrid must be finite");
+        }
+        let radius = radius.min(self.cell_size * MAX_VIEW_RADIUS_CELLS);
+        let new_cells = self.cells_in_view(x, y, radius);
+        let old_cells = self
+            .views
+            .insert(
+                *user_key,
+                View {
+                    cells: new_cells.clone(),
+                },
+            )
+            .map(|view| view.cells)
+            .unwrap_or_default();
+
+        let mut changes = Vec::new();
+        for cell in old_cells.difference(&new_cells) {
+            if let Some(users) = self.users_of_cell.get_mut(cell) {
+                users.remove(user_key);
+                if users.is_empty() {
+                    self.users_of_cell.remove(cell);
+                }
+            }
+            if let Some(entities) = self.entities_of_cell.get(cell) {
+                for entity in entities {
+                    changes.push((*user_key, *entity, false));
+                }
+            }
+        }
+        for cell in new_cells.difference(&old_cells) {
+            self.users_of_cell
+                .entry(*cell)
+                .or_default()
+                .insert(*user_key);
+            if let Some(entities) = self.entities_of_cell.get(cell) {
+                for entity in entities {
+                    changes.push((*user_key, *entity, true));
+                }
+            }
+        }
+        changes
+    }
+
+    /// Removes a User's view from the grid
+    pub fn remove_user(&mut self, user_key: &UserKey) {
+        if let Some(view) = self.views.remove(user_key) {
+            for cell in view.cells {
+                if let Some(users) = self.users_of_cell.get_mut(&cell) {
+                    users.remove(user_key);
+                    if users.is_empty() {
+                        self.users_of_cell.remove(&cell);
+                    }
+                }
+            }
+        }
+    }
+
+    fn remove_from_cell(&mut self, entity: &E, cell: &Cell) {
+        if let Some(entities) = self.entities_of_cell.get_mut(cell) {
+            entities.remove(entity);
+            if entities.is_empty() {
+                self.entities_of_cell.remove(cell);
+            }
+        }
+    }
+
+    fn cell_at(&self, x: f32, y: f32) -> Cell {
+        (
+            (x / self.cell_size).floor() as i32,
+            (y / self.cell_size).floor() as i32,
+        )
+    }
+
+    // Every cell which overlaps the circle of the view
+    fn cells_in_view(&self, x: f32, y: f32, radius: f32) -> HashSet<Cell> {
+        let mut cells = HashSet::new();
+        if radius < 0.0 {
+            return cells;
+        }
+
+        let (min_x, min_y) = self.cell_at(x - radius, y - radius);
+        let (max_x, max_y) = self.cell_at(x + radius, y + radius);
+        for cell_x in min_x..=max_x {
+            for cell_y in min_y..=max_y {
+                // distance from the view's center to the nearest point of
+                // the cell
+                let left = cell_x as f32 * self.cell_size;
+                let top = cell_y as f32 * self.cell_size;
+                let dx = (left - x).max(x - (left + self.cell_size)).max(0.0);
+                let dy = (top - y).max(y - (top + self.cell_size)).max(0.0);
+                if dx * dx + dy * dy <= radius * radius {
+                    cells.insert((cell_x, cell_y));
+                }
+            }
+        }
+        cells
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use naia_shared::BigMapKey;
+
+    use super::SpatialGrid;
+    use crate::user::UserKey;
+
+    fn sorted(mut changes: Vec<(UserKey, u32, bool)>) -> Vec<(u64, u32, bool)> {
+        let mut output: Vec<(u64, u32, bool)> = changes
+            .drain(..)
+            .map(|(user_key, entity, in_scope)| (user_key.to_u64(), entity, in_scope))
+            .collect();
+        output.sort();
+        output
+    }
+
+    #[test]
+    fn view_includes_nearby_entities() {
+        let mut grid = SpatialGrid::new(10.0);
+        grid.set_entity_position(&1, 5.0, 5.0);
+        grid.set_entity_position(&2, 15.0, 5.0);
+        grid.set_entity_position(&3, 95.0, 95.0);
+
+        let user = UserKey::from_u64(0);
+        let changes = grid.set_user_view(&user, 5.0, 5.0, 8.0);
+
+        assert_eq!(sorted(changes), vec![(0, 1, true), (0, 2, true)]);
+    }
+
+    #[test]
+    fn moving_within_a_cell_changes_nothing() {
+        let mut grid = SpatialGrid::new(10.0);
+        let user = UserKey::from_u64(0);
+        grid.set_user_view(&user, 0.0, 0.0, 5.0);
+
+        assert_eq!(
+            sorted(grid.set_entity_position(&1, 1.0, 1.0)),
+            vec![(0, 1, true)]
+        );
+        assert!(grid.set_entity_position(&1, 2.0, 3.0).is_empty());
+        assert!(grid.set_user_view(&user, 1.0, 1.0, 5.0).is_empty());
+    }
+
+    #[test]
+    fn entity_leaving_view_is_excluded() {
+        let mut grid = SpatialGrid::new(10.0);
+        let user_a = UserKey::from_u64(0);
+        let user_b = UserKey::from_u64(1);
+        grid.set_user_view(&user_a, 5.0, 5.0, 1.0);
+        grid.set_user_view(&user_b, 25.0, 5.0, 1.0);
+        grid.set_entity_position(&1, 5.0, 5.0);
+
+        let changes = grid.set_entity_position(&1, 25.0, 5.0);
+
+        assert_eq!(sorted(changes), vec![(0, 1, false), (1, 1, true)]);
+    }
+
+    #[test]
+    fn moving_view_only_changes_cells_entered_and_left() {
+        let mut grid = SpatialGrid::new(10.0);
+        grid.set_entity_position(&1, 5.0, 5.0);
+        grid.set_entity_position(&2, 15.0, 5.0);
+        grid.set_entity_position(&3, 25.0, 5.0);
+        let user = UserKey::from_u64(0);
+        grid.set_user_view(&user, 10.0, 5.0, 4.0);
+
+        let changes = grid.set_user_view(&user, 20.0, 5.0, 4.0);
+
+        assert_eq!(sorted(changes), vec![(0, 1, false), (0, 3, true)]);
+    }
+
+    #[test]
+    fn view_radius_is_clamped() {
+        let mut grid = SpatialGrid::new(1.0);
+        grid.set_entity_position(&1, 10.0, 0.0);
+        grid.set_entity_position(&2, 1000.0, 0.0);
+
+        let user = UserKey::from_u64(0);
+        let changes = grid.set_user_view(&user, 0.0, 0.0, f32::MAX);
+
+        assert_eq!(sorted(changes), vec![(0, 1, true)]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn infinite_view_radius_panics() {
+        let mut grid = SpatialGrid::<u32>::new(1.0);
+        grid.set_user_view(&UserKey::from_u64(0), 0.0, 0.0, f32::INFINITY);
+    }
+
+    #[test]
+    fn removed_entity_is_not_scoped() {
+        let mut grid = SpatialGrid::new(10.0);
+        grid.set_entity_position(&1, 5.0, 5.0);
+        grid.remove_entity(&1);
+
+        let user = UserKey::from_u64(0);
+        assert!(grid.set_user_view(&user, 5.0, 5.0, 20.0).is_empty());
+    }
+}
@@ -33,6 +33,17 @@ impl<E: Copy + Eq + Hash, K: ProtocolKindType> UserDiffHandler<E, K> {
         }
     }
 
+    /// Starts collecting the changes to a Component from the point its whole
+    /// value is written to the User, so that changes made before the User
+    /// has received it are sent once it has
+    pub fn component_written(&self, addr: &SocketAddr, entity: &E, component_kind: &K) {
+        if let Ok(global_handler) = self.global_diff_handler.as_ref().read() {
+            if let Some(receiver) = global_handler.receiver(addr, entity, component_kind) {
+                receiver.clear_mask();
+            }
+        }
+    }
+
     pub fn deregister_component(&mut self, entity: &E, component_kind: &K) {
         self.receivers.remove(&(*entity, *component_kind));
     }
@@ -65,6 +76,11 @@ impl<E: Copy + Eq + Hash, K: ProtocolKindType> UserDiffHandler<E, K> {
         current_diff_mask.or_mask(other_mask);
     }
 
+    pub fn nand_diff_mask(&mut self, entity: &E, component_kind: &K, other_mask: &DiffMask) {
+        let current_diff_mask = self.receivers.get_mut(&(*entity, *component_kind)).unwrap();
+        current_diff_mask.nand_mask(other_mask);
+    }
+
     pub fn clear_diff_mask(&mut self, entity: &E, component_kind: &K) {
         let receiver = self.receivers.get_mut(&(*entity, *component_kind)).unwrap();
         receiver.clear_mask();
@@ -6,17 +6,17 @@ use std::{
 };
 
 use naia_shared::{
-    ChannelIndex, ChannelSender, EntityAction, EntityActionReceiver, KeyGenerator, NetEntity,
-    ProtocolKindType, Protocolize, ReliableSender,
+    ChannelIndex, ChannelSender, EntityAction, EntityActionEvent, EntityActionReceiver,
+    KeyGenerator, NetEntity, ProtocolKindType, Protocolize, ReliableSender, Tick,
 };
 
 use crate::{
     protocol::{
-        entity_action_event::EntityActionEvent, entity_manager::ActionId,
-        entity_message_waitlist::EntityMessageWaitlist, global_diff_handler::GlobalDiffHandler,
-        user_diff_handler::UserDiffHandler,
+        entity_manager::ActionId, entity_message_waitlist::EntityMessageWaitlist,
+        global_diff_handler::GlobalDiffHandler, user_diff_handler::UserDiffHandler,
     },
     server::Instant,
+    server_config::DespawnGracePeriod,
 };
 
 const RESEND_ACTION_RTT_FACTOR: f32 = 1.5;
@@ -43,6 +43,12 @@ pub struct WorldChannel<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: Ch
     host_world: CheckedMap<E, CheckedSet<P::Kind>>,
     remote_world: CheckedMap<E, CheckedSet<P::Kind>>,
     entity_channels: CheckedMap<E, EntityChannel<P::Kind>>,
+    authorized_entities: HashSet<E>,
+    // Entities which have left the User's scope, along with the time & Tick
+    // they left at, which stay spawned until the despawn grace period has
+    // passed
+    lingering_entities: HashMap<E, (Instant, Option<Tick>)>,
+    despawn_grace_period: DespawnGracePeriod,
     outgoing_actions: ReliableSender<EntityActionEvent<E, P::Kind>>,
     delivered_actions: EntityActionReceiver<E, P::Kind>,
 
@@ -58,11 +64,15 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> WorldCh
     pub fn new(
         address: SocketAddr,
         diff_handler: &Arc<RwLock<GlobalDiffHandler<E, P::Kind>>>,
+        despawn_grace_period: DespawnGracePeriod,
     ) -> Self {
         Self {
             host_world: CheckedMap::new(),
             remote_world: CheckedMap::new(),
             entity_channels: CheckedMap::new(),
+            authorized_entities: HashSet::new(),
+            lingering_entities: HashMap::new(),
+            despawn_grace_period,
             outgoing_actions: ReliableSender::new(RESEND_ACTION_RTT_FACTOR),
             delivered_actions: EntityActionReceiver::default(),
 
@@ -77,7 +87,16 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> WorldCh
 
     // Main
 
+    /// Whether the Entity is in the User's scope. An Entity lingering after
+    /// leaving their scope is still replicated, see `host_is_replicating`,
+    /// but is not in it
     pub fn host_has_entity(&self, entity: &E) -> bool {
+        self.host_world.contains_key(entity) && !self.lingering_entities.contains_key(entity)
+    }
+
+    /// Whether the Entity is replicated to the User, including while it
+    /// lingers after leaving their scope
+    pub fn host_is_replicating(&self, entity: &E) -> bool {
         self.host_world.contains_key(entity)
     }
 
@@ -91,6 +110,11 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> WorldCh
     // Host Updates
 
     pub fn host_spawn_entity(&mut self, entity: &E) {
+        if self.lingering_entities.remove(entity).is_some() {
+            // entity re-entered scope before it was despawned, do nothing
+            return;
+        }
+
         if self.host_world.contains_key(entity) {
             // do nothing
             return;
@@ -115,6 +139,7 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> WorldCh
         }
 
         self.host_world.remove(entity);
+        self.lingering_entities.remove(entity);
 
         let mut despawn = false;
         let mut removing_components = Vec::new();
@@ -143,6 +168,43 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> WorldCh
         }
     }
 
+    /// Removes the Entity from the User's scope. An Entity which has spawned
+    /// stays spawned until the despawn grace period has passed, so that
+    /// re-entering the scope in the meantime does not spawn it again
+    pub fn host_exclude_entity(&mut self, entity: &E, server_tick: Option<Tick>) {
+        if !self.host_world.contains_key(entity) || self.lingering_entities.contains_key(entity) {
+            // do nothing
+            return;
+        }
+
+        if self.despawn_grace_period.is_zero() || !self.entity_channel_is_open(entity) {
+            self.host_despawn_entity(entity);
+            return;
+        }
+
+        self.lingering_entities
+            .insert(*entity, (Instant::now(), server_tick));
+    }
+
+    /// Returns the excluded Entities whose despawn grace period has passed
+    pub fn expired_lingering_entities(&self, server_tick: Option<Tick>) -> Vec<E> {
+        self.lingering_entities
+            .iter()
+            .filter(
+                |(_, (excluded_at, excluded_tick))| match self.despawn_grace_period {
+                    DespawnGracePeriod::Duration(duration) => excluded_at.elapsed() >= duration,
+                    DespawnGracePeriod::Ticks(ticks) => match (excluded_tick, server_tick) {
+                        (Some(excluded_tick), Some(server_tick)) => {
+                            server_tick.wrapping_sub(*excluded_tick) >= ticks
+                        }
+                        _ => true,
+                    },
+                },
+            )
+            .map(|(entity, _)| *entity)
+            .collect()
+    }
+
     pub fn host_insert_component(&mut self, entity: &E, component: &P::Kind) {
         if !self.host_world.contains_key(entity) {
             panic!("cannot insert component into non-existent entity");
@@ -196,6 +258,31 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> WorldCh
         }
     }
 
+    pub fn host_grant_authority(&mut self, entity: &E) {
+        if !self.authorized_entities.insert(*entity) {
+            // do nothing
+            return;
+        }
+
+        // otherwise, authority is granted once the Entity has spawned
+        if let Some(EntityChannel::Spawned(_)) = self.entity_channels.get(entity) {
+            self.outgoing_actions
+                .send_message(EntityActionEvent::GrantAuthority(*entity));
+        }
+    }
+
+    pub fn host_revoke_authority(&mut self, entity: &E) {
+        if !self.authorized_entities.remove(entity) {
+            // do nothing
+            return;
+        }
+
+        if let Some(EntityChannel::Spawned(_)) = self.entity_channels.get(entity) {
+            self.outgoing_actions
+                .send_message(EntityActionEvent::RevokeAuthority(*entity));
+        }
+    }
+
     // Remote Actions
 
     pub fn remote_spawn_entity(&mut self, entity: E, inserted_components: HashSet<P::Kind>) {
@@ -235,6 +322,12 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> WorldCh
                     .insert(entity, EntityChannel::Spawned(component_channels));
                 self.on_entity_channel_opened(&entity);
 
+                if self.authorized_entities.contains(&entity) {
+                    // grant authority
+                    self.outgoing_actions
+                        .send_message(EntityActionEvent::GrantAuthority(entity));
+                }
+
                 // receive inserted components
                 for component in &inserted_components {
                     self.remote_insert_component(entity, *component);
@@ -381,6 +474,11 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> WorldCh
             .register_component(&self.address, entity, component);
     }
 
+    pub fn on_component_written(&self, entity: &E, component: &P::Kind) {
+        self.diff_handler
+            .component_written(&self.address, entity, component);
+    }
+
     fn on_component_channel_closing(&mut self, entity: &E, component: &P::Kind) {
         self.diff_handler.deregister_component(entity, component);
     }
@@ -411,7 +509,9 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> WorldCh
                 EntityAction::RemoveComponent(entity, component) => {
                     self.remote_remove_component(entity, component);
                 }
-                EntityAction::Noop => {
+                EntityAction::GrantAuthority(_)
+                | EntityAction::RevokeAuthority(_)
+                | EntityAction::Noop => {
                     // do nothing
                 }
             }
@@ -0,0 +1,162 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    hash::Hash,
+    net::{Ipv4Addr, SocketAddr, SocketAddrV4},
+};
+
+use naia_shared::{sequence_greater_than, Protocolize, Tick, WorldRefType};
+
+use super::{
+    global_diff_handler::GlobalDiffHandler, mut_channel::MutReceiver, world_record::WorldRecord,
+};
+
+// Changes to recorded Components are received as if by a User at this
+// address, which no Client can connect from
+const HISTORY_ADDRESS: SocketAddr = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, 0));
+
+struct ComponentHistory<P> {
+    // None once the Component's mutations can no longer be received, so that
+    // a Component inserted again is copied in full
+    receiver: Option<MutReceiver>,
+    // oldest Tick at the front, each holding the state the Component changed
+    // to at that Tick, or None if it was removed
+    states: VecDeque<(Tick, Option<P>)>,
+}
+
+/// Keeps the states of chosen Components of every Entity over the last few
+/// Ticks, so that the World can be looked at as it was at some earlier Tick.
+/// A Component's state is only copied at the Ticks during which it changed.
+pub struct WorldHistory<P: Protocolize, E: Copy + Eq + Hash> {
+    capacity: usize,
+    component_kinds: HashSet<P::Kind>,
+    // oldest Tick at the front
+    ticks: VecDeque<Tick>,
+    components: HashMap<(E, P::Kind), ComponentHistory<P>>,
+}
+
+impl<P: Protocolize, E: Copy + Eq + Hash> WorldHistory<P, E> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            component_kinds: HashSet::new(),
+            ticks: VecDeque::new(),
+            components: HashMap::new(),
+        }
+    }
+
+    pub fn record_kind(&mut self, component_kind: &P::Kind) {
+        self.component_kinds.insert(*component_kind);
+    }
+
+    /// Stores the state of every recorded Component which has changed since
+    /// the last Tick recorded. Does nothing if that Tick has already been
+    /// recorded.
+    pub fn record<W: WorldRefType<P, E>>(
+        &mut self,
+        world: &W,
+        world_record: &WorldRecord<E, P::Kind>,
+        diff_handler: &GlobalDiffHandler<E, P::Kind>,
+        tick: Tick,
+    ) {
+        if self.capacity == 0 || self.component_kinds.is_empty() {
+            return;
+        }
+        if let Some(last_tick) = self.ticks.back() {
+            if !sequence_greater_than(tick, *last_tick) {
+                return;
+            }
+        }
+
+        // inserted or changed Components
+        let mut present = HashSet::new();
+        for (entity, entity_kinds) in world_record.entities() {
+            for component_kind in entity_kinds.intersection(&self.component_kinds) {
+                let key = (*entity, *component_kind);
+                present.insert(key);
+
+                let history = self
+                    .components
+                    .entry(key)
+                    .or_insert_with(|| ComponentHistory {
+                        receiver: None,
+                        states: VecDeque::new(),
+                    });
+                let changed = match &history.receiver {
+                    Some(receiver) => !receiver.diff_mask_is_clear(),
+                    None => {
+                        history.receiver =
+                            diff_handler.receiver(&HISTORY_ADDRESS, entity, component_kind);
+                        true
+                    }
+                };
+                if !changed {
+                    continue;
+                }
+                if let Some(receiver) = &history.receiver {
+                    receiver.clear_mask();
+                }
+                if let Some(component) = world.component_of_kind(entity, component_kind) {
+                    history
+                        .states
+                        .push_back((tick, Some(component.protocol_copy())));
+                }
+            }
+        }
+
+        // removed Components
+        for (key, history) in self.components.iter_mut() {
+            if present.contains(key) {
+                continue;
+            }
+            history.receiver = None;
+            if let Some((_, Some(_))) = history.states.back() {
+                history.states.push_back((tick, None));
+            }
+        }
+
+        self.ticks.push_back(tick);
+        while self.ticks.len() > self.capacity {
+            self.ticks.pop_front();
+        }
+
+        // forget the states replaced before the oldest Tick kept
+        let oldest_tick = *self.ticks.front().unwrap();
+        self.components.retain(|_, history| {
+            while history.states.len() > 1
+                && !sequence_greater_than(history.states[1].0, oldest_tick)
+            {
+                history.states.pop_front();
+            }
+            // nothing is left to look up once only a removal remains
+            !history.states.is_empty()
+                && !matches!(history.states.front(), Some((_, None)) if history.states.len() == 1)
+        });
+    }
+
+    /// Marks a Component as removed, so that it is recorded in full should it
+    /// be inserted again before the next Tick is recorded
+    pub fn remove_component(&mut self, entity: &E, component_kind: &P::Kind) {
+        if let Some(history) = self.components.get_mut(&(*entity, *component_kind)) {
+            history.receiver = None;
+        }
+    }
+
+    /// Returns the state of an Entity's Component as it was at the given Tick,
+    /// or None if the Component did not exist then, or the Tick is older than
+    /// the history kept
+    pub fn component(&self, entity: &E, component_kind: &P::Kind, tick: Tick) -> Option<&P> {
+        let oldest_tick = self.ticks.front()?;
+        if sequence_greater_than(*oldest_tick, tick) {
+            return None;
+        }
+
+        // the latest state at or before the given Tick
+        let history = self.components.get(&(*entity, *component_kind))?;
+        let (_, state) = history
+            .states
+            .iter()
+            .rev()
+            .find(|(state_tick, _)| !sequence_greater_than(*state_tick, tick))?;
+        state.as_ref()
+    }
+}
@@ -1,12 +1,23 @@
-use std::{collections::HashMap, hash::Hash};
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    time::Duration,
+};
 
 use naia_shared::{BigMap, EntityHandle, EntityHandleConverter, ProtocolKindType};
 
-use crate::{protocol::global_entity_record::GlobalEntityRecord, room::RoomKey};
+use crate::{
+    protocol::global_entity_record::{GlobalEntityRecord, DEFAULT_ENTITY_PRIORITY},
+    room::RoomKey,
+};
 
 pub struct WorldRecord<E: Copy + Eq + Hash, K: ProtocolKindType> {
     entity_records: HashMap<E, GlobalEntityRecord<K>>,
     handle_entity_map: BigMap<EntityHandle, E>,
+    kind_update_intervals: HashMap<K, Duration>,
+    compared_update_kinds: HashSet<K>,
+    // the Entity whose Components are the Server's Resources
+    resource_entity: Option<E>,
 }
 
 impl<E: Copy + Eq + Hash, K: ProtocolKindType> Default for WorldRecord<E, K> {
@@ -14,6 +25,9 @@ impl<E: Copy + Eq + Hash, K: ProtocolKindType> Default for WorldRecord<E, K> {
         Self {
             entity_records: HashMap::default(),
             handle_entity_map: BigMap::default(),
+            kind_update_intervals: HashMap::default(),
+            compared_update_kinds: HashSet::default(),
+            resource_entity: None,
         }
     }
 }
@@ -35,6 +49,10 @@ impl<E: Copy + Eq + Hash, K: ProtocolKindType> WorldRecord<E, K> {
             panic!("entity does not exist!");
         }
 
+        if self.resource_entity == Some(*entity) {
+            self.resource_entity = None;
+        }
+
         self.entity_records.remove(entity)
     }
 
@@ -50,10 +68,12 @@ impl<E: Copy + Eq + Hash, K: ProtocolKindType> WorldRecord<E, K> {
         if !self.entity_records.contains_key(entity) {
             panic!("entity does not exist!");
         }
-        let component_kind_set = &mut self.entity_records.get_mut(entity).unwrap().component_kinds;
-        if !component_kind_set.remove(component_kind) {
+        let entity_record = self.entity_records.get_mut(entity).unwrap();
+        if !entity_record.component_kinds.remove(component_kind) {
             panic!("component does not exist!");
         }
+        entity_record.update_intervals.remove(component_kind);
+        entity_record.component_priorities.remove(component_kind);
     }
 
     // Access
@@ -62,6 +82,13 @@ impl<E: Copy + Eq + Hash, K: ProtocolKindType> WorldRecord<E, K> {
         self.entity_records.contains_key(entity)
     }
 
+    /// Iterates over every Entity, along with the kinds of its Components
+    pub fn entities(&self) -> impl Iterator<Item = (&E, &HashSet<K>)> {
+        self.entity_records
+            .iter()
+            .map(|(entity, entity_record)| (entity, &entity_record.component_kinds))
+    }
+
     pub fn component_kinds(&self, entity: &E) -> Option<Vec<K>> {
         if !self.entity_records.contains_key(entity) {
             return None;
@@ -71,6 +98,116 @@ impl<E: Copy + Eq + Hash, K: ProtocolKindType> WorldRecord<E, K> {
         return Some(component_kind_set.iter().copied().collect());
     }
 
+    pub fn set_entity_priority(&mut self, entity: &E, priority: f32) {
+        if let Some(entity_record) = self.entity_records.get_mut(entity) {
+            entity_record.priority = priority;
+        }
+    }
+
+    pub fn set_component_priority(&mut self, entity: &E, component_kind: &K, priority: f32) {
+        if let Some(entity_record) = self.entity_records.get_mut(entity) {
+            entity_record
+                .component_priorities
+                .insert(*component_kind, priority);
+        }
+    }
+
+    /// Returns the priority of sending updates to the given Components of an
+    /// Entity, which is the highest priority among them. Components without
+    /// a priority of their own use the Entity's priority
+    pub fn updates_priority(&self, entity: &E, component_kinds: &HashSet<K>) -> f32 {
+        let entity_record = match self.entity_records.get(entity) {
+            Some(entity_record) => entity_record,
+            None => return DEFAULT_ENTITY_PRIORITY,
+        };
+        component_kinds
+            .iter()
+            .map(|component_kind| {
+                entity_record
+                    .component_priorities
+                    .get(component_kind)
+                    .copied()
+                    .unwrap_or(entity_record.priority)
+            })
+            .reduce(f32::max)
+            .unwrap_or(entity_record.priority)
+    }
+
+    // Update Intervals
+
+    /// Returns the minimum duration between updates of a Component, if any.
+    /// An interval set for a specific Entity's Component takes precedence
+    /// over one set for the whole Component kind
+    pub fn update_interval(&self, entity: &E, component_kind: &K) -> Option<Duration> {
+        if let Some(entity_record) = self.entity_records.get(entity) {
+            if let Some(interval) = entity_record.update_intervals.get(component_kind) {
+                return Some(*interval);
+            }
+        }
+        self.kind_update_intervals.get(component_kind).copied()
+    }
+
+    pub fn set_kind_update_interval(&mut self, component_kind: &K, interval: Option<Duration>) {
+        match interval {
+            Some(interval) => {
+                self.kind_update_intervals.insert(*component_kind, interval);
+            }
+            None => {
+                self.kind_update_intervals.remove(component_kind);
+            }
+        }
+    }
+
+    pub fn set_component_update_interval(
+        &mut self,
+        entity: &E,
+        component_kind: &K,
+        interval: Option<Duration>,
+    ) {
+        if let Some(entity_record) = self.entity_records.get_mut(entity) {
+            match interval {
+                Some(interval) => {
+                    entity_record
+                        .update_intervals
+                        .insert(*component_kind, interval);
+                }
+                None => {
+                    entity_record.update_intervals.remove(component_kind);
+                }
+            }
+        }
+    }
+
+    // Update Comparison
+
+    /// Returns whether the Properties of a Component kind should be compared
+    /// with the values last sent to each User, before sending an update
+    pub fn compares_updates(&self, component_kind: &K) -> bool {
+        self.compared_update_kinds.contains(component_kind)
+    }
+
+    pub fn set_kind_compares_updates(&mut self, component_kind: &K, enabled: bool) {
+        if enabled {
+            self.compared_update_kinds.insert(*component_kind);
+        } else {
+            self.compared_update_kinds.remove(component_kind);
+        }
+    }
+
+    // Resources
+
+    pub fn resource_entity(&self) -> Option<E> {
+        self.resource_entity
+    }
+
+    pub fn is_resource_entity(&self, entity: &E) -> bool {
+        self.resource_entity == Some(*entity)
+    }
+
+    pub(crate) fn set_resource_entity(&mut self, entity: &E) {
+        self.resource_entity = Some(*entity);
+    }
+
     // Rooms
 
     pub(crate) fn entity_is_in_room(&self, entity: &E, room_key: &RoomKey) -> bool {
@@ -82,6 +219,12 @@ impl<E: Copy + Eq + Hash, K: ProtocolKindType> WorldRecord<E, K> {
         false
     }
 
+    pub(crate) fn entity_room(&self, entity: &E) -> Option<RoomKey> {
+        self.entity_records
+            .get(entity)
+            .and_then(|entity_record| entity_record.room_key)
+    }
+
     pub(crate) fn entity_enter_room(&mut self, entity: &E, room_key: &RoomKey) {
         if let Some(entity_record) = self.entity_records.get_mut(entity) {
             if entity_record.room_key.is_some() {
@@ -114,3 +257,47 @@ impl<E: Copy + Eq + Hash, K: ProtocolKindType> EntityHandleConverter<E> for Worl
             .entity_handle;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{any::TypeId, collections::HashSet};
+
+    use naia_shared::{derive_serde, serde, ProtocolKindType};
+
+    use super::WorldRecord;
+
+    #[derive(Copy, Eq, Hash)]
+    #[derive_serde]
+    enum TestKind {
+        Position,
+        Color,
+    }
+
+    impl ProtocolKindType for TestKind {
+        fn to_type_id(&self) -> TypeId {
+            TypeId::of::<TestKind>()
+        }
+    }
+
+    #[test]
+    fn component_priorities_override_entity_priority() {
+        let mut world_record = WorldRecord::<u32, TestKind>::default();
+        world_record.spawn_entity(&1);
+        world_record.add_component(&1, &TestKind::Position);
+        world_record.add_component(&1, &TestKind::Color);
+        world_record.set_entity_priority(&1, 2.0);
+        world_record.set_component_priority(&1, &TestKind::Position, 8.0);
+        world_record.set_component_priority(&1, &TestKind::Color, 0.5);
+
+        let only_high: HashSet<TestKind> = [TestKind::Position].into_iter().collect();
+        let only_low: HashSet<TestKind> = [TestKind::Color].into_iter().collect();
+        let both: HashSet<TestKind> = [TestKind::Position, TestKind::Color].into_iter().collect();
+        assert_eq!(world_record.updates_priority(&1, &only_high), 8.0);
+        assert_eq!(world_record.updates_priority(&1, &only_low), 0.5);
+        assert_eq!(world_record.updates_priority(&1, &both), 8.0);
+
+        // the override is forgotten along with the Component
+        world_record.remove_component(&1, &TestKind::Position);
+        assert_eq!(world_record.updates_priority(&1, &only_high), 2.0);
+    }
+}
@@ -1,5 +1,5 @@
 use std::{
-    collections::{hash_set::Iter, HashSet, VecDeque},
+    collections::{hash_set::Iter, HashSet},
     hash::Hash,
 };
 
@@ -25,7 +25,6 @@ impl BigMapKey for RoomKey {
 pub struct Room<E: Copy + Eq + Hash> {
     users: HashSet<UserKey>,
     entities: HashSet<E>,
-    entity_removal_queue: VecDeque<(UserKey, E)>,
 }
 
 impl<E: Copy + Eq + Hash> Room<E> {
@@ -33,7 +32,6 @@ impl<E: Copy + Eq + Hash> Room<E> {
         Room {
             users: HashSet::new(),
             entities: HashSet::new(),
-            entity_removal_queue: VecDeque::new(),
         }
     }
 
@@ -49,9 +47,6 @@ impl<E: Copy + Eq + Hash> Room<E> {
 
     pub(crate) fn unsubscribe_user(&mut self, user_key: &UserKey) {
         self.users.remove(user_key);
-        for entity in self.entities.iter() {
-            self.entity_removal_queue.push_back((*user_key, *entity));
-        }
     }
 
     pub(crate) fn user_keys(&self) -> Iter<UserKey> {
@@ -70,9 +65,6 @@ impl<E: Copy + Eq + Hash> Room<E> {
 
     pub(crate) fn remove_entity(&mut self, entity: &E) -> bool {
         if self.entities.remove(entity) {
-            for user_key in self.users.iter() {
-                self.entity_removal_queue.push_back((*user_key, *entity));
-            }
             true
         } else {
             panic!("Room does not contain Entity");
@@ -83,10 +75,6 @@ impl<E: Copy + Eq + Hash> Room<E> {
         self.entities.iter()
     }
 
-    pub(crate) fn pop_entity_removal_queue(&mut self) -> Option<(UserKey, E)> {
-        self.entity_removal_queue.pop_front()
-    }
-
     pub(crate) fn entities_count(&self) -> usize {
         self.entities.len()
     }
@@ -1,24 +1,29 @@
 use std::{
-    collections::{HashMap, VecDeque},
+    collections::{HashMap, HashSet, VecDeque},
     hash::Hash,
     net::SocketAddr,
     panic,
     sync::{Arc, RwLock},
+    time::Duration,
 };
 
 use naia_server_socket::{ServerAddrs, Socket};
 use naia_shared::{
-    serde::{BitWriter, Serde},
-    ChannelIndex, EntityHandle, EntityHandleConverter, Tick,
+    serde::{BitReader, BitWriter, Serde, SerdeErr},
+    ChannelIndex, ComponentUpdate, EntityConverter, EntityHandle, EntityHandleConverter,
+    ProtocolInserter, ProtocolIo, Tick, JITTER_BUFFER_FACTOR,
 };
 pub use naia_shared::{
     wrapping_diff, BaseConnection, BigMap, ConnectionConfig, Instant, KeyGenerator, NetEntity,
     PacketType, PingConfig, PropertyMutate, PropertyMutator, ProtocolKindType, Protocolize,
-    Replicate, ReplicateSafe, SharedConfig, StandardHeader, Timer, Timestamp, WorldMutType,
-    WorldRefType,
+    ReplicaMutWrapper, ReplicaRefWrapper, Replicate, ReplicateSafe, SharedConfig, StandardHeader,
+    Timer, Timestamp, WorldMutType, WorldRefType,
 };
 
 use crate::{
+    client_entity::{
+        ClientEntity, ClientEntityAction, ClientEntityKey, CLIENT_ENTITY_PENDING_ACTION_LIMIT,
+    },
     connection::{
         connection::Connection,
         handshake_manager::{HandshakeManager, HandshakeResult},
@@ -28,6 +33,9 @@ use crate::{
         entity_ref::{EntityMut, EntityRef},
         entity_scope_map::EntityScopeMap,
         global_diff_handler::GlobalDiffHandler,
+        scope_checks::ScopeChecks,
+        spatial_grid::SpatialGrid,
+        world_history::WorldHistory,
         world_record::WorldRecord,
     },
     tick::tick_manager::TickManager,
@@ -37,11 +45,16 @@ use super::{
     error::NaiaServerError,
     event::Event,
     room::{Room, RoomKey, RoomMut, RoomRef},
-    server_config::ServerConfig,
+    server_config::{DespawnGracePeriod, ServerConfig},
     user::{User, UserKey, UserMut, UserRef},
     user_scope::UserScopeMut,
 };
 
+// The most changes from a User to Entities it has authority over which are
+// held until Server::apply_authority_updates is called. Beyond this, the
+// oldest changes are discarded
+const AUTHORITY_UPDATE_QUEUE_LIMIT: usize = 1024;
+
 /// A server that uses either UDP or WebRTC communication to send/receive
 /// messages to/from connected clients, and syncs registered entities to
 /// clients to whom they are in-scope
@@ -63,12 +76,27 @@ pub struct Server<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelI
     // Entities
     world_record: WorldRecord<E, P::Kind>,
     entity_scope_map: EntityScopeMap<E>,
+    scope_checks: ScopeChecks<E>,
+    spatial_grid: Option<SpatialGrid<E>>,
+    spatial_scope_map: EntityScopeMap<E>,
+    global_entities: HashSet<E>,
+    world_history: WorldHistory<P, E>,
     // Components
     diff_handler: Arc<RwLock<GlobalDiffHandler<E, P::Kind>>>,
+    // Client-owned Entities
+    client_entities: BigMap<ClientEntityKey, ClientEntity<P, E>>,
+    client_entity_keys: HashMap<(UserKey, NetEntity), ClientEntityKey>,
+    client_owned_entities: HashMap<E, ClientEntityKey>,
+    // Entity Authority
+    entity_authorities: HashMap<E, UserKey>,
+    #[allow(clippy::type_complexity)]
+    incoming_authority_updates: HashMap<UserKey, VecDeque<(E, ComponentUpdate<P::Kind>)>>,
     // Events
     incoming_events: VecDeque<Result<Event<P, C>, NaiaServerError>>,
     // Ticks
     tick_manager: Option<TickManager>,
+    // Malformed packets which have been dropped
+    invalid_packet_count: u64,
 }
 
 impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<P, E, C> {
@@ -78,6 +106,23 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
 
         let tick_manager = { shared_config.tick_interval.map(TickManager::new) };
 
+        if matches!(
+            server_config.despawn_grace_period,
+            DespawnGracePeriod::Ticks(_)
+        ) && tick_manager.is_none()
+        {
+            panic!("a despawn grace period in Ticks requires SharedConfig.tick_interval to be set");
+        }
+
+        // one recorded state per Tick
+        let history_capacity = match shared_config.tick_interval {
+            Some(tick_interval) => {
+                (server_config.history_duration.as_millis() / tick_interval.as_millis().max(1))
+                    as usize
+            }
+            None => 0,
+        };
+
         Server {
             // Config
             server_config: server_config.clone(),
@@ -91,7 +136,10 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
             heartbeat_timer: Timer::new(server_config.connection.heartbeat_interval),
             timeout_timer: Timer::new(server_config.connection.disconnection_timeout_duration),
             ping_timer: Timer::new(server_config.connection.ping.ping_interval),
-            handshake_manager: HandshakeManager::new(server_config.require_auth),
+            handshake_manager: HandshakeManager::new(
+                server_config.require_auth,
+                shared_config.fingerprint::<P>(),
+            ),
             // Users
             users: BigMap::default(),
             user_connections: HashMap::new(),
@@ -100,12 +148,25 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
             // Entities
             world_record: WorldRecord::default(),
             entity_scope_map: EntityScopeMap::new(),
+            scope_checks: ScopeChecks::new(),
+            spatial_grid: server_config.spatial_cell_size.map(SpatialGrid::new),
+            spatial_scope_map: EntityScopeMap::new(),
+            global_entities: HashSet::new(),
+            world_history: WorldHistory::new(history_capacity),
             // Components
             diff_handler: Arc::new(RwLock::new(GlobalDiffHandler::default())),
+            // Client-owned Entities
+            client_entities: BigMap::default(),
+            client_entity_keys: HashMap::new(),
+            client_owned_entities: HashMap::new(),
+            // Entity Authority
+            entity_authorities: HashMap::new(),
+            incoming_authority_updates: HashMap::new(),
             // Events
             incoming_events: VecDeque::new(),
             // Ticks
             tick_manager,
+            invalid_packet_count: 0,
         }
     }
 
@@ -191,12 +252,26 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
                 user.address,
                 user_key,
                 &self.diff_handler,
+                self.server_config.despawn_grace_period,
             );
             // send connectaccept response
             let mut writer = self.handshake_manager.write_connect_response();
             self.io.send_writer(&user.address, &mut writer);
             //
             self.user_connections.insert(user.address, new_connection);
+            // global Entities are in scope for every connected User
+            for entity in self.global_entities.iter() {
+                self.scope_checks.mark_dirty(user_key, entity);
+            }
+            // scope changes made before the User connected could not be applied
+            // yet, so evaluate every Entity they share a Room with
+            for (_, room) in self.rooms.iter() {
+                if room.has_user(user_key) {
+                    for entity in room.entities() {
+                        self.scope_checks.mark_dirty(user_key, entity);
+                    }
+                }
+            }
             if self.io.bandwidth_monitor_enabled() {
                 self.io.register_client(&user.address);
             }
@@ -248,10 +323,14 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
                     };
                     if all_entities_in_scope {
                         // All necessary entities are in scope, so send message
-                        connection
-                            .base
-                            .message_manager
-                            .send_message(channel, message.protocol_copy());
+                        let converter =
+                            EntityConverter::new(&self.world_record, &connection.entity_manager);
+                        let channel_writer = ProtocolIo::new(&converter);
+                        connection.base.message_manager.send_message(
+                            &channel_writer,
+                            channel,
+                            message.protocol_copy(),
+                        );
                     } else {
                         // Entity hasn't been added to the User Scope yet, or replicated to Client
                         // yet
@@ -260,10 +339,14 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
                             .queue_entity_message(entities, channel, message);
                     }
                 } else {
-                    connection
-                        .base
-                        .message_manager
-                        .send_message(channel, message.protocol_copy());
+                    let converter =
+                        EntityConverter::new(&self.world_record, &connection.entity_manager);
+                    let channel_writer = ProtocolIo::new(&converter);
+                    connection.base.message_manager.send_message(
+                        &channel_writer,
+                        channel,
+                        message.protocol_copy(),
+                    );
                 }
             }
         }
@@ -274,17 +357,14 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
     /// Used to evaluate whether, given a User & Entity that are in the
     /// same Room, said Entity should be in scope for the given User.
     ///
-    /// While Rooms allow for a very simple scope to which an Entity can belong,
-    /// this provides complete customization for advanced scopes.
-    ///
-    /// Return a collection of Entity Scope Sets, being a unique combination of
-    /// a related Room, User, and Entity, used to determine which Entities to
-    /// replicate to which Users
+    /// Returns every Entity Scope Set, being a unique combination of a
+    /// related Room, User, and Entity, whether or not it has changed
+    #[deprecated(
+        note = "use `pending_scope_checks`, which returns only the Scope Sets that have changed"
+    )]
     pub fn scope_checks(&self) -> Vec<(RoomKey, UserKey, E)> {
         let mut list: Vec<(RoomKey, UserKey, E)> = Vec::new();
 
-        // TODO: precache this, instead of generating a new list every call
-        // likely this is called A LOT
         for (room_key, room) in self.rooms.iter() {
             for user_key in room.user_keys() {
                 for entity in room.entities() {
@@ -296,6 +376,19 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
         list
     }
 
+    /// Used to evaluate whether, given a User & Entity that are in the
+    /// same Room, said Entity should be in scope for the given User.
+    ///
+    /// Returns only the Entity Scope Sets which have changed since the last
+    /// call, being those whose User & Entity have come to share a Room, or
+    /// whose Entity has been marked as scope dirty with
+    /// `EntityMut::mark_scope_dirty`, so that the cost of evaluating scopes
+    /// scales with the number of changes rather than the number of Users &
+    /// Entities.
+    pub fn pending_scope_checks(&mut self) -> Vec<(RoomKey, UserKey, E)> {
+        self.scope_checks.take_pending()
+    }
+
     /// Sends all update messages to all Clients. If you don't call this
     /// method, the Server will never communicate with it's connected
     /// Clients
@@ -305,6 +398,20 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
         // update entity scopes
         self.update_entity_scopes(&world);
 
+        // record the state of the World sent at this Tick
+        if let Some(tick_manager) = &self.tick_manager {
+            self.world_history.record(
+                &world,
+                &self.world_record,
+                &self
+                    .diff_handler
+                    .as_ref()
+                    .read()
+                    .expect("DiffHandler should be initialized"),
+                tick_manager.server_tick(),
+            );
+        }
+
         // loop through all connections, send packet
         let mut user_addresses: Vec<SocketAddr> = self.user_connections.keys().copied().collect();
         fastrand::shuffle(&mut user_addresses);
@@ -367,7 +474,329 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
 
     /// Gets a Vec of all Entities in the given World
     pub fn entities<W: WorldRefType<P, E>>(&self, world: W) -> Vec<E> {
-        world.entities()
+        let mut entities = world.entities();
+        if let Some(resource_entity) = self.world_record.resource_entity() {
+            entities.retain(|entity| *entity != resource_entity);
+        }
+        entities
+    }
+
+    /// Sets the minimum duration between updates sent for every Component of
+    /// the given type. Changes made in the meantime are accumulated and sent
+    /// together once the interval has elapsed. Pass None to send updates as
+    /// soon as possible again.
+    pub fn set_component_update_interval<R: ReplicateSafe<P>>(
+        &mut self,
+        interval: Option<Duration>,
+    ) {
+        self.world_record
+            .set_kind_update_interval(&P::kind_of::<R>(), interval);
+    }
+
+    /// Sets whether the Properties of every Component of the given type are
+    /// compared with the values last sent to each User before sending them.
+    /// A Property which has been changed, but has ended up with the value the
+    /// User already has, is then not sent again.
+    pub fn compare_component_updates<R: ReplicateSafe<P>>(&mut self, enabled: bool) {
+        self.world_record
+            .set_kind_compares_updates(&P::kind_of::<R>(), enabled);
+    }
+
+    // Resources
+
+    /// Inserts a Resource, a singleton which is replicated to every connected
+    /// User, replacing the Resource of the same type if one exists.
+    /// Changes to a Resource are sent to Clients just as changes to
+    /// Components are
+    pub fn insert_resource<R: ReplicateSafe<P>, W: WorldMutType<P, E>>(
+        &mut self,
+        mut world: W,
+        resource: R,
+    ) {
+        let resource_entity = match self.world_record.resource_entity() {
+            Some(resource_entity) => resource_entity,
+            None => {
+                // Resources are the Components of an Entity which is in
+                // scope for every User
+                let resource_entity = world.spawn_resource_entity();
+                self.spawn_entity_init(&resource_entity);
+                self.world_record.set_resource_entity(&resource_entity);
+                self.entity_set_global(&resource_entity, true);
+                resource_entity
+            }
+        };
+
+        let resource_kind = resource.kind();
+        if world.has_component_of_kind(&resource_entity, &resource_kind) {
+            self.remove_component_of_kind(&mut world, &resource_entity, &resource_kind);
+        }
+
+        self.insert_component(&mut world, &resource_entity, resource);
+    }
+
+    /// Removes the Resource of the given type, if it exists
+    pub fn remove_resource<R: Replicate<P>, W: WorldMutType<P, E>>(
+        &mut self,
+        mut world: W,
+    ) -> Option<R> {
+        let resource_entity = self.world_record.resource_entity()?;
+        if !world.has_component::<R>(&resource_entity) {
+            return None;
+        }
+        self.remove_component::<R, W>(&mut world, &resource_entity)
+    }
+
+    /// Returns whether a Resource of the given type exists
+    pub fn has_resource<R: ReplicateSafe<P>, W: WorldRefType<P, E>>(&self, world: W) -> bool {
+        match self.world_record.resource_entity() {
+            Some(resource_entity) => world.has_component::<R>(&resource_entity),
+            None => false,
+        }
+    }
+
+    /// Gets a reference to the Resource of the given type, if it exists
+    pub fn resource<'w, R: ReplicateSafe<P>, W: WorldRefType<P, E>>(
+        &self,
+        world: &'w W,
+    ) -> Option<ReplicaRefWrapper<'w, P, R>> {
+        world.component::<R>(&self.world_record.resource_entity()?)
+    }
+
+    /// Gets a mutable reference to the Resource of the given type, if it
+    /// exists. Changes made through it are replicated to every User
+    pub fn resource_mut<'w, R: ReplicateSafe<P>, W: WorldMutType<P, E>>(
+        &self,
+        world: &'w mut W,
+    ) -> Option<ReplicaMutWrapper<'w, P, R>> {
+        world.component_mut::<R>(&self.world_record.resource_entity()?)
+    }
+
+    /// Gets the Entity whose Components are the Server's Resources, if any
+    /// Resources have been inserted
+    pub fn resource_entity(&self) -> Option<E> {
+        self.world_record.resource_entity()
+    }
+
+    // Lag Compensation
+
+    /// Records the state of every Component of the given type each Tick, for
+    /// as long as the `history_duration` in the ServerConfig, so that it can
+    /// be looked up later with `component_at` or `rewind_component`.
+    /// Requires a tick interval to be set in the SharedConfig.
+    pub fn record_component_history<R: ReplicateSafe<P>>(&mut self) {
+        self.world_history.record_kind(&P::kind_of::<R>());
+    }
+
+    /// Returns the state of an Entity's Component at the given Tick, if it
+    /// was recorded
+    pub fn component_at<R: Replicate<P>>(&self, entity: &E, tick: Tick) -> Option<R> {
+        self.world_history
+            .component(entity, &P::kind_of::<R>(), tick)
+            .and_then(|component| component.clone().cast::<R>())
+    }
+
+    /// Returns the state of an Entity's Component as the given User was
+    /// seeing it, at the Tick given by `user_view_tick`
+    pub fn rewind_component<R: Replicate<P>>(&self, user_key: &UserKey, entity: &E) -> Option<R> {
+        let tick = self.user_view_tick(user_key)?;
+        self.component_at::<R>(entity, tick)
+    }
+
+    /// Estimates the Tick of the World the given User's Client is currently
+    /// showing, from the time updates take to reach it, the time they wait in
+    /// its jitter buffer, and the `interpolation_delay` in the ServerConfig
+    pub fn user_view_tick(&self, user_key: &UserKey) -> Option<Tick> {
+        let server_tick = self.server_tick()?;
+        let tick_interval_millis = self.shared_config.tick_interval?.as_millis() as f32;
+        let user = self.users.get(user_key)?;
+        let connection = self.user_connections.get(&user.address)?;
+
+        let delay_millis = (connection.ping_manager.rtt / 2.0)
+            + (connection.ping_manager.jitter * JITTER_BUFFER_FACTOR)
+            + (self.server_config.interpolation_delay.as_millis() as f32);
+        let delay_ticks = (delay_millis / tick_interval_millis).round() as Tick;
+
+        Some(server_tick.wrapping_sub(delay_ticks))
+    }
+
+    // Client-owned Entities
+
+    /// Accepts the oldest change made by a Client to one of its Entities which
+    /// is still awaiting a decision, and applies it to the World.
+    /// Each SpawnEntity, DespawnEntity, InsertComponent, RemoveComponent &
+    /// UpdateComponent Event should be accepted or rejected, in the order
+    /// they were received for a given ClientEntityKey.
+    /// Once its spawn is accepted, the Entity is replicated to other Users
+    /// like any other Entity, but never back to the Client who owns it.
+    pub fn accept_client_entity_action<W: WorldMutType<P, E>>(
+        &mut self,
+        mut world: W,
+        client_entity_key: &ClientEntityKey,
+    ) {
+        let (user_key, entity_opt, action) =
+            if let Some(client_entity) = self.client_entities.get_mut(client_entity_key) {
+                if let Some(action) = client_entity.pending_actions.pop_front() {
+                    (client_entity.user_key, client_entity.entity, action)
+                } else {
+                    return;
+                }
+            } else {
+                return;
+            };
+
+        // the Entity may have been despawned by the Server in the meantime
+        let entity_opt = entity_opt.filter(|entity| world.has_entity(entity));
+
+        match action {
+            ClientEntityAction::SpawnEntity => {
+                let entity = world.spawn_entity();
+                self.spawn_entity_init(&entity);
+                self.client_owned_entities
+                    .insert(entity, *client_entity_key);
+                self.client_entities
+                    .get_mut(client_entity_key)
+                    .unwrap()
+                    .entity = Some(entity);
+            }
+            ClientEntityAction::DespawnEntity => {
+                if let Some(entity) = entity_opt {
+                    self.despawn_entity(&mut world, &entity);
+                }
+                self.client_entities.remove(client_entity_key);
+            }
+            ClientEntityAction::InsertComponent(component) => {
+                if let Some(entity) = entity_opt {
+                    if !world.has_component_of_kind(&entity, &component.dyn_ref().kind()) {
+                        let mut inserter = ClientComponentInserter {
+                            server: self,
+                            world: &mut world,
+                        };
+                        component.extract_and_insert(&entity, &mut inserter);
+                    }
+                }
+            }
+            ClientEntityAction::RemoveComponent(component_kind) => {
+                if let Some(entity) = entity_opt {
+                    if world.has_component_of_kind(&entity, &component_kind) {
+                        self.remove_component_of_kind(&mut world, &entity, &component_kind);
+                    }
+                }
+            }
+            ClientEntityAction::UpdateComponent(update) => {
+                if let Some(entity) = entity_opt {
+                    let component_kind = update.kind;
+                    if !world.has_component_of_kind(&entity, &component_kind) {
+                        return;
+                    }
+                    if let Some(user) = self.users.get(&user_key) {
+                        if let Some(connection) = self.user_connections.get(&user.address) {
+                            let diff_mask = update.diff_mask().clone();
+
+                            let converter = EntityConverter::new(
+                                &self.world_record,
+                                &connection.entity_manager,
+                            );
+                            world.component_apply_update(
+                                &converter,
+                                &entity,
+                                &component_kind,
+                                update,
+                            );
+
+                            // pass the changes on to every User the Entity is in scope for
+                            self.diff_handler
+                                .as_ref()
+                                .read()
+                                .expect("DiffHandler should be initialized")
+                                .mutate(&entity, &component_kind, &diff_mask, None);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Rejects the oldest change made by a Client to one of its Entities which
+    /// is still awaiting a decision, discarding it.
+    /// Rejecting a spawn discards the Entity, along with every later change
+    /// made to it. The Client is told, and despawns its Entity.
+    /// Rejecting a despawn leaves the Entity in the World, owned by the
+    /// Server from then on.
+    pub fn reject_client_entity_action(&mut self, client_entity_key: &ClientEntityKey) {
+        if let Some(client_entity) = self.client_entities.get_mut(client_entity_key) {
+            match client_entity.pending_actions.pop_front() {
+                Some(ClientEntityAction::SpawnEntity) => {
+                    self.reject_client_entity(client_entity_key);
+                }
+                Some(ClientEntityAction::DespawnEntity) => {
+                    if let Some(entity) = client_entity.entity {
+                        self.client_owned_entities.remove(&entity);
+                        // the former owner may now have the Entity in scope
+                        self.scope_checks
+                            .mark_dirty(&client_entity.user_key, &entity);
+                    }
+                    self.client_entities.remove(client_entity_key);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Gets the Entity associated with a Client-owned Entity, if its spawn has
+    /// been accepted
+    pub fn client_entity(&self, client_entity_key: &ClientEntityKey) -> Option<E> {
+        self.client_entities
+            .get(client_entity_key)
+            .and_then(|client_entity| client_entity.entity)
+    }
+
+    /// Gets the User who owns a Client-owned Entity
+    pub fn client_entity_owner(&self, client_entity_key: &ClientEntityKey) -> Option<UserKey> {
+        self.client_entities
+            .get(client_entity_key)
+            .map(|client_entity| client_entity.user_key)
+    }
+
+    // Entity Authority
+
+    /// Applies the changes Users have made to the Entities they've been given
+    /// authority over, and passes them on to every other User in scope.
+    /// Changes received after authority was taken away are discarded.
+    /// Should be called after each call to Server::receive, as only the
+    /// latest changes from each User are held until then.
+    pub fn apply_authority_updates<W: WorldMutType<P, E>>(&mut self, mut world: W) {
+        let incoming_authority_updates = std::mem::take(&mut self.incoming_authority_updates)
+            .into_iter()
+            .flat_map(|(user_key, updates)| {
+                updates
+                    .into_iter()
+                    .map(move |(entity, update)| (user_key, entity, update))
+            });
+        for (user_key, entity, update) in incoming_authority_updates {
+            if self.entity_authorities.get(&entity) != Some(&user_key) {
+                continue;
+            }
+            let component_kind = update.kind;
+            if !world.has_component_of_kind(&entity, &component_kind) {
+                continue;
+            }
+            if let Some(user) = self.users.get(&user_key) {
+                if let Some(connection) = self.user_connections.get(&user.address) {
+                    let diff_mask = update.diff_mask().clone();
+
+                    let converter =
+                        EntityConverter::new(&self.world_record, &connection.entity_manager);
+                    world.component_apply_update(&converter, &entity, &component_kind, update);
+
+                    // pass the changes on to every other User the Entity is in scope for
+                    self.diff_handler
+                        .as_ref()
+                        .read()
+                        .expect("DiffHandler should be initialized")
+                        .mutate(&entity, &component_kind, &diff_mask, Some(&user.address));
+                }
+            }
+        }
     }
 
     // Users
@@ -511,6 +940,12 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
         self.io.incoming_bandwidth_from_client(address)
     }
 
+    /// Gets the number of incoming packets which have been dropped because
+    /// they could not be read
+    pub fn invalid_packet_count(&self) -> u64 {
+        self.invalid_packet_count
+    }
+
     // Ping
     /// Gets the average Round Trip Time measured to the given User's Client
     pub fn rtt(&self, user_key: &UserKey) -> Option<f32> {
@@ -533,6 +968,18 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
         None
     }
 
+    /// Gets the rate, in bytes per second, at which packets are currently
+    /// allowed to be sent to the given User's Client. Returns None if
+    /// congestion control is disabled
+    pub fn send_rate(&self, user_key: &UserKey) -> Option<f32> {
+        if let Some(user) = self.users.get(user_key) {
+            if let Some(user_connection) = self.user_connections.get(&user.address) {
+                return user_connection.base.send_rate();
+            }
+        }
+        None
+    }
+
     // Crate-Public methods
 
     //// Entities
@@ -546,6 +993,8 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
             panic!("attempted to de-spawn nonexistent entity");
         }
 
+        self.entity_take_authority(entity);
+
         // TODO: we can make this more efficient in the future by caching which Entities
         // are in each User's scope
         for (_, user_connection) in self.user_connections.iter_mut() {
@@ -563,11 +1012,60 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
 
         // Delete scope
         self.entity_scope_map.remove_entity(entity);
+        self.scope_checks.remove_entity(entity);
+        self.spatial_scope_map.remove_entity(entity);
+        if let Some(spatial_grid) = &mut self.spatial_grid {
+            spatial_grid.remove_entity(entity);
+        }
+        self.global_entities.remove(entity);
+
+        // The owning Client can no longer change the Entity
+        if let Some(client_entity_key) = self.client_owned_entities.remove(entity) {
+            if let Some(client_entity) = self.client_entities.get_mut(&client_entity_key) {
+                client_entity.entity = None;
+            }
+            self.reject_client_entity(&client_entity_key);
+        }
 
         // Remove from ECS Record
         self.world_record.despawn_entity(entity);
     }
 
+    //// Entity Authority
+
+    /// Gives a User authority over the Entity, taking it from whichever User
+    /// held it before
+    pub(crate) fn entity_give_authority(&mut self, entity: &E, user_key: &UserKey) {
+        if !self.users.contains_key(user_key) {
+            panic!("attempted to give authority to nonexistent user");
+        }
+
+        if let Some(old_user_key) = self.entity_authorities.insert(*entity, *user_key) {
+            if old_user_key == *user_key {
+                return;
+            }
+            self.user_revoke_authority(&old_user_key, entity);
+        }
+
+        if let Some(user) = self.users.get(user_key) {
+            if let Some(user_connection) = self.user_connections.get_mut(&user.address) {
+                user_connection.entity_manager.grant_authority(entity);
+            }
+        }
+    }
+
+    /// Takes authority over the Entity away from whichever User holds it
+    pub(crate) fn entity_take_authority(&mut self, entity: &E) {
+        if let Some(user_key) = self.entity_authorities.remove(entity) {
+            self.user_revoke_authority(&user_key, entity);
+        }
+    }
+
+    /// Gets the User which holds authority over the Entity, if any
+    pub(crate) fn entity_authority(&self, entity: &E) -> Option<UserKey> {
+        self.entity_authorities.get(entity).copied()
+    }
+
     //// Entity Scopes
 
     pub(crate) fn user_scope_set_entity(
@@ -576,8 +1074,134 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
         entity: &E,
         is_contained: bool,
     ) {
-        self.entity_scope_map
-            .insert(*user_key, *entity, is_contained);
+        if self
+            .entity_scope_map
+            .insert(*user_key, *entity, is_contained)
+            != Some(is_contained)
+        {
+            self.scope_checks.mark_dirty(user_key, entity);
+        }
+    }
+
+    /// Marks the Entity's Scope Sets as needing to be evaluated again, to be
+    /// returned by `Server::pending_scope_checks`
+    pub(crate) fn entity_mark_scope_dirty(&mut self, entity: &E) {
+        if let Some(room_key) = self.world_record.entity_room(entity) {
+            if let Some(room) = self.rooms.get(&room_key) {
+                for user_key in room.user_keys() {
+                    self.scope_checks.mark_pending(&room_key, user_key, entity);
+                }
+            }
+        }
+    }
+
+    pub(crate) fn user_scope_set_entity_priority(
+        &mut self,
+        user_key: &UserKey,
+        entity: &E,
+        priority: f32,
+    ) {
+        if let Some(user) = self.users.get(user_key) {
+            if let Some(connection) = self.user_connections.get_mut(&user.address) {
+                connection
+                    .entity_manager
+                    .set_entity_priority(entity, priority);
+            }
+        }
+    }
+
+    /// Moves the Entity to a position in the spatial grid, so that it is only
+    /// in the scope of Users whose view reaches it, whether or not they share
+    /// a Room. Panics if
+    /// `ServerConfig.spatial_cell_size` is not set
+    pub(crate) fn entity_set_position(&mut self, entity: &E, x: f32, y: f32) {
+        let scope_changes = self.spatial_grid_mut().set_entity_position(entity, x, y);
+        self.apply_spatial_scope_changes(scope_changes);
+    }
+
+    /// Moves the User's view of the spatial grid, so that positioned Entities
+    /// are only in their scope while within `radius` of it. Panics if
+    /// `ServerConfig.spatial_cell_size` is not set
+    pub(crate) fn user_set_view(&mut self, user_key: &UserKey, x: f32, y: f32, radius: f32) {
+        let scope_changes = self
+            .spatial_grid_mut()
+            .set_user_view(user_key, x, y, radius);
+        self.apply_spatial_scope_changes(scope_changes);
+    }
+
+    fn spatial_grid_mut(&mut self) -> &mut SpatialGrid<E> {
+        self.spatial_grid
+            .as_mut()
+            .expect("the spatial grid is disabled, set ServerConfig.spatial_cell_size to use it")
+    }
+
+    fn apply_spatial_scope_changes(&mut self, scope_changes: Vec<(UserKey, E, bool)>) {
+        for (user_key, entity, in_view) in scope_changes {
+            if self.spatial_scope_map.insert(user_key, entity, in_view) != Some(in_view) {
+                self.scope_checks.mark_dirty(&user_key, &entity);
+            }
+        }
+    }
+
+    /// Whether the User's scope includes the Entity. A positioned Entity must
+    /// be within the User's view & not excluded from their scope, any other
+    /// Entity must have been included in it
+    fn user_scope_includes(&self, user_key: &UserKey, entity: &E) -> bool {
+        let scope_set = self.entity_scope_map.get(user_key, entity).copied();
+        let is_positioned = self
+            .spatial_grid
+            .as_ref()
+            .map(|spatial_grid| spatial_grid.has_entity(entity))
+            .unwrap_or(false);
+        if is_positioned {
+            let in_view = self.spatial_scope_map.get(user_key, entity) == Some(&true);
+            in_view && scope_set != Some(false)
+        } else {
+            scope_set == Some(true)
+        }
+    }
+
+    //// Global Entities
+
+    /// Sets whether the Entity is global, being in scope for every connected
+    /// User regardless of Rooms or their User Scope
+    pub(crate) fn entity_set_global(&mut self, entity: &E, is_global: bool) {
+        let changed = if is_global {
+            self.global_entities.insert(*entity)
+        } else {
+            self.global_entities.remove(entity)
+        };
+        if !changed {
+            return;
+        }
+
+        for connection in self.user_connections.values() {
+            self.scope_checks.mark_dirty(&connection.user_key, entity);
+        }
+    }
+
+    //// Entity Priority
+
+    pub(crate) fn entity_set_priority(&mut self, entity: &E, priority: f32) {
+        self.world_record.set_entity_priority(entity, priority);
+    }
+
+    pub(crate) fn entity_set_component_priority<R: ReplicateSafe<P>>(
+        &mut self,
+        entity: &E,
+        priority: f32,
+    ) {
+        self.world_record
+            .set_component_priority(entity, &P::kind_of::<R>(), priority);
+    }
+
+    pub(crate) fn entity_set_component_update_interval<R: ReplicateSafe<P>>(
+        &mut self,
+        entity: &E,
+        interval: Option<Duration>,
+    ) {
+        self.world_record
+            .set_component_update_interval(entity, &P::kind_of::<R>(), interval);
     }
 
     //// Components
@@ -607,7 +1231,8 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
         // actually insert component into world
         world.insert_component(entity, component_ref);
 
-        // add component to connections already tracking entity
+        // add component to connections already tracking entity. Those it lingers
+        // on after leaving their scope are given every Component on re-entry
         for (_, user_connection) in self.user_connections.iter_mut() {
             // insert component into user's connection
             if user_connection.entity_manager.scope_has_entity(entity) {
@@ -645,6 +1270,27 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
         world.remove_component::<R>(entity)
     }
 
+    /// Removes a Component from an Entity, given the Component's kind
+    fn remove_component_of_kind<W: WorldMutType<P, E>>(
+        &mut self,
+        world: &mut W,
+        entity: &E,
+        component_kind: &P::Kind,
+    ) -> Option<P> {
+        // clean up component on all connections
+        for (_, user_connection) in self.user_connections.iter_mut() {
+            user_connection
+                .entity_manager
+                .remove_component(entity, component_kind);
+        }
+
+        // cleanup all other loose ends
+        self.component_cleanup(entity, component_kind);
+
+        // remove from world
+        world.remove_component_of_kind(entity, component_kind)
+    }
+
     //// Users
 
     /// Get a User's Socket Address, given the associated UserKey
@@ -660,6 +1306,11 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
         if let Some(user) = self.users.remove(user_key) {
             if self.user_connections.remove(&user.address).is_some() {
                 self.entity_scope_map.remove_user(user_key);
+                self.scope_checks.remove_user(user_key);
+                self.spatial_scope_map.remove_user(user_key);
+                if let Some(spatial_grid) = &mut self.spatial_grid {
+                    spatial_grid.remove_user(user_key);
+                }
                 self.handshake_manager.delete_user(&user.address);
 
                 // TODO: cache this?
@@ -668,6 +1319,27 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
                     room.unsubscribe_user(user_key);
                 }
 
+                // Entities spawned by the User stay in the World, owned by the Server
+                let client_entity_keys: Vec<ClientEntityKey> = self
+                    .client_entities
+                    .iter()
+                    .filter(|(_, client_entity)| client_entity.user_key == *user_key)
+                    .map(|(client_entity_key, _)| client_entity_key)
+                    .collect();
+                for client_entity_key in client_entity_keys {
+                    let client_entity = self.client_entities.remove(&client_entity_key).unwrap();
+                    self.client_entity_keys
+                        .remove(&(*user_key, client_entity.net_entity));
+                    if let Some(entity) = client_entity.entity {
+                        self.client_owned_entities.remove(&entity);
+                    }
+                }
+
+                // Entities the User had authority over go back to the Server
+                self.entity_authorities
+                    .retain(|_, authority_user_key| authority_user_key != user_key);
+                self.incoming_authority_updates.remove(user_key);
+
                 if self.io.bandwidth_monitor_enabled() {
                     self.io.deregister_client(&user.address);
                 }
@@ -715,12 +1387,19 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
     pub(crate) fn room_add_user(&mut self, room_key: &RoomKey, user_key: &UserKey) {
         if let Some(room) = self.rooms.get_mut(room_key) {
             room.subscribe_user(user_key);
+            for entity in room.entities() {
+                self.scope_checks.add_scope_set(room_key, user_key, entity);
+            }
         }
     }
 
     /// Removes a User from a Room
     pub(crate) fn room_remove_user(&mut self, room_key: &RoomKey, user_key: &UserKey) {
         if let Some(room) = self.rooms.get_mut(room_key) {
+            for entity in room.entities() {
+                self.scope_checks
+                    .remove_scope_set(room_key, user_key, entity);
+            }
             room.unsubscribe_user(user_key);
         }
     }
@@ -748,6 +1427,9 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
         let mut is_some = false;
         if let Some(room) = self.rooms.get_mut(room_key) {
             room.add_entity(entity);
+            for user_key in room.user_keys() {
+                self.scope_checks.add_scope_set(room_key, user_key, entity);
+            }
             is_some = true;
         }
         if is_some {
@@ -758,6 +1440,10 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
     /// Remove an Entity from a Room, associated with the given RoomKey
     pub(crate) fn room_remove_entity(&mut self, room_key: &RoomKey, entity: &E) {
         if let Some(room) = self.rooms.get_mut(room_key) {
+            for user_key in room.user_keys() {
+                self.scope_checks
+                    .remove_scope_set(room_key, user_key, entity);
+            }
             room.remove_entity(entity);
             self.world_record.entity_leave_rooms(entity);
         }
@@ -768,6 +1454,10 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
         if let Some(room) = self.rooms.get_mut(room_key) {
             let entities: Vec<E> = room.entities().copied().collect();
             for entity in entities {
+                for user_key in room.user_keys() {
+                    self.scope_checks
+                        .remove_scope_set(room_key, user_key, &entity);
+                }
                 room.remove_entity(&entity);
                 self.world_record.entity_leave_rooms(&entity);
             }
@@ -867,149 +1557,201 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
                 Ok(Some((address, owned_reader))) => {
                     let mut reader = owned_reader.borrow();
 
-                    // Read header
-                    let header = StandardHeader::de(&mut reader).unwrap();
+                    if self.read_packet(&address, &mut reader).is_err() {
+                        // drop the malformed packet
+                        self.invalid_packet_count += 1;
+                    }
+                }
+                Ok(None) => {
+                    // No more packets, break loop
+                    break;
+                }
+                Err(error) => {
+                    self.incoming_events
+                        .push_back(Err(NaiaServerError::Wrapped(Box::new(error))));
+                }
+            }
+        }
+    }
 
-                    // Handshake stuff
-                    match header.packet_type {
-                        PacketType::ClientChallengeRequest => {
-                            let mut writer =
-                                self.handshake_manager.recv_challenge_request(&mut reader);
-                            self.io.send_writer(&address, &mut writer);
-                            continue;
-                        }
-                        PacketType::ClientConnectRequest => {
-                            match self.handshake_manager.recv_connect_request(&mut reader) {
-                                HandshakeResult::Success(auth_message_opt) => {
-                                    if self.user_connections.contains_key(&address) {
-                                        // send connectaccept response
-                                        let mut writer =
-                                            self.handshake_manager.write_connect_response();
-                                        self.io.send_writer(&address, &mut writer);
-                                        //
-                                    } else {
-                                        let user = User::new(address);
-                                        let user_key = self.users.insert(user);
-
-                                        if let Some(auth_message) = auth_message_opt {
-                                            self.incoming_events.push_back(Ok(
-                                                Event::Authorization(user_key, auth_message),
-                                            ));
-                                        } else {
-                                            self.accept_connection(&user_key);
-                                        }
-                                    }
-                                }
-                                HandshakeResult::Invalid => {
-                                    // do nothing
-                                }
+    /// Reads an incoming packet, returning an error if it is malformed
+    fn read_packet(
+        &mut self,
+        address: &SocketAddr,
+        reader: &mut BitReader,
+    ) -> Result<(), SerdeErr> {
+        // Read header
+        let header = StandardHeader::de(reader)?;
+
+        // Handshake stuff
+        match header.packet_type {
+            PacketType::ClientChallengeRequest => {
+                let mut writer = self.handshake_manager.recv_challenge_request(reader)?;
+                self.io.send_writer(address, &mut writer);
+                return Ok(());
+            }
+            PacketType::ClientConnectRequest => {
+                match self.handshake_manager.recv_connect_request(reader)? {
+                    HandshakeResult::Success(auth_message_opt) => {
+                        if self.user_connections.contains_key(address) {
+                            // send connectaccept response
+                            let mut writer = self.handshake_manager.write_connect_response();
+                            self.io.send_writer(address, &mut writer);
+                            //
+                        } else {
+                            let user = User::new(*address);
+                            let user_key = self.users.insert(user);
+
+                            if let Some(auth_message) = auth_message_opt {
+                                self.incoming_events
+                                    .push_back(Ok(Event::Authorization(user_key, auth_message)));
+                            } else {
+                                self.accept_connection(&user_key);
                             }
-                            continue;
                         }
-                        _ => {}
                     }
+                    HandshakeResult::Rejected(reason) => {
+                        // let the Client know why it will not be connected
+                        let mut writer = self.handshake_manager.write_reject_response(&reason);
+                        self.io.send_writer(address, &mut writer);
+                    }
+                    HandshakeResult::Invalid => {
+                        // do nothing
+                    }
+                }
+                return Ok(());
+            }
+            _ => {}
+        }
 
-                    // Packets requiring established connection
-                    if let Some(user_connection) = self.user_connections.get_mut(&address) {
-                        // Mark that we've heard from the client
-                        user_connection.base.mark_heard();
-
-                        // Process incoming header
-                        user_connection.process_incoming_header(&header);
-
-                        match header.packet_type {
-                            PacketType::Data => {
-                                // read client tick
-                                let server_and_client_tick_opt = {
-                                    if let Some(tick_manager) = self.tick_manager.as_ref() {
-                                        let client_tick =
-                                            tick_manager.read_client_tick(&mut reader);
-                                        user_connection.recv_client_tick(client_tick);
-
-                                        let server_tick = tick_manager.server_tick();
-
-                                        Some((server_tick, client_tick))
-                                    } else {
-                                        None
-                                    }
-                                };
-
-                                // process data
-                                user_connection.process_incoming_data(
-                                    server_and_client_tick_opt,
-                                    &mut reader,
-                                    &self.world_record,
-                                );
-                            }
-                            PacketType::Disconnect => {
-                                if self
-                                    .handshake_manager
-                                    .verify_disconnect_request(user_connection, &mut reader)
-                                {
-                                    let user_key = user_connection.user_key;
-                                    self.disconnect_user(&user_key);
-                                }
-                            }
-                            PacketType::Heartbeat => {
-                                // read client tick, don't need to do anything else
-                                if let Some(tick_manager) = self.tick_manager.as_ref() {
-                                    let client_tick = tick_manager.read_client_tick(&mut reader);
-                                    user_connection.recv_client_tick(client_tick);
-                                }
-                            }
-                            PacketType::Ping => {
-                                // read client tick
-                                if let Some(tick_manager) = self.tick_manager.as_ref() {
-                                    let client_tick = tick_manager.read_client_tick(&mut reader);
-                                    user_connection.recv_client_tick(client_tick);
-                                }
-
-                                // read incoming ping index
-                                let ping_index = u16::de(&mut reader).unwrap();
+        // Packets requiring established connection
+        if let Some(user_connection) = self.user_connections.get_mut(address) {
+            // Mark that we've heard from the client
+            user_connection.base.mark_heard();
 
-                                // write pong payload
-                                let mut writer = BitWriter::default();
+            // Process acks in the incoming header
+            user_connection.process_incoming_header(&header);
 
-                                // write header
-                                user_connection
-                                    .base
-                                    .write_outgoing_header(PacketType::Pong, &mut writer);
+            match header.packet_type {
+                PacketType::Data => {
+                    // read client tick
+                    let server_and_client_tick_opt = {
+                        if let Some(tick_manager) = self.tick_manager.as_ref() {
+                            let client_tick = tick_manager.read_client_tick(reader)?;
+                            user_connection.recv_client_tick(client_tick);
 
-                                // write server tick
-                                if let Some(tick_manager) = self.tick_manager.as_ref() {
-                                    tick_manager.write_server_tick(&mut writer);
-                                }
+                            let server_tick = tick_manager.server_tick();
 
-                                // write index
-                                ping_index.ser(&mut writer);
+                            Some((server_tick, client_tick))
+                        } else {
+                            None
+                        }
+                    };
 
-                                // send packet
-                                self.io.send_writer(&address, &mut writer);
-                                user_connection.base.mark_sent();
+                    // process data
+                    let result = user_connection.process_incoming_data(
+                        server_and_client_tick_opt,
+                        reader,
+                        &self.world_record,
+                    );
+
+                    // queue up changes to Client-owned Entities, even those read
+                    // before any malformed part of the packet
+                    let user_key = user_connection.user_key;
+                    let incoming_actions = user_connection
+                        .remote_entity_manager
+                        .take_incoming_actions();
+                    self.receive_client_entity_actions(&user_key, incoming_actions);
+
+                    // queue up changes to Entities the User has authority over
+                    let user_connection = self.user_connections.get_mut(address).unwrap();
+                    for (net_entity, update) in user_connection
+                        .remote_entity_manager
+                        .take_authority_updates()
+                    {
+                        if let Some(entity) =
+                            user_connection.entity_manager.world_entity(&net_entity)
+                        {
+                            if self.entity_authorities.get(&entity) != Some(&user_key) {
+                                continue;
                             }
-                            PacketType::Pong => {
-                                // read client tick
-                                if let Some(tick_manager) = self.tick_manager.as_ref() {
-                                    let client_tick = tick_manager.read_client_tick(&mut reader);
-                                    user_connection.recv_client_tick(client_tick);
-                                }
-
-                                user_connection.ping_manager.process_pong(&mut reader);
+                            let user_updates =
+                                self.incoming_authority_updates.entry(user_key).or_default();
+                            if user_updates.len() >= AUTHORITY_UPDATE_QUEUE_LIMIT {
+                                user_updates.pop_front();
                             }
-                            _ => {}
+                            user_updates.push_back((entity, update));
                         }
                     }
+
+                    result?;
                 }
-                Ok(None) => {
-                    // No more packets, break loop
-                    break;
+                PacketType::Disconnect => {
+                    if self
+                        .handshake_manager
+                        .verify_disconnect_request(user_connection, reader)?
+                    {
+                        let user_key = user_connection.user_key;
+                        self.disconnect_user(&user_key);
+                    }
                 }
-                Err(error) => {
-                    self.incoming_events
-                        .push_back(Err(NaiaServerError::Wrapped(Box::new(error))));
+                PacketType::Heartbeat => {
+                    // read client tick, don't need to do anything else
+                    if let Some(tick_manager) = self.tick_manager.as_ref() {
+                        let client_tick = tick_manager.read_client_tick(reader)?;
+                        user_connection.recv_client_tick(client_tick);
+                    }
+                }
+                PacketType::Ping => {
+                    // read client tick
+                    if let Some(tick_manager) = self.tick_manager.as_ref() {
+                        let client_tick = tick_manager.read_client_tick(reader)?;
+                        user_connection.recv_client_tick(client_tick);
+                    }
+
+                    // read incoming ping index
+                    let ping_index = u16::de(reader)?;
+
+                    // write pong payload
+                    let mut writer = BitWriter::default();
+
+                    // write header
+                    user_connection
+                        .base
+                        .write_outgoing_header(PacketType::Pong, &mut writer);
+
+                    // write server tick
+                    if let Some(tick_manager) = self.tick_manager.as_ref() {
+                        tick_manager.write_server_tick(&mut writer);
+                    }
+
+                    // write index
+                    ping_index.ser(&mut writer);
+
+                    // send packet
+                    self.io.send_writer(address, &mut writer);
+                    user_connection.base.mark_sent();
                 }
+                PacketType::Pong => {
+                    // read client tick
+                    if let Some(tick_manager) = self.tick_manager.as_ref() {
+                        let client_tick = tick_manager.read_client_tick(reader)?;
+                        user_connection.recv_client_tick(client_tick);
+                    }
+
+                    user_connection.ping_manager.process_pong(reader)?;
+                }
+                _ => {}
+            }
+
+            // only ack the packet once its payload has been read without error,
+            // so that the Client resends anything in a malformed packet
+            if let Some(user_connection) = self.user_connections.get_mut(address) {
+                user_connection.base.mark_packet_received(&header);
             }
         }
+
+        Ok(())
     }
 
     pub(crate) fn disconnect_user(&mut self, user_key: &UserKey) {
@@ -1025,60 +1767,203 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
         self.world_record.spawn_entity(entity);
     }
 
+    // Entity Authority Helpers
+
+    fn user_revoke_authority(&mut self, user_key: &UserKey, entity: &E) {
+        if let Some(user) = self.users.get(user_key) {
+            if let Some(user_connection) = self.user_connections.get_mut(&user.address) {
+                user_connection.entity_manager.revoke_authority(entity);
+            }
+        }
+    }
+
+    // Client-owned Entity Helpers
+
+    /// Stops accepting changes to a Client-owned Entity, and tells its owner
+    /// to despawn it. If its spawn was accepted, the Entity stays in the
+    /// World, owned by the Server
+    fn reject_client_entity(&mut self, client_entity_key: &ClientEntityKey) {
+        let client_entity =
+            if let Some(client_entity) = self.client_entities.get_mut(client_entity_key) {
+                client_entity
+            } else {
+                return;
+            };
+
+        client_entity.rejected = true;
+        client_entity.pending_actions.clear();
+
+        let user_key = client_entity.user_key;
+        let net_entity = client_entity.net_entity;
+
+        if let Some(entity) = client_entity.entity.take() {
+            self.client_owned_entities.remove(&entity);
+            // the former owner may now have the Entity in scope
+            self.scope_checks.mark_dirty(&user_key, &entity);
+        }
+
+        if let Some(user) = self.users.get(&user_key) {
+            if let Some(connection) = self.user_connections.get_mut(&user.address) {
+                connection.entity_manager.reject_client_entity(&net_entity);
+            }
+        }
+    }
+
+    fn receive_client_entity_actions(
+        &mut self,
+        user_key: &UserKey,
+        incoming_actions: Vec<(NetEntity, ClientEntityAction<P>)>,
+    ) {
+        for (net_entity, action) in incoming_actions {
+            let client_entity_key = if let ClientEntityAction::SpawnEntity = action {
+                if self
+                    .client_entity_keys
+                    .contains_key(&(*user_key, net_entity))
+                {
+                    continue;
+                }
+                let client_entity_key = self
+                    .client_entities
+                    .insert(ClientEntity::new(user_key, &net_entity));
+                self.client_entity_keys
+                    .insert((*user_key, net_entity), client_entity_key);
+                client_entity_key
+            } else if let Some(client_entity_key) =
+                self.client_entity_keys.get(&(*user_key, net_entity))
+            {
+                *client_entity_key
+            } else {
+                continue;
+            };
+
+            if let ClientEntityAction::DespawnEntity = action {
+                self.client_entity_keys.remove(&(*user_key, net_entity));
+            }
+
+            self.receive_client_entity_action(&client_entity_key, action);
+        }
+    }
+
+    fn receive_client_entity_action(
+        &mut self,
+        client_entity_key: &ClientEntityKey,
+        action: ClientEntityAction<P>,
+    ) {
+        let client_entity = self.client_entities.get_mut(client_entity_key).unwrap();
+
+        if client_entity.rejected {
+            // ignore any changes made to the Entity, until it's gone
+            if let ClientEntityAction::DespawnEntity = action {
+                self.client_entities.remove(client_entity_key);
+            }
+            return;
+        }
+
+        let user_key = client_entity.user_key;
+        let event = match &action {
+            ClientEntityAction::SpawnEntity => Event::SpawnEntity(user_key, *client_entity_key),
+            ClientEntityAction::DespawnEntity => Event::DespawnEntity(user_key, *client_entity_key),
+            ClientEntityAction::InsertComponent(component) => {
+                let component_kind = component.dyn_ref().kind();
+                if !client_entity.component_kinds.insert(component_kind) {
+                    return;
+                }
+                Event::InsertComponent(user_key, *client_entity_key, component_kind)
+            }
+            ClientEntityAction::RemoveComponent(component_kind) => {
+                if !client_entity.component_kinds.remove(component_kind) {
+                    return;
+                }
+                Event::RemoveComponent(user_key, *client_entity_key, *component_kind)
+            }
+            ClientEntityAction::UpdateComponent(update) => {
+                if !client_entity.component_kinds.contains(&update.kind) {
+                    return;
+                }
+                Event::UpdateComponent(user_key, *client_entity_key, update.kind)
+            }
+        };
+
+        // a Client which changes its Entity faster than the Server decides loses it
+        let is_despawn = matches!(action, ClientEntityAction::DespawnEntity);
+        if !is_despawn && client_entity.pending_actions.len() >= CLIENT_ENTITY_PENDING_ACTION_LIMIT
+        {
+            self.reject_client_entity(client_entity_key);
+            return;
+        }
+
+        client_entity.pending_actions.push_back(action);
+        self.incoming_events.push_back(Ok(event));
+    }
+
     // Entity Scopes
 
     fn update_entity_scopes<W: WorldRefType<P, E>>(&mut self, world: &W) {
-        for (_, room) in self.rooms.iter_mut() {
-            while let Some((removed_user, removed_entity)) = room.pop_entity_removal_queue() {
-                if let Some(user) = self.users.get(&removed_user) {
-                    if let Some(user_connection) = self.user_connections.get_mut(&user.address) {
-                        //remove entity from user connection
-                        user_connection
-                            .entity_manager
-                            .despawn_entity(&removed_entity);
-                    }
+        let server_tick = self.server_tick();
+
+        // only the Users & Entities which have changed since the last update need
+        // to be evaluated, which includes every pair split up by leaving a Room.
+        // Pairs of Users who are not yet connected are marked again once accepted
+        for (user_key, entity) in self.scope_checks.take_dirty() {
+            if !world.has_entity(&entity) {
+                // the Entity has not reached the World yet, evaluate it next time
+                if self.world_record.has_entity(&entity) {
+                    self.scope_checks.mark_dirty(&user_key, &entity);
                 }
+                continue;
             }
 
-            // TODO: we should be able to cache these tuples of keys to avoid building a new
-            // list each time
-            for user_key in room.user_keys() {
-                for entity in room.entities() {
-                    if world.has_entity(entity) {
-                        if let Some(user) = self.users.get(user_key) {
-                            if let Some(user_connection) =
-                                self.user_connections.get_mut(&user.address)
+            let should_be_in_scope = self.user_scope_includes(&user_key, &entity);
+
+            if let Some(user) = self.users.get(&user_key) {
+                if let Some(user_connection) = self.user_connections.get_mut(&user.address) {
+                    let currently_in_scope =
+                        user_connection.entity_manager.scope_has_entity(&entity);
+
+                    let shares_room = self
+                        .world_record
+                        .entity_room(&entity)
+                        .and_then(|room_key| self.rooms.get(&room_key))
+                        .map(|room| room.has_user(&user_key))
+                        .unwrap_or(false);
+
+                    // never replicate an Entity back to the Client who owns it
+                    let owned_by_user = self
+                        .client_owned_entities
+                        .get(&entity)
+                        .and_then(|client_entity_key| self.client_entities.get(client_entity_key))
+                        .map(|client_entity| client_entity.user_key == user_key)
+                        .unwrap_or(false);
+                    let is_global = self.global_entities.contains(&entity);
+                    // positioned Entities are scoped by the spatial grid, with or
+                    // without a Room
+                    let is_positioned = self
+                        .spatial_grid
+                        .as_ref()
+                        .map(|spatial_grid| spatial_grid.has_entity(&entity))
+                        .unwrap_or(false);
+                    let should_be_in_scope = (is_global
+                        || ((shares_room || is_positioned) && should_be_in_scope))
+                        && !owned_by_user;
+
+                    if should_be_in_scope {
+                        if !currently_in_scope {
+                            // add entity to the connections local scope
+                            user_connection.entity_manager.spawn_entity(&entity);
+                            // add components to connections local scope
+                            for component_kind in
+                                self.world_record.component_kinds(&entity).unwrap()
                             {
-                                let currently_in_scope =
-                                    user_connection.entity_manager.scope_has_entity(entity);
-
-                                let should_be_in_scope = if let Some(in_scope) =
-                                    self.entity_scope_map.get(user_key, entity)
-                                {
-                                    *in_scope
-                                } else {
-                                    false
-                                };
-
-                                if should_be_in_scope {
-                                    if !currently_in_scope {
-                                        // add entity to the connections local scope
-                                        user_connection.entity_manager.spawn_entity(entity);
-                                        // add components to connections local scope
-                                        for component_kind in
-                                            self.world_record.component_kinds(entity).unwrap()
-                                        {
-                                            user_connection
-                                                .entity_manager
-                                                .insert_component(entity, &component_kind);
-                                        }
-                                    }
-                                } else if currently_in_scope {
-                                    // remove entity from the connections local scope
-                                    user_connection.entity_manager.despawn_entity(entity);
-                                }
+                                user_connection
+                                    .entity_manager
+                                    .insert_component(&entity, &component_kind);
                             }
                         }
+                    } else if currently_in_scope {
+                        // remove entity from the connections local scope
+                        user_connection
+                            .entity_manager
+                            .exclude_entity(&entity, server_tick);
                     }
                 }
             }
@@ -1107,6 +1992,7 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Server<
 
     fn component_cleanup(&mut self, entity: &E, component_kind: &P::Kind) {
         self.world_record.remove_component(entity, component_kind);
+        self.world_history.remove_component(entity, component_kind);
         self.diff_handler
             .as_ref()
             .write()
@@ -1126,3 +2012,31 @@ impl<P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> EntityH
         self.world_record.entity_to_handle(entity)
     }
 }
+
+// Inserts Components accepted from a Client, as if they had been inserted by
+// the Server
+struct ClientComponentInserter<
+    's,
+    'w,
+    P: Protocolize,
+    E: Copy + Eq + Hash + Send + Sync,
+    C: ChannelIndex,
+    W: WorldMutType<P, E>,
+> {
+    server: &'s mut Server<P, E, C>,
+    world: &'w mut W,
+}
+
+impl<
+        's,
+        'w,
+        P: Protocolize,
+        E: Copy + Eq + Hash + Send + Sync,
+        C: ChannelIndex,
+        W: WorldMutType<P, E>,
+    > ProtocolInserter<P, E> for ClientComponentInserter<'s, 'w, P, E, C, W>
+{
+    fn insert<R: ReplicateSafe<P>>(&mut self, entity: &E, component: R) {
+        self.server.insert_component(self.world, entity, component);
+    }
+}
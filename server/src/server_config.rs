@@ -1,4 +1,4 @@
-use std::default::Default;
+use std::{default::Default, time::Duration};
 
 use naia_shared::ConnectionConfig;
 
@@ -10,6 +10,22 @@ pub struct ServerConfig {
     /// Determines whether to require that the Client send some auth message
     /// in order to connect.
     pub require_auth: bool,
+    /// How long the history of Components recorded for lag compensation is
+    /// kept for
+    pub history_duration: Duration,
+    /// How far behind the latest state received from the Server that Clients
+    /// render the World, for example due to interpolation. Used to determine
+    /// which Tick a User was looking at.
+    pub interpolation_delay: Duration,
+    /// The width & height of the cells of the spatial grid, which scopes
+    /// positioned Entities to the Users whose view reaches their cell,
+    /// without needing them to share a Room. The grid is disabled when this
+    /// is None
+    pub spatial_cell_size: Option<f32>,
+    /// How long an Entity which has left a User's scope stays replicated to
+    /// them before it is despawned on their Client. Should the Entity
+    /// re-enter their scope within this time, it is not spawned again
+    pub despawn_grace_period: DespawnGracePeriod,
 }
 
 impl Default for ServerConfig {
@@ -17,6 +33,29 @@ impl Default for ServerConfig {
         Self {
             connection: ConnectionConfig::default(),
             require_auth: true,
+            history_duration: Duration::from_secs(1),
+            interpolation_delay: Duration::ZERO,
+            spatial_cell_size: None,
+            despawn_grace_period: DespawnGracePeriod::Duration(Duration::ZERO),
+        }
+    }
+}
+
+/// How long an Entity which has left a User's scope stays replicated to them
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DespawnGracePeriod {
+    /// Measured in time
+    Duration(Duration),
+    /// Measured in Server Ticks, which requires `SharedConfig.tick_interval`
+    /// to be set
+    Ticks(u16),
+}
+
+impl DespawnGracePeriod {
+    pub fn is_zero(&self) -> bool {
+        match self {
+            Self::Duration(duration) => duration.is_zero(),
+            Self::Ticks(ticks) => *ticks == 0,
         }
     }
 }
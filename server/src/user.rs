@@ -88,4 +88,18 @@ impl<'s, P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex> Use
 
         self
     }
+
+    // Spatial Scope
+
+    /// Sets the center & radius of the User's view. Every Entity given a
+    /// position with `EntityMut::set_position` is only in the User's scope
+    /// while it is in a cell of the spatial grid within the view. Excluding
+    /// such an Entity with `UserScopeMut::exclude` hides it even in view,
+    /// while including it does not bring it into scope out of view. Panics
+    /// if `ServerConfig.spatial_cell_size` is not set
+    pub fn set_view(&mut self, x: f32, y: f32, radius: f32) -> &mut Self {
+        self.server.user_set_view(&self.key, x, y, radius);
+
+        self
+    }
 }
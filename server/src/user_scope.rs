@@ -27,4 +27,13 @@ impl<'s, P: Protocolize, E: Copy + Eq + Hash + Send + Sync, C: ChannelIndex>
 
         self
     }
+
+    /// Overrides the priority of an Entity's updates for this User only,
+    /// until the Entity leaves the User's scope
+    pub fn set_priority(&mut self, entity: &E, priority: f32) -> &mut Self {
+        self.server
+            .user_scope_set_entity_priority(&self.key, entity, priority);
+
+        self
+    }
 }
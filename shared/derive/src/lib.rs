@@ -5,25 +5,42 @@
 #![deny(trivial_casts, trivial_numeric_casts, unstable_features)]
 
 mod channel_index;
+mod message;
+mod nested_replicate;
 mod protocolize;
 mod replicate;
 
 use channel_index::channels_impl;
+use message::message_impl;
+use nested_replicate::nested_replicate_impl;
 use protocolize::protocolize_impl;
 use replicate::replicate_impl;
 
 /// Derives the Protocolize trait for a given enum
-#[proc_macro_derive(Protocolize)]
+#[proc_macro_derive(Protocolize, attributes(protocol))]
 pub fn protocolize_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     protocolize_impl(input)
 }
 
 /// Derives the Replicate trait for a given struct
-#[proc_macro_derive(Replicate, attributes(protocol_path))]
+#[proc_macro_derive(Replicate, attributes(protocol_path, property, replicate))]
 pub fn replicate_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     replicate_impl(input)
 }
 
+/// Derives the NestedReplicate trait for a given struct
+#[proc_macro_derive(NestedReplicate, attributes(property, replicate))]
+pub fn nested_replicate_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    nested_replicate_impl(input)
+}
+
+/// Derives the Replicate trait for a given struct of plain fields, to be sent
+/// as a Message without tracking changes to its fields
+#[proc_macro_derive(Message, attributes(protocol_path))]
+pub fn message_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    message_impl(input)
+}
+
 #[proc_macro_attribute]
 pub fn derive_channels(
     first_input: proc_macro::TokenStream,
@@ -0,0 +1,272 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Data, DeriveInput, Fields, Ident, Type};
+
+use crate::replicate::{
+    dyn_mut_method, dyn_ref_method, entities_method, has_entity_properties_method,
+    layout_hash_method, protocol_path, Property,
+};
+
+pub fn message_impl(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    // Helper Properties
+    let fields = fields(&input);
+
+    // Paths
+    let (protocol_path, protocol_name) = protocol_path(&input);
+
+    // Names
+    let message_name = input.ident;
+    let protocol_kind_name = format_ident!("{}Kind", protocol_name);
+
+    // Message Methods
+    let new_complete_method = new_complete_method(&message_name, &fields);
+    let read_method = read_method(&protocol_name, &message_name, &fields);
+    let read_create_update_method = read_create_update_method(&message_name, &protocol_kind_name);
+    let layout_hash_method = layout_hash_method(&fields);
+
+    // ReplicateSafe Derive Methods
+    let dyn_ref_method = dyn_ref_method(&protocol_name);
+    let dyn_mut_method = dyn_mut_method(&protocol_name);
+    let write_method = write_method(&fields);
+    let clone_method = clone_method(&fields);
+    let has_entity_properties = has_entity_properties_method(&fields);
+    let entities = entities_method(&fields);
+
+    // generated within an anonymous const, so that several Messages can be
+    // derived in the same module
+    let gen = quote! {
+        const _: () = {
+            use naia_shared::{DiffMask, ReplicateSafe, Replicate, PropertyMutator, ComponentUpdate,
+                Protocolize, ReplicaDynRef, ReplicaDynMut, serde::{BitReader, BitWrite, Serde, SerdeErr, SerdeErrReason},
+                NetEntityHandleConverter, NoBaselines, PropertyBaselines};
+            use #protocol_path::{#protocol_name, #protocol_kind_name};
+            mod internal {
+                pub use naia_shared::{EntityProperty, EntityHandle};
+            }
+
+            impl #message_name {
+                #new_complete_method
+                #read_method
+                #read_create_update_method
+                #layout_hash_method
+            }
+            impl ReplicateSafe<#protocol_name> for #message_name {
+                // Messages are never updated, so have no DiffMask
+                fn diff_mask_size(&self) -> u8 { 0 }
+                fn kind(&self) -> #protocol_kind_name {
+                    Protocolize::kind_of::<Self>()
+                }
+                #dyn_ref_method
+                #dyn_mut_method
+                fn into_protocol(self) -> #protocol_name {
+                    #protocol_name::#message_name(self)
+                }
+                fn protocol_copy(&self) -> #protocol_name {
+                    #protocol_name::#message_name(self.clone())
+                }
+                fn mirror(&mut self, other: &#protocol_name) {
+                    if let #protocol_name::#message_name(message) = other {
+                        *self = message.clone();
+                    }
+                }
+                fn set_mutator(&mut self, _: &PropertyMutator) {}
+                #write_method
+                fn write_update_against(&self, _: &DiffMask, _: &mut dyn BitWrite, _: &dyn NetEntityHandleConverter, _: &mut dyn PropertyBaselines) {}
+                fn read_apply_update(&mut self, _: &dyn NetEntityHandleConverter, _: ComponentUpdate<#protocol_kind_name>) {}
+                #has_entity_properties
+                #entities
+            }
+            impl Replicate<#protocol_name> for #message_name {}
+            impl Clone for #message_name {
+                #clone_method
+            }
+        };
+    };
+
+    proc_macro::TokenStream::from(gen)
+}
+
+/// Gets each field of the Message, as a Property of the field's whole type
+/// (which must implement Serde), or an EntityProperty
+fn fields(input: &DeriveInput) -> Vec<Property> {
+    let mut fields = Vec::new();
+
+    if let Data::Struct(data_struct) = &input.data {
+        match &data_struct.fields {
+            Fields::Named(fields_named) => {
+                for field in fields_named.named.iter() {
+                    if let Some(variable_name) = &field.ident {
+                        if is_entity_property(&field.ty) {
+                            fields.push(Property::entity(variable_name.clone()));
+                        } else {
+                            fields.push(Property::normal(
+                                variable_name.clone(),
+                                field.ty.clone(),
+                                None,
+                            ));
+                        }
+                    }
+                }
+                return fields;
+            }
+            Fields::Unit => {
+                return fields;
+            }
+            Fields::Unnamed(_) => {}
+        }
+    }
+
+    panic!("'Message' can only be derived for a struct with named fields, or a unit struct");
+}
+
+fn is_entity_property(field_type: &Type) -> bool {
+    if let Type::Path(type_path) = field_type {
+        if let Some(last_seg) = type_path.path.segments.last() {
+            return last_seg.ident == "EntityProperty";
+        }
+    }
+    false
+}
+
+fn new_complete_method(message_name: &Ident, fields: &[Property]) -> TokenStream {
+    let mut args = quote! {};
+    let mut field_inits = quote! {};
+    for field in fields.iter() {
+        match field {
+            Property::Normal(field) => {
+                let field_name = &field.variable_name;
+                let field_type = &field.inner_type;
+                args = quote! {
+                    #args #field_name: #field_type,
+                };
+                field_inits = quote! {
+                    #field_inits #field_name,
+                };
+            }
+            Property::Entity(field) => {
+                let field_name = &field.variable_name;
+                field_inits = quote! {
+                    #field_inits #field_name: internal::EntityProperty::new(0),
+                };
+            }
+            Property::Nested(_) | Property::Collection(_) => unreachable!(),
+        }
+    }
+
+    quote! {
+        pub fn new_complete(#args) -> #message_name {
+            #message_name {
+                #field_inits
+            }
+        }
+    }
+}
+
+fn read_method(protocol_name: &Ident, message_name: &Ident, fields: &[Property]) -> TokenStream {
+    let mut field_reads = quote! {};
+    let mut field_names = quote! {};
+    for field in fields.iter() {
+        let field_name = field.variable_name();
+        let field_read = match field {
+            Property::Normal(field) => {
+                let field_type = &field.inner_type;
+                quote! {
+                    let #field_name = <#field_type as Serde>::de(bit_reader)?;
+                }
+            }
+            Property::Entity(_) => {
+                quote! {
+                    let #field_name = internal::EntityProperty::new_read(bit_reader, 0, converter)?;
+                }
+            }
+            Property::Nested(_) | Property::Collection(_) => unreachable!(),
+        };
+        field_reads = quote! {
+            #field_reads
+            #field_read
+        };
+        field_names = quote! {
+            #field_names #field_name,
+        };
+    }
+
+    quote! {
+        #[allow(unused_variables)]
+        pub fn read(bit_reader: &mut BitReader, converter: &dyn NetEntityHandleConverter) -> Result<#protocol_name, SerdeErr> {
+            #field_reads
+
+            Ok(#protocol_name::#message_name(#message_name {
+                #field_names
+            }))
+        }
+    }
+}
+
+fn read_create_update_method(message_name: &Ident, kind_name: &Ident) -> TokenStream {
+    quote! {
+        pub fn read_create_update(bit_reader: &mut BitReader) -> Result<ComponentUpdate::<#kind_name>, SerdeErr> {
+            Self::read_create_update_against(bit_reader, &mut NoBaselines)
+        }
+
+        pub fn read_create_update_against(bit_reader: &mut BitReader, _: &mut dyn PropertyBaselines) -> Result<ComponentUpdate::<#kind_name>, SerdeErr> {
+            // Messages are never updated, so an update for one is invalid
+            Err(SerdeErr::new(
+                bit_reader.bit_offset(),
+                stringify!(#message_name),
+                SerdeErrReason::InvalidValue,
+            ))
+        }
+    }
+}
+
+fn write_method(fields: &[Property]) -> TokenStream {
+    let mut field_writes = quote! {};
+    for field in fields.iter() {
+        let field_name = field.variable_name();
+        let field_write = match field {
+            Property::Normal(_) => {
+                quote! {
+                    self.#field_name.ser(bit_writer);
+                }
+            }
+            Property::Entity(_) => {
+                quote! {
+                    self.#field_name.write(bit_writer, converter);
+                }
+            }
+            Property::Nested(_) | Property::Collection(_) => unreachable!(),
+        };
+        field_writes = quote! {
+            #field_writes
+            #field_write
+        };
+    }
+
+    quote! {
+        #[allow(unused_variables)]
+        fn write(&self, bit_writer: &mut dyn BitWrite, converter: &dyn NetEntityHandleConverter) {
+            self.kind().ser(bit_writer);
+            #field_writes
+        }
+    }
+}
+
+fn clone_method(fields: &[Property]) -> TokenStream {
+    let mut field_clones = quote! {};
+    for field in fields.iter() {
+        let field_name = field.variable_name();
+        field_clones = quote! {
+            #field_clones #field_name: self.#field_name.clone(),
+        };
+    }
+
+    quote! {
+        fn clone(&self) -> Self {
+            Self {
+                #field_clones
+            }
+        }
+    }
+}
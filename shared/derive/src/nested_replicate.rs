@@ -0,0 +1,153 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, DeriveInput, Ident};
+
+use crate::replicate::{
+    clone_method, entities_method, has_entity_properties_method, layout_hash_body, local_fields,
+    local_fields_default, new_complete_method, properties, property_count, property_enum,
+    read_apply_update_body, read_body, read_write_update_body, set_mutator_method, write_body,
+    write_update_body, Property,
+};
+
+pub fn nested_replicate_impl(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    // Helper Properties
+    let properties = properties(&input);
+    let local_fields = local_fields(&input);
+
+    // Names
+    let struct_name = input.ident;
+    let enum_name = format_ident!("{}Property", struct_name);
+
+    // Definitions
+    let property_enum_definition = property_enum(&enum_name, &properties);
+
+    // Struct Methods
+    let new_complete_method =
+        new_complete_method(&struct_name, &enum_name, &properties, &local_fields);
+    let clone_method = clone_method(&struct_name, &properties, &local_fields);
+
+    // NestedReplicate Derive Methods
+    let property_count = property_count(&properties);
+    let layout_hash_body = layout_hash_body(&properties);
+    let set_mutator_method = set_mutator_method(&properties);
+    let mirror_method = mirror_method(&properties);
+    let write_body = write_body(&properties);
+    let offset = quote! { offset };
+    let write_update_body = write_update_body(&enum_name, &offset, &properties);
+    let new_read_method = new_read_method(&enum_name, &properties, &local_fields);
+    let read_write_update_body = read_write_update_body(&enum_name, &offset, &properties);
+    let read_apply_update_body = read_apply_update_body(&properties, &quote! { ? });
+    let has_entity_properties = has_entity_properties_method(&properties);
+    let entities = entities_method(&properties);
+
+    // generated within an anonymous const, so that several structs can be
+    // derived in the same module
+    let gen = quote! {
+        const _: () = {
+            use naia_shared::{DiffMask, PropertyMutator, NestedReplicate,
+                serde::{BitReader, BitWrite, BitWriter, Serde, SerdeErr}, NetEntityHandleConverter,
+                PropertyBaselines};
+            mod internal {
+                pub use naia_shared::{EntityProperty, EntityHandle};
+            }
+
+            #property_enum_definition
+
+            impl #struct_name {
+                #new_complete_method
+            }
+            impl NestedReplicate for #struct_name {
+                const PROPERTY_COUNT: usize = #property_count;
+
+                fn layout_hash() -> u64 {
+                    #layout_hash_body
+                }
+                #set_mutator_method
+                #mirror_method
+                fn write(&self, bit_writer: &mut dyn BitWrite, converter: &dyn NetEntityHandleConverter) {
+                    #write_body
+                }
+                #[allow(unused_variables)]
+                fn write_update(&self, diff_mask: &DiffMask, offset: u8, writer: &mut dyn BitWrite, converter: &dyn NetEntityHandleConverter, baselines: &mut dyn PropertyBaselines) {
+                    #write_update_body
+                }
+                #new_read_method
+                #[allow(unused_variables)]
+                fn read_write_update(bit_reader: &mut BitReader, update_writer: &mut BitWriter, diff_mask: &mut DiffMask, offset: u8, baselines: &mut dyn PropertyBaselines) -> Result<(), SerdeErr> {
+                    #read_write_update_body
+                    Ok(())
+                }
+                #[allow(unused_variables)]
+                fn read_apply_update(&mut self, reader: &mut BitReader, converter: &dyn NetEntityHandleConverter) -> Result<(), SerdeErr> {
+                    #read_apply_update_body
+                    Ok(())
+                }
+                #has_entity_properties
+                #entities
+            }
+            impl Clone for #struct_name {
+                #clone_method
+            }
+        };
+    };
+
+    proc_macro::TokenStream::from(gen)
+}
+
+fn mirror_method(properties: &[Property]) -> TokenStream {
+    let mut output = quote! {};
+
+    for property in properties.iter() {
+        let field_name = property.variable_name();
+        let new_output_right = quote! {
+            self.#field_name.mirror(&other.#field_name);
+        };
+        let new_output_result = quote! {
+            #output
+            #new_output_right
+        };
+        output = new_output_result;
+    }
+
+    quote! {
+        fn mirror(&mut self, other: &Self) {
+            #output
+        }
+    }
+}
+
+fn new_read_method(
+    enum_name: &Ident,
+    properties: &[Property],
+    local_fields: &[Ident],
+) -> TokenStream {
+    let mut prop_names = quote! {};
+    for property in properties.iter() {
+        let field_name = property.variable_name();
+        let new_output_right = quote! {
+            #field_name
+        };
+        let new_output_result = quote! {
+            #prop_names
+            #new_output_right,
+        };
+        prop_names = new_output_result;
+    }
+
+    let prop_reads = read_body(enum_name, properties);
+    let local_fields = local_fields_default(local_fields);
+
+    quote! {
+        #[allow(unused_variables)]
+        fn new_read(bit_reader: &mut BitReader, converter: &dyn NetEntityHandleConverter) -> Result<Self, SerdeErr> {
+            #prop_reads
+
+            Ok(Self {
+                #prop_names
+                #local_fields
+            })
+        }
+    }
+}
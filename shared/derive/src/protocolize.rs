@@ -1,6 +1,6 @@
 use proc_macro2::{Punct, Spacing, Span, TokenStream};
 use quote::{format_ident, quote};
-use syn::{parse_macro_input, Data, DeriveInput, Ident};
+use syn::{parse_macro_input, Data, DeriveInput, Ident, Lit, Meta, NestedMeta, Variant};
 
 pub fn protocolize_impl(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
@@ -8,11 +8,13 @@ pub fn protocolize_impl(input: proc_macro::TokenStream) -> proc_macro::TokenStre
     let protocol_name = input.ident;
 
     let variants = variants(&input.data);
+    let variant_ids = variant_ids(&input.data);
 
     let kind_enum_name = format_ident!("{}Kind", protocol_name);
-    let kind_enum_def = kind_enum(&kind_enum_name, &variants);
+    let kind_enum_def = kind_enum(&kind_enum_name, &variants, &variant_ids);
     let kind_of_method = kind_of_method();
     let type_to_kind_method = type_to_kind_method(&kind_enum_name, &variants);
+    let protocol_hash_method = protocol_hash_method(&variants, &variant_ids);
     let dyn_ref_method = dyn_ref_method(&protocol_name, &variants);
     let dyn_mut_method = dyn_mut_method(&protocol_name, &variants);
     let cast_method = cast_method(&protocol_name, &variants);
@@ -29,7 +31,7 @@ pub fn protocolize_impl(input: proc_macro::TokenStream) -> proc_macro::TokenStre
         use std::{any::{Any, TypeId}, ops::{Deref, DerefMut}, sync::RwLock, collections::HashMap};
         use naia_shared::{ProtocolInserter, ProtocolKindType, ReplicateSafe, ComponentUpdate,
             DiffMask, ReplicaDynRef, ReplicaDynMut, Replicate, derive_serde, serde, serde::Serde,
-            NetEntityHandleConverter};
+            NetEntityHandleConverter, PropertyBaselines};
 
         #kind_enum_def
 
@@ -37,6 +39,7 @@ pub fn protocolize_impl(input: proc_macro::TokenStream) -> proc_macro::TokenStre
             type Kind = #kind_enum_name;
             #kind_of_method
             #type_to_kind_method
+            #protocol_hash_method
             #read_method
             #read_create_update_method
             #dyn_ref_method
@@ -67,7 +70,75 @@ pub fn variants(data: &Data) -> Vec<Ident> {
     variants
 }
 
-pub fn kind_enum(enum_name: &Ident, variants: &Vec<Ident>) -> TokenStream {
+/// Gets the ids given to every variant with `#[protocol(id = N)]`, which are
+/// then written instead of the variant's position, so that variants can be
+/// reordered or added without breaking compatibility with deployed builds.
+/// Returns None if no ids were given
+pub fn variant_ids(data: &Data) -> Option<Vec<u16>> {
+    let mut ids: Vec<(Ident, Option<u16>)> = Vec::new();
+    if let Data::Enum(ref data) = *data {
+        for variant in data.variants.iter() {
+            ids.push((variant.ident.clone(), variant_id(variant)));
+        }
+    }
+
+    if ids.iter().all(|(_, id)| id.is_none()) {
+        return None;
+    }
+
+    let mut output = Vec::new();
+    for (index, (variant_name, id)) in ids.iter().enumerate() {
+        let id = id.unwrap_or_else(|| {
+            panic!(
+                "Variant '{}' has no id. When any variant of a Protocol is given an id, every variant must be. IE: '#[protocol(id = 1)]'",
+                variant_name
+            )
+        });
+        for (other_name, other_id) in ids.iter().take(index) {
+            if *other_id == Some(id) {
+                panic!(
+                    "Variants '{}' & '{}' have the same id: {}. Each variant of a Protocol must have a unique id",
+                    other_name, variant_name, id
+                );
+            }
+        }
+        output.push(id);
+    }
+
+    Some(output)
+}
+
+/// Gets the id given to a variant with `#[protocol(id = N)]`
+fn variant_id(variant: &Variant) -> Option<u16> {
+    for attr in variant.attrs.iter() {
+        if !attr.path.is_ident("protocol") {
+            continue;
+        }
+        if let Ok(Meta::List(meta_list)) = attr.parse_meta() {
+            for nested_meta in meta_list.nested.iter() {
+                if let NestedMeta::Meta(Meta::NameValue(meta_name_value)) = nested_meta {
+                    if meta_name_value.path.is_ident("id") {
+                        if let Lit::Int(lit_int) = &meta_name_value.lit {
+                            return Some(
+                                lit_int
+                                    .base10_parse()
+                                    .expect("'id' must be an integer between 0 & 65535"),
+                            );
+                        }
+                    }
+                }
+            }
+        }
+        panic!("Expected a Protocol attribute such as '#[protocol(id = 1)]'");
+    }
+    None
+}
+
+pub fn kind_enum(
+    enum_name: &Ident,
+    variants: &Vec<Ident>,
+    variant_ids: &Option<Vec<u16>>,
+) -> TokenStream {
     let hashtag = Punct::new('#', Spacing::Alone);
 
     let mut variant_definitions = quote! {};
@@ -101,13 +172,73 @@ pub fn kind_enum(enum_name: &Ident, variants: &Vec<Ident>) -> TokenStream {
         }
     }
 
+    let variant_ids = match variant_ids {
+        Some(variant_ids) => variant_ids,
+        None => {
+            return quote! {
+                #hashtag[derive(Hash, Eq, Copy, Debug)]
+                #hashtag[derive_serde]
+                pub enum #enum_name {
+                    #variant_definitions
+                }
+
+                impl ProtocolKindType for #enum_name {
+                    fn to_type_id(&self) -> TypeId {
+                        match self {
+                            #variants_to_type_id
+                        }
+                    }
+                }
+            };
+        }
+    };
+
+    // with explicit ids, the id is written as a variable length integer, so
+    // that adding a variant with a larger id does not change how the others
+    // are written
+    let mut ser_variants = quote! {};
+    let mut de_variants = quote! {};
+    for (variant_name, id) in variants.iter().zip(variant_ids.iter()) {
+        ser_variants = quote! {
+            #ser_variants
+            #enum_name::#variant_name => #id,
+        };
+        de_variants = quote! {
+            #de_variants
+            #id => return Ok(#enum_name::#variant_name),
+        };
+    }
+
     return quote! {
-        #hashtag[derive(Hash, Eq, Copy, Debug)]
-        #hashtag[derive_serde]
+        #hashtag[derive(Hash, Eq, PartialEq, Clone, Copy, Debug)]
         pub enum #enum_name {
             #variant_definitions
         }
 
+        impl Serde for #enum_name {
+            fn ser(&self, writer: &mut dyn serde::BitWrite) {
+                let id: u16 = match self {
+                    #ser_variants
+                };
+                serde::UnsignedVariableInteger::<7>::new(id).ser(writer);
+            }
+
+            fn de(reader: &mut serde::BitReader) -> Result<Self, serde::SerdeErr> {
+                let id = serde::UnsignedVariableInteger::<7>::de(reader)?.get();
+                if let Ok(id) = u16::try_from(id) {
+                    match id {
+                        #de_variants
+                        _ => {}
+                    }
+                }
+                Err(serde::SerdeErr::new(
+                    reader.bit_offset(),
+                    stringify!(#enum_name),
+                    serde::SerdeErrReason::InvalidValue,
+                ))
+            }
+        }
+
         impl ProtocolKindType for #enum_name {
             fn to_type_id(&self) -> TypeId {
                 match self {
@@ -166,6 +297,40 @@ fn type_to_kind_method(enum_name: &Ident, variants: &Vec<Ident>) -> TokenStream
     };
 }
 
+fn protocol_hash_method(variants: &[Ident], variant_ids: &Option<Vec<u16>>) -> TokenStream {
+    let has_explicit_ids = variant_ids.is_some();
+    let mut variant_hashes = quote! {};
+
+    // variants are hashed in order of kind id, so that reordering explicitly
+    // numbered variants does not change the hash
+    let mut variants: Vec<(&Ident, u16)> = match variant_ids {
+        Some(ids) => variants.iter().zip(ids.iter().copied()).collect(),
+        None => variants.iter().zip(0..variants.len() as u16).collect(),
+    };
+    variants.sort_by_key(|(_, id)| *id);
+
+    for (variant_name, id) in variants {
+        let new_output_right = quote! {
+            hasher.write_u64(#id as u64);
+            hasher.write_u64(#variant_name::layout_hash());
+        };
+        let new_output_result = quote! {
+            #variant_hashes
+            #new_output_right
+        };
+        variant_hashes = new_output_result;
+    }
+
+    quote! {
+        fn protocol_hash() -> u64 {
+            let mut hasher = naia_shared::FingerprintHasher::default();
+            hasher.write_u64(#has_explicit_ids as u64);
+            #variant_hashes
+            hasher.finish()
+        }
+    }
+}
+
 pub fn read_method(enum_name: &Ident, variants: &Vec<Ident>) -> TokenStream {
     let mut variants_build = quote! {};
 
@@ -186,8 +351,8 @@ pub fn read_method(enum_name: &Ident, variants: &Vec<Ident>) -> TokenStream {
     }
 
     return quote! {
-        fn read(bit_reader: &mut serde::BitReader, converter: &dyn NetEntityHandleConverter) -> Self {
-            let protocol_kind: Self::Kind = Self::Kind::de(bit_reader).unwrap();
+        fn read(bit_reader: &mut serde::BitReader, converter: &dyn NetEntityHandleConverter) -> Result<Self, serde::SerdeErr> {
+            let protocol_kind: Self::Kind = Self::Kind::de(bit_reader)?;
             match protocol_kind {
                 #variants_build
             }
@@ -204,7 +369,7 @@ pub fn read_create_update_method(enum_name: &Ident, variants: &Vec<Ident>) -> To
         // Variants build() match branch
         {
             let new_output_right = quote! {
-                #enum_name::#variant_name => #variant_name::read_create_update(bit_reader),
+                #enum_name::#variant_name => #variant_name::read_create_update_against(bit_reader, baselines),
             };
             let new_output_result = quote! {
                 #variants_build
@@ -215,9 +380,8 @@ pub fn read_create_update_method(enum_name: &Ident, variants: &Vec<Ident>) -> To
     }
 
     return quote! {
-        fn read_create_update(bit_reader: &mut serde::BitReader) -> ComponentUpdate<Self::Kind> {
-            let protocol_kind: Self::Kind = Self::Kind::de(bit_reader).unwrap();
-            match protocol_kind {
+        fn read_create_update_of(kind: &Self::Kind, bit_reader: &mut serde::BitReader, baselines: &mut dyn PropertyBaselines) -> Result<ComponentUpdate<Self::Kind>, serde::SerdeErr> {
+            match kind {
                 #variants_build
             }
         }
@@ -1,8 +1,8 @@
 use proc_macro2::{Punct, Spacing, Span, TokenStream};
 use quote::{format_ident, quote};
 use syn::{
-    parse_macro_input, Data, DeriveInput, Fields, GenericArgument, Ident, Lit, Meta, Path,
-    PathArguments, Result, Type,
+    parse_macro_input, parse_quote, Data, DeriveInput, Field, Fields, GenericArgument, Ident, Lit,
+    Meta, NestedMeta, Path, PathArguments, Result, Type,
 };
 
 pub fn replicate_impl(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
@@ -10,6 +10,7 @@ pub fn replicate_impl(input: proc_macro::TokenStream) -> proc_macro::TokenStream
 
     // Helper Properties
     let properties = properties(&input);
+    let local_fields = local_fields(&input);
 
     // Paths
     let (protocol_path, protocol_name) = protocol_path(&input);
@@ -23,25 +24,31 @@ pub fn replicate_impl(input: proc_macro::TokenStream) -> proc_macro::TokenStream
     let property_enum_definition = property_enum(&enum_name, &properties);
 
     // Replica Methods
-    let new_complete_method = new_complete_method(&replica_name, &enum_name, &properties);
-    let read_method = read_method(&protocol_name, &replica_name, &enum_name, &properties);
-    let read_create_update_method =
-        read_create_update_method(&replica_name, &protocol_kind_name, &properties);
+    let new_complete_method =
+        new_complete_method(&replica_name, &enum_name, &properties, &local_fields);
+    let read_method = read_method(
+        &protocol_name,
+        &replica_name,
+        &enum_name,
+        &properties,
+        &local_fields,
+    );
+    let diff_mask_size = diff_mask_size(&properties);
+    let read_create_update_method = read_create_update_method(
+        &replica_name,
+        &protocol_kind_name,
+        &enum_name,
+        &diff_mask_size,
+        &properties,
+    );
+    let layout_hash_method = layout_hash_method(&properties);
 
     // ReplicateSafe Derive Methods
-    let diff_mask_size = {
-        let len = properties.len();
-        if len == 0 {
-            0
-        } else {
-            ((len - 1) / 8) + 1
-        }
-    } as u8;
     let dyn_ref_method = dyn_ref_method(&protocol_name);
     let dyn_mut_method = dyn_mut_method(&protocol_name);
     let to_protocol_method = into_protocol_method(&protocol_name, &replica_name);
     let protocol_copy_method = protocol_copy_method(&protocol_name, &replica_name);
-    let clone_method = clone_method(&replica_name, &properties);
+    let clone_method = clone_method(&replica_name, &properties, &local_fields);
     let mirror_method = mirror_method(&protocol_name, &replica_name, &properties);
     let set_mutator_method = set_mutator_method(&properties);
     let read_apply_update_method = read_apply_update_method(&protocol_kind_name, &properties);
@@ -53,7 +60,8 @@ pub fn replicate_impl(input: proc_macro::TokenStream) -> proc_macro::TokenStream
     let gen = quote! {
         use std::{rc::Rc, cell::RefCell, io::Cursor};
         use naia_shared::{DiffMask, PropertyMutate, ReplicateSafe, PropertyMutator, ComponentUpdate,
-            Protocolize, ReplicaDynRef, ReplicaDynMut, serde::{BitReader, BitWrite, BitWriter, OwnedBitReader, Serde}, NetEntityHandleConverter};
+            Protocolize, ReplicaDynRef, ReplicaDynMut, serde::{BitReader, BitWrite, BitWriter, OwnedBitReader, Serde, SerdeErr}, NetEntityHandleConverter,
+            NoBaselines, PropertyBaselines};
         use #protocol_path::{#protocol_name, #protocol_kind_name};
         mod internal {
             pub use naia_shared::{EntityProperty, EntityHandle};
@@ -65,6 +73,7 @@ pub fn replicate_impl(input: proc_macro::TokenStream) -> proc_macro::TokenStream
             #new_complete_method
             #read_method
             #read_create_update_method
+            #layout_hash_method
         }
         impl ReplicateSafe<#protocol_name> for #replica_name {
             fn diff_mask_size(&self) -> u8 { #diff_mask_size }
@@ -96,6 +105,25 @@ pub struct NormalProperty {
     pub variable_name: Ident,
     pub inner_type: Type,
     pub uppercase_variable_name: Ident,
+    // given with `#[property(codec = "...")]`
+    pub codec: Option<Path>,
+}
+
+impl NormalProperty {
+    /// The name of a method of Property, followed by the PropertyCodec to use
+    /// if one was given
+    fn method(&self, name: &str) -> TokenStream {
+        match &self.codec {
+            Some(codec) => {
+                let method_name = format_ident!("{}_with", name);
+                quote! { #method_name::<#codec> }
+            }
+            None => {
+                let method_name = format_ident!("{}", name);
+                quote! { #method_name }
+            }
+        }
+    }
 }
 
 pub struct EntityProperty {
@@ -103,17 +131,35 @@ pub struct EntityProperty {
     pub uppercase_variable_name: Ident,
 }
 
+pub struct NestedProperty {
+    pub variable_name: Ident,
+    pub inner_type: Type,
+    pub uppercase_variable_name: Ident,
+}
+
+pub struct CollectionProperty {
+    pub variable_name: Ident,
+    // i.e. `PropertyVec<T>`
+    pub property_type: Type,
+    // i.e. `Vec<T>`
+    pub value_type: Type,
+    pub uppercase_variable_name: Ident,
+}
+
 #[allow(clippy::large_enum_variant)]
 pub enum Property {
     Normal(NormalProperty),
     Entity(EntityProperty),
+    Nested(NestedProperty),
+    Collection(CollectionProperty),
 }
 
 impl Property {
-    pub fn normal(variable_name: Ident, inner_type: Type) -> Self {
+    pub fn normal(variable_name: Ident, inner_type: Type, codec: Option<Path>) -> Self {
         Self::Normal(NormalProperty {
             variable_name: variable_name.clone(),
             inner_type,
+            codec,
             uppercase_variable_name: Ident::new(
                 variable_name.to_string().to_uppercase().as_str(),
                 Span::call_site(),
@@ -131,10 +177,35 @@ impl Property {
         })
     }
 
+    pub fn nested(variable_name: Ident, inner_type: Type) -> Self {
+        Self::Nested(NestedProperty {
+            variable_name: variable_name.clone(),
+            inner_type,
+            uppercase_variable_name: Ident::new(
+                variable_name.to_string().to_uppercase().as_str(),
+                Span::call_site(),
+            ),
+        })
+    }
+
+    pub fn collection(variable_name: Ident, property_type: Type, value_type: Type) -> Self {
+        Self::Collection(CollectionProperty {
+            variable_name: variable_name.clone(),
+            property_type,
+            value_type,
+            uppercase_variable_name: Ident::new(
+                variable_name.to_string().to_uppercase().as_str(),
+                Span::call_site(),
+            ),
+        })
+    }
+
     pub fn variable_name(&self) -> &Ident {
         match self {
             Self::Normal(property) => &property.variable_name,
             Self::Entity(property) => &property.variable_name,
+            Self::Nested(property) => &property.variable_name,
+            Self::Collection(property) => &property.variable_name,
         }
     }
 
@@ -142,16 +213,35 @@ impl Property {
         match self {
             Self::Normal(property) => &property.uppercase_variable_name,
             Self::Entity(property) => &property.uppercase_variable_name,
+            Self::Nested(property) => &property.uppercase_variable_name,
+            Self::Collection(property) => &property.uppercase_variable_name,
+        }
+    }
+
+    /// The number of bits the Property takes up in the DiffMask
+    pub fn bit_count(&self) -> TokenStream {
+        match self {
+            Self::Normal(_) | Self::Entity(_) => quote! { 1 },
+            // one bit for the whole collection, one for its operations
+            Self::Collection(_) => quote! { 2 },
+            Self::Nested(property) => {
+                let inner_type = &property.inner_type;
+                quote! { <#inner_type as naia_shared::NestedReplicate>::PROPERTY_COUNT }
+            }
         }
     }
 }
 
-fn properties(input: &DeriveInput) -> Vec<Property> {
+pub fn properties(input: &DeriveInput) -> Vec<Property> {
     let mut fields = Vec::new();
 
     if let Data::Struct(data_struct) = &input.data {
         if let Fields::Named(fields_named) = &data_struct.fields {
             for field in fields_named.named.iter() {
+                if is_local(field) {
+                    continue;
+                }
+                let codec = property_codec(field);
                 if let Some(variable_name) = &field.ident {
                     if let Type::Path(type_path) = &field.ty {
                         if let Some(property_seg) = type_path.path.segments.first() {
@@ -165,10 +255,36 @@ fn properties(input: &DeriveInput) -> Vec<Property> {
                                 if let Some(GenericArgument::Type(inner_type)) =
                                     angle_args.args.first()
                                 {
-                                    fields.push(Property::normal(
-                                        variable_name.clone(),
-                                        inner_type.clone(),
-                                    ));
+                                    if codec.is_some() && property_type != "Property" {
+                                        panic!("A codec can only be given for a field of type 'Property<T>'");
+                                    }
+
+                                    if property_type == "PropertyVec" {
+                                        let args = &angle_args.args;
+                                        fields.push(Property::collection(
+                                            variable_name.clone(),
+                                            field.ty.clone(),
+                                            parse_quote! { Vec<#args> },
+                                        ));
+                                    } else if property_type == "PropertyMap" {
+                                        let args = &angle_args.args;
+                                        fields.push(Property::collection(
+                                            variable_name.clone(),
+                                            field.ty.clone(),
+                                            parse_quote! { std::collections::HashMap<#args> },
+                                        ));
+                                    } else if property_type == "NestedProperty" {
+                                        fields.push(Property::nested(
+                                            variable_name.clone(),
+                                            inner_type.clone(),
+                                        ));
+                                    } else {
+                                        fields.push(Property::normal(
+                                            variable_name.clone(),
+                                            inner_type.clone(),
+                                            codec,
+                                        ));
+                                    }
                                     continue;
                                 }
                             }
@@ -182,7 +298,71 @@ fn properties(input: &DeriveInput) -> Vec<Property> {
     fields
 }
 
-fn protocol_path(input: &DeriveInput) -> (Path, Ident) {
+/// The names of the fields marked with `#[replicate(skip)]`, which are not
+/// synced & are initialized with `Default` when read
+pub fn local_fields(input: &DeriveInput) -> Vec<Ident> {
+    let mut fields = Vec::new();
+
+    if let Data::Struct(data_struct) = &input.data {
+        if let Fields::Named(fields_named) = &data_struct.fields {
+            for field in fields_named.named.iter() {
+                if let Some(variable_name) = &field.ident {
+                    if is_local(field) {
+                        fields.push(variable_name.clone());
+                    }
+                }
+            }
+        }
+    }
+
+    fields
+}
+
+/// Whether a field is marked with `#[replicate(skip)]`
+fn is_local(field: &Field) -> bool {
+    for attr in field.attrs.iter() {
+        if !attr.path.is_ident("replicate") {
+            continue;
+        }
+        if let Ok(Meta::List(meta_list)) = attr.parse_meta() {
+            if let Some(NestedMeta::Meta(Meta::Path(path))) = meta_list.nested.first() {
+                if meta_list.nested.len() == 1 && path.is_ident("skip") {
+                    return true;
+                }
+            }
+        }
+        panic!("Expected a Replicate attribute such as '#[replicate(skip)]'");
+    }
+    false
+}
+
+/// Gets the PropertyCodec given for a field with `#[property(codec = "...")]`
+fn property_codec(field: &Field) -> Option<Path> {
+    for attr in field.attrs.iter() {
+        if !attr.path.is_ident("property") {
+            continue;
+        }
+        if let Ok(Meta::List(meta_list)) = attr.parse_meta() {
+            for nested_meta in meta_list.nested.iter() {
+                if let NestedMeta::Meta(Meta::NameValue(meta_name_value)) = nested_meta {
+                    if meta_name_value.path.is_ident("codec") {
+                        if let Lit::Str(lit_str) = &meta_name_value.lit {
+                            return Some(
+                                lit_str
+                                    .parse()
+                                    .expect("'codec' must be the path of a PropertyCodec"),
+                            );
+                        }
+                    }
+                }
+            }
+        }
+        panic!("Expected a Property attribute such as '#[property(codec = \"path::to::Codec\")]'");
+    }
+    None
+}
+
+pub fn protocol_path(input: &DeriveInput) -> (Path, Ident) {
     let mut path_result: Option<Result<Path>> = None;
 
     let attrs = &input.attrs;
@@ -215,7 +395,7 @@ fn protocol_path(input: &DeriveInput) -> (Path, Ident) {
     panic!("When deriving 'Replicate' you MUST specify the path of the accompanying protocol. IE: '#[protocol_path = \"crate::MyProtocol\"]'");
 }
 
-fn property_enum(enum_name: &Ident, properties: &[Property]) -> TokenStream {
+pub fn property_enum(enum_name: &Ident, properties: &[Property]) -> TokenStream {
     if properties.is_empty() {
         return quote! {
             enum #enum_name {}
@@ -225,18 +405,23 @@ fn property_enum(enum_name: &Ident, properties: &[Property]) -> TokenStream {
     let hashtag = Punct::new('#', Spacing::Alone);
 
     let mut variant_list = quote! {};
+    let mut offset = quote! { 0 };
 
-    for (index, property) in properties.iter().enumerate() {
+    for property in properties.iter() {
         let uppercase_variant_name = property.uppercase_variable_name();
 
+        // each variant is the index of the Property's first bit in the DiffMask
         let new_output_right = quote! {
-            #uppercase_variant_name = #index as u8,
+            #uppercase_variant_name = (#offset) as u8,
         };
         let new_output_result = quote! {
             #variant_list
             #new_output_right
         };
         variant_list = new_output_result;
+
+        let bit_count = property.bit_count();
+        offset = quote! { #offset + #bit_count };
     }
 
     return quote! {
@@ -247,6 +432,41 @@ fn property_enum(enum_name: &Ident, properties: &[Property]) -> TokenStream {
     };
 }
 
+/// The number of bits all of the Properties take up in the DiffMask
+pub fn property_count(properties: &[Property]) -> TokenStream {
+    let mut output = quote! { 0 };
+    for property in properties.iter() {
+        let bit_count = property.bit_count();
+        output = quote! { #output + #bit_count };
+    }
+    output
+}
+
+/// The number of bytes of the DiffMask
+fn diff_mask_size(properties: &[Property]) -> TokenStream {
+    let has_nested = properties
+        .iter()
+        .any(|property| matches!(property, Property::Nested(_)));
+    if !has_nested {
+        let len = properties.len();
+        let size = if len == 0 { 0 } else { ((len - 1) / 8) + 1 } as u8;
+        return quote! { #size };
+    }
+
+    // the size of NestedProperties is only known once the type is compiled
+    let property_count = property_count(properties);
+    quote! {
+        {
+            const PROPERTY_COUNT: usize = #property_count;
+            if PROPERTY_COUNT == 0 {
+                0
+            } else {
+                (((PROPERTY_COUNT - 1) / 8) + 1) as u8
+            }
+        }
+    }
+}
+
 fn protocol_copy_method(protocol_name: &Ident, replica_name: &Ident) -> TokenStream {
     return quote! {
         fn protocol_copy(&self) -> #protocol_name {
@@ -279,14 +499,31 @@ pub fn dyn_mut_method(protocol_name: &Ident) -> TokenStream {
     };
 }
 
-fn clone_method(replica_name: &Ident, properties: &[Property]) -> TokenStream {
+pub fn clone_method(
+    replica_name: &Ident,
+    properties: &[Property],
+    local_fields: &[Ident],
+) -> TokenStream {
     let mut output = quote! {};
     let mut entity_property_output = quote! {};
 
+    for field_name in local_fields.iter() {
+        entity_property_output = quote! {
+            #entity_property_output
+            new_clone.#field_name = self.#field_name.clone();
+        };
+    }
+
     for property in properties.iter() {
         match property {
-            Property::Normal(property) => {
-                let field_name = &property.variable_name;
+            Property::Normal(NormalProperty {
+                variable_name: field_name,
+                ..
+            })
+            | Property::Nested(NestedProperty {
+                variable_name: field_name,
+                ..
+            }) => {
                 let new_output_right = quote! {
                     (*self.#field_name).clone(),
                 };
@@ -307,6 +544,18 @@ fn clone_method(replica_name: &Ident, properties: &[Property]) -> TokenStream {
                 };
                 entity_property_output = new_output_result;
             }
+            Property::Collection(property) => {
+                // mirrored afterwards, to also copy the collection's version
+                let field_name = &property.variable_name;
+                output = quote! {
+                    #output
+                    Default::default(),
+                };
+                entity_property_output = quote! {
+                    #entity_property_output
+                    new_clone.#field_name.mirror(&self.#field_name);
+                };
+            }
         };
     }
 
@@ -347,7 +596,7 @@ fn mirror_method(
     };
 }
 
-fn set_mutator_method(properties: &[Property]) -> TokenStream {
+pub fn set_mutator_method(properties: &[Property]) -> TokenStream {
     let mut output = quote! {};
 
     for property in properties.iter() {
@@ -373,14 +622,21 @@ pub fn new_complete_method(
     replica_name: &Ident,
     enum_name: &Ident,
     properties: &[Property],
+    local_fields: &[Ident],
 ) -> TokenStream {
     let mut args = quote! {};
     for property in properties.iter() {
         match property {
-            Property::Normal(property) => {
-                let field_name = &property.variable_name;
-                let field_type = &property.inner_type;
-
+            Property::Normal(NormalProperty {
+                variable_name: field_name,
+                inner_type: field_type,
+                ..
+            })
+            | Property::Nested(NestedProperty {
+                variable_name: field_name,
+                inner_type: field_type,
+                ..
+            }) => {
                 let new_output_right = quote! {
                     #field_name: #field_type,
                 };
@@ -390,6 +646,15 @@ pub fn new_complete_method(
                 };
                 args = new_output_result;
             }
+            Property::Collection(CollectionProperty {
+                variable_name: field_name,
+                value_type,
+                ..
+            }) => {
+                args = quote! {
+                    #args #field_name: #value_type,
+                };
+            }
             Property::Entity(_) => {
                 continue;
             }
@@ -414,6 +679,22 @@ pub fn new_complete_method(
                     #field_name: EntityProperty::new(#enum_name::#uppercase_variant_name as u8)
                 }
             }
+            Property::Nested(property) => {
+                let field_name = &property.variable_name;
+                let field_type = &property.inner_type;
+                let uppercase_variant_name = &property.uppercase_variable_name;
+                quote! {
+                    #field_name: NestedProperty::<#field_type>::new(#field_name, #enum_name::#uppercase_variant_name as u8)
+                }
+            }
+            Property::Collection(property) => {
+                let field_name = &property.variable_name;
+                let property_type = &property.property_type;
+                let uppercase_variant_name = &property.uppercase_variable_name;
+                quote! {
+                    #field_name: <#property_type>::new(#field_name, #enum_name::#uppercase_variant_name as u8)
+                }
+            }
         };
 
         let new_output_result = quote! {
@@ -422,21 +703,36 @@ pub fn new_complete_method(
         };
         fields = new_output_result;
     }
+    let local_fields = local_fields_default(local_fields);
 
     return quote! {
         pub fn new_complete(#args) -> #replica_name {
             #replica_name {
                 #fields
+                #local_fields
             }
         }
     };
 }
 
+/// Initializes each local field with its Default value
+pub fn local_fields_default(local_fields: &[Ident]) -> TokenStream {
+    let mut output = quote! {};
+    for field_name in local_fields.iter() {
+        output = quote! {
+            #output
+            #field_name: Default::default(),
+        };
+    }
+    output
+}
+
 pub fn read_method(
     protocol_name: &Ident,
     replica_name: &Ident,
     enum_name: &Ident,
     properties: &[Property],
+    local_fields: &[Ident],
 ) -> TokenStream {
     let mut prop_names = quote! {};
     for property in properties.iter() {
@@ -451,6 +747,23 @@ pub fn read_method(
         prop_names = new_output_result;
     }
 
+    let prop_reads = read_body(enum_name, properties);
+    let local_fields = local_fields_default(local_fields);
+
+    return quote! {
+        pub fn read(bit_reader: &mut BitReader, converter: &dyn NetEntityHandleConverter) -> Result<#protocol_name, SerdeErr> {
+            #prop_reads
+
+            return Ok(#protocol_name::#replica_name(#replica_name {
+                #prop_names
+                #local_fields
+            }));
+        }
+    };
+}
+
+/// Reads each Property from `bit_reader` into a local variable of the same name
+pub fn read_body(enum_name: &Ident, properties: &[Property]) -> TokenStream {
     let mut prop_reads = quote! {};
     for property in properties.iter() {
         let new_output_right = match property {
@@ -458,15 +771,32 @@ pub fn read_method(
                 let field_name = &property.variable_name;
                 let field_type = &property.inner_type;
                 let uppercase_variant_name = &property.uppercase_variable_name;
+                let new_read = property.method("new_read");
                 quote! {
-                    let #field_name = Property::<#field_type>::new_read(bit_reader, #enum_name::#uppercase_variant_name as u8);
+                    let #field_name = Property::<#field_type>::#new_read(bit_reader, #enum_name::#uppercase_variant_name as u8)?;
                 }
             }
             Property::Entity(property) => {
                 let field_name = &property.variable_name;
                 let uppercase_variant_name = &property.uppercase_variable_name;
                 quote! {
-                    let #field_name = EntityProperty::new_read(bit_reader, #enum_name::#uppercase_variant_name as u8, converter);
+                    let #field_name = EntityProperty::new_read(bit_reader, #enum_name::#uppercase_variant_name as u8, converter)?;
+                }
+            }
+            Property::Nested(property) => {
+                let field_name = &property.variable_name;
+                let field_type = &property.inner_type;
+                let uppercase_variant_name = &property.uppercase_variable_name;
+                quote! {
+                    let #field_name = NestedProperty::<#field_type>::new_read(bit_reader, #enum_name::#uppercase_variant_name as u8, converter)?;
+                }
+            }
+            Property::Collection(property) => {
+                let field_name = &property.variable_name;
+                let property_type = &property.property_type;
+                let uppercase_variant_name = &property.uppercase_variable_name;
+                quote! {
+                    let #field_name = <#property_type>::new_read(bit_reader, #enum_name::#uppercase_variant_name as u8)?;
                 }
             }
         };
@@ -478,33 +808,121 @@ pub fn read_method(
         prop_reads = new_output_result;
     }
 
-    return quote! {
-        pub fn read(bit_reader: &mut BitReader, converter: &dyn NetEntityHandleConverter) -> #protocol_name {
-            #prop_reads
+    prop_reads
+}
 
-            return #protocol_name::#replica_name(#replica_name {
-                #prop_names
-            });
+pub fn layout_hash_method(properties: &[Property]) -> TokenStream {
+    let body = layout_hash_body(properties);
+
+    quote! {
+        pub fn layout_hash() -> u64 {
+            #body
         }
-    };
+    }
+}
+
+pub fn layout_hash_body(properties: &[Property]) -> TokenStream {
+    let mut layout = String::new();
+    let mut nested_hashes = quote! {};
+    for property in properties.iter() {
+        let type_name = match property {
+            Property::Normal(property) => {
+                let field_type = &property.inner_type;
+                match &property.codec {
+                    Some(codec) => format!("{} as {}", quote! { #field_type }, quote! { #codec }),
+                    None => quote! { #field_type }.to_string(),
+                }
+            }
+            Property::Entity(_) => "EntityProperty".to_string(),
+            Property::Nested(property) => {
+                let field_type = &property.inner_type;
+                nested_hashes = quote! {
+                    #nested_hashes
+                    hasher.write_u64(<#field_type as naia_shared::NestedReplicate>::layout_hash());
+                };
+                format!("NestedProperty<{}>", quote! { #field_type })
+            }
+            Property::Collection(property) => {
+                let property_type = &property.property_type;
+                quote! { #property_type }.to_string()
+            }
+        };
+        layout.push_str(&format!("{}:{};", property.variable_name(), type_name));
+    }
+
+    quote! {
+        let mut hasher = naia_shared::FingerprintHasher::default();
+        hasher.write_str(#layout);
+        #nested_hashes
+        hasher.finish()
+    }
 }
 
 pub fn read_create_update_method(
     replica_name: &Ident,
     kind_name: &Ident,
+    enum_name: &Ident,
+    diff_mask_size: &TokenStream,
+    properties: &[Property],
+) -> TokenStream {
+    let prop_read_writes = read_write_update_body(enum_name, &quote! { 0 }, properties);
+
+    return quote! {
+        pub fn read_create_update(bit_reader: &mut BitReader) -> Result<ComponentUpdate::<#kind_name>, SerdeErr> {
+            Self::read_create_update_against(bit_reader, &mut NoBaselines)
+        }
+
+        pub fn read_create_update_against(bit_reader: &mut BitReader, baselines: &mut dyn PropertyBaselines) -> Result<ComponentUpdate::<#kind_name>, SerdeErr> {
+
+            let mut update_writer = BitWriter::default();
+            #[allow(unused_mut)]
+            let mut diff_mask = DiffMask::new(#diff_mask_size);
+
+            #[allow(unused_variables)]
+            {
+                let update_writer = &mut update_writer;
+                let diff_mask = &mut diff_mask;
+                #prop_read_writes
+            }
+
+            let (length, buffer) = update_writer.flush();
+            let owned_reader = OwnedBitReader::new(&buffer[..length]);
+
+            return Ok(ComponentUpdate::new(#kind_name::#replica_name, diff_mask, owned_reader));
+        }
+    };
+}
+
+/// Reads each Property of an update from `bit_reader` into the `update_writer`
+/// reference, marking it in the `diff_mask` reference, where the Properties
+/// start at bit `offset`
+pub fn read_write_update_body(
+    enum_name: &Ident,
+    offset: &TokenStream,
     properties: &[Property],
 ) -> TokenStream {
     let mut prop_read_writes = quote! {};
     for property in properties.iter() {
+        let uppercase_variant_name = property.uppercase_variable_name();
+        let bit_index = quote! { #offset + #enum_name::#uppercase_variant_name as u8 };
         let new_output_right = match property {
             Property::Normal(property) => {
                 let field_type = &property.inner_type;
+                let read_write = match &property.codec {
+                    Some(codec) => quote! {
+                        Property::<#field_type>::read_write_update_with::<#codec>(#bit_index, baselines, bit_reader, update_writer)?
+                    },
+                    None => quote! {
+                        Property::<#field_type>::read_write(bit_reader, update_writer)?
+                    },
+                };
                 quote! {
                     {
-                        let should_read = bool::de(bit_reader).unwrap();
-                        should_read.ser(&mut update_writer);
+                        let should_read = bool::de(bit_reader)?;
+                        should_read.ser(update_writer);
                         if should_read {
-                            Property::<#field_type>::read_write(bit_reader, &mut update_writer);
+                            #read_write;
+                            diff_mask.set_bit(#bit_index, true);
                         }
                     }
                 }
@@ -512,10 +930,35 @@ pub fn read_create_update_method(
             Property::Entity(_) => {
                 quote! {
                     {
-                        let should_read = bool::de(bit_reader).unwrap();
-                        should_read.ser(&mut update_writer);
+                        let should_read = bool::de(bit_reader)?;
+                        should_read.ser(update_writer);
                         if should_read {
-                            EntityProperty::read_write(bit_reader, &mut update_writer);
+                            EntityProperty::read_write(bit_reader, update_writer)?;
+                            diff_mask.set_bit(#bit_index, true);
+                        }
+                    }
+                }
+            }
+            Property::Nested(property) => {
+                let field_type = &property.inner_type;
+                quote! {
+                    {
+                        let should_read = bool::de(bit_reader)?;
+                        should_read.ser(update_writer);
+                        if should_read {
+                            NestedProperty::<#field_type>::read_write(bit_reader, update_writer, diff_mask, #bit_index, baselines)?;
+                        }
+                    }
+                }
+            }
+            Property::Collection(property) => {
+                let property_type = &property.property_type;
+                quote! {
+                    {
+                        let should_read = bool::de(bit_reader)?;
+                        should_read.ser(update_writer);
+                        if should_read {
+                            <#property_type>::read_write(bit_reader, update_writer, diff_mask, #bit_index)?;
                         }
                     }
                 }
@@ -529,39 +972,59 @@ pub fn read_create_update_method(
         prop_read_writes = new_output_result;
     }
 
-    return quote! {
-        pub fn read_create_update(bit_reader: &mut BitReader) -> ComponentUpdate::<#kind_name> {
-
-            let mut update_writer = BitWriter::default();
-
-            #prop_read_writes
+    prop_read_writes
+}
 
-            let (length, buffer) = update_writer.flush();
-            let owned_reader = OwnedBitReader::new(&buffer[..length]);
+fn read_apply_update_method(kind_name: &Ident, properties: &[Property]) -> TokenStream {
+    let output = read_apply_update_body(properties, &quote! { .expect(UPDATE_VALIDATED) });
 
-            return ComponentUpdate::new(#kind_name::#replica_name, owned_reader);
+    return quote! {
+        fn read_apply_update(&mut self, converter: &dyn NetEntityHandleConverter, mut update: ComponentUpdate<#kind_name>) {
+            // the update's contents were already validated by `read_create_update`
+            const UPDATE_VALIDATED: &str = "ComponentUpdate was validated on receipt";
+            let reader = &mut update.reader();
+            #output
         }
     };
 }
 
-fn read_apply_update_method(kind_name: &Ident, properties: &[Property]) -> TokenStream {
+/// Reads each updated Property from `reader`, handling each read's Result with
+/// `handle_result`
+pub fn read_apply_update_body(properties: &[Property], handle_result: &TokenStream) -> TokenStream {
     let mut output = quote! {};
 
     for property in properties.iter() {
         let new_output_right = match property {
             Property::Normal(property) => {
                 let field_name = &property.variable_name;
+                let read = property.method("read");
                 quote! {
-                    if bool::de(reader).unwrap() {
-                        Property::read(&mut self.#field_name, reader);
+                    if bool::de(reader)#handle_result {
+                        Property::#read(&mut self.#field_name, reader)#handle_result;
                     }
                 }
             }
             Property::Entity(property) => {
                 let field_name = &property.variable_name;
                 quote! {
-                    if bool::de(reader).unwrap() {
-                        EntityProperty::read(&mut self.#field_name, reader, converter);
+                    if bool::de(reader)#handle_result {
+                        EntityProperty::read(&mut self.#field_name, reader, converter)#handle_result;
+                    }
+                }
+            }
+            Property::Nested(property) => {
+                let field_name = &property.variable_name;
+                quote! {
+                    if bool::de(reader)#handle_result {
+                        NestedProperty::read(&mut self.#field_name, reader, converter)#handle_result;
+                    }
+                }
+            }
+            Property::Collection(property) => {
+                let field_name = &property.variable_name;
+                quote! {
+                    if bool::de(reader)#handle_result {
+                        self.#field_name.read(reader)#handle_result;
                     }
                 }
             }
@@ -574,23 +1037,31 @@ fn read_apply_update_method(kind_name: &Ident, properties: &[Property]) -> Token
         output = new_output_result;
     }
 
+    output
+}
+
+fn write_method(properties: &[Property]) -> TokenStream {
+    let property_writes = write_body(properties);
+
     return quote! {
-        fn read_apply_update(&mut self, converter: &dyn NetEntityHandleConverter, mut update: ComponentUpdate<#kind_name>) {
-            let reader = &mut update.reader();
-            #output
+        fn write(&self, bit_writer: &mut dyn BitWrite, converter: &dyn NetEntityHandleConverter) {
+            self.kind().ser(bit_writer);
+            #property_writes
         }
     };
 }
 
-fn write_method(properties: &[Property]) -> TokenStream {
+/// Writes each Property into `bit_writer`
+pub fn write_body(properties: &[Property]) -> TokenStream {
     let mut property_writes = quote! {};
 
     for property in properties.iter() {
         let new_output_right = match property {
             Property::Normal(property) => {
                 let field_name = &property.variable_name;
+                let write = property.method("write");
                 quote! {
-                    Property::write(&self.#field_name, bit_writer);
+                    Property::#write(&self.#field_name, bit_writer);
                 }
             }
             Property::Entity(property) => {
@@ -599,6 +1070,18 @@ fn write_method(properties: &[Property]) -> TokenStream {
                     EntityProperty::write(&self.#field_name, bit_writer, converter);
                 }
             }
+            Property::Nested(property) => {
+                let field_name = &property.variable_name;
+                quote! {
+                    NestedProperty::write(&self.#field_name, bit_writer, converter);
+                }
+            }
+            Property::Collection(property) => {
+                let field_name = &property.variable_name;
+                quote! {
+                    self.#field_name.write(bit_writer);
+                }
+            }
         };
 
         let new_output_result = quote! {
@@ -608,15 +1091,26 @@ fn write_method(properties: &[Property]) -> TokenStream {
         property_writes = new_output_result;
     }
 
+    property_writes
+}
+
+fn write_update_method(enum_name: &Ident, properties: &[Property]) -> TokenStream {
+    let output = write_update_body(enum_name, &quote! { 0 }, properties);
+
     return quote! {
-        fn write(&self, bit_writer: &mut dyn BitWrite, converter: &dyn NetEntityHandleConverter) {
-            self.kind().ser(bit_writer);
-            #property_writes
+        fn write_update_against(&self, diff_mask: &DiffMask, writer: &mut dyn BitWrite, converter: &dyn NetEntityHandleConverter, baselines: &mut dyn PropertyBaselines) {
+            #output
         }
     };
 }
 
-fn write_update_method(enum_name: &Ident, properties: &[Property]) -> TokenStream {
+/// Writes each Property marked in `diff_mask` into `writer`, where the
+/// Properties start at bit `offset`
+pub fn write_update_body(
+    enum_name: &Ident,
+    offset: &TokenStream,
+    properties: &[Property],
+) -> TokenStream {
     let mut output = quote! {};
 
     for property in properties.iter() {
@@ -624,10 +1118,19 @@ fn write_update_method(enum_name: &Ident, properties: &[Property]) -> TokenStrea
             Property::Normal(property) => {
                 let field_name = &property.variable_name;
                 let uppercase_variant_name = &property.uppercase_variable_name;
+                let bit_index = quote! { #offset + #enum_name::#uppercase_variant_name as u8 };
+                let write = match &property.codec {
+                    Some(codec) => quote! {
+                        Property::write_update_with::<#codec>(&self.#field_name, #bit_index, baselines, writer)
+                    },
+                    None => quote! {
+                        Property::write(&self.#field_name, writer)
+                    },
+                };
                 quote! {
-                    if let Some(true) = diff_mask.bit(#enum_name::#uppercase_variant_name as u8) {
+                    if let Some(true) = diff_mask.bit(#bit_index) {
                         true.ser(writer);
-                        Property::write(&self.#field_name, writer);
+                        #write;
                     } else {
                         false.ser(writer);
                     }
@@ -637,7 +1140,7 @@ fn write_update_method(enum_name: &Ident, properties: &[Property]) -> TokenStrea
                 let field_name = &property.variable_name;
                 let uppercase_variant_name = &property.uppercase_variable_name;
                 quote! {
-                    if let Some(true) = diff_mask.bit(#enum_name::#uppercase_variant_name as u8) {
+                    if let Some(true) = diff_mask.bit(#offset + #enum_name::#uppercase_variant_name as u8) {
                         true.ser(writer);
                         EntityProperty::write(&self.#field_name, writer, converter);
                     } else {
@@ -645,6 +1148,28 @@ fn write_update_method(enum_name: &Ident, properties: &[Property]) -> TokenStrea
                     }
                 }
             }
+            Property::Nested(property) => {
+                let field_name = &property.variable_name;
+                quote! {
+                    if self.#field_name.is_mutated(diff_mask, #offset) {
+                        true.ser(writer);
+                        NestedProperty::write_update(&self.#field_name, diff_mask, #offset, writer, converter, baselines);
+                    } else {
+                        false.ser(writer);
+                    }
+                }
+            }
+            Property::Collection(property) => {
+                let field_name = &property.variable_name;
+                quote! {
+                    if self.#field_name.is_mutated(diff_mask, #offset) {
+                        true.ser(writer);
+                        self.#field_name.write_update(diff_mask, #offset, writer);
+                    } else {
+                        false.ser(writer);
+                    }
+                }
+            }
         };
 
         let new_output_result = quote! {
@@ -654,48 +1179,64 @@ fn write_update_method(enum_name: &Ident, properties: &[Property]) -> TokenStrea
         output = new_output_result;
     }
 
-    return quote! {
-        fn write_update(&self, diff_mask: &DiffMask, writer: &mut dyn BitWrite, converter: &dyn NetEntityHandleConverter) {
-            #output
-        }
-    };
+    output
 }
 
-fn has_entity_properties_method(properties: &[Property]) -> TokenStream {
+pub fn has_entity_properties_method(properties: &[Property]) -> TokenStream {
+    let mut nested_output = quote! { false };
+
     for property in properties.iter() {
-        if let Property::Entity(_) = property {
-            return quote! {
-                fn has_entity_properties(&self) -> bool {
-                    return true;
-                }
-            };
+        match property {
+            Property::Entity(_) => {
+                return quote! {
+                    fn has_entity_properties(&self) -> bool {
+                        return true;
+                    }
+                };
+            }
+            Property::Nested(property) => {
+                let field_name = &property.variable_name;
+                nested_output = quote! {
+                    #nested_output || self.#field_name.has_entity_properties()
+                };
+            }
+            Property::Normal(_) | Property::Collection(_) => {}
         }
     }
 
     return quote! {
         fn has_entity_properties(&self) -> bool {
-            return false;
+            return #nested_output;
         }
     };
 }
 
-fn entities_method(properties: &[Property]) -> TokenStream {
+pub fn entities_method(properties: &[Property]) -> TokenStream {
     let mut body = quote! {};
 
     for property in properties.iter() {
-        if let Property::Entity(entity_prop) = property {
-            let field_name = &entity_prop.variable_name;
-            let body_add_right = quote! {
-                if let Some(handle) = self.#field_name.handle() {
-                    output.push(handle);
-                }
-            };
-            let new_body = quote! {
-                #body
-                #body_add_right
-            };
-            body = new_body;
-        }
+        let body_add_right = match property {
+            Property::Entity(entity_prop) => {
+                let field_name = &entity_prop.variable_name;
+                quote! {
+                    if let Some(handle) = self.#field_name.handle() {
+                        output.push(handle);
+                    }
+                }
+            }
+            Property::Nested(nested_prop) => {
+                let field_name = &nested_prop.variable_name;
+                quote! {
+                    output.extend(self.#field_name.entities());
+                }
+            }
+            Property::Normal(_) | Property::Collection(_) => continue,
+        };
+        let new_body = quote! {
+            #body
+            #body_add_right
+        };
+        body = new_body;
     }
 
     return quote! {
@@ -4,7 +4,7 @@
 //! https://docs.rs/syn/0.15.44/syn/enum.Type.html
 //! https://ziglang.org/documentation/0.5.0/#toc-typeInfo
 
-use proc_macro::{Delimiter, Group, TokenStream, TokenTree};
+use proc_macro::{Delimiter, Group, Literal, TokenStream, TokenTree};
 
 use std::iter::Peekable;
 
@@ -22,6 +22,7 @@ pub enum Visibility {
 }
 
 pub struct Field {
+    pub attributes: Vec<Attribute>,
     pub vis: Visibility,
     pub field_name: Option<String>,
     pub ty: Type,
@@ -133,6 +134,18 @@ pub fn next_group(source: &mut Peekable<impl Iterator<Item = TokenTree>>) -> Opt
     }
 }
 
+pub fn next_literal(source: &mut Peekable<impl Iterator<Item = TokenTree>>) -> Option<Literal> {
+    if let Some(TokenTree::Literal(_)) = source.peek() {
+        let literal = match source.next().unwrap() {
+            TokenTree::Literal(literal) => literal,
+            _ => unreachable!("just checked with peek()!"),
+        };
+        Some(literal)
+    } else {
+        None
+    }
+}
+
 #[allow(dead_code)]
 pub fn debug_current_token(source: &mut Peekable<impl Iterator<Item = TokenTree>>) {
     println!("{:?}", source.peek());
@@ -164,9 +177,9 @@ fn next_type<T: Iterator<Item = TokenTree>>(source: &mut Peekable<T>) -> Option<
 
     let angel_bracket = next_exact_punct(source, "<");
     if angel_bracket.is_some() {
-        let mut generic_type = next_type(source).expect("Expecting generic argument");
+        let mut generic_type = next_generic_argument(source).expect("Expecting generic argument");
         while let Some(_comma) = next_exact_punct(source, ",") {
-            let next_ty = next_type(source).expect("Expecting generic argument");
+            let next_ty = next_generic_argument(source).expect("Expecting generic argument");
             generic_type.path.push_str(&format!(", {}", next_ty.path));
         }
 
@@ -192,6 +205,58 @@ fn next_type<T: Iterator<Item = TokenTree>>(source: &mut Peekable<T>) -> Option<
     }
 }
 
+// read either a type or a const generic argument like `-100` or `{ N + 1 }`
+fn next_generic_argument<T: Iterator<Item = TokenTree>>(source: &mut Peekable<T>) -> Option<Type> {
+    if let Some(TokenTree::Group(group)) = source.peek() {
+        if group.delimiter() == Delimiter::Brace {
+            let group = next_group(source).unwrap();
+            return Some(Type {
+                is_option: false,
+                path: format!("{{ {} }}", group.stream()),
+            });
+        }
+    }
+
+    let negative = next_exact_punct(source, "-").is_some();
+    if let Some(literal) = next_literal(source) {
+        let sign = if negative { "-" } else { "" };
+        return Some(Type {
+            is_option: false,
+            path: format!("{}{}", sign, literal),
+        });
+    }
+    if negative {
+        panic!("Expecting literal after -");
+    }
+
+    next_type(source)
+}
+
+// read attributes like `#[name(a = 1, b)]`, where `tokens` holds what is inside
+// the parenthesis
+fn next_attributes(source: &mut Peekable<impl Iterator<Item = TokenTree>>) -> Vec<Attribute> {
+    let mut attributes = vec![];
+
+    while next_exact_punct(source, "#").is_some() {
+        let group = next_group(source).expect("Expecting attribute body after #");
+        let mut body = group.stream().into_iter().peekable();
+
+        let name = next_ident(&mut body).expect("Expecting attribute name");
+        let tokens = match next_group(&mut body) {
+            Some(group) => group
+                .stream()
+                .into_iter()
+                .map(|token| format!("{}", token))
+                .collect(),
+            None => vec![],
+        };
+
+        attributes.push(Attribute { name, tokens });
+    }
+
+    attributes
+}
+
 fn next_fields(body: &mut Peekable<impl Iterator<Item = TokenTree>>, named: bool) -> Vec<Field> {
     let mut fields = vec![];
 
@@ -200,6 +265,7 @@ fn next_fields(body: &mut Peekable<impl Iterator<Item = TokenTree>>, named: bool
             break;
         }
 
+        let attributes = next_attributes(body);
         let _visibility = next_visibility_modifier(body);
         let field_name = if named {
             let field_name = next_ident(body).expect("Field name expected");
@@ -214,6 +280,7 @@ fn next_fields(body: &mut Peekable<impl Iterator<Item = TokenTree>>, named: bool
         let _punct = next_punct(body);
 
         fields.push(Field {
+            attributes,
             vis: Visibility::Public,
             field_name,
             ty,
@@ -280,6 +347,8 @@ fn next_enum(source: &mut Peekable<impl Iterator<Item = TokenTree>>) -> Enum {
             break;
         }
 
+        // skip doc comments & other attributes on the variant
+        let _attributes = next_attributes(&mut body);
         let variant_name = next_ident(&mut body).expect("Unnamed variants are not supported");
         let group = next_group(&mut body);
         if group.is_none() {
@@ -321,6 +390,9 @@ fn next_enum(source: &mut Peekable<impl Iterator<Item = TokenTree>>) -> Enum {
 pub fn parse_data(input: TokenStream) -> Data {
     let mut source = input.into_iter().peekable();
 
+    // skip doc comments & other attributes on the type
+    let _attributes = next_attributes(&mut source);
+
     let pub_or_type = next_ident(&mut source).expect("Not an ident");
 
     let type_keyword = if pub_or_type == "pub" {
@@ -1,4 +1,4 @@
-use crate::parse::Enum;
+use crate::{parse::Enum, shared::FieldEncoding};
 
 fn bits_needed_for(max_value: usize) -> u8 {
     let mut bits = 1;
@@ -39,7 +39,12 @@ pub fn derive_serde_enum(enum_: &Enum) -> String {
         else if !variant.tuple {
             l!(ser_variants, "Self::{} {{", variant.name);
             for field in &variant.fields {
-                l!(ser_variants, "{}, ", field.field_name.as_ref().unwrap());
+                let field_name = field.field_name.as_ref().unwrap();
+                if FieldEncoding::of(field).is_skipped() {
+                    l!(ser_variants, "{}: _, ", field_name);
+                } else {
+                    l!(ser_variants, "{}, ", field_name);
+                }
             }
             l!(ser_variants, "} => {");
 
@@ -53,10 +58,10 @@ pub fn derive_serde_enum(enum_: &Enum) -> String {
             l!(ser_variants, "index.ser(writer);");
 
             for field in &variant.fields {
+                let encoding = FieldEncoding::of(field);
                 l!(
                     ser_variants,
-                    "{}.ser(writer);",
-                    field.field_name.as_ref().unwrap()
+                    &encoding.ser(field, &format!("(*{})", field.field_name.as_ref().unwrap()))
                 );
             }
             l!(ser_variants, "}");
@@ -64,8 +69,12 @@ pub fn derive_serde_enum(enum_: &Enum) -> String {
         // Tuple Variant
         else if variant.tuple {
             l!(ser_variants, "Self::{} (", variant.name);
-            for (n, _) in variant.fields.iter().enumerate() {
-                l!(ser_variants, "f{}, ", n);
+            for (n, field) in variant.fields.iter().enumerate() {
+                if FieldEncoding::of(field).is_skipped() {
+                    l!(ser_variants, "_, ");
+                } else {
+                    l!(ser_variants, "f{}, ", n);
+                }
             }
             l!(ser_variants, ") => {");
 
@@ -78,8 +87,9 @@ pub fn derive_serde_enum(enum_: &Enum) -> String {
             );
             l!(ser_variants, "index.ser(writer);");
 
-            for (n, _) in variant.fields.iter().enumerate() {
-                l!(ser_variants, "f{}.ser(writer);", n);
+            for (n, field) in variant.fields.iter().enumerate() {
+                let encoding = FieldEncoding::of(field);
+                l!(ser_variants, &encoding.ser(field, &format!("(*f{})", n)));
             }
             l!(ser_variants, "}");
         }
@@ -103,10 +113,12 @@ pub fn derive_serde_enum(enum_: &Enum) -> String {
                 variant.name
             );
             for field in &variant.fields {
+                let encoding = FieldEncoding::of(field);
                 l!(
                     de_variants,
-                    "{}: Serde::de(reader)?,",
-                    field.field_name.as_ref().unwrap()
+                    "{}: {},",
+                    field.field_name.as_ref().unwrap(),
+                    encoding.de(field)
                 );
             }
             l!(de_variants, "},");
@@ -114,8 +126,9 @@ pub fn derive_serde_enum(enum_: &Enum) -> String {
         // Tuple Variant
         else if variant.tuple {
             l!(de_variants, "{} => Self::{} (", variant_index, variant.name);
-            for _ in &variant.fields {
-                l!(de_variants, "Serde::de(reader)?,");
+            for field in &variant.fields {
+                let encoding = FieldEncoding::of(field);
+                l!(de_variants, "{},", encoding.de(field));
             }
             l!(de_variants, "),");
         }
@@ -139,7 +152,11 @@ pub fn derive_serde_enum(enum_: &Enum) -> String {
                     let index_u16: u16 = index.get() as u16;
                     Ok(match index_u16 {{
                         {de_variants}
-                        _ => return std::result::Result::Err(SerdeErr{{}})
+                        _ => return std::result::Result::Err(SerdeErr::new(
+                            reader.bit_offset(),
+                            \"{name}\",
+                            SerdeErrReason::InvalidValue,
+                        ))
                     }})
                 }}
             }}
@@ -1,22 +1,25 @@
-use crate::parse::Struct;
+use crate::{parse::Struct, shared::FieldEncoding};
 
 pub fn derive_serde_struct(struct_: &Struct) -> String {
     let mut ser_body = String::new();
     let mut de_body = String::new();
 
     for field in &struct_.fields {
+        let field_name = field.field_name.as_ref().unwrap();
+        let encoding = FieldEncoding::of(field);
         l!(
             ser_body,
-            "self.{}.ser(writer);",
-            field.field_name.as_ref().unwrap()
+            &encoding.ser(field, &format!("self.{}", field_name))
         );
     }
 
     for field in &struct_.fields {
+        let encoding = FieldEncoding::of(field);
         l!(
             de_body,
-            "{}: Serde::de(reader)?,",
-            field.field_name.as_ref().unwrap()
+            "{}: {},",
+            field.field_name.as_ref().unwrap(),
+            encoding.de(field)
         );
     }
 
@@ -1,16 +1,18 @@
-use crate::parse::Struct;
+use crate::{parse::Struct, shared::FieldEncoding};
 
 pub fn derive_serde_tuple_struct(struct_: &Struct) -> String {
     let mut ser_body = String::new();
 
-    for (n, _) in struct_.fields.iter().enumerate() {
-        l!(ser_body, "self.{}.ser(writer);", n);
+    for (n, field) in struct_.fields.iter().enumerate() {
+        let encoding = FieldEncoding::of(field);
+        l!(ser_body, &encoding.ser(field, &format!("self.{}", n)));
     }
 
     let mut de_body = String::new();
 
-    for (n, _) in struct_.fields.iter().enumerate() {
-        l!(de_body, "{}: Serde::de(reader)?,", n);
+    for (n, field) in struct_.fields.iter().enumerate() {
+        let encoding = FieldEncoding::of(field);
+        l!(de_body, "{}: {},", n, encoding.de(field));
     }
 
     let name = &struct_.name;
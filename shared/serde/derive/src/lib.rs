@@ -1,6 +1,7 @@
 extern crate proc_macro;
 
 use naia_parse::parse;
+use proc_macro::{Group, TokenStream, TokenTree};
 
 #[macro_use]
 mod shared;
@@ -8,12 +9,28 @@ mod impls;
 
 use impls::*;
 
+/// Derives the Serde trait for a given struct or enum. Each field may have
+/// one `#[serde(..)]` attribute changing how it is written:
+///
+/// * `#[serde(skip)]`: the field is never written, and is read back as its
+///   `Default`
+/// * `#[serde(bits = N)]`: an integer field is written with N bits. Writing
+///   a value which doesn't fit panics in debug builds, and writes the value
+///   clamped to the range otherwise. Reading a value which doesn't fit into
+///   the field's type is an error
+/// * `#[serde(varint)]` or `#[serde(varint = N)]`: an integer field, other
+///   than a u128 or i128, is written as a variable length integer in blocks
+///   of N bits, 7 by default
+/// * `#[serde(quantize(min, max, bits))]`: an f32 or f64 field is written as
+///   a `SerdeFloat`. An f64 is quantized as an f32, so may use at most 24
+///   bits
 #[proc_macro_attribute]
 pub fn derive_serde(
     _: proc_macro::TokenStream,
     input: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
-    let define_string = input.to_string();
+    // `#[serde(..)]` field attributes are only meaningful to this macro
+    let define_string = strip_serde_attributes(input.clone()).to_string();
 
     let input = parse::parse_data(input);
 
@@ -37,3 +54,35 @@ pub fn derive_serde(
 
     output
 }
+
+fn strip_serde_attributes(input: TokenStream) -> TokenStream {
+    let mut output = Vec::new();
+    let mut source = input.into_iter().peekable();
+
+    while let Some(token) = source.next() {
+        match token {
+            TokenTree::Punct(punct) if punct.as_char() == '#' => {
+                if let Some(TokenTree::Group(group)) = source.peek() {
+                    let is_serde = matches!(
+                        group.stream().into_iter().next(),
+                        Some(TokenTree::Ident(ident)) if ident.to_string() == "serde"
+                    );
+                    if is_serde {
+                        source.next();
+                        continue;
+                    }
+                }
+                output.push(TokenTree::Punct(punct));
+            }
+            TokenTree::Group(group) => {
+                let mut stripped =
+                    Group::new(group.delimiter(), strip_serde_attributes(group.stream()));
+                stripped.set_span(group.span());
+                output.push(TokenTree::Group(stripped));
+            }
+            token => output.push(token),
+        }
+    }
+
+    output.into_iter().collect()
+}
@@ -7,3 +7,237 @@ macro_rules! l {
         $target.push_str(&format!($line, $($param,)*));
     };
 }
+
+use crate::parse::Field;
+
+/// How a field is written to the bit stream, as set by its `#[serde(..)]`
+/// attribute
+pub enum FieldEncoding {
+    Default,
+    Bits(u8),
+    VarInt(u8),
+    Skip,
+    // min & max are multiplied by the divisor, to allow fractional bounds
+    Quantize(i32, i32, u8, u32),
+}
+
+const DEFAULT_VARINT_BITS: u8 = 7;
+const F32_MANTISSA_BITS: u8 = 24;
+
+impl FieldEncoding {
+    pub fn of(field: &Field) -> Self {
+        let mut output = FieldEncoding::Default;
+
+        for attribute in &field.attributes {
+            if attribute.name != "serde" {
+                continue;
+            }
+            if !matches!(output, FieldEncoding::Default) {
+                panic!("Only one #[serde(..)] attribute is allowed per field");
+            }
+
+            let tokens: Vec<&str> = attribute.tokens.iter().map(|t| t.as_str()).collect();
+            output = match tokens.as_slice() {
+                ["skip"] => FieldEncoding::Skip,
+                ["varint"] => FieldEncoding::VarInt(varint_bits(field, DEFAULT_VARINT_BITS)),
+                ["varint", "=", bits] => {
+                    FieldEncoding::VarInt(varint_bits(field, parse_bits(bits)))
+                }
+                ["bits", "=", bits] => {
+                    let bits = parse_bits(bits);
+                    if let Some(width) = integer_width(&field.ty.path) {
+                        if bits > width {
+                            panic!(
+                                "#[serde(bits = {})] can't be larger than the {} bits of {}",
+                                bits, width, field.ty.path
+                            );
+                        }
+                    }
+                    FieldEncoding::Bits(bits)
+                }
+                ["quantize", args] => {
+                    let args: Vec<String> = args
+                        .trim_start_matches('(')
+                        .trim_end_matches(')')
+                        .split(',')
+                        .map(|arg| arg.split_whitespace().collect())
+                        .collect();
+                    if args.len() != 3 {
+                        panic!("Expected #[serde(quantize(min, max, bits))]");
+                    }
+                    let (min, max, divisor) = parse_bounds(&args[0], &args[1]);
+                    let bits = parse_bits(&args[2]);
+                    if bits > 32 {
+                        panic!("quantize can't use more than 32 bits");
+                    }
+                    // values are quantized as f32s, whose mantissa holds 24 bits
+                    if field.ty.path == "f64" && bits > F32_MANTISSA_BITS {
+                        panic!(
+                            "quantize can't use more than {} bits on an f64, as it is quantized as an f32",
+                            F32_MANTISSA_BITS
+                        );
+                    }
+                    FieldEncoding::Quantize(min, max, bits, divisor)
+                }
+                _ => panic!(
+                    "Unsupported serde attribute: #[serde({})]",
+                    attribute.tokens.join(" ")
+                ),
+            };
+        }
+
+        output
+    }
+
+    /// Whether the field is never written, and is read back as `Default`
+    pub fn is_skipped(&self) -> bool {
+        matches!(self, FieldEncoding::Skip)
+    }
+
+    /// Statement writing the field, `value` being an expression of the field's
+    /// type. Values which don't fit into the given number of bits panic in
+    /// debug builds, and are clamped otherwise
+    pub fn ser(&self, field: &Field, value: &str) -> String {
+        match self {
+            FieldEncoding::Default => format!("{}.ser(writer);", value),
+            FieldEncoding::Skip => String::new(),
+            FieldEncoding::Bits(bits) => {
+                let max = i128::MAX >> (127 - *bits as u32);
+                let min = if self.is_signed(field) { -max } else { 0 };
+                format!(
+                    "{{
+                        let value = <i128 as std::convert::TryFrom<_>>::try_from({value}).unwrap_or(i128::MAX);
+                        debug_assert!(({min}..={max}).contains(&value), \"{value} doesn't fit into {bits} bits\");
+                        <{integer}>::new(value.clamp({min}, {max})).ser(writer);
+                    }}",
+                    integer = self.integer_type(field),
+                    value = value,
+                    bits = bits,
+                    min = min,
+                    max = max,
+                )
+            }
+            FieldEncoding::VarInt(_) => format!(
+                "<{}>::new({} as i128).ser(writer);",
+                self.integer_type(field),
+                value
+            ),
+            FieldEncoding::Quantize(min, max, bits, divisor) => format!(
+                "SerdeFloat::<{}, {}, {}, {}>::new({} as f32).ser(writer);",
+                min, max, bits, divisor, value
+            ),
+        }
+    }
+
+    /// Expression reading the field. Values which don't fit into the field's
+    /// type are an error
+    pub fn de(&self, field: &Field) -> String {
+        let ty = &field.ty.path;
+        match self {
+            FieldEncoding::Default => "Serde::de(reader)?".to_string(),
+            FieldEncoding::Skip => "Default::default()".to_string(),
+            FieldEncoding::Bits(_) | FieldEncoding::VarInt(_) => format!(
+                "<{ty} as std::convert::TryFrom<i128>>::try_from(<{integer} as Serde>::de(reader)?.get()).map_err(|_| SerdeErr::new(reader.bit_offset(), \"{ty}\", SerdeErrReason::InvalidValue))?",
+                ty = ty,
+                integer = self.integer_type(field),
+            ),
+            FieldEncoding::Quantize(min, max, bits, divisor) => format!(
+                "<SerdeFloat<{}, {}, {}, {}> as Serde>::de(reader)?.get() as {}",
+                min, max, bits, divisor, ty
+            ),
+        }
+    }
+
+    fn is_signed(&self, field: &Field) -> bool {
+        let ty = field.ty.path.as_str();
+        match ty {
+            "u8" | "u16" | "u32" | "u64" | "u128" | "usize" => false,
+            "i8" | "i16" | "i32" | "i64" | "i128" | "isize" => true,
+            _ => panic!(
+                "#[serde(bits)] and #[serde(varint)] only apply to primitive integers, not {}",
+                ty
+            ),
+        }
+    }
+
+    fn integer_type(&self, field: &Field) -> String {
+        match (self, self.is_signed(field)) {
+            (FieldEncoding::Bits(bits), false) => format!("UnsignedInteger<{}>", bits),
+            (FieldEncoding::Bits(bits), true) => format!("SignedInteger<{}>", bits),
+            (FieldEncoding::VarInt(bits), false) => format!("UnsignedVariableInteger<{}>", bits),
+            (FieldEncoding::VarInt(bits), true) => format!("SignedVariableInteger<{}>", bits),
+            _ => unreachable!("only called for integer encodings"),
+        }
+    }
+}
+
+/// The number of bits of a primitive integer type
+fn integer_width(ty: &str) -> Option<u8> {
+    match ty {
+        "u8" | "i8" => Some(8),
+        "u16" | "i16" => Some(16),
+        "u32" | "i32" => Some(32),
+        "u64" | "i64" | "usize" | "isize" => Some(64),
+        // SerdeInteger holds at most 127 bits
+        "u128" | "i128" => Some(127),
+        _ => None,
+    }
+}
+
+/// Checks that the field's values can be held by a variable length integer,
+/// which is at most an i128
+fn varint_bits(field: &Field, bits: u8) -> u8 {
+    if matches!(field.ty.path.as_str(), "u128" | "i128") {
+        panic!(
+            "#[serde(varint)] doesn't support {}, as not all of its values can be encoded",
+            field.ty.path
+        );
+    }
+    bits
+}
+
+fn parse_bits(bits: &str) -> u8 {
+    let bits: u8 = bits.parse().expect("bits must be an integer");
+    if bits == 0 {
+        panic!("bits must be greater than 0");
+    }
+    bits
+}
+
+/// Parses the decimal bounds of a quantized float, returning them scaled to
+/// integers along with the divisor which scales them back
+fn parse_bounds(min: &str, max: &str) -> (i32, i32, u32) {
+    let decimals = |bound: &str| {
+        bound
+            .split_once('.')
+            .map_or(0, |(_, fraction)| fraction.len())
+    };
+    let places = decimals(min).max(decimals(max));
+    if places > 9 {
+        panic!("quantize bounds can have at most 9 decimal places");
+    }
+    let divisor = 10_u32.pow(places as u32);
+
+    let scale = |bound: &str, name: &str| -> i32 {
+        let (whole, fraction) = bound.split_once('.').unwrap_or((bound, ""));
+        let digits = format!(
+            "{}{}{}",
+            whole,
+            fraction,
+            "0".repeat(places - fraction.len())
+        );
+        digits.parse().unwrap_or_else(|_| {
+            panic!(
+                "quantize {} must be a number within the range of an i32, scaled by {}",
+                name, divisor
+            )
+        })
+    };
+    let min = scale(min, "min");
+    let max = scale(max, "max");
+    if min >= max {
+        panic!("quantize min must be less than max");
+    }
+
+    (min, max, divisor)
+}
@@ -0,0 +1,135 @@
+use crate::{
+    error::{SerdeErr, SerdeErrReason},
+    reader_writer::{BitReader, BitWrite},
+    serde::Serde,
+};
+
+/// An integer within the range `MIN..=MAX`, written as its offset from `MIN`
+/// using the minimum number of bits needed to represent the range
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct SerdeBoundedInteger<const MIN: i64, const MAX: i64> {
+    inner: i64,
+}
+
+impl<const MIN: i64, const MAX: i64> SerdeBoundedInteger<MIN, MAX> {
+    // Evaluated wherever the type is used, so that an empty range fails to
+    // compile rather than panicking at runtime
+    const VALID: () = assert!(
+        MIN <= MAX,
+        "SerdeBoundedInteger requires MIN to be no greater than MAX"
+    );
+
+    pub fn new<T: Into<i64>>(value: T) -> Self {
+        let () = Self::VALID;
+
+        let inner = Into::<i64>::into(value);
+        if inner < MIN || inner > MAX {
+            panic!(
+                "can't encode number {} outside of range {}..={}",
+                inner, MIN, MAX
+            );
+        }
+
+        Self { inner }
+    }
+
+    pub fn get(&self) -> i64 {
+        self.inner
+    }
+
+    /// The number of bits each value is written with
+    pub fn bits() -> u8 {
+        let () = Self::VALID;
+
+        let range = (MAX as i128 - MIN as i128) as u64;
+        (64 - range.leading_zeros()) as u8
+    }
+}
+
+impl<const MIN: i64, const MAX: i64> Serde for SerdeBoundedInteger<MIN, MAX> {
+    fn ser(&self, writer: &mut dyn BitWrite) {
+        let mut value = (self.inner as i128 - MIN as i128) as u64;
+        for _ in 0..Self::bits() {
+            writer.write_bit(value & 1 != 0);
+            value >>= 1;
+        }
+    }
+
+    fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+        let mut output: u64 = 0;
+        for index in 0..Self::bits() {
+            if reader
+                .read_bit()
+                .map_err(|err| err.expecting("SerdeBoundedInteger"))?
+            {
+                output |= 1 << index;
+            }
+        }
+
+        let value = MIN as i128 + output as i128;
+        if value > MAX as i128 {
+            return Err(SerdeErr::new(
+                reader.bit_offset(),
+                "SerdeBoundedInteger",
+                SerdeErrReason::InvalidValue,
+            ));
+        }
+        Ok(Self {
+            inner: value as i64,
+        })
+    }
+}
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        bounded_integer::SerdeBoundedInteger,
+        reader_writer::{BitReader, BitWriter},
+        serde::Serde,
+    };
+
+    #[test]
+    fn bits_needed() {
+        assert_eq!(SerdeBoundedInteger::<0, 0>::bits(), 0);
+        assert_eq!(SerdeBoundedInteger::<0, 1>::bits(), 1);
+        assert_eq!(SerdeBoundedInteger::<-8, 7>::bits(), 4);
+        assert_eq!(SerdeBoundedInteger::<1000, 1100>::bits(), 7);
+        assert_eq!(
+            SerdeBoundedInteger::<{ i64::MIN }, { i64::MAX }>::bits(),
+            64
+        );
+    }
+
+    #[test]
+    fn read_write() {
+        // Write
+        let mut writer = BitWriter::default();
+
+        let in_1 = SerdeBoundedInteger::<-8, 7>::new(-5);
+        let in_2 = SerdeBoundedInteger::<1000, 1100>::new(1099);
+        let in_3 = SerdeBoundedInteger::<{ i64::MIN }, { i64::MAX }>::new(i64::MIN);
+
+        in_1.ser(&mut writer);
+        in_2.ser(&mut writer);
+        in_3.ser(&mut writer);
+
+        let (buffer_length, buffer) = writer.flush();
+
+        // 4 + 7 + 64 bits
+        assert_eq!(buffer_length, 10);
+
+        // Read
+
+        let mut reader = BitReader::new(&buffer[..buffer_length]);
+
+        let out_1 = Serde::de(&mut reader).unwrap();
+        let out_2 = Serde::de(&mut reader).unwrap();
+        let out_3 = Serde::de(&mut reader).unwrap();
+
+        assert_eq!(in_1, out_1);
+        assert_eq!(in_2, out_2);
+        assert_eq!(in_3, out_3);
+    }
+}
@@ -1,11 +1,52 @@
 /// The error message when failing to serialize/deserialize to/from the bit
 /// stream.
 #[derive(Clone)]
-pub struct SerdeErr {}
+pub struct SerdeErr {
+    /// The position in the bit stream at which reading failed
+    pub bit_offset: usize,
+    /// The name of the type which was being read
+    pub expected: &'static str,
+    /// Why reading failed
+    pub reason: SerdeErrReason,
+}
+
+/// The reason a value could not be read from the bit stream
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SerdeErrReason {
+    /// Attempted to read past the end of the buffer
+    EndOfBuffer,
+    /// The bits read do not form a valid value of the expected type
+    InvalidValue,
+}
+
+impl SerdeErr {
+    pub fn new(bit_offset: usize, expected: &'static str, reason: SerdeErrReason) -> Self {
+        Self {
+            bit_offset,
+            expected,
+            reason,
+        }
+    }
+
+    /// Replaces the expected type, so that errors raised while reading
+    /// individual bits name the value they were part of
+    pub fn expecting(mut self, expected: &'static str) -> Self {
+        self.expected = expected;
+        self
+    }
+}
 
 impl std::fmt::Debug for SerdeErr {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Bin deserialize error",)
+        let reason = match self.reason {
+            SerdeErrReason::EndOfBuffer => "unexpected end of buffer",
+            SerdeErrReason::InvalidValue => "invalid value",
+        };
+        write!(
+            f,
+            "Bin deserialize error: {} while reading {} at bit {}",
+            reason, self.expected, self.bit_offset
+        )
     }
 }
 
@@ -0,0 +1,158 @@
+use crate::{
+    error::SerdeErr,
+    reader_writer::{BitReader, BitWrite},
+    serde::Serde,
+};
+
+/// A floating point number within the range `MIN / DIVISOR..=MAX / DIVISOR`,
+/// quantized to be written using only `BITS` bits. `DIVISOR` allows for
+/// fractional bounds, for example `SerdeFloat<-5, 5, 8, 10>` covers
+/// `-0.5..=0.5`. The value is stored in its quantized form, so that the value
+/// read on the remote host is identical to the local one.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct SerdeFloat<const MIN: i32, const MAX: i32, const BITS: u8, const DIVISOR: u32 = 1> {
+    inner: u32,
+}
+
+impl<const MIN: i32, const MAX: i32, const BITS: u8, const DIVISOR: u32>
+    SerdeFloat<MIN, MAX, BITS, DIVISOR>
+{
+    // Evaluated wherever the type is used, so that invalid parameters fail to
+    // compile rather than panicking at runtime
+    const VALID: () = {
+        assert!(MIN < MAX, "SerdeFloat requires MIN to be less than MAX");
+        assert!(BITS > 0, "SerdeFloat requires at least 1 bit");
+        assert!(
+            BITS <= 32,
+            "SerdeFloat can't be written with more than 32 bits"
+        );
+        assert!(DIVISOR > 0, "SerdeFloat requires a DIVISOR greater than 0");
+    };
+
+    pub fn new(value: f32) -> Self {
+        let () = Self::VALID;
+
+        // values outside of the range are clamped
+        let normalized =
+            ((value as f64 - Self::min()) / (Self::max() - Self::min())).clamp(0.0, 1.0);
+
+        Self {
+            inner: (normalized * Self::max_step() as f64).round() as u32,
+        }
+    }
+
+    pub fn get(&self) -> f32 {
+        let normalized = self.inner as f64 / Self::max_step() as f64;
+        (Self::min() + (normalized * (Self::max() - Self::min()))) as f32
+    }
+
+    fn min() -> f64 {
+        MIN as f64 / DIVISOR as f64
+    }
+
+    fn max() -> f64 {
+        MAX as f64 / DIVISOR as f64
+    }
+
+    fn max_step() -> u32 {
+        if BITS >= 32 {
+            u32::MAX
+        } else {
+            (1 << BITS) - 1
+        }
+    }
+}
+
+impl<const MIN: i32, const MAX: i32, const BITS: u8, const DIVISOR: u32> Serde
+    for SerdeFloat<MIN, MAX, BITS, DIVISOR>
+{
+    fn ser(&self, writer: &mut dyn BitWrite) {
+        let mut value = self.inner;
+        for _ in 0..BITS {
+            writer.write_bit(value & 1 != 0);
+            value >>= 1;
+        }
+    }
+
+    fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+        let () = Self::VALID;
+
+        let mut output: u32 = 0;
+        for index in 0..BITS {
+            if reader
+                .read_bit()
+                .map_err(|err| err.expecting("SerdeFloat"))?
+            {
+                output |= 1 << index;
+            }
+        }
+        Ok(Self { inner: output })
+    }
+}
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        float::SerdeFloat,
+        reader_writer::{BitReader, BitWriter},
+        serde::Serde,
+    };
+
+    #[test]
+    fn in_and_out() {
+        let middle = SerdeFloat::<-100, 100, 16>::new(12.345);
+        let out = middle.get();
+
+        // precision of 200 / (2^16 - 1)
+        assert!((out - 12.345).abs() < 0.002);
+    }
+
+    #[test]
+    fn clamps_to_range() {
+        assert_eq!(SerdeFloat::<0, 10, 8>::new(-5.0).get(), 0.0);
+        assert_eq!(SerdeFloat::<0, 10, 8>::new(15.0).get(), 10.0);
+    }
+
+    #[test]
+    fn fractional_range() {
+        assert_eq!(SerdeFloat::<-5, 5, 8, 10>::new(-1.0).get(), -0.5);
+        assert_eq!(SerdeFloat::<-5, 5, 8, 10>::new(1.0).get(), 0.5);
+
+        let middle = SerdeFloat::<0, 1, 16, 4>::new(0.1);
+        // precision of 0.25 / (2^16 - 1)
+        assert!((middle.get() - 0.1).abs() < 0.00001);
+    }
+
+    #[test]
+    fn read_write() {
+        // Write
+        let mut writer = BitWriter::default();
+
+        let in_1 = SerdeFloat::<-180, 180, 10>::new(-45.5);
+        let in_2 = SerdeFloat::<0, 1, 1>::new(0.8);
+        let in_3 = SerdeFloat::<-1000, 1000, 32>::new(123.456);
+
+        in_1.ser(&mut writer);
+        in_2.ser(&mut writer);
+        in_3.ser(&mut writer);
+
+        let (buffer_length, buffer) = writer.flush();
+
+        // 10 + 1 + 32 bits
+        assert_eq!(buffer_length, 6);
+
+        // Read
+
+        let mut reader = BitReader::new(&buffer[..buffer_length]);
+
+        let out_1 = Serde::de(&mut reader).unwrap();
+        let out_2 = Serde::de(&mut reader).unwrap();
+        let out_3 = Serde::de(&mut reader).unwrap();
+
+        assert_eq!(in_1, out_1);
+        assert_eq!(in_2, out_2);
+        assert_eq!(in_3, out_3);
+    }
+}
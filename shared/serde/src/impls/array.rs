@@ -1,5 +1,5 @@
 use crate::{
-    error::SerdeErr,
+    error::{SerdeErr, SerdeErrReason},
     reader_writer::{BitReader, BitWrite},
     serde::Serde,
 };
@@ -11,8 +11,13 @@ impl<T: Serde> Serde for &[T] {
         }
     }
 
-    fn de(_: &mut BitReader) -> Result<Self, SerdeErr> {
-        Err(SerdeErr {})
+    fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+        // slices are borrowed, so can't be read into
+        Err(SerdeErr::new(
+            reader.bit_offset(),
+            "slice",
+            SerdeErrReason::InvalidValue,
+        ))
     }
 }
 
@@ -2,8 +2,9 @@ use crate::{
     error::SerdeErr,
     reader_writer::{BitReader, BitWrite},
     serde::Serde,
-    UnsignedVariableInteger,
 };
+
+use super::vector::{read_length, write_length};
 use std::{
     collections::{HashMap, HashSet},
     hash::Hash,
@@ -11,18 +12,16 @@ use std::{
 
 impl<K: Serde + Eq + Hash> Serde for HashSet<K> {
     fn ser(&self, writer: &mut dyn BitWrite) {
-        let length = UnsignedVariableInteger::<5>::new(self.len() as u64);
-        length.ser(writer);
+        write_length::<K>(writer, self.len(), "HashSet");
         for value in self {
             value.ser(writer);
         }
     }
 
     fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
-        let length_int = UnsignedVariableInteger::<5>::de(reader)?;
-        let length_usize = length_int.get() as usize;
+        let length = read_length::<K>(reader, "HashSet")?;
         let mut output: HashSet<K> = HashSet::new();
-        for _ in 0..length_usize {
+        for _ in 0..length {
             let value = K::de(reader)?;
             output.insert(value);
         }
@@ -32,8 +31,7 @@ impl<K: Serde + Eq + Hash> Serde for HashSet<K> {
 
 impl<K: Serde + Eq + Hash, V: Serde> Serde for HashMap<K, V> {
     fn ser(&self, writer: &mut dyn BitWrite) {
-        let length = UnsignedVariableInteger::<5>::new(self.len() as u64);
-        length.ser(writer);
+        write_length::<(K, V)>(writer, self.len(), "HashMap");
         for (key, value) in self {
             key.ser(writer);
             value.ser(writer);
@@ -41,10 +39,9 @@ impl<K: Serde + Eq + Hash, V: Serde> Serde for HashMap<K, V> {
     }
 
     fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
-        let length_int = UnsignedVariableInteger::<5>::de(reader)?;
-        let length_usize = length_int.get() as usize;
+        let length = read_length::<(K, V)>(reader, "HashMap")?;
         let mut output: HashMap<K, V> = HashMap::new();
-        for _ in 0..length_usize {
+        for _ in 0..length {
             let key = K::de(reader)?;
             let value = V::de(reader)?;
             output.insert(key, value);
@@ -58,8 +55,10 @@ impl<K: Serde + Eq + Hash, V: Serde> Serde for HashMap<K, V> {
 #[cfg(test)]
 mod tests {
     use crate::{
+        error::SerdeErrReason,
         reader_writer::{BitReader, BitWriter},
         serde::Serde,
+        UnsignedVariableInteger,
     };
     use std::collections::{HashMap, HashSet};
 
@@ -126,4 +125,50 @@ mod tests {
         assert_eq!(in_1, out_1);
         assert_eq!(in_2, out_2);
     }
+
+    #[test]
+    fn read_oversized_length() {
+        // Write
+        let mut writer = BitWriter::default();
+
+        UnsignedVariableInteger::<5>::new(1000).ser(&mut writer);
+        true.ser(&mut writer);
+
+        let (buffer_length, buffer) = writer.flush();
+
+        // Read
+
+        let mut reader = BitReader::new(&buffer[..buffer_length]);
+        let error = HashSet::<bool>::de(&mut reader).unwrap_err();
+        assert_eq!(error.reason, SerdeErrReason::EndOfBuffer);
+
+        let mut reader = BitReader::new(&buffer[..buffer_length]);
+        let error = HashMap::<bool, bool>::de(&mut reader).unwrap_err();
+        assert_eq!(error.reason, SerdeErrReason::EndOfBuffer);
+    }
+
+    #[test]
+    fn read_zero_sized_elements() {
+        // Write
+        let mut writer = BitWriter::default();
+
+        UnsignedVariableInteger::<5>::new(3).ser(&mut writer);
+        0u32.ser(&mut writer);
+
+        let (buffer_length, buffer) = writer.flush();
+
+        // Read
+
+        let mut reader = BitReader::new(&buffer[..buffer_length]);
+        let error = HashMap::<(), ()>::de(&mut reader).unwrap_err();
+        assert_eq!(error.reason, SerdeErrReason::InvalidValue);
+    }
+
+    #[test]
+    #[should_panic]
+    fn write_zero_sized_elements() {
+        let mut writer = BitWriter::default();
+
+        HashSet::from([()]).ser(&mut writer);
+    }
 }
@@ -1,13 +1,13 @@
 use crate::{
-    error::SerdeErr,
+    error::{SerdeErr, SerdeErrReason},
     reader_writer::{BitReader, BitWrite},
     serde::Serde,
-    UnsignedInteger,
+    UnsignedVariableInteger,
 };
 
 impl Serde for String {
     fn ser(&self, writer: &mut dyn BitWrite) {
-        let length = UnsignedInteger::<9>::new(self.len() as u64);
+        let length = UnsignedVariableInteger::<9>::new(self.len() as u64);
         length.ser(writer);
         let bytes = self.as_bytes();
         for byte in bytes {
@@ -16,15 +16,28 @@ impl Serde for String {
     }
 
     fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
-        let length_int = UnsignedInteger::<9>::de(reader)?;
+        let length_int = UnsignedVariableInteger::<9>::de(reader)?;
         let length_usize = length_int.get() as usize;
+        if length_usize > reader.remaining_bits() / 8 {
+            return Err(SerdeErr::new(
+                reader.bit_offset(),
+                "String",
+                SerdeErrReason::EndOfBuffer,
+            ));
+        }
         let mut bytes: Vec<u8> = Vec::with_capacity(length_usize);
         for _ in 0..length_usize {
-            bytes.push(reader.read_byte());
+            bytes.push(reader.read_byte().map_err(|err| err.expecting("String"))?);
         }
 
-        let result = std::str::from_utf8(&bytes).unwrap().to_string();
-        Ok(result)
+        match String::from_utf8(bytes) {
+            Ok(result) => Ok(result),
+            Err(_) => Err(SerdeErr::new(
+                reader.bit_offset(),
+                "String",
+                SerdeErrReason::InvalidValue,
+            )),
+        }
     }
 }
 
@@ -33,8 +46,10 @@ impl Serde for String {
 #[cfg(test)]
 mod tests {
     use crate::{
+        error::SerdeErrReason,
         reader_writer::{BitReader, BitWriter},
         serde::Serde,
+        UnsignedVariableInteger,
     };
 
     #[test]
@@ -60,4 +75,42 @@ mod tests {
         assert_eq!(in_1, out_1);
         assert_eq!(in_2, out_2);
     }
+
+    #[test]
+    fn read_truncated() {
+        // Write
+        let mut writer = BitWriter::default();
+
+        let in_1 = "Hello world!".to_string();
+
+        in_1.ser(&mut writer);
+
+        let (buffer_length, buffer) = writer.flush();
+
+        // Read
+
+        let mut reader = BitReader::new(&buffer[..buffer_length - 4]);
+
+        let error = String::de(&mut reader).unwrap_err();
+
+        assert_eq!(error.expected, "String");
+    }
+
+    #[test]
+    fn read_oversized_length() {
+        // Write
+        let mut writer = BitWriter::default();
+
+        UnsignedVariableInteger::<9>::new(u64::MAX).ser(&mut writer);
+
+        let (buffer_length, buffer) = writer.flush();
+
+        // Read
+
+        let mut reader = BitReader::new(&buffer[..buffer_length]);
+
+        let error = String::de(&mut reader).unwrap_err();
+
+        assert_eq!(error.reason, SerdeErrReason::EndOfBuffer);
+    }
 }
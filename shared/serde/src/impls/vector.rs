@@ -1,26 +1,62 @@
-use std::collections::VecDeque;
+use std::{collections::VecDeque, mem};
 
 use crate::{
-    error::SerdeErr,
+    error::{SerdeErr, SerdeErrReason},
     reader_writer::{BitReader, BitWrite},
     serde::Serde,
     UnsignedVariableInteger,
 };
 
+/// Writes the length of a collection of `T`s. Zero-sized `T`s take no room at
+/// all, so `read_length` rejects non-empty collections of them, and they
+/// can't be written either.
+pub(crate) fn write_length<T>(writer: &mut dyn BitWrite, length: usize, expected: &'static str) {
+    assert!(
+        length == 0 || mem::size_of::<T>() != 0,
+        "a non-empty {} of zero-sized elements can't be written",
+        expected
+    );
+    UnsignedVariableInteger::<5>::new(length as u64).ser(writer);
+}
+
+/// Reads the length of a collection of `T`s, rejecting any the rest of the
+/// buffer couldn't hold, so that untrusted lengths can't make the reader loop
+/// or allocate far beyond the size of the packet. Zero-sized `T`s take no
+/// room at all, so collections of them must be empty.
+pub(crate) fn read_length<T>(
+    reader: &mut BitReader,
+    expected: &'static str,
+) -> Result<usize, SerdeErr> {
+    let length = UnsignedVariableInteger::<5>::de(reader)?.get();
+    if length > reader.remaining_bits() as i128 {
+        return Err(SerdeErr::new(
+            reader.bit_offset(),
+            expected,
+            SerdeErrReason::EndOfBuffer,
+        ));
+    }
+    if length > 0 && mem::size_of::<T>() == 0 {
+        return Err(SerdeErr::new(
+            reader.bit_offset(),
+            expected,
+            SerdeErrReason::InvalidValue,
+        ));
+    }
+    Ok(length as usize)
+}
+
 impl<T: Serde> Serde for Vec<T> {
     fn ser(&self, writer: &mut dyn BitWrite) {
-        let length = UnsignedVariableInteger::<5>::new(self.len() as u64);
-        length.ser(writer);
+        write_length::<T>(writer, self.len(), "Vec");
         for item in self {
             item.ser(writer);
         }
     }
 
     fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
-        let length_int = UnsignedVariableInteger::<5>::de(reader)?;
-        let length_usize = length_int.get() as usize;
-        let mut output: Vec<T> = Vec::with_capacity(length_usize);
-        for _ in 0..length_usize {
+        let length = read_length::<T>(reader, "Vec")?;
+        let mut output: Vec<T> = Vec::with_capacity(length);
+        for _ in 0..length {
             output.push(T::de(reader)?)
         }
         Ok(output)
@@ -29,18 +65,16 @@ impl<T: Serde> Serde for Vec<T> {
 
 impl<T: Serde> Serde for VecDeque<T> {
     fn ser(&self, writer: &mut dyn BitWrite) {
-        let length = UnsignedVariableInteger::<5>::new(self.len() as u64);
-        length.ser(writer);
+        write_length::<T>(writer, self.len(), "VecDeque");
         for item in self {
             item.ser(writer);
         }
     }
 
     fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
-        let length_int = UnsignedVariableInteger::<5>::de(reader)?;
-        let length_usize = length_int.get() as usize;
-        let mut output: VecDeque<T> = VecDeque::with_capacity(length_usize);
-        for _ in 0..length_usize {
+        let length = read_length::<T>(reader, "VecDeque")?;
+        let mut output: VecDeque<T> = VecDeque::with_capacity(length);
+        for _ in 0..length {
             output.push_back(T::de(reader)?)
         }
         Ok(output)
@@ -52,8 +86,10 @@ impl<T: Serde> Serde for VecDeque<T> {
 #[cfg(test)]
 mod tests {
     use crate::{
+        error::SerdeErrReason,
         reader_writer::{BitReader, BitWriter},
         serde::Serde,
+        UnsignedVariableInteger,
     };
     use std::collections::VecDeque;
 
@@ -117,4 +153,69 @@ mod tests {
         assert_eq!(in_1, out_1);
         assert_eq!(in_2, out_2);
     }
+
+    #[test]
+    fn read_oversized_length() {
+        // Write
+        let mut writer = BitWriter::default();
+
+        UnsignedVariableInteger::<5>::new(1000).ser(&mut writer);
+        true.ser(&mut writer);
+
+        let (buffer_length, buffer) = writer.flush();
+
+        // Read
+
+        let mut reader = BitReader::new(&buffer[..buffer_length]);
+
+        let error = Vec::<bool>::de(&mut reader).unwrap_err();
+
+        assert_eq!(error.reason, SerdeErrReason::EndOfBuffer);
+    }
+
+    #[test]
+    fn read_zero_sized_elements() {
+        // Write
+        let mut writer = BitWriter::default();
+
+        UnsignedVariableInteger::<5>::new(3).ser(&mut writer);
+        0u32.ser(&mut writer);
+
+        let (buffer_length, buffer) = writer.flush();
+
+        // Read
+
+        let mut reader = BitReader::new(&buffer[..buffer_length]);
+
+        let error = Vec::<()>::de(&mut reader).unwrap_err();
+
+        assert_eq!(error.reason, SerdeErrReason::InvalidValue);
+    }
+
+    #[test]
+    #[should_panic]
+    fn write_zero_sized_elements() {
+        let mut writer = BitWriter::default();
+
+        vec![(); 3].ser(&mut writer);
+    }
+
+    #[test]
+    fn read_write_empty_zero_sized_elements() {
+        // Write
+        let mut writer = BitWriter::default();
+
+        let in_1: Vec<()> = Vec::new();
+        in_1.ser(&mut writer);
+
+        let (buffer_length, buffer) = writer.flush();
+
+        // Read
+
+        let mut reader = BitReader::new(&buffer[..buffer_length]);
+
+        let out_1: Vec<()> = Serde::de(&mut reader).unwrap();
+
+        assert_eq!(in_1, out_1);
+    }
 }
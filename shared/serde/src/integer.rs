@@ -1,5 +1,5 @@
 use crate::{
-    error::SerdeErr,
+    error::{SerdeErr, SerdeErrReason},
     reader_writer::{BitReader, BitWrite},
     serde::Serde,
 };
@@ -21,21 +21,23 @@ impl<const SIGNED: bool, const VARIABLE: bool, const BITS: u8>
         self.inner
     }
 
+    const VALID: () = {
+        assert!(BITS > 0, "can't create an integer with 0 bits...");
+        assert!(
+            BITS <= 127,
+            "can't create an integer with more than 127 bits..."
+        );
+    };
+
     pub fn new<T: Into<i128>>(value: T) -> Self {
+        let () = Self::VALID;
         let inner = Into::<i128>::into(value);
 
         if inner < 0 && !SIGNED {
             panic!("can't encode a negative number with an Unsigned Integer!");
         }
 
-        if BITS == 0 {
-            panic!("can't create an integer with 0 bits...");
-        }
-        if BITS > 127 {
-            panic!("can't create an integer with more than 127 bits...");
-        }
-
-        if !VARIABLE {
+        if !VARIABLE && BITS < 127 {
             let max_value: i128 = 2_i128.pow(BITS as u32);
             if inner >= max_value {
                 panic!(
@@ -72,11 +74,7 @@ impl<const SIGNED: bool, const VARIABLE: bool, const BITS: u8> Serde
         if SIGNED {
             // 1 if negative, 0 if positive
             writer.write_bit(negative);
-            if negative {
-                value = -self.inner as u128;
-            } else {
-                value = self.inner as u128;
-            }
+            value = self.inner.unsigned_abs();
         } else {
             value = self.inner as u128;
         }
@@ -108,32 +106,44 @@ impl<const SIGNED: bool, const VARIABLE: bool, const BITS: u8> Serde
     }
 
     fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+        let () = Self::VALID;
         let mut negative: bool = false;
         if SIGNED {
-            negative = reader.read_bit();
+            negative = reader
+                .read_bit()
+                .map_err(|err| err.expecting("SerdeInteger"))?;
         }
 
         if VARIABLE {
-            let mut total_bits: usize = 0;
+            let mut total_bits: u32 = 0;
             let mut output: u128 = 0;
 
             loop {
-                let proceed = reader.read_bit();
+                let proceed = reader
+                    .read_bit()
+                    .map_err(|err| err.expecting("SerdeInteger"))?;
 
                 for _ in 0..BITS {
-                    total_bits += 1;
-
-                    output <<= 1;
-
-                    if reader.read_bit() {
-                        output |= 1;
+                    if reader
+                        .read_bit()
+                        .map_err(|err| err.expecting("SerdeInteger"))?
+                    {
+                        // a set bit which doesn't fit in an i128 can only come
+                        // from a corrupt or malicious stream
+                        if total_bits >= 127 {
+                            return Err(SerdeErr::new(
+                                reader.bit_offset(),
+                                "SerdeInteger",
+                                SerdeErrReason::InvalidValue,
+                            ));
+                        }
+                        output |= 1 << total_bits;
                     }
+
+                    total_bits = total_bits.saturating_add(1);
                 }
 
                 if !proceed {
-                    output <<= 128 - total_bits;
-                    output = output.reverse_bits();
-
                     let value: i128 = output as i128;
                     if negative {
                         return Ok(SerdeInteger::new_unchecked(-value));
@@ -148,7 +158,10 @@ impl<const SIGNED: bool, const VARIABLE: bool, const BITS: u8> Serde
             for _ in 0..BITS {
                 output <<= 1;
 
-                if reader.read_bit() {
+                if reader
+                    .read_bit()
+                    .map_err(|err| err.expecting("SerdeInteger"))?
+                {
                     output |= 1;
                 }
             }
@@ -171,6 +184,7 @@ impl<const SIGNED: bool, const VARIABLE: bool, const BITS: u8> Serde
 #[cfg(test)]
 mod tests {
     use crate::{
+        error::SerdeErrReason,
         integer::{SignedInteger, SignedVariableInteger, UnsignedInteger, UnsignedVariableInteger},
         reader_writer::{BitReader, BitWriter},
         serde::Serde,
@@ -296,4 +310,35 @@ mod tests {
         assert_eq!(in_2, out_2);
         assert_eq!(in_3, out_3);
     }
+
+    #[test]
+    fn read_write_widest_variable() {
+        // Write
+        let mut writer = BitWriter::default();
+
+        let in_1 = UnsignedVariableInteger::<5>::new(i128::MAX);
+
+        in_1.ser(&mut writer);
+
+        let (buffer_length, buffer) = writer.flush();
+
+        // Read
+
+        let mut reader = BitReader::new(&buffer[..buffer_length]);
+
+        let out_1 = Serde::de(&mut reader).unwrap();
+
+        assert_eq!(in_1, out_1);
+    }
+
+    #[test]
+    fn read_overlong_variable() {
+        let buffer = [u8::MAX; 64];
+
+        let mut reader = BitReader::new(&buffer);
+
+        let error = UnsignedVariableInteger::<3>::de(&mut reader).unwrap_err();
+
+        assert_eq!(error.reason, SerdeErrReason::InvalidValue);
+    }
 }
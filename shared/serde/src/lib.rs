@@ -1,13 +1,17 @@
 pub use naia_serde_derive::*;
 
+mod bounded_integer;
 mod consts;
 mod error;
+mod float;
 mod impls;
 mod integer;
 mod reader_writer;
 mod serde;
 
-pub use error::SerdeErr;
+pub use bounded_integer::SerdeBoundedInteger;
+pub use error::{SerdeErr, SerdeErrReason};
+pub use float::SerdeFloat;
 pub use integer::{SignedInteger, SignedVariableInteger, UnsignedInteger, UnsignedVariableInteger};
 pub use reader_writer::{BitCounter, BitReader, BitWrite, BitWriter, OwnedBitReader};
 pub use serde::Serde;
@@ -1,29 +1,32 @@
-use crate::consts::MAX_BUFFER_SIZE;
+use crate::{
+    consts::MAX_BUFFER_SIZE,
+    error::{SerdeErr, SerdeErrReason},
+};
 
 // BitWrite
 
 pub trait BitWrite {
     fn write_bit(&mut self, bit: bool);
     fn write_byte(&mut self, byte: u8);
-    fn bit_count(&self) -> u16;
+    fn bit_count(&self) -> u32;
 }
 
 // BitCounter
 #[derive(Default)]
 pub struct BitCounter {
-    count: u16,
+    count: u32,
 }
 
 impl BitWrite for BitCounter {
     fn write_bit(&mut self, _: bool) {
-        self.count += 1;
+        self.count = self.count.saturating_add(1);
     }
 
     fn write_byte(&mut self, _: u8) {
-        self.count += 8;
+        self.count = self.count.saturating_add(8);
     }
 
-    fn bit_count(&self) -> u16 {
+    fn bit_count(&self) -> u32 {
         self.count
     }
 }
@@ -33,8 +36,7 @@ impl BitWrite for BitCounter {
 pub struct BitWriter {
     scratch: u8,
     scratch_index: u8,
-    buffer: [u8; MAX_BUFFER_SIZE],
-    buffer_index: usize,
+    buffer: Vec<u8>,
 }
 
 impl Default for BitWriter {
@@ -42,31 +44,44 @@ impl Default for BitWriter {
         Self {
             scratch: 0,
             scratch_index: 0,
-            buffer: [0; MAX_BUFFER_SIZE],
-            buffer_index: 0,
+            buffer: Vec::with_capacity(MAX_BUFFER_SIZE),
         }
     }
 }
 
 impl BitWriter {
     pub fn flush(&mut self) -> (usize, [u8; MAX_BUFFER_SIZE]) {
-        if self.scratch_index > 0 {
-            self.buffer[self.buffer_index] =
-                (self.scratch << (8 - self.scratch_index)).reverse_bits();
-            self.buffer_index += 1;
-        }
-
-        let output_length = self.buffer_index;
+        self.finalize_scratch();
 
-        self.buffer_index = 0;
-        self.scratch_index = 0;
-        self.scratch = 0;
+        let output_length = self.buffer.len();
+        if output_length > MAX_BUFFER_SIZE {
+            panic!("Written data exceeds the maximum packet size");
+        }
 
         let mut output_buffer = [0; MAX_BUFFER_SIZE];
-        output_buffer.clone_from_slice(&self.buffer[0..MAX_BUFFER_SIZE]);
+        output_buffer[..output_length].clone_from_slice(&self.buffer);
+
+        self.buffer.clear();
 
         (output_length, output_buffer)
     }
+
+    /// Consumes the writer, returning all written bytes. Unlike `flush()`,
+    /// the output is not limited to the size of a single packet.
+    pub fn to_bytes(mut self) -> Box<[u8]> {
+        self.finalize_scratch();
+        self.buffer.into_boxed_slice()
+    }
+
+    fn finalize_scratch(&mut self) {
+        if self.scratch_index > 0 {
+            self.buffer
+                .push((self.scratch << (8 - self.scratch_index)).reverse_bits());
+        }
+
+        self.scratch_index = 0;
+        self.scratch = 0;
+    }
 }
 
 impl BitWrite for BitWriter {
@@ -80,9 +95,8 @@ impl BitWrite for BitWriter {
         self.scratch_index += 1;
 
         if self.scratch_index >= 8 {
-            self.buffer[self.buffer_index] = self.scratch.reverse_bits();
+            self.buffer.push(self.scratch.reverse_bits());
 
-            self.buffer_index += 1;
             self.scratch_index -= 8;
             self.scratch = 0;
         }
@@ -96,8 +110,8 @@ impl BitWrite for BitWriter {
         }
     }
 
-    fn bit_count(&self) -> u16 {
-        ((self.buffer_index * 8) + (self.scratch_index as usize)) as u16
+    fn bit_count(&self) -> u32 {
+        ((self.buffer.len() * 8) + (self.scratch_index as usize)) as u32
     }
 }
 
@@ -127,10 +141,25 @@ impl<'b> BitReader<'b> {
         }
     }
 
-    pub(crate) fn read_bit(&mut self) -> bool {
+    /// The number of bits read so far
+    pub fn bit_offset(&self) -> usize {
+        (self.state.buffer_index * 8) - (self.state.scratch_index as usize)
+    }
+
+    /// The number of bits left to read, used to bound lengths read off the
+    /// wire before allocating for them
+    pub fn remaining_bits(&self) -> usize {
+        (self.buffer.len() * 8) - self.bit_offset()
+    }
+
+    pub(crate) fn read_bit(&mut self) -> Result<bool, SerdeErr> {
         if self.state.scratch_index == 0 {
             if self.state.buffer_index == self.buffer.len() {
-                panic!("no more bytes to read");
+                return Err(SerdeErr::new(
+                    self.bit_offset(),
+                    "bit",
+                    SerdeErrReason::EndOfBuffer,
+                ));
             }
 
             self.state.scratch = self.buffer[self.state.buffer_index];
@@ -145,21 +174,21 @@ impl<'b> BitReader<'b> {
 
         self.state.scratch_index -= 1;
 
-        value != 0
+        Ok(value != 0)
     }
 
-    pub(crate) fn read_byte(&mut self) -> u8 {
+    pub(crate) fn read_byte(&mut self) -> Result<u8, SerdeErr> {
         let mut output = 0;
         for _ in 0..7 {
-            if self.read_bit() {
+            if self.read_bit().map_err(|err| err.expecting("byte"))? {
                 output |= 128;
             }
             output >>= 1;
         }
-        if self.read_bit() {
+        if self.read_bit().map_err(|err| err.expecting("byte"))? {
             output |= 128;
         }
-        output
+        Ok(output)
     }
 }
 
@@ -212,7 +241,7 @@ mod tests {
 
         let mut reader = BitReader::new(&buffer[..buffer_length]);
 
-        assert!(reader.read_bit());
+        assert!(reader.read_bit().unwrap());
     }
 
     #[test]
@@ -229,9 +258,9 @@ mod tests {
 
         let mut reader = BitReader::new(&buffer[..buffer_length]);
 
-        assert!(!reader.read_bit());
-        assert!(reader.read_bit());
-        assert!(reader.read_bit());
+        assert!(!reader.read_bit().unwrap());
+        assert!(reader.read_bit().unwrap());
+        assert!(reader.read_bit().unwrap());
     }
 
     #[test]
@@ -254,15 +283,15 @@ mod tests {
 
         let mut reader = BitReader::new(&buffer[..buffer_length]);
 
-        assert!(!reader.read_bit());
-        assert!(reader.read_bit());
-        assert!(!reader.read_bit());
-        assert!(reader.read_bit());
+        assert!(!reader.read_bit().unwrap());
+        assert!(reader.read_bit().unwrap());
+        assert!(!reader.read_bit().unwrap());
+        assert!(reader.read_bit().unwrap());
 
-        assert!(reader.read_bit());
-        assert!(!reader.read_bit());
-        assert!(!reader.read_bit());
-        assert!(!reader.read_bit());
+        assert!(reader.read_bit().unwrap());
+        assert!(!reader.read_bit().unwrap());
+        assert!(!reader.read_bit().unwrap());
+        assert!(!reader.read_bit().unwrap());
     }
 
     #[test]
@@ -292,22 +321,22 @@ mod tests {
 
         let mut reader = BitReader::new(&buffer[..buffer_length]);
 
-        assert!(!reader.read_bit());
-        assert!(reader.read_bit());
-        assert!(!reader.read_bit());
-        assert!(reader.read_bit());
+        assert!(!reader.read_bit().unwrap());
+        assert!(reader.read_bit().unwrap());
+        assert!(!reader.read_bit().unwrap());
+        assert!(reader.read_bit().unwrap());
 
-        assert!(reader.read_bit());
-        assert!(!reader.read_bit());
-        assert!(!reader.read_bit());
-        assert!(!reader.read_bit());
+        assert!(reader.read_bit().unwrap());
+        assert!(!reader.read_bit().unwrap());
+        assert!(!reader.read_bit().unwrap());
+        assert!(!reader.read_bit().unwrap());
 
-        assert!(reader.read_bit());
-        assert!(!reader.read_bit());
-        assert!(reader.read_bit());
-        assert!(reader.read_bit());
+        assert!(reader.read_bit().unwrap());
+        assert!(!reader.read_bit().unwrap());
+        assert!(reader.read_bit().unwrap());
+        assert!(reader.read_bit().unwrap());
 
-        assert!(reader.read_bit());
+        assert!(reader.read_bit().unwrap());
     }
 
     #[test]
@@ -340,25 +369,25 @@ mod tests {
 
         let mut reader = BitReader::new(&buffer[..buffer_length]);
 
-        assert!(!reader.read_bit());
-        assert!(reader.read_bit());
-        assert!(!reader.read_bit());
-        assert!(reader.read_bit());
-
-        assert!(reader.read_bit());
-        assert!(!reader.read_bit());
-        assert!(!reader.read_bit());
-        assert!(!reader.read_bit());
-
-        assert!(reader.read_bit());
-        assert!(!reader.read_bit());
-        assert!(reader.read_bit());
-        assert!(reader.read_bit());
-
-        assert!(reader.read_bit());
-        assert!(!reader.read_bit());
-        assert!(reader.read_bit());
-        assert!(reader.read_bit());
+        assert!(!reader.read_bit().unwrap());
+        assert!(reader.read_bit().unwrap());
+        assert!(!reader.read_bit().unwrap());
+        assert!(reader.read_bit().unwrap());
+
+        assert!(reader.read_bit().unwrap());
+        assert!(!reader.read_bit().unwrap());
+        assert!(!reader.read_bit().unwrap());
+        assert!(!reader.read_bit().unwrap());
+
+        assert!(reader.read_bit().unwrap());
+        assert!(!reader.read_bit().unwrap());
+        assert!(reader.read_bit().unwrap());
+        assert!(reader.read_bit().unwrap());
+
+        assert!(reader.read_bit().unwrap());
+        assert!(!reader.read_bit().unwrap());
+        assert!(reader.read_bit().unwrap());
+        assert!(reader.read_bit().unwrap());
     }
 
     #[test]
@@ -373,7 +402,7 @@ mod tests {
 
         let mut reader = BitReader::new(&buffer[..buffer_length]);
 
-        assert_eq!(123, reader.read_byte());
+        assert_eq!(123, reader.read_byte().unwrap());
     }
 
     #[test]
@@ -392,10 +421,67 @@ mod tests {
 
         let mut reader = BitReader::new(&buffer[..buffer_length]);
 
-        assert_eq!(48, reader.read_byte());
-        assert_eq!(151, reader.read_byte());
-        assert_eq!(62, reader.read_byte());
-        assert_eq!(34, reader.read_byte());
-        assert_eq!(2, reader.read_byte());
+        assert_eq!(48, reader.read_byte().unwrap());
+        assert_eq!(151, reader.read_byte().unwrap());
+        assert_eq!(62, reader.read_byte().unwrap());
+        assert_eq!(34, reader.read_byte().unwrap());
+        assert_eq!(2, reader.read_byte().unwrap());
+    }
+
+    #[test]
+    fn read_write_beyond_packet_size() {
+        use crate::reader_writer::{BitReader, BitWrite, BitWriter};
+
+        let mut writer = BitWriter::default();
+
+        writer.write_bit(true);
+        for i in 0..1000 {
+            writer.write_byte((i % 256) as u8);
+        }
+
+        let buffer = writer.to_bytes();
+
+        let mut reader = BitReader::new(&buffer);
+
+        assert!(reader.read_bit().unwrap());
+        for i in 0..1000 {
+            assert_eq!((i % 256) as u8, reader.read_byte().unwrap());
+        }
+    }
+
+    #[test]
+    fn read_past_end() {
+        use crate::{
+            error::SerdeErrReason,
+            reader_writer::{BitReader, BitWrite, BitWriter},
+        };
+
+        let mut writer = BitWriter::default();
+
+        writer.write_byte(123);
+
+        let (buffer_length, buffer) = writer.flush();
+
+        let mut reader = BitReader::new(&buffer[..buffer_length]);
+
+        assert_eq!(123, reader.read_byte().unwrap());
+
+        let error = reader.read_bit().unwrap_err();
+        assert_eq!(error.reason, SerdeErrReason::EndOfBuffer);
+        assert_eq!(error.bit_offset, 8);
+    }
+
+    #[test]
+    fn bit_count_past_u16() {
+        use crate::reader_writer::{BitWrite, BitWriter};
+
+        let mut writer = BitWriter::default();
+
+        for _ in 0..10000 {
+            writer.write_byte(0);
+        }
+        writer.write_bit(true);
+
+        assert_eq!(writer.bit_count(), 80001);
     }
 }
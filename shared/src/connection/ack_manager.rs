@@ -4,12 +4,12 @@ use crate::{
     messages::{channel_config::ChannelIndex, message_manager::MessageManager},
     protocol::protocolize::Protocolize,
     types::PacketIndex,
-    wrapping_number::sequence_greater_than,
+    wrapping_number::{sequence_greater_than, sequence_less_than},
 };
 
 use super::{
-    packet_notifiable::PacketNotifiable, packet_type::PacketType, sequence_buffer::SequenceBuffer,
-    standard_header::StandardHeader,
+    congestion_controller::CongestionController, packet_notifiable::PacketNotifiable,
+    packet_type::PacketType, sequence_buffer::SequenceBuffer, standard_header::StandardHeader,
 };
 
 pub const REDUNDANT_PACKET_ACKS_SIZE: u16 = 32;
@@ -48,20 +48,18 @@ impl AckManager {
     }
 
     /// Process an incoming packet, handle notifications of delivered / dropped
-    /// packets
+    /// packets. The packet itself is only acked once it is marked as received
+    /// with `mark_packet_received`
     pub fn process_incoming_header<P: Protocolize, C: ChannelIndex>(
         &mut self,
         header: &StandardHeader,
         message_manager: &mut MessageManager<P, C>,
         packet_notifiable: &mut Option<&mut dyn PacketNotifiable>,
+        congestion_controller: &mut Option<CongestionController>,
     ) {
-        let sender_packet_index = header.sender_packet_index;
         let sender_ack_index = header.sender_ack_index;
         let mut sender_ack_bitfield = header.sender_ack_bitfield;
 
-        self.received_packets
-            .insert(sender_packet_index, ReceivedPacket {});
-
         // ensure that `self.sender_ack_index` is always increasing (with
         // wrapping)
         if sequence_greater_than(sender_ack_index, self.last_recv_packet_index) {
@@ -73,6 +71,9 @@ impl AckManager {
             if sent_packet.packet_type == PacketType::Data {
                 self.notify_packet_delivered(sender_ack_index, message_manager, packet_notifiable);
             }
+            if let Some(controller) = congestion_controller {
+                controller.record_delivered();
+            }
 
             self.sent_packets.remove(&sender_ack_index);
         }
@@ -82,8 +83,8 @@ impl AckManager {
         // If so, we have no need to resend old packets.
         for i in 1..=REDUNDANT_PACKET_ACKS_SIZE {
             let sent_packet_index = sender_ack_index.wrapping_sub(i);
-            if let Some(sent_packet) = self.sent_packets.get(&sent_packet_index) {
-                if sender_ack_bitfield & 1 == 1 {
+            if sender_ack_bitfield & 1 == 1 {
+                if let Some(sent_packet) = self.sent_packets.get(&sent_packet_index) {
                     if sent_packet.packet_type == PacketType::Data {
                         self.notify_packet_delivered(
                             sent_packet_index,
@@ -91,15 +92,39 @@ impl AckManager {
                             packet_notifiable,
                         );
                     }
+                    if let Some(controller) = congestion_controller {
+                        controller.record_delivered();
+                    }
 
-                    self.sent_packets.remove(&sent_packet_index);
-                } else {
                     self.sent_packets.remove(&sent_packet_index);
                 }
             }
 
             sender_ack_bitfield >>= 1;
         }
+
+        // a packet which isn't acked yet may still be waiting to be read by the
+        // remote host, so it is only dropped once it can no longer be acked
+        let oldest_ackable_index = self
+            .last_recv_packet_index
+            .wrapping_sub(REDUNDANT_PACKET_ACKS_SIZE);
+        self.sent_packets.retain(|sent_packet_index, _| {
+            if sequence_less_than(*sent_packet_index, oldest_ackable_index) {
+                if let Some(controller) = congestion_controller {
+                    controller.record_dropped();
+                }
+                return false;
+            }
+            true
+        });
+    }
+
+    /// Records that the packet with the given index has been read in full, so
+    /// that it is acked to the remote host. A packet which fails to be read
+    /// is never acked, so the remote host sends its contents again
+    pub fn mark_packet_received(&mut self, packet_index: PacketIndex) {
+        self.received_packets
+            .insert(packet_index, ReceivedPacket {});
     }
 
     /// Records the packet with the given packet index
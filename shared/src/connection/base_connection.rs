@@ -1,6 +1,6 @@
 use std::net::SocketAddr;
 
-use naia_serde::{BitWriter, Serde};
+use naia_serde::{BitWrite, BitWriter, Serde};
 
 use crate::{
     backends::Timer,
@@ -13,8 +13,9 @@ use crate::{
 };
 
 use super::{
-    ack_manager::AckManager, connection_config::ConnectionConfig,
-    packet_notifiable::PacketNotifiable, packet_type::PacketType, standard_header::StandardHeader,
+    ack_manager::AckManager, congestion_controller::CongestionController,
+    connection_config::ConnectionConfig, packet_notifiable::PacketNotifiable,
+    packet_type::PacketType, standard_header::StandardHeader,
 };
 
 /// Represents a connection to a remote host, and provides functionality to
@@ -24,6 +25,7 @@ pub struct BaseConnection<P: Protocolize, C: ChannelIndex> {
     heartbeat_timer: Timer,
     timeout_timer: Timer,
     ack_manager: AckManager,
+    congestion_controller: Option<CongestionController>,
     pub message_manager: MessageManager<P, C>,
 }
 
@@ -40,6 +42,10 @@ impl<P: Protocolize, C: ChannelIndex> BaseConnection<P, C> {
             heartbeat_timer: Timer::new(connection_config.heartbeat_interval),
             timeout_timer: Timer::new(connection_config.disconnection_timeout_duration),
             ack_manager: AckManager::default(),
+            congestion_controller: connection_config
+                .congestion
+                .as_ref()
+                .map(CongestionController::new),
             message_manager: MessageManager::new(host_type, channel_config),
         }
     }
@@ -85,9 +91,17 @@ impl<P: Protocolize, C: ChannelIndex> BaseConnection<P, C> {
             header,
             &mut self.message_manager,
             packet_notifiable,
+            &mut self.congestion_controller,
         );
     }
 
+    /// Records that the packet has been read in full, so that it is acked to
+    /// the remote host
+    pub fn mark_packet_received(&mut self, header: &StandardHeader) {
+        self.ack_manager
+            .mark_packet_received(header.sender_packet_index);
+    }
+
     /// Given a packet payload, start tracking the packet via it's index, attach
     /// the appropriate header, and return the packet's resulting underlying
     /// bytes
@@ -102,4 +116,41 @@ impl<P: Protocolize, C: ChannelIndex> BaseConnection<P, C> {
     pub fn next_packet_index(&self) -> PacketIndex {
         self.ack_manager.next_sender_packet_index()
     }
+
+    // Congestion Control
+
+    /// Refills the send budget according to the time elapsed, and adjusts the
+    /// send rate to the current state of the network. Should be called once
+    /// before each batch of outgoing packets
+    pub fn update_send_budget(&mut self, rtt_millis: &f32) {
+        if let Some(controller) = &mut self.congestion_controller {
+            controller.update(*rtt_millis);
+        }
+    }
+
+    /// Returns whether there is enough send budget left to send another packet.
+    /// Outgoing data that doesn't fit in the budget should be deferred until
+    /// the next update
+    pub fn can_send_packet(&self) -> bool {
+        match &self.congestion_controller {
+            Some(controller) => controller.can_send(),
+            None => true,
+        }
+    }
+
+    /// Spends a sent packet's size from the send budget
+    pub fn record_sent_packet(&mut self, writer: &BitWriter) {
+        if let Some(controller) = &mut self.congestion_controller {
+            let bytes = (writer.bit_count() as usize).div_ceil(8);
+            controller.record_sent(bytes);
+        }
+    }
+
+    /// Returns the current send rate, in bytes per second, if congestion
+    /// control is enabled
+    pub fn send_rate(&self) -> Option<f32> {
+        self.congestion_controller
+            .as_ref()
+            .map(|controller| controller.send_rate())
+    }
 }
@@ -0,0 +1,83 @@
+use std::{default::Default, time::Duration};
+
+/// Contains Config properties used to limit the rate at which packets are
+/// sent to a remote host
+#[derive(Clone, Debug)]
+pub struct CongestionConfig {
+    /// The send rate, in bytes per second, used when a connection is first
+    /// established
+    pub initial_send_rate: u32,
+    /// The send rate, in bytes per second, will never be lowered beneath this
+    pub min_send_rate: u32,
+    /// The send rate, in bytes per second, will never be raised above this
+    pub max_send_rate: u32,
+    /// The amount, in bytes per second, that the send rate is raised by after
+    /// each interval in which no congestion was detected
+    pub send_rate_increase: u32,
+    /// The factor the send rate is multiplied by when congestion is detected
+    pub send_rate_decrease_factor: f32,
+    /// The fraction of dropped packets within an interval above which the
+    /// connection is considered congested
+    pub loss_threshold: f32,
+    /// If the measured RTT rises above the lowest RTT within `rtt_window`
+    /// multiplied by this factor, the connection is considered congested
+    pub rtt_threshold_factor: f32,
+    /// The measured RTT must also rise at least this far above that lowest
+    /// RTT for the connection to be considered congested, so that jitter
+    /// on low-latency connections is not mistaken for congestion
+    pub rtt_threshold_min_increase: Duration,
+    /// The lowest RTT is taken over the samples measured within this
+    /// duration, so that a route change which raises the RTT for good is not
+    /// taken as congestion once older samples expire
+    pub rtt_window: Duration,
+    /// The maximum duration worth of unused send budget that can be saved up
+    /// in order to send a burst of packets
+    pub max_burst_duration: Duration,
+}
+
+impl CongestionConfig {
+    /// Creates a new CongestionConfig, used to pace outgoing packets
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        initial_send_rate: u32,
+        min_send_rate: u32,
+        max_send_rate: u32,
+        send_rate_increase: u32,
+        send_rate_decrease_factor: f32,
+        loss_threshold: f32,
+        rtt_threshold_factor: f32,
+        rtt_threshold_min_increase: Duration,
+        rtt_window: Duration,
+        max_burst_duration: Duration,
+    ) -> Self {
+        CongestionConfig {
+            initial_send_rate,
+            min_send_rate,
+            max_send_rate,
+            send_rate_increase,
+            send_rate_decrease_factor,
+            loss_threshold,
+            rtt_threshold_factor,
+            rtt_threshold_min_increase,
+            rtt_window,
+            max_burst_duration,
+        }
+    }
+}
+
+impl Default for CongestionConfig {
+    fn default() -> Self {
+        Self {
+            initial_send_rate: 64_000,
+            min_send_rate: 8_000,
+            max_send_rate: 1_000_000,
+            send_rate_increase: 8_000,
+            send_rate_decrease_factor: 0.75,
+            loss_threshold: 0.05,
+            rtt_threshold_factor: 2.0,
+            rtt_threshold_min_increase: Duration::from_millis(20),
+            rtt_window: Duration::from_secs(10),
+            max_burst_duration: Duration::from_millis(100),
+        }
+    }
+}
@@ -0,0 +1,251 @@
+use std::{collections::VecDeque, time::Duration};
+
+use naia_socket_shared::Instant;
+
+use super::congestion_config::CongestionConfig;
+
+const MIN_ADJUST_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Limits the rate at which packets are sent to a remote host, raising the
+/// rate while the connection is healthy and lowering it when packet loss or
+/// rising RTT indicate congestion
+pub struct CongestionController {
+    config: CongestionConfig,
+    send_rate: f32,
+    send_budget: f32,
+    last_refill: Instant,
+    last_adjust: Instant,
+    delivered_packets: u32,
+    dropped_packets: u32,
+    // RTT samples within the window, each lower than those after it, so that
+    // the lowest is at the front
+    rtt_samples: VecDeque<(Instant, f32)>,
+}
+
+impl CongestionController {
+    pub fn new(config: &CongestionConfig) -> Self {
+        let send_rate = config.initial_send_rate as f32;
+        Self {
+            config: config.clone(),
+            send_rate,
+            send_budget: send_rate * config.max_burst_duration.as_secs_f32(),
+            last_refill: Instant::now(),
+            last_adjust: Instant::now(),
+            delivered_packets: 0,
+            dropped_packets: 0,
+            rtt_samples: VecDeque::new(),
+        }
+    }
+
+    /// Returns the current send rate, in bytes per second
+    pub fn send_rate(&self) -> f32 {
+        self.send_rate
+    }
+
+    /// Record that a sent packet has been acknowledged by the remote host
+    pub fn record_delivered(&mut self) {
+        self.delivered_packets += 1;
+    }
+
+    /// Record that a sent packet has been dropped
+    pub fn record_dropped(&mut self) {
+        self.dropped_packets += 1;
+    }
+
+    /// Record that a packet of the given size has been sent, spending it from
+    /// the send budget
+    pub fn record_sent(&mut self, bytes: usize) {
+        self.send_budget -= bytes as f32;
+    }
+
+    /// Returns whether there is budget left to send another packet
+    pub fn can_send(&self) -> bool {
+        self.send_budget > 0.0
+    }
+
+    /// Adds to the send budget according to the time elapsed since the last
+    /// update, and adjusts the send rate if enough time has passed to measure
+    /// the state of the connection
+    pub fn update(&mut self, rtt_millis: f32) {
+        // Refill budget
+        let elapsed = self.last_refill.elapsed().as_secs_f32();
+        self.last_refill = Instant::now();
+        let max_budget = self.send_rate * self.config.max_burst_duration.as_secs_f32();
+        self.send_budget = (self.send_budget + (self.send_rate * elapsed)).min(max_budget);
+
+        // Adjust rate once per round trip
+        let adjust_interval = Duration::from_millis(rtt_millis as u64).max(MIN_ADJUST_INTERVAL);
+        if self.last_adjust.elapsed() >= adjust_interval {
+            self.last_adjust = Instant::now();
+            self.adjust_send_rate(rtt_millis);
+        }
+    }
+
+    fn adjust_send_rate(&mut self, rtt_millis: f32) {
+        // drop samples which have left the window
+        while let Some((sampled_at, _)) = self.rtt_samples.front() {
+            if sampled_at.elapsed() <= self.config.rtt_window {
+                break;
+            }
+            self.rtt_samples.pop_front();
+        }
+        // samples at least as high as this one can never be the lowest again
+        while matches!(self.rtt_samples.back(), Some((_, rtt)) if *rtt >= rtt_millis) {
+            self.rtt_samples.pop_back();
+        }
+        self.rtt_samples.push_back((Instant::now(), rtt_millis));
+        let lowest_rtt = self.rtt_samples.front().unwrap().1;
+
+        let total_packets = self.delivered_packets + self.dropped_packets;
+        let loss = if total_packets > 0 {
+            self.dropped_packets as f32 / total_packets as f32
+        } else {
+            0.0
+        };
+        self.delivered_packets = 0;
+        self.dropped_packets = 0;
+
+        let min_increase_millis = self.config.rtt_threshold_min_increase.as_secs_f32() * 1000.0;
+        let rtt_threshold =
+            (lowest_rtt * self.config.rtt_threshold_factor).max(lowest_rtt + min_increase_millis);
+        let congested = loss > self.config.loss_threshold || rtt_millis > rtt_threshold;
+
+        if congested {
+            self.send_rate *= self.config.send_rate_decrease_factor;
+        } else if total_packets > 0 {
+            self.send_rate += self.config.send_rate_increase as f32;
+        }
+
+        self.send_rate = self.send_rate.clamp(
+            self.config.min_send_rate as f32,
+            self.config.max_send_rate as f32,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::connection::congestion_config::CongestionConfig;
+
+    use super::CongestionController;
+
+    #[test]
+    fn decrease_on_loss() {
+        let config = CongestionConfig::default();
+        let mut controller = CongestionController::new(&config);
+
+        for _ in 0..10 {
+            controller.record_delivered();
+        }
+        for _ in 0..5 {
+            controller.record_dropped();
+        }
+        controller.adjust_send_rate(100.0);
+
+        assert!(controller.send_rate() < config.initial_send_rate as f32);
+    }
+
+    #[test]
+    fn increase_when_healthy() {
+        let config = CongestionConfig::default();
+        let mut controller = CongestionController::new(&config);
+
+        for _ in 0..10 {
+            controller.record_delivered();
+        }
+        controller.adjust_send_rate(100.0);
+
+        assert!(controller.send_rate() > config.initial_send_rate as f32);
+    }
+
+    #[test]
+    fn decrease_on_rising_rtt() {
+        let config = CongestionConfig::default();
+        let mut controller = CongestionController::new(&config);
+
+        controller.record_delivered();
+        controller.adjust_send_rate(50.0);
+        let send_rate = controller.send_rate();
+
+        controller.record_delivered();
+        controller.adjust_send_rate(500.0);
+
+        assert!(controller.send_rate() < send_rate);
+    }
+
+    #[test]
+    fn low_rtt_jitter_is_not_congestion() {
+        let config = CongestionConfig::default();
+        let mut controller = CongestionController::new(&config);
+
+        controller.record_delivered();
+        controller.adjust_send_rate(1.0);
+
+        // several times the lowest RTT, but still only a few milliseconds
+        for rtt in [4.0, 2.0, 7.0, 3.0, 9.0] {
+            let send_rate = controller.send_rate();
+            controller.record_delivered();
+            controller.adjust_send_rate(rtt);
+            assert!(controller.send_rate() > send_rate);
+        }
+
+        // rising well beyond the minimum increase is still congestion
+        let send_rate = controller.send_rate();
+        controller.record_delivered();
+        controller.adjust_send_rate(50.0);
+        assert!(controller.send_rate() < send_rate);
+    }
+
+    #[test]
+    fn lowest_rtt_expires_after_window() {
+        let config = CongestionConfig::default();
+        let mut controller = CongestionController::new(&config);
+
+        controller.record_delivered();
+        controller.adjust_send_rate(20.0);
+
+        // a route change raises the RTT, which at first looks like congestion
+        let send_rate = controller.send_rate();
+        controller.record_delivered();
+        controller.adjust_send_rate(200.0);
+        assert!(controller.send_rate() < send_rate);
+
+        // but no longer once the lower sample has left the window
+        let window_millis = config.rtt_window.as_millis() as u32;
+        for (sampled_at, _) in controller.rtt_samples.iter_mut() {
+            sampled_at.subtract_millis(window_millis + 1);
+        }
+        let send_rate = controller.send_rate();
+        controller.record_delivered();
+        controller.adjust_send_rate(200.0);
+        assert!(controller.send_rate() > send_rate);
+    }
+
+    #[test]
+    fn budget_limits_sending() {
+        let config = CongestionConfig::default();
+        let mut controller = CongestionController::new(&config);
+
+        let mut sent_packets = 0;
+        while controller.can_send() {
+            controller.record_sent(500);
+            sent_packets += 1;
+        }
+
+        // 100ms worth of 64KB/s is ~6400 bytes
+        assert_eq!(sent_packets, 13);
+    }
+
+    #[test]
+    fn rate_is_clamped() {
+        let config = CongestionConfig::default();
+        let mut controller = CongestionController::new(&config);
+
+        for _ in 0..100 {
+            controller.record_dropped();
+            controller.adjust_send_rate(100.0);
+        }
+
+        assert_eq!(controller.send_rate(), config.min_send_rate as f32);
+    }
+}
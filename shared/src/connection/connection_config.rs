@@ -1,4 +1,4 @@
-use crate::PingConfig;
+use crate::{CongestionConfig, PingConfig};
 use std::{default::Default, time::Duration};
 
 /// Contains Config properties which will be used by a Server or Client
@@ -15,6 +15,10 @@ pub struct ConnectionConfig {
     pub bandwidth_measure_duration: Option<Duration>,
     /// Configuration used to monitor the ping & jitter on the network
     pub ping: PingConfig,
+    /// Configuration used to pace outgoing packets according to the measured
+    /// state of the network. Set to None to send packets as fast as possible,
+    /// which is the default.
+    pub congestion: Option<CongestionConfig>,
 }
 
 impl ConnectionConfig {
@@ -24,12 +28,14 @@ impl ConnectionConfig {
         heartbeat_interval: Duration,
         bandwidth_measure_duration: Option<Duration>,
         ping: PingConfig,
+        congestion: Option<CongestionConfig>,
     ) -> Self {
         ConnectionConfig {
             disconnection_timeout_duration,
             heartbeat_interval,
             bandwidth_measure_duration,
             ping,
+            congestion,
         }
     }
 }
@@ -41,6 +47,7 @@ impl Default for ConnectionConfig {
             heartbeat_interval: Duration::from_secs(4),
             bandwidth_measure_duration: None,
             ping: PingConfig::default(),
+            congestion: None,
         }
     }
 }
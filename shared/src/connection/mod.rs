@@ -2,6 +2,8 @@ pub mod ack_manager;
 pub mod bandwidth_monitor;
 pub mod base_connection;
 pub mod compression_config;
+pub mod congestion_config;
+pub mod congestion_controller;
 pub mod connection_config;
 pub mod decoder;
 pub mod encoder;
@@ -9,5 +11,6 @@ pub mod packet_notifiable;
 pub mod packet_type;
 pub mod ping_config;
 pub mod ping_manager;
+pub mod reject_reason;
 pub mod sequence_buffer;
 pub mod standard_header;
@@ -1,7 +1,7 @@
 // An enum representing the different types of packets that can be
 // sent/received
 
-use naia_serde::{BitReader, BitWrite, SerdeErr, UnsignedInteger};
+use naia_serde::{BitReader, BitWrite, SerdeErr, SerdeErrReason, UnsignedInteger};
 
 #[derive(Copy, Debug, Clone, PartialEq)]
 pub enum PacketType {
@@ -26,10 +26,16 @@ pub enum PacketType {
     Pong,
     // Used to request a graceful Client disconnect from the Server
     Disconnect,
+    // Sent by the Server in response to a connect request it has refused,
+    // along with the reason why
+    ServerRejectResponse,
 }
 
 // Most packets should be Data, so lets compress this a bit more.
 // Could do this with another enum, but code would get messy.
+// Non-Data packet types take 4 bits since ServerRejectResponse was added,
+// which 3 bits could no longer hold. This breaks wire compatibility with
+// earlier builds, see CHANGELOG.md
 impl crate::serde::Serde for PacketType {
     fn ser(&self, writer: &mut dyn BitWrite) {
         let is_data = *self == PacketType::Data;
@@ -49,18 +55,19 @@ impl crate::serde::Serde for PacketType {
             PacketType::Ping => 5,
             PacketType::Pong => 6,
             PacketType::Disconnect => 7,
+            PacketType::ServerRejectResponse => 8,
         };
 
-        UnsignedInteger::<3>::new(index).ser(writer);
+        UnsignedInteger::<4>::new(index).ser(writer);
     }
 
     fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
-        let is_data = bool::de(reader).unwrap();
+        let is_data = bool::de(reader)?;
         if is_data {
             return Ok(PacketType::Data);
         }
 
-        let index = UnsignedInteger::<3>::de(reader).unwrap().get();
+        let index = UnsignedInteger::<4>::de(reader)?.get();
         return match index {
             0 => Ok(PacketType::Heartbeat),
             1 => Ok(PacketType::ClientChallengeRequest),
@@ -70,7 +77,12 @@ impl crate::serde::Serde for PacketType {
             5 => Ok(PacketType::Ping),
             6 => Ok(PacketType::Pong),
             7 => Ok(PacketType::Disconnect),
-            _ => panic!("shouldn't happen, caught above"),
+            8 => Ok(PacketType::ServerRejectResponse),
+            _ => Err(SerdeErr::new(
+                reader.bit_offset(),
+                "PacketType",
+                SerdeErrReason::InvalidValue,
+            )),
         };
     }
 }
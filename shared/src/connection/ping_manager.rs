@@ -1,6 +1,6 @@
 use std::collections::VecDeque;
 
-use naia_serde::{BitReader, BitWriter, Serde};
+use naia_serde::{BitReader, BitWriter, Serde, SerdeErr};
 
 use naia_socket_shared::Instant;
 
@@ -48,8 +48,8 @@ impl PingManager {
     }
 
     /// Process an incoming pong payload
-    pub fn process_pong(&mut self, reader: &mut BitReader) {
-        let ping_index = PingIndex::de(reader).unwrap();
+    pub fn process_pong(&mut self, reader: &mut BitReader) -> Result<(), SerdeErr> {
+        let ping_index = PingIndex::de(reader)?;
 
         match self.sent_pings.remove(ping_index) {
             None => {}
@@ -58,6 +58,8 @@ impl PingManager {
                 self.process_new_rtt(rtt_millis);
             }
         }
+
+        Ok(())
     }
 
     fn process_new_rtt(&mut self, rtt_millis: f32) {
@@ -104,6 +106,11 @@ impl SentPings {
     }
 
     pub fn remove(&mut self, ping_index: PingIndex) -> Option<Instant> {
+        // a pong can arrive before any ping was sent, if it's forged
+        if self.buffer.is_empty() {
+            return None;
+        }
+
         let mut vec_index = self.buffer.len();
         let mut found = false;
 
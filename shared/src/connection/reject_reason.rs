@@ -0,0 +1,12 @@
+use naia_serde::derive_serde;
+
+use crate::serde;
+
+/// The reason the Server gives for refusing a Client's connect request
+#[derive(Copy, Debug, Eq)]
+#[derive_serde]
+pub enum RejectReason {
+    /// The Client was built with a Protocol or Channel layout which does not
+    /// match the Server's
+    ProtocolMismatch,
+}
@@ -1,7 +1,32 @@
 /// The maximum of bytes that can be used for the payload of a given packet.
 /// (See #38 of http://ithare.com/64-network-dos-and-donts-for-game-engines-part-v-udp/)
 pub const MTU_SIZE_BYTES: u16 = 508;
-pub const MTU_SIZE_BITS: u16 = MTU_SIZE_BYTES * 8;
+pub const MTU_SIZE_BITS: u32 = MTU_SIZE_BYTES as u32 * 8;
+
+/// How many times the average jitter a Client holds incoming packets in its
+/// jitter buffer for, so that late packets still arrive in time
+pub const JITTER_BUFFER_FACTOR: f32 = 4.0;
 
 // Number of messages to keep in tick buffer
 pub const MESSAGE_HISTORY_SIZE: u16 = 64;
+
+/// The room kept in a packet for its headers. Unreliable Messages can't be
+/// split into fragments, so those larger than what is left of the MTU are
+/// dropped
+pub const PACKET_HEADER_ROOM_BYTES: u16 = 32;
+pub const PACKET_HEADER_ROOM_BITS: u32 = PACKET_HEADER_ROOM_BYTES as u32 * 8;
+
+/// Messages on reliable Channels larger than this will be split into
+/// fragments, each of which is sent as a separate reliable message
+pub const FRAGMENTATION_LIMIT_BYTES: u16 = 400;
+pub const FRAGMENTATION_LIMIT_BITS: u32 = FRAGMENTATION_LIMIT_BYTES as u32 * 8;
+
+/// The maximum number of Fragments a single Message can be split into.
+/// Messages which would need more are dropped by the sender, and Fragments
+/// claiming more are rejected by the receiver
+pub const FRAGMENT_COUNT_LIMIT: u16 = 1024;
+
+/// The maximum number of fragmented Messages in flight per Channel. The
+/// sender holds back further Messages until earlier ones are delivered, and
+/// the receiver rejects Fragments which would start another partial Message
+pub const FRAGMENT_REASSEMBLY_LIMIT: usize = 32;
@@ -0,0 +1,72 @@
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// A FNV-1a hasher used to fingerprint the layout of a Protocol & its
+/// Channels. Unlike `std`'s `DefaultHasher`, the output is guaranteed to be
+/// stable between builds, so that a Client & Server compiled separately
+/// produce the same fingerprint for the same layout.
+pub struct FingerprintHasher {
+    hash: u64,
+}
+
+impl Default for FingerprintHasher {
+    fn default() -> Self {
+        Self {
+            hash: FNV_OFFSET_BASIS,
+        }
+    }
+}
+
+impl FingerprintHasher {
+    /// Feed a slice of bytes into the hasher
+    pub fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.hash ^= *byte as u64;
+            self.hash = self.hash.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    /// Feed a string into the hasher, delimited so that consecutive strings
+    /// can not run into each other
+    pub fn write_str(&mut self, value: &str) {
+        self.write_u64(value.len() as u64);
+        self.write(value.as_bytes());
+    }
+
+    /// Feed a u64 into the hasher
+    pub fn write_u64(&mut self, value: u64) {
+        self.write(&value.to_le_bytes());
+    }
+
+    /// Get the resulting hash
+    pub fn finish(&self) -> u64 {
+        self.hash
+    }
+}
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use super::FingerprintHasher;
+
+    #[test]
+    fn stable_output() {
+        let mut hasher = FingerprintHasher::default();
+        hasher.write(b"a");
+        assert_eq!(hasher.finish(), 0xaf63_dc4c_8601_ec8c);
+    }
+
+    #[test]
+    fn delimited_strings() {
+        let mut hasher_a = FingerprintHasher::default();
+        hasher_a.write_str("ab");
+        hasher_a.write_str("c");
+
+        let mut hasher_b = FingerprintHasher::default();
+        hasher_b.write_str("a");
+        hasher_b.write_str("bc");
+
+        assert_ne!(hasher_a.finish(), hasher_b.finish());
+    }
+}
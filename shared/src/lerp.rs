@@ -0,0 +1,20 @@
+/// A value which can be blended with another value of the same type, used to
+/// smooth out replicated state which is only received once per Tick
+pub trait Lerp {
+    /// Returns the value a `fraction` of the way from self to `other`, where
+    /// 0.0 is self and 1.0 is `other`. Fractions above 1.0 continue past
+    /// `other`, extrapolating.
+    fn lerp(&self, other: &Self, fraction: f32) -> Self;
+}
+
+impl Lerp for f32 {
+    fn lerp(&self, other: &Self, fraction: f32) -> Self {
+        self + ((other - self) * fraction)
+    }
+}
+
+impl Lerp for f64 {
+    fn lerp(&self, other: &Self, fraction: f32) -> Self {
+        self + ((other - self) * f64::from(fraction))
+    }
+}
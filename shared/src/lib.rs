@@ -32,7 +32,9 @@ mod protocol;
 
 mod bigmap;
 mod constants;
+mod fingerprint;
 mod key_generator;
+mod lerp;
 mod shared_config;
 mod types;
 mod world_type;
@@ -44,6 +46,8 @@ pub use connection::{
     bandwidth_monitor::BandwidthMonitor,
     base_connection::BaseConnection,
     compression_config::{CompressionConfig, CompressionMode},
+    congestion_config::CongestionConfig,
+    congestion_controller::CongestionController,
     connection_config::ConnectionConfig,
     decoder::Decoder,
     encoder::Encoder,
@@ -51,6 +55,7 @@ pub use connection::{
     packet_type::PacketType,
     ping_config::PingConfig,
     ping_manager::{PingIndex, PingManager},
+    reject_reason::RejectReason,
     standard_header::StandardHeader,
 };
 pub use messages::{
@@ -58,7 +63,9 @@ pub use messages::{
         Channel, ChannelConfig, ChannelDirection, ChannelIndex, ChannelMode, DefaultChannels,
         ReliableSettings, TickBufferSettings,
     },
+    fragment::{Fragment, FragmentId, FragmentReceiver, FragmentSendQueue, FragmentWriter},
     message_channel::{ChannelReader, ChannelReceiver, ChannelSender, ChannelWriter},
+    message_container::MessageContainer,
     message_list_header,
     message_manager::MessageManager,
     ordered_reliable_receiver::OrderedReliableReceiver,
@@ -69,6 +76,7 @@ pub use protocol::{
     component_update::ComponentUpdate,
     diff_mask::DiffMask,
     entity_action::EntityAction,
+    entity_action_event::EntityActionEvent,
     entity_action_receiver::EntityActionReceiver,
     entity_action_type::EntityActionType,
     entity_handle::EntityHandle,
@@ -76,9 +84,14 @@ pub use protocol::{
         EntityConverter, EntityHandleConverter, EntityProperty, FakeEntityConverter,
         NetEntityConverter, NetEntityHandleConverter,
     },
+    nested_property::{NestedProperty, NestedReplicate},
     net_entity::NetEntity,
     property::Property,
+    property_baselines::{NoBaselines, PropertyBaselines, MAX_BASELINE_DISTANCE},
+    property_codec::{PropertyCodec, SerdeCodec},
+    property_map::PropertyMap,
     property_mutate::{PropertyMutate, PropertyMutator},
+    property_vec::PropertyVec,
     protocol_io::ProtocolIo,
     protocolize::{ProtocolInserter, ProtocolKindType, Protocolize},
     replica_ref::{
@@ -90,8 +103,14 @@ pub use protocol::{
 };
 
 pub use bigmap::{BigMap, BigMapKey};
-pub use constants::{MESSAGE_HISTORY_SIZE, MTU_SIZE_BITS, MTU_SIZE_BYTES};
+pub use constants::{
+    FRAGMENTATION_LIMIT_BITS, FRAGMENTATION_LIMIT_BYTES, FRAGMENT_COUNT_LIMIT,
+    FRAGMENT_REASSEMBLY_LIMIT, JITTER_BUFFER_FACTOR, MESSAGE_HISTORY_SIZE, MTU_SIZE_BITS,
+    MTU_SIZE_BYTES, PACKET_HEADER_ROOM_BITS, PACKET_HEADER_ROOM_BYTES,
+};
+pub use fingerprint::FingerprintHasher;
 pub use key_generator::KeyGenerator;
+pub use lerp::Lerp;
 pub use shared_config::SharedConfig;
 pub use types::{HostType, MessageId, PacketIndex, ShortMessageId, Tick};
 pub use world_type::{WorldMutType, WorldRefType};
@@ -1,6 +1,11 @@
 use std::{collections::HashMap, hash::Hash};
 
-use crate::{derive_serde, serde, serde::Serde};
+use crate::{
+    derive_serde,
+    fingerprint::FingerprintHasher,
+    serde,
+    serde::{BitWriter, Serde},
+};
 
 // ChannelConfig
 #[derive(Clone)]
@@ -35,6 +40,31 @@ impl<C: ChannelIndex> ChannelConfig<C> {
     pub fn channels(&self) -> &HashMap<C, Channel<C>> {
         &self.channels
     }
+
+    /// Gets a stable hash of every registered Channel's index, mode &
+    /// direction, independent of the order they were registered in
+    pub fn fingerprint(&self) -> u64 {
+        let mut channels: Vec<(Box<[u8]>, &Channel<C>)> = self
+            .channels
+            .iter()
+            .map(|(index, channel)| {
+                let mut writer = BitWriter::default();
+                index.ser(&mut writer);
+                (writer.to_bytes(), channel)
+            })
+            .collect();
+        channels.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let mut hasher = FingerprintHasher::default();
+        for (index_bytes, channel) in channels {
+            hasher.write(&index_bytes);
+            hasher.write(&[
+                channel.mode.discriminant(),
+                channel.direction.discriminant(),
+            ]);
+        }
+        hasher.finish()
+    }
 }
 
 // ChannelIndex
@@ -130,6 +160,15 @@ impl ChannelMode {
     pub fn tick_buffered(&self) -> bool {
         matches!(self, ChannelMode::TickBuffered(_))
     }
+
+    fn discriminant(&self) -> u8 {
+        match self {
+            ChannelMode::UnorderedUnreliable => 0,
+            ChannelMode::UnorderedReliable(_) => 1,
+            ChannelMode::OrderedReliable(_) => 2,
+            ChannelMode::TickBuffered(_) => 3,
+        }
+    }
 }
 
 // ChannelDirection
@@ -140,6 +179,16 @@ pub enum ChannelDirection {
     Bidirectional,
 }
 
+impl ChannelDirection {
+    fn discriminant(&self) -> u8 {
+        match self {
+            ChannelDirection::ClientToServer => 0,
+            ChannelDirection::ServerToClient => 1,
+            ChannelDirection::Bidirectional => 2,
+        }
+    }
+}
+
 // Default Channels
 #[derive(Eq, Hash)]
 #[derive_serde]
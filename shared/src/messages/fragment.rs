@@ -0,0 +1,476 @@
+use std::collections::{HashMap, VecDeque};
+
+use naia_serde::{
+    BitReader, BitWrite, BitWriter, Serde, SerdeErr, SerdeErrReason, UnsignedVariableInteger,
+};
+
+use crate::constants::{
+    FRAGMENTATION_LIMIT_BYTES, FRAGMENT_COUNT_LIMIT, FRAGMENT_REASSEMBLY_LIMIT,
+};
+
+use super::{
+    message_channel::{ChannelReader, ChannelWriter},
+    message_container::MessageContainer,
+};
+
+pub type FragmentId = u16;
+
+// Fragment
+
+/// A piece of a serialized Message which was too large to fit into a single
+/// packet
+#[derive(Clone, PartialEq)]
+pub struct Fragment {
+    id: FragmentId,
+    index: u16,
+    total: u16,
+    bytes: Vec<u8>,
+}
+
+impl Serde for Fragment {
+    fn ser(&self, writer: &mut dyn BitWrite) {
+        self.id.ser(writer);
+        UnsignedVariableInteger::<5>::new(self.index).ser(writer);
+        // total is always >= 1
+        UnsignedVariableInteger::<5>::new(self.total - 1).ser(writer);
+        self.bytes.ser(writer);
+    }
+
+    fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+        let id = FragmentId::de(reader)?;
+        let index = UnsignedVariableInteger::<5>::de(reader)?.get();
+        // total is always >= 1
+        let total = UnsignedVariableInteger::<5>::de(reader)?.get() + 1;
+        if total > FRAGMENT_COUNT_LIMIT as i128 || index < 0 || index >= total {
+            return Err(SerdeErr::new(
+                reader.bit_offset(),
+                "Fragment",
+                SerdeErrReason::InvalidValue,
+            ));
+        }
+        let index = index as u16;
+        let total = total as u16;
+        let bytes = Vec::<u8>::de(reader)?;
+        Ok(Self {
+            id,
+            index,
+            total,
+            bytes,
+        })
+    }
+}
+
+// FragmentWriter
+
+/// Splits serialized Messages into Fragments
+pub struct FragmentWriter {
+    next_fragment_id: FragmentId,
+}
+
+impl FragmentWriter {
+    pub fn new() -> Self {
+        Self {
+            next_fragment_id: 0,
+        }
+    }
+
+    /// Splits the Message into Fragments, or returns None if it would take
+    /// more than `FRAGMENT_COUNT_LIMIT` Fragments to send
+    pub fn fragment_message<P>(
+        &mut self,
+        channel_writer: &dyn ChannelWriter<P>,
+        message: &P,
+    ) -> Option<Vec<Fragment>> {
+        let mut bit_writer = BitWriter::default();
+        channel_writer.write(&mut bit_writer, message);
+        let bytes = bit_writer.to_bytes();
+
+        let chunks = bytes.chunks(FRAGMENTATION_LIMIT_BYTES as usize);
+        if chunks.len() > FRAGMENT_COUNT_LIMIT as usize {
+            return None;
+        }
+        let total = chunks.len() as u16;
+
+        let fragment_id = self.next_fragment_id;
+        self.next_fragment_id = self.next_fragment_id.wrapping_add(1);
+
+        Some(
+            chunks
+                .enumerate()
+                .map(|(index, chunk)| Fragment {
+                    id: fragment_id,
+                    index: index as u16,
+                    total,
+                    bytes: chunk.to_vec(),
+                })
+                .collect(),
+        )
+    }
+}
+
+impl Default for FragmentWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// FragmentSendQueue
+
+/// Holds back the Messages sent on a reliable Channel while
+/// `FRAGMENT_REASSEMBLY_LIMIT` fragmented Messages are undelivered, so that
+/// the receiver never has more partial Messages than it can reassemble.
+/// Whole Messages queue up behind held back ones, to keep them in order.
+pub struct FragmentSendQueue<P> {
+    // number of undelivered Fragments, per fragmented Message in flight
+    undelivered_fragments: HashMap<FragmentId, u16>,
+    // the containers of each Message not yet sent, oldest first
+    queued_messages: VecDeque<Vec<MessageContainer<P>>>,
+}
+
+impl<P> FragmentSendQueue<P> {
+    pub fn new() -> Self {
+        Self {
+            undelivered_fragments: HashMap::new(),
+            queued_messages: VecDeque::new(),
+        }
+    }
+
+    /// Queues the containers of a Message, returning those which can be
+    /// sent now
+    pub fn send_message(
+        &mut self,
+        containers: Vec<MessageContainer<P>>,
+    ) -> Vec<MessageContainer<P>> {
+        self.queued_messages.push_back(containers);
+        self.release_messages()
+    }
+
+    /// Records that the container has been delivered, returning queued
+    /// containers which can now be sent
+    pub fn notify_delivered(
+        &mut self,
+        container: &MessageContainer<P>,
+    ) -> Vec<MessageContainer<P>> {
+        if let MessageContainer::Fragment(fragment) = container {
+            if let Some(undelivered) = self.undelivered_fragments.get_mut(&fragment.id) {
+                *undelivered -= 1;
+                if *undelivered == 0 {
+                    self.undelivered_fragments.remove(&fragment.id);
+                }
+            }
+        }
+        self.release_messages()
+    }
+
+    fn release_messages(&mut self) -> Vec<MessageContainer<P>> {
+        let mut output = Vec::new();
+        while let Some(containers) = self.queued_messages.front() {
+            if let Some(MessageContainer::Fragment(fragment)) = containers.first() {
+                if self.undelivered_fragments.len() >= FRAGMENT_REASSEMBLY_LIMIT {
+                    break;
+                }
+                self.undelivered_fragments
+                    .insert(fragment.id, containers.len() as u16);
+            }
+            output.extend(self.queued_messages.pop_front().unwrap());
+        }
+        output
+    }
+}
+
+impl<P> Default for FragmentSendQueue<P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// FragmentReceiver
+
+/// Collects incoming Fragments and reassembles them into Messages once all
+/// of their parts have arrived. At most `FRAGMENT_REASSEMBLY_LIMIT` Messages
+/// are reassembled at once, which a `FragmentSendQueue` never exceeds.
+pub struct FragmentReceiver {
+    #[allow(clippy::type_complexity)]
+    incoming_fragments: HashMap<FragmentId, (u16, Vec<Option<Vec<u8>>>)>,
+}
+
+impl FragmentReceiver {
+    pub fn new() -> Self {
+        Self {
+            incoming_fragments: HashMap::new(),
+        }
+    }
+
+    /// Buffers the Fragment, returning the reassembled Message if this was
+    /// the last missing part. Returns an error if the Fragment disagrees
+    /// with those already received for the same Message, dropping them, or
+    /// would start a partial Message past `FRAGMENT_REASSEMBLY_LIMIT`. The
+    /// sender keeps to that limit, so the error is never raised for a
+    /// well-behaved sender, whose reliable Messages would otherwise be lost.
+    pub fn receive_fragment<P>(
+        &mut self,
+        channel_reader: &dyn ChannelReader<P>,
+        fragment: Fragment,
+    ) -> Result<Option<P>, SerdeErr> {
+        let Fragment {
+            id,
+            index,
+            total,
+            bytes,
+        } = fragment;
+
+        if !self.incoming_fragments.contains_key(&id) {
+            if self.incoming_fragments.len() >= FRAGMENT_REASSEMBLY_LIMIT {
+                return Err(SerdeErr::new(0, "Fragment", SerdeErrReason::InvalidValue));
+            }
+            self.incoming_fragments
+                .insert(id, (0, vec![None; total as usize]));
+        }
+
+        let (received_count, parts) = self.incoming_fragments.get_mut(&id).unwrap();
+
+        if parts.len() != total as usize {
+            // the Message can't be reassembled from Fragments which disagree
+            self.incoming_fragments.remove(&id);
+            return Err(SerdeErr::new(0, "Fragment", SerdeErrReason::InvalidValue));
+        }
+
+        let part = parts.get_mut(index as usize).unwrap();
+        if part.is_some() {
+            // already received this fragment
+            return Ok(None);
+        }
+        *part = Some(bytes);
+        *received_count += 1;
+
+        if *received_count < total {
+            return Ok(None);
+        }
+
+        let (_, parts) = self.incoming_fragments.remove(&id).unwrap();
+        let mut message_bytes = Vec::new();
+        for part in parts.into_iter().flatten() {
+            message_bytes.extend(part);
+        }
+
+        let mut bit_reader = BitReader::new(&message_bytes);
+        channel_reader.read(&mut bit_reader).map(Some)
+    }
+}
+
+impl Default for FragmentReceiver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use naia_serde::{BitReader, BitWrite, BitWriter, Serde, SerdeErr, UnsignedVariableInteger};
+
+    use crate::{
+        constants::{FRAGMENTATION_LIMIT_BYTES, FRAGMENT_COUNT_LIMIT, FRAGMENT_REASSEMBLY_LIMIT},
+        messages::{
+            message_channel::{ChannelReader, ChannelWriter},
+            message_container::MessageContainer,
+        },
+    };
+
+    use super::{Fragment, FragmentReceiver, FragmentSendQueue, FragmentWriter};
+
+    struct StringIo;
+
+    impl ChannelWriter<String> for StringIo {
+        fn write(&self, writer: &mut dyn BitWrite, data: &String) {
+            data.ser(writer);
+        }
+    }
+
+    impl ChannelReader<String> for StringIo {
+        fn read(&self, reader: &mut BitReader) -> Result<String, SerdeErr> {
+            String::de(reader)
+        }
+    }
+
+    #[test]
+    fn fragment_and_reassemble() {
+        let message: String = (0..3000).map(|i| (b'a' + (i % 26) as u8) as char).collect();
+
+        let mut writer = FragmentWriter::new();
+        let mut fragments = writer.fragment_message(&StringIo, &message).unwrap();
+        assert!(fragments.len() > 1);
+
+        // deliver out of order
+        fragments.reverse();
+        let last_fragment = fragments.pop().unwrap();
+
+        let mut receiver = FragmentReceiver::new();
+        for fragment in fragments {
+            assert!(receiver
+                .receive_fragment(&StringIo, fragment)
+                .unwrap()
+                .is_none());
+        }
+        let output = receiver.receive_fragment(&StringIo, last_fragment).unwrap();
+
+        assert_eq!(Some(message), output);
+    }
+
+    #[test]
+    fn duplicate_fragments_are_ignored() {
+        let message: String = (0..1000).map(|_| 'z').collect();
+
+        let mut writer = FragmentWriter::new();
+        let fragments = writer.fragment_message(&StringIo, &message).unwrap();
+        assert_eq!(fragments.len(), 3);
+
+        let mut receiver = FragmentReceiver::new();
+        assert!(receiver
+            .receive_fragment(&StringIo, fragments[0].clone())
+            .unwrap()
+            .is_none());
+        assert!(receiver
+            .receive_fragment(&StringIo, fragments[0].clone())
+            .unwrap()
+            .is_none());
+        assert!(receiver
+            .receive_fragment(&StringIo, fragments[1].clone())
+            .unwrap()
+            .is_none());
+        let output = receiver
+            .receive_fragment(&StringIo, fragments[2].clone())
+            .unwrap();
+
+        assert_eq!(Some(message), output);
+    }
+
+    #[test]
+    fn crafted_fragment_headers_are_rejected() {
+        // total - 1 at the maximum a varint can hold
+        let mut writer = BitWriter::default();
+        0u16.ser(&mut writer);
+        UnsignedVariableInteger::<5>::new(0).ser(&mut writer);
+        UnsignedVariableInteger::<5>::new(u64::MAX).ser(&mut writer);
+        Vec::<u8>::new().ser(&mut writer);
+        let (length, buffer) = writer.flush();
+        assert!(Fragment::de(&mut BitReader::new(&buffer[..length])).is_err());
+
+        // index past the end
+        let mut writer = BitWriter::default();
+        0u16.ser(&mut writer);
+        UnsignedVariableInteger::<5>::new(3).ser(&mut writer);
+        UnsignedVariableInteger::<5>::new(2).ser(&mut writer);
+        Vec::<u8>::new().ser(&mut writer);
+        let (length, buffer) = writer.flush();
+        assert!(Fragment::de(&mut BitReader::new(&buffer[..length])).is_err());
+    }
+
+    #[test]
+    fn mismatched_fragments_are_rejected() {
+        let mut receiver = FragmentReceiver::new();
+        let fragment = |index, total| Fragment {
+            id: 7,
+            index,
+            total,
+            bytes: vec![0],
+        };
+
+        assert!(receiver
+            .receive_fragment(&StringIo, fragment(0, 3))
+            .unwrap()
+            .is_none());
+        assert!(receiver
+            .receive_fragment(&StringIo, fragment(1, 4))
+            .is_err());
+
+        // the partial Message is dropped, so the id can be reused
+        assert!(receiver
+            .receive_fragment(&StringIo, fragment(1, 4))
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn partial_messages_past_the_limit_are_rejected() {
+        let message: String = (0..1000).map(|_| 'z').collect();
+
+        let mut writer = FragmentWriter::new();
+        let first_fragments = writer.fragment_message(&StringIo, &message).unwrap();
+
+        let mut receiver = FragmentReceiver::new();
+        assert!(receiver
+            .receive_fragment(&StringIo, first_fragments[0].clone())
+            .unwrap()
+            .is_none());
+
+        // only ever send the first Fragment of many other Messages
+        for _ in 1..FRAGMENT_REASSEMBLY_LIMIT {
+            let fragments = writer.fragment_message(&StringIo, &message).unwrap();
+            assert!(receiver
+                .receive_fragment(&StringIo, fragments[0].clone())
+                .unwrap()
+                .is_none());
+        }
+        let fragments = writer.fragment_message(&StringIo, &message).unwrap();
+        assert!(receiver
+            .receive_fragment(&StringIo, fragments[0].clone())
+            .is_err());
+
+        // the partial Messages already started can still be completed
+        assert!(receiver
+            .receive_fragment(&StringIo, first_fragments[1].clone())
+            .unwrap()
+            .is_none());
+        let output = receiver
+            .receive_fragment(&StringIo, first_fragments[2].clone())
+            .unwrap();
+        assert_eq!(Some(message), output);
+    }
+
+    #[test]
+    fn messages_past_the_limit_are_held_back_until_delivered() {
+        let message: String = (0..1000).map(|_| 'z').collect();
+
+        let mut writer = FragmentWriter::new();
+        let mut queue = FragmentSendQueue::<String>::new();
+        let fragmented = |writer: &mut FragmentWriter| {
+            writer
+                .fragment_message(&StringIo, &message)
+                .unwrap()
+                .into_iter()
+                .map(MessageContainer::Fragment)
+                .collect::<Vec<_>>()
+        };
+
+        let first_containers = fragmented(&mut writer);
+        assert_eq!(queue.send_message(first_containers.clone()).len(), 3);
+        for _ in 1..FRAGMENT_REASSEMBLY_LIMIT {
+            assert_eq!(queue.send_message(fragmented(&mut writer)).len(), 3);
+        }
+
+        // the window is full, so the next Message waits, & whole Messages
+        // stay behind it
+        assert!(queue.send_message(fragmented(&mut writer)).is_empty());
+        assert!(queue
+            .send_message(vec![MessageContainer::Message("a".to_string())])
+            .is_empty());
+
+        assert!(queue.notify_delivered(&first_containers[0]).is_empty());
+        assert!(queue.notify_delivered(&first_containers[1]).is_empty());
+        let released = queue.notify_delivered(&first_containers[2]);
+        assert_eq!(released.len(), 4);
+        assert!(matches!(released[3], MessageContainer::Message(_)));
+    }
+
+    #[test]
+    fn messages_needing_too_many_fragments_are_not_split() {
+        let length = FRAGMENTATION_LIMIT_BYTES as usize * FRAGMENT_COUNT_LIMIT as usize;
+        let message: String = (0..length).map(|_| 'z').collect();
+
+        let mut writer = FragmentWriter::new();
+
+        assert!(writer.fragment_message(&StringIo, &message).is_none());
+    }
+}
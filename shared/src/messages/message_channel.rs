@@ -1,4 +1,4 @@
-use naia_serde::{BitReader, BitWrite, BitWriter};
+use naia_serde::{BitReader, BitWrite, BitWriter, SerdeErr};
 use naia_socket_shared::Instant;
 
 use crate::types::MessageId;
@@ -12,11 +12,16 @@ pub trait ChannelSender<P>: Send + Sync {
         channel_writer: &dyn ChannelWriter<P>,
         bit_writer: &mut BitWriter,
     ) -> Option<Vec<MessageId>>;
-    fn notify_message_delivered(&mut self, message_id: &MessageId);
+    /// Returns the Message if this is the first time it has been delivered
+    fn notify_message_delivered(&mut self, message_id: &MessageId) -> Option<P>;
 }
 
 pub trait ChannelReceiver<P>: Send + Sync {
-    fn read_messages(&mut self, channel_reader: &dyn ChannelReader<P>, bit_reader: &mut BitReader);
+    fn read_messages(
+        &mut self,
+        channel_reader: &dyn ChannelReader<P>,
+        bit_reader: &mut BitReader,
+    ) -> Result<(), SerdeErr>;
     fn receive_messages(&mut self) -> Vec<P>;
 }
 
@@ -25,5 +30,5 @@ pub trait ChannelWriter<T> {
 }
 
 pub trait ChannelReader<T> {
-    fn read(&self, reader: &mut BitReader) -> T;
+    fn read(&self, reader: &mut BitReader) -> Result<T, SerdeErr>;
 }
@@ -0,0 +1,66 @@
+use naia_serde::{BitReader, BitWrite, Serde, SerdeErr};
+
+use super::{
+    fragment::Fragment,
+    message_channel::{ChannelReader, ChannelWriter},
+};
+
+/// What is actually sent over a Channel: either a whole Message, or a
+/// Fragment of a Message that was too large to fit into a single packet
+#[derive(Clone)]
+pub enum MessageContainer<P> {
+    Message(P),
+    Fragment(Fragment),
+}
+
+// MessageContainerWriter
+
+pub struct MessageContainerWriter<'w, P> {
+    channel_writer: &'w dyn ChannelWriter<P>,
+}
+
+impl<'w, P> MessageContainerWriter<'w, P> {
+    pub fn new(channel_writer: &'w dyn ChannelWriter<P>) -> Self {
+        Self { channel_writer }
+    }
+}
+
+impl<'w, P> ChannelWriter<MessageContainer<P>> for MessageContainerWriter<'w, P> {
+    fn write(&self, writer: &mut dyn BitWrite, data: &MessageContainer<P>) {
+        match data {
+            MessageContainer::Message(message) => {
+                // write that this is a whole message
+                false.ser(writer);
+                self.channel_writer.write(writer, message);
+            }
+            MessageContainer::Fragment(fragment) => {
+                // write that this is a fragment
+                true.ser(writer);
+                fragment.ser(writer);
+            }
+        }
+    }
+}
+
+// MessageContainerReader
+
+pub struct MessageContainerReader<'r, P> {
+    channel_reader: &'r dyn ChannelReader<P>,
+}
+
+impl<'r, P> MessageContainerReader<'r, P> {
+    pub fn new(channel_reader: &'r dyn ChannelReader<P>) -> Self {
+        Self { channel_reader }
+    }
+}
+
+impl<'r, P> ChannelReader<MessageContainer<P>> for MessageContainerReader<'r, P> {
+    fn read(&self, reader: &mut BitReader) -> Result<MessageContainer<P>, SerdeErr> {
+        let is_fragment = bool::de(reader)?;
+        if is_fragment {
+            Ok(MessageContainer::Fragment(Fragment::de(reader)?))
+        } else {
+            Ok(MessageContainer::Message(self.channel_reader.read(reader)?))
+        }
+    }
+}
@@ -1,17 +1,27 @@
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, HashSet},
+    mem,
+};
 
-use naia_serde::{BitReader, BitWriter, Serde, UnsignedVariableInteger};
+use naia_serde::{
+    BitCounter, BitReader, BitWrite, BitWriter, Serde, SerdeErr, UnsignedVariableInteger,
+};
 use naia_socket_shared::Instant;
 
+use log::warn;
+
 use crate::{
     connection::packet_notifiable::PacketNotifiable,
+    constants::{FRAGMENTATION_LIMIT_BITS, MTU_SIZE_BITS, PACKET_HEADER_ROOM_BITS},
     protocol::protocolize::Protocolize,
     types::{HostType, MessageId, PacketIndex},
 };
 
 use super::{
     channel_config::{ChannelConfig, ChannelIndex, ChannelMode},
+    fragment::{FragmentReceiver, FragmentSendQueue, FragmentWriter},
     message_channel::{ChannelReader, ChannelReceiver, ChannelSender, ChannelWriter},
+    message_container::{MessageContainer, MessageContainerReader, MessageContainerWriter},
     ordered_reliable_receiver::OrderedReliableReceiver,
     reliable_sender::ReliableSender,
     unordered_reliable_receiver::UnorderedReliableReceiver,
@@ -22,9 +32,14 @@ use super::{
 /// Handles incoming/outgoing messages, tracks the delivery status of Messages
 /// so that guaranteed Messages can be re-transmitted to the remote host
 pub struct MessageManager<P: Protocolize, C: ChannelIndex> {
-    channel_senders: HashMap<C, Box<dyn ChannelSender<P>>>,
-    channel_receivers: HashMap<C, Box<dyn ChannelReceiver<P>>>,
+    channel_senders: HashMap<C, Box<dyn ChannelSender<MessageContainer<P>>>>,
+    channel_receivers: HashMap<C, Box<dyn ChannelReceiver<MessageContainer<P>>>>,
     packet_to_message_map: HashMap<PacketIndex, Vec<(C, Vec<MessageId>)>>,
+    reliable_senders: HashSet<C>,
+    fragment_writer: FragmentWriter,
+    fragment_send_queues: HashMap<C, FragmentSendQueue<P>>,
+    fragment_receivers: HashMap<C, FragmentReceiver>,
+    received_messages: Vec<(C, P)>,
 }
 
 impl<P: Protocolize, C: ChannelIndex> MessageManager<P, C> {
@@ -33,7 +48,9 @@ impl<P: Protocolize, C: ChannelIndex> MessageManager<P, C> {
         // initialize all reliable channels
 
         // initialize senders
-        let mut channel_senders = HashMap::<C, Box<dyn ChannelSender<P>>>::new();
+        let mut channel_senders = HashMap::<C, Box<dyn ChannelSender<MessageContainer<P>>>>::new();
+        let mut reliable_senders = HashSet::<C>::new();
+        let mut fragment_send_queues = HashMap::<C, FragmentSendQueue<P>>::new();
         for (channel_index, channel) in channel_config.channels() {
             match &host_type {
                 HostType::Server => {
@@ -60,19 +77,25 @@ impl<P: Protocolize, C: ChannelIndex> MessageManager<P, C> {
                         channel_index.clone(),
                         Box::new(ReliableSender::new(settings.rtt_resend_factor)),
                     );
+                    reliable_senders.insert(channel_index.clone());
+                    fragment_send_queues.insert(channel_index.clone(), FragmentSendQueue::new());
                 }
                 ChannelMode::OrderedReliable(settings) => {
                     channel_senders.insert(
                         channel_index.clone(),
                         Box::new(ReliableSender::new(settings.rtt_resend_factor)),
                     );
+                    reliable_senders.insert(channel_index.clone());
+                    fragment_send_queues.insert(channel_index.clone(), FragmentSendQueue::new());
                 }
                 _ => {}
             };
         }
 
         // initialize receivers
-        let mut channel_receivers = HashMap::<C, Box<dyn ChannelReceiver<P>>>::new();
+        let mut channel_receivers =
+            HashMap::<C, Box<dyn ChannelReceiver<MessageContainer<P>>>>::new();
+        let mut fragment_receivers = HashMap::<C, FragmentReceiver>::new();
         for (channel_index, channel) in channel_config.channels() {
             match &host_type {
                 HostType::Server => {
@@ -99,12 +122,14 @@ impl<P: Protocolize, C: ChannelIndex> MessageManager<P, C> {
                         channel_index.clone(),
                         Box::new(UnorderedReliableReceiver::default()),
                     );
+                    fragment_receivers.insert(channel_index.clone(), FragmentReceiver::new());
                 }
                 ChannelMode::OrderedReliable(_) => {
                     channel_receivers.insert(
                         channel_index.clone(),
                         Box::new(OrderedReliableReceiver::default()),
                     );
+                    fragment_receivers.insert(channel_index.clone(), FragmentReceiver::new());
                 }
                 _ => {}
             };
@@ -114,15 +139,64 @@ impl<P: Protocolize, C: ChannelIndex> MessageManager<P, C> {
             channel_senders,
             channel_receivers,
             packet_to_message_map: HashMap::new(),
+            reliable_senders,
+            fragment_writer: FragmentWriter::new(),
+            fragment_send_queues,
+            fragment_receivers,
+            received_messages: Vec::new(),
         }
     }
 
     // Outgoing Messages
 
-    /// Queues an Message to be transmitted to the remote host
-    pub fn send_message(&mut self, channel_index: C, message: P) {
+    /// Queues an Message to be transmitted to the remote host. On reliable
+    /// Channels, Messages larger than `FRAGMENTATION_LIMIT_BITS` are split
+    /// into Fragments. Unreliable Channels can't fragment, and send Messages
+    /// up to the MTU, less `PACKET_HEADER_ROOM_BITS`. Messages which are too
+    /// large to be sent are dropped. A reliable Channel holds back Messages
+    /// while `FRAGMENT_REASSEMBLY_LIMIT` fragmented Messages are undelivered.
+    pub fn send_message(
+        &mut self,
+        channel_writer: &dyn ChannelWriter<P>,
+        channel_index: C,
+        message: P,
+    ) {
         if let Some(channel) = self.channel_senders.get_mut(&channel_index) {
-            channel.send_message(message);
+            let mut counter = BitCounter::default();
+            channel_writer.write(&mut counter, &message);
+
+            let containers = if !self.reliable_senders.contains(&channel_index) {
+                if counter.bit_count() > MTU_SIZE_BITS - PACKET_HEADER_ROOM_BITS {
+                    warn!("Dropping Message which is too large to fit into a single packet, and can only be sent over a reliable Channel");
+                    return;
+                }
+
+                vec![MessageContainer::Message(message)]
+            } else if counter.bit_count() <= FRAGMENTATION_LIMIT_BITS {
+                vec![MessageContainer::Message(message)]
+            } else {
+                match self
+                    .fragment_writer
+                    .fragment_message(channel_writer, &message)
+                {
+                    Some(fragments) => fragments
+                        .into_iter()
+                        .map(MessageContainer::Fragment)
+                        .collect(),
+                    None => {
+                        warn!("Dropping Message which is too large to be split into Fragments");
+                        return;
+                    }
+                }
+            };
+
+            let containers = match self.fragment_send_queues.get_mut(&channel_index) {
+                Some(fragment_send_queue) => fragment_send_queue.send_message(containers),
+                None => containers,
+            };
+            for container in containers {
+                channel.send_message(container);
+            }
         }
     }
 
@@ -159,13 +233,15 @@ impl<P: Protocolize, C: ChannelIndex> MessageManager<P, C> {
         // write channel count
         UnsignedVariableInteger::<3>::new(channels_to_write.len() as u64).ser(bit_writer);
 
+        let container_writer = MessageContainerWriter::new(channel_writer);
+
         for channel_index in channels_to_write {
             let channel = self.channel_senders.get_mut(&channel_index).unwrap();
 
             // write channel index
             channel_index.ser(bit_writer);
 
-            if let Some(message_ids) = channel.write_messages(channel_writer, bit_writer) {
+            if let Some(message_ids) = channel.write_messages(&container_writer, bit_writer) {
                 self.packet_to_message_map
                     .entry(packet_index)
                     .or_insert_with(Vec::new);
@@ -181,30 +257,60 @@ impl<P: Protocolize, C: ChannelIndex> MessageManager<P, C> {
         &mut self,
         channel_reader: &dyn ChannelReader<P>,
         bit_reader: &mut BitReader,
-    ) {
+    ) -> Result<(), SerdeErr> {
         // read channel count
-        let channel_count = UnsignedVariableInteger::<3>::de(bit_reader).unwrap().get();
+        let channel_count = UnsignedVariableInteger::<3>::de(bit_reader)?.get();
+
+        let container_reader = MessageContainerReader::new(channel_reader);
+
+        // the Channels have already taken the Messages as received, so a bad
+        // Fragment must not stop the Messages after it from being read
+        let mut fragment_err = None;
 
         for _ in 0..channel_count {
             // read channel index
-            let channel_index = C::de(bit_reader).unwrap();
+            let channel_index = C::de(bit_reader)?;
 
             // continue read inside channel
             if let Some(channel) = self.channel_receivers.get_mut(&channel_index) {
-                channel.read_messages(channel_reader, bit_reader);
+                channel.read_messages(&container_reader, bit_reader)?;
+
+                // reassemble any fragmented messages
+                for container in channel.receive_messages() {
+                    match container {
+                        MessageContainer::Message(message) => {
+                            self.received_messages
+                                .push((channel_index.clone(), message));
+                        }
+                        MessageContainer::Fragment(fragment) => {
+                            if let Some(fragment_receiver) =
+                                self.fragment_receivers.get_mut(&channel_index)
+                            {
+                                match fragment_receiver.receive_fragment(channel_reader, fragment) {
+                                    Ok(Some(message)) => {
+                                        self.received_messages
+                                            .push((channel_index.clone(), message));
+                                    }
+                                    Ok(None) => {}
+                                    Err(err) => {
+                                        fragment_err.get_or_insert(err);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
             }
         }
+
+        match fragment_err {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
     }
 
     pub fn receive_messages(&mut self) -> Vec<(C, P)> {
-        let mut output = Vec::new();
-        for (channel_index, channel) in &mut self.channel_receivers {
-            let mut messages = channel.receive_messages();
-            for message in messages.drain(..) {
-                output.push((channel_index.clone(), message));
-            }
-        }
-        output
+        mem::take(&mut self.received_messages)
     }
 }
 
@@ -216,7 +322,14 @@ impl<P: Protocolize, C: ChannelIndex> PacketNotifiable for MessageManager<P, C>
             for (channel_index, message_ids) in channel_list {
                 if let Some(channel) = self.channel_senders.get_mut(channel_index) {
                     for message_id in message_ids {
-                        channel.notify_message_delivered(message_id);
+                        let delivered = channel.notify_message_delivered(message_id);
+                        if let (Some(container), Some(fragment_send_queue)) =
+                            (delivered, self.fragment_send_queues.get_mut(channel_index))
+                        {
+                            for container in fragment_send_queue.notify_delivered(&container) {
+                                channel.send_message(container);
+                            }
+                        }
                     }
                 }
             }
@@ -1,5 +1,7 @@
 pub mod channel_config;
+pub mod fragment;
 pub mod message_channel;
+pub mod message_container;
 pub mod message_list_header;
 pub mod message_manager;
 pub mod ordered_reliable_receiver;
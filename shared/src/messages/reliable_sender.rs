@@ -218,7 +218,7 @@ impl<P: Clone + Send + Sync> ChannelSender<P> for ReliableSender<P> {
         }
     }
 
-    fn notify_message_delivered(&mut self, message_id: &MessageId) {
-        self.deliver_message(message_id);
+    fn notify_message_delivered(&mut self, message_id: &MessageId) -> Option<P> {
+        self.deliver_message(message_id)
     }
 }
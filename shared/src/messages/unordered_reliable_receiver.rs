@@ -1,6 +1,6 @@
 use std::{collections::VecDeque, mem};
 
-use naia_serde::BitReader;
+use naia_serde::{BitReader, SerdeErr};
 
 use crate::{sequence_less_than, types::MessageId};
 
@@ -28,7 +28,8 @@ impl<P> Default for UnorderedReliableReceiver<P> {
 impl<P> UnorderedReliableReceiver<P> {
     // Private methods
 
-    pub fn buffer_message(&mut self, message_id: MessageId, message: P) {
+    /// Buffers the message, returning false if it had already been received
+    pub fn buffer_message(&mut self, message_id: MessageId, message: P) -> bool {
         // moving from oldest incoming message to newest
         // compare existing slots and see if the message_id has been instantiated
         // already if it has, put the message into the slot
@@ -38,7 +39,7 @@ impl<P> UnorderedReliableReceiver<P> {
 
         if sequence_less_than(message_id, self.oldest_received_message_id) {
             // already moved sliding window past this message id
-            return;
+            return false;
         }
 
         let mut index = 0;
@@ -50,10 +51,10 @@ impl<P> UnorderedReliableReceiver<P> {
                         if !(*old_message) {
                             *old_message = true;
                             self.received_messages.push((*old_message_id, message));
-                            return;
+                            return true;
                         } else {
                             // already received this message
-                            return;
+                            return false;
                         }
                     }
                 }
@@ -63,7 +64,7 @@ impl<P> UnorderedReliableReceiver<P> {
                 if next_message_id == message_id {
                     self.record.push_back((next_message_id, true));
                     self.received_messages.push((message_id, message));
-                    return;
+                    return true;
                 } else {
                     self.record.push_back((next_message_id, false));
                     // keep filling up buffer
@@ -97,11 +98,16 @@ impl<P> UnorderedReliableReceiver<P> {
 }
 
 impl<P: Send + Sync> ChannelReceiver<P> for UnorderedReliableReceiver<P> {
-    fn read_messages(&mut self, channel_reader: &dyn ChannelReader<P>, bit_reader: &mut BitReader) {
-        let id_w_msgs = ReliableReceiver::read_incoming_messages(channel_reader, bit_reader);
+    fn read_messages(
+        &mut self,
+        channel_reader: &dyn ChannelReader<P>,
+        bit_reader: &mut BitReader,
+    ) -> Result<(), SerdeErr> {
+        let id_w_msgs = ReliableReceiver::read_incoming_messages(channel_reader, bit_reader)?;
         for (id, message) in id_w_msgs {
             self.buffer_message(id, message);
         }
+        Ok(())
     }
 
     fn receive_messages(&mut self) -> Vec<P> {
@@ -105,7 +105,8 @@ impl<P: Send + Sync> ChannelSender<P> for UnorderedUnreliableSender<P> {
         }
     }
 
-    fn notify_message_delivered(&mut self, _: &MessageId) {
+    fn notify_message_delivered(&mut self, _: &MessageId) -> Option<P> {
         // not necessary for an unreliable channel
+        None
     }
 }
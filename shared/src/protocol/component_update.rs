@@ -1,18 +1,29 @@
 use naia_serde::{BitReader, OwnedBitReader};
 
-use super::protocolize::ProtocolKindType;
+use super::{diff_mask::DiffMask, protocolize::ProtocolKindType};
 
 pub struct ComponentUpdate<K: ProtocolKindType> {
     pub kind: K,
+    diff_mask: DiffMask,
     buffer: OwnedBitReader,
 }
 
 impl<K: ProtocolKindType> ComponentUpdate<K> {
-    pub fn new(kind: K, buffer: OwnedBitReader) -> Self {
-        Self { kind, buffer }
+    pub fn new(kind: K, diff_mask: DiffMask, buffer: OwnedBitReader) -> Self {
+        Self {
+            kind,
+            diff_mask,
+            buffer,
+        }
     }
 
     pub fn reader(&self) -> BitReader {
         self.buffer.borrow()
     }
+
+    /// Gets a DiffMask with a bit set for every Property contained in the
+    /// update
+    pub fn diff_mask(&self) -> &DiffMask {
+        &self.diff_mask
+    }
 }
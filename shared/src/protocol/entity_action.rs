@@ -5,6 +5,8 @@ pub enum EntityAction<E: Copy, K: ProtocolKindType> {
     DespawnEntity(E),
     InsertComponent(E, K),
     RemoveComponent(E, K),
+    GrantAuthority(E),
+    RevokeAuthority(E),
     Noop,
 }
 
@@ -15,6 +17,8 @@ impl<E: Copy, K: ProtocolKindType> EntityAction<E, K> {
             EntityAction::DespawnEntity(entity) => Some(*entity),
             EntityAction::InsertComponent(entity, _) => Some(*entity),
             EntityAction::RemoveComponent(entity, _) => Some(*entity),
+            EntityAction::GrantAuthority(entity) => Some(*entity),
+            EntityAction::RevokeAuthority(entity) => Some(*entity),
             EntityAction::Noop => None,
         }
     }
@@ -1,4 +1,4 @@
-use naia_shared::ProtocolKindType;
+use super::protocolize::ProtocolKindType;
 
 #[derive(Clone, PartialEq, Eq)]
 pub enum EntityActionEvent<E: Copy, K: ProtocolKindType> {
@@ -6,4 +6,6 @@ pub enum EntityActionEvent<E: Copy, K: ProtocolKindType> {
     DespawnEntity(E),
     InsertComponent(E, K),
     RemoveComponent(E, K),
+    GrantAuthority(E),
+    RevokeAuthority(E),
 }
@@ -24,7 +24,8 @@ impl<E: Copy + Hash + Eq, K: ProtocolKindType> Default for EntityActionReceiver<
 }
 
 impl<E: Copy + Hash + Eq, K: ProtocolKindType> EntityActionReceiver<E, K> {
-    pub fn buffer_action(&mut self, action_id: ActionId, action: EntityAction<E, K>) {
+    /// Buffers the action, returning false if it had already been received
+    pub fn buffer_action(&mut self, action_id: ActionId, action: EntityAction<E, K>) -> bool {
         self.receiver.buffer_message(action_id, action)
     }
 
@@ -53,6 +54,8 @@ pub struct EntityChannel<E: Copy + Hash + Eq, K: ProtocolKindType> {
     components: HashMap<K, ComponentChannel<E, K>>,
     waiting_spawns: OrderedIds<Vec<K>>,
     waiting_despawns: OrderedIds<()>,
+    last_authority_id: Option<ActionId>,
+    waiting_authority: Option<(ActionId, bool)>,
 }
 
 impl<E: Copy + Hash + Eq, K: ProtocolKindType> EntityChannel<E, K> {
@@ -64,6 +67,8 @@ impl<E: Copy + Hash + Eq, K: ProtocolKindType> EntityChannel<E, K> {
             waiting_spawns: OrderedIds::new(),
             waiting_despawns: OrderedIds::new(),
             last_canonical_id: None,
+            last_authority_id: None,
+            waiting_authority: None,
         }
     }
 
@@ -94,6 +99,12 @@ impl<E: Copy + Hash + Eq, K: ProtocolKindType> EntityChannel<E, K> {
                     outgoing_actions,
                 );
             }
+            EntityAction::GrantAuthority(_) => {
+                self.receive_authority_action(incoming_action_id, true, outgoing_actions);
+            }
+            EntityAction::RevokeAuthority(_) => {
+                self.receive_authority_action(incoming_action_id, false, outgoing_actions);
+            }
             EntityAction::Noop => {}
         }
     }
@@ -113,6 +124,15 @@ impl<E: Copy + Hash + Eq, K: ProtocolKindType> EntityChannel<E, K> {
 
         if !self.spawned {
             self.spawned = true;
+
+            // Components included in the spawn are inserted along with the Entity
+            for component in &components {
+                self.components
+                    .entry(*component)
+                    .or_insert_with(|| ComponentChannel::new(Some(id)))
+                    .inserted = true;
+            }
+
             outgoing_actions.push(EntityAction::SpawnEntity(self.entity, components));
 
             // pop ALL waiting spawns, despawns, inserts, and removes OLDER than spawn_id
@@ -134,6 +154,11 @@ impl<E: Copy + Hash + Eq, K: ProtocolKindType> EntityChannel<E, K> {
                     self.receive_insert_component_action(id, component, outgoing_actions);
                 }
             }
+
+            // process any waiting authority change
+            if let Some((authority_id, granted)) = self.waiting_authority.take() {
+                self.receive_authority_action(authority_id, granted, outgoing_actions);
+            }
         } else {
             // buffer spawn for later
             self.waiting_spawns.push_back(id, components);
@@ -262,10 +287,54 @@ impl<E: Copy + Hash + Eq, K: ProtocolKindType> EntityChannel<E, K> {
         }
     }
 
+    pub fn receive_authority_action(
+        &mut self,
+        id: ActionId,
+        granted: bool,
+        outgoing_actions: &mut Vec<EntityAction<E, K>>,
+    ) {
+        // do not process any authority change OLDER than last received spawn id /
+        // despawn id
+        if let Some(last_id) = self.last_canonical_id {
+            if sequence_less_than(id, last_id) {
+                return;
+            }
+        }
+
+        // only the most recent authority change matters
+        if let Some(last_id) = self.last_authority_id {
+            if sequence_less_than(id, last_id) {
+                return;
+            }
+        }
+
+        if self.spawned {
+            self.last_authority_id = Some(id);
+            if granted {
+                outgoing_actions.push(EntityAction::GrantAuthority(self.entity));
+            } else {
+                outgoing_actions.push(EntityAction::RevokeAuthority(self.entity));
+            }
+        } else {
+            // buffer authority change until the Entity is spawned
+            match self.waiting_authority {
+                Some((waiting_id, _)) if sequence_less_than(id, waiting_id) => {}
+                _ => {
+                    self.waiting_authority = Some((id, granted));
+                }
+            }
+        }
+    }
+
     pub fn receive_canonical(&mut self, id: ActionId) {
         // pop ALL waiting spawns, despawns, inserts, and removes OLDER than id
         self.waiting_spawns.pop_front_until_and_including(id);
         self.waiting_despawns.pop_front_until_and_including(id);
+        if let Some((authority_id, _)) = self.waiting_authority {
+            if authority_id == id || sequence_less_than(authority_id, id) {
+                self.waiting_authority = None;
+            }
+        }
         for component_state in self.components.values_mut() {
             component_state.receive_canonical(id);
         }
@@ -373,3 +442,62 @@ impl<P> OrderedIds<P> {
         }
     }
 }
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+    use std::any::TypeId;
+
+    use crate::{derive_serde, serde, EntityAction, ProtocolKindType};
+
+    use super::EntityActionReceiver;
+
+    #[derive(Copy, Eq, Hash)]
+    #[derive_serde]
+    enum TestKind {
+        Position,
+    }
+
+    impl ProtocolKindType for TestKind {
+        fn to_type_id(&self) -> TypeId {
+            TypeId::of::<TestKind>()
+        }
+    }
+
+    fn authority_changes(actions: Vec<EntityAction<u16, TestKind>>) -> Vec<bool> {
+        actions
+            .into_iter()
+            .filter_map(|action| match action {
+                EntityAction::GrantAuthority(_) => Some(true),
+                EntityAction::RevokeAuthority(_) => Some(false),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn authority_waits_for_spawn() {
+        let mut receiver = EntityActionReceiver::<u16, TestKind>::default();
+
+        receiver.buffer_action(1, EntityAction::GrantAuthority(7));
+        assert!(receiver.receive_actions().is_empty());
+
+        receiver.buffer_action(0, EntityAction::SpawnEntity(7, vec![TestKind::Position]));
+        let actions = receiver.receive_actions();
+        assert!(matches!(actions[0], EntityAction::SpawnEntity(7, _)));
+        assert_eq!(authority_changes(actions), vec![true]);
+    }
+
+    #[test]
+    fn stale_authority_is_ignored() {
+        let mut receiver = EntityActionReceiver::<u16, TestKind>::default();
+
+        receiver.buffer_action(0, EntityAction::SpawnEntity(7, Vec::new()));
+        receiver.receive_actions();
+
+        receiver.buffer_action(2, EntityAction::RevokeAuthority(7));
+        receiver.buffer_action(1, EntityAction::GrantAuthority(7));
+        assert_eq!(authority_changes(receiver.receive_actions()), vec![false]);
+    }
+}
@@ -13,6 +13,10 @@ pub enum EntityActionType {
     InsertComponent,
     // Action indicating a Component to be deleted
     RemoveComponent,
+    // Action indicating authority over an Entity to be given to the receiver
+    GrantAuthority,
+    // Action indicating authority over an Entity to be taken from the receiver
+    RevokeAuthority,
     // Action indicating a non-operation
     Noop,
 }
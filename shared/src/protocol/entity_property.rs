@@ -1,6 +1,6 @@
 use std::hash::Hash;
 
-use naia_serde::{BitReader, BitWrite, BitWriter, Serde};
+use naia_serde::{BitReader, BitWrite, BitWriter, Serde, SerdeErr};
 
 use crate::{
     bigmap::BigMapKey,
@@ -42,30 +42,31 @@ impl EntityProperty {
         reader: &mut BitReader,
         mutator_index: u8,
         converter: &dyn NetEntityHandleConverter,
-    ) -> Self {
-        if let Some(net_entity) = Option::<NetEntity>::de(reader).unwrap() {
-            let handle = converter.net_entity_to_handle(&net_entity);
-            let mut new_prop = Self::new(mutator_index);
-            *new_prop.handle_prop = Some(handle);
-            new_prop
-        } else {
-            let mut new_prop = Self::new(mutator_index);
-            *new_prop.handle_prop = None;
-            new_prop
-        }
+    ) -> Result<Self, SerdeErr> {
+        // a reference to an Entity which doesn't exist on this end is dropped
+        let mut new_prop = Self::new(mutator_index);
+        *new_prop.handle_prop = Option::<NetEntity>::de(reader)?
+            .and_then(|net_entity| converter.net_entity_to_handle(&net_entity));
+        Ok(new_prop)
     }
 
-    pub fn read_write(bit_reader: &mut BitReader, bit_writer: &mut BitWriter) {
-        Option::<NetEntity>::de(bit_reader).unwrap().ser(bit_writer);
+    pub fn read_write(
+        bit_reader: &mut BitReader,
+        bit_writer: &mut BitWriter,
+    ) -> Result<(), SerdeErr> {
+        Option::<NetEntity>::de(bit_reader)?.ser(bit_writer);
+        Ok(())
     }
 
-    pub fn read(&mut self, reader: &mut BitReader, converter: &dyn NetEntityHandleConverter) {
-        if let Some(net_entity) = Option::<NetEntity>::de(reader).unwrap() {
-            let handle = converter.net_entity_to_handle(&net_entity);
-            *self.handle_prop = Some(handle);
-        } else {
-            *self.handle_prop = None;
-        }
+    pub fn read(
+        &mut self,
+        reader: &mut BitReader,
+        converter: &dyn NetEntityHandleConverter,
+    ) -> Result<(), SerdeErr> {
+        // a reference to an Entity which doesn't exist on this end is dropped
+        *self.handle_prop = Option::<NetEntity>::de(reader)?
+            .and_then(|net_entity| converter.net_entity_to_handle(&net_entity));
+        Ok(())
     }
 
     // Comparison
@@ -103,12 +104,14 @@ pub trait EntityHandleConverter<E: Copy + Eq + Hash> {
 
 pub trait NetEntityHandleConverter {
     fn handle_to_net_entity(&self, entity_handle: &EntityHandle) -> NetEntity;
-    fn net_entity_to_handle(&self, net_entity: &NetEntity) -> EntityHandle;
+    /// Returns None if no Entity is associated with the NetEntity, which a
+    /// peer can't be trusted not to send
+    fn net_entity_to_handle(&self, net_entity: &NetEntity) -> Option<EntityHandle>;
 }
 
 pub trait NetEntityConverter<E: Copy + Eq + Hash> {
     fn entity_to_net_entity(&self, entity: &E) -> NetEntity;
-    fn net_entity_to_entity(&self, net_entity: &NetEntity) -> E;
+    fn net_entity_to_entity(&self, net_entity: &NetEntity) -> Option<E>;
 }
 
 pub struct FakeEntityConverter;
@@ -118,8 +121,8 @@ impl NetEntityHandleConverter for FakeEntityConverter {
         NetEntity::from(0)
     }
 
-    fn net_entity_to_handle(&self, _: &NetEntity) -> EntityHandle {
-        EntityHandle::from_u64(0)
+    fn net_entity_to_handle(&self, _: &NetEntity) -> Option<EntityHandle> {
+        Some(EntityHandle::from_u64(0))
     }
 }
 
@@ -146,8 +149,9 @@ impl<'a, 'b, E: Copy + Eq + Hash> NetEntityHandleConverter for EntityConverter<'
         self.net_entity_converter.entity_to_net_entity(&entity)
     }
 
-    fn net_entity_to_handle(&self, net_entity: &NetEntity) -> EntityHandle {
-        let entity = self.net_entity_converter.net_entity_to_entity(net_entity);
-        self.handle_converter.entity_to_handle(&entity)
+    fn net_entity_to_handle(&self, net_entity: &NetEntity) -> Option<EntityHandle> {
+        self.net_entity_converter
+            .net_entity_to_entity(net_entity)
+            .map(|entity| self.handle_converter.entity_to_handle(&entity))
     }
 }
@@ -1,13 +1,20 @@
 pub mod component_update;
 pub mod diff_mask;
 pub mod entity_action;
+pub mod entity_action_event;
 pub mod entity_action_receiver;
 pub mod entity_action_type;
 pub mod entity_handle;
 pub mod entity_property;
+pub mod nested_property;
 pub mod net_entity;
 pub mod property;
+pub mod property_baselines;
+pub mod property_codec;
+mod property_log;
+pub mod property_map;
 pub mod property_mutate;
+pub mod property_vec;
 pub mod protocol_io;
 pub mod protocolize;
 pub mod replica_ref;
@@ -0,0 +1,209 @@
+use std::ops::{Deref, DerefMut};
+
+use naia_serde::{BitReader, BitWrite, BitWriter, SerdeErr};
+
+use crate::protocol::{
+    diff_mask::DiffMask,
+    entity_handle::EntityHandle,
+    entity_property::NetEntityHandleConverter,
+    property_baselines::PropertyBaselines,
+    property_mutate::{PropertyMutate, PropertyMutator},
+};
+
+/// A struct of Properties which can be nested inside of a Replica. Each of its
+/// Properties takes a bit of the containing Replica's DiffMask, so that only
+/// the Properties which have changed are synced
+pub trait NestedReplicate: Clone + Send + Sync + 'static {
+    /// The number of bits the struct takes up in the containing Replica's
+    /// DiffMask
+    const PROPERTY_COUNT: usize;
+
+    /// Gets a stable hash of the layout of the struct's Properties
+    fn layout_hash() -> u64;
+    /// Set the PropertyMutator which tracks changes to each of the struct's
+    /// Properties
+    fn set_mutator(&mut self, mutator: &PropertyMutator);
+    /// Sets every Property to the value of the same Property of another struct
+    fn mirror(&mut self, other: &Self);
+    /// Writes every Property into an outgoing byte stream
+    fn write(&self, bit_writer: &mut dyn BitWrite, converter: &dyn NetEntityHandleConverter);
+    /// Writes the Properties marked in the DiffMask into an outgoing byte
+    /// stream, where the struct's Properties start at `offset`, writing
+    /// delta-encoded Properties against the given PropertyBaselines
+    fn write_update(
+        &self,
+        diff_mask: &DiffMask,
+        offset: u8,
+        bit_writer: &mut dyn BitWrite,
+        converter: &dyn NetEntityHandleConverter,
+        baselines: &mut dyn PropertyBaselines,
+    );
+    /// Reads every Property from an incoming byte stream
+    fn new_read(
+        bit_reader: &mut BitReader,
+        converter: &dyn NetEntityHandleConverter,
+    ) -> Result<Self, SerdeErr>;
+    /// Reads an update from an incoming byte stream and immediately writes it
+    /// to another, marking the Properties it contains in the DiffMask, where
+    /// the struct's Properties start at `offset`, reading delta-encoded
+    /// Properties against the given PropertyBaselines
+    fn read_write_update(
+        bit_reader: &mut BitReader,
+        bit_writer: &mut BitWriter,
+        diff_mask: &mut DiffMask,
+        offset: u8,
+        baselines: &mut dyn PropertyBaselines,
+    ) -> Result<(), SerdeErr>;
+    /// Reads an update from an incoming byte stream, and applies it
+    fn read_apply_update(
+        &mut self,
+        bit_reader: &mut BitReader,
+        converter: &dyn NetEntityHandleConverter,
+    ) -> Result<(), SerdeErr>;
+    /// Returns whether any of the struct's Properties hold an Entity
+    fn has_entity_properties(&self) -> bool;
+    /// Returns a list of Entities contained within the struct's Properties
+    fn entities(&self) -> Vec<EntityHandle>;
+}
+
+/// A Property of a Replica which holds a NestedReplicate struct, and syncs
+/// each of that struct's Properties separately
+#[derive(Clone)]
+pub struct NestedProperty<T: NestedReplicate> {
+    inner: T,
+    offset: u8,
+}
+
+impl<T: NestedReplicate> NestedProperty<T> {
+    /// Create a new NestedProperty, whose Properties start at the given bit of
+    /// the containing Replica's DiffMask
+    pub fn new(value: T, offset: u8) -> Self {
+        Self {
+            inner: value,
+            offset,
+        }
+    }
+
+    /// Set value to the value of another NestedProperty, queues the Properties
+    /// within for update
+    pub fn mirror(&mut self, other: &NestedProperty<T>) {
+        self.inner.mirror(&other.inner);
+    }
+
+    // Serialization / deserialization
+
+    /// Writes contained value into outgoing byte stream
+    pub fn write(&self, writer: &mut dyn BitWrite, converter: &dyn NetEntityHandleConverter) {
+        self.inner.write(writer, converter);
+    }
+
+    /// Writes the Properties within which are marked in the DiffMask into an
+    /// outgoing byte stream. `parent_offset` is where the containing struct's
+    /// Properties start
+    pub fn write_update(
+        &self,
+        diff_mask: &DiffMask,
+        parent_offset: u8,
+        writer: &mut dyn BitWrite,
+        converter: &dyn NetEntityHandleConverter,
+        baselines: &mut dyn PropertyBaselines,
+    ) {
+        self.inner.write_update(
+            diff_mask,
+            parent_offset + self.offset,
+            writer,
+            converter,
+            baselines,
+        );
+    }
+
+    /// Returns whether any of the Properties within are marked in the DiffMask
+    pub fn is_mutated(&self, diff_mask: &DiffMask, parent_offset: u8) -> bool {
+        let start = parent_offset + self.offset;
+        (0..T::PROPERTY_COUNT).any(|index| diff_mask.bit(start + index as u8) == Some(true))
+    }
+
+    /// Given a cursor into incoming packet data, initializes the
+    /// NestedProperty with the synced value
+    pub fn new_read(
+        reader: &mut BitReader,
+        offset: u8,
+        converter: &dyn NetEntityHandleConverter,
+    ) -> Result<Self, SerdeErr> {
+        Ok(Self::new(T::new_read(reader, converter)?, offset))
+    }
+
+    /// Reads an update from a stream and immediately writes it to a stream,
+    /// marking the updated Properties in the DiffMask, starting from `offset`.
+    /// Used to buffer updates for later
+    pub fn read_write(
+        bit_reader: &mut BitReader,
+        bit_writer: &mut BitWriter,
+        diff_mask: &mut DiffMask,
+        offset: u8,
+        baselines: &mut dyn PropertyBaselines,
+    ) -> Result<(), SerdeErr> {
+        T::read_write_update(bit_reader, bit_writer, diff_mask, offset, baselines)
+    }
+
+    /// Given a cursor into incoming packet data, updates the Properties within
+    /// with the synced values
+    pub fn read(
+        &mut self,
+        reader: &mut BitReader,
+        converter: &dyn NetEntityHandleConverter,
+    ) -> Result<(), SerdeErr> {
+        self.inner.read_apply_update(reader, converter)
+    }
+
+    /// Returns whether any of the Properties within hold an Entity
+    pub fn has_entity_properties(&self) -> bool {
+        self.inner.has_entity_properties()
+    }
+
+    /// Returns a list of Entities contained within the Properties within
+    pub fn entities(&self) -> Vec<EntityHandle> {
+        self.inner.entities()
+    }
+
+    // Internal
+
+    /// Set an PropertyMutator to track changes to the Properties within
+    pub fn set_mutator(&mut self, mutator: &PropertyMutator) {
+        let offset_mutator = OffsetMutator {
+            mutator: mutator.clone_new(),
+            offset: self.offset,
+        };
+        self.inner
+            .set_mutator(&PropertyMutator::new(offset_mutator));
+    }
+}
+
+impl<T: NestedReplicate> Deref for NestedProperty<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<T: NestedReplicate> DerefMut for NestedProperty<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // the Properties within queue themselves for update when changed
+        &mut self.inner
+    }
+}
+
+// Shifts the index of each mutated Property within a NestedProperty, to its
+// position in the containing Replica's DiffMask
+#[derive(Clone)]
+struct OffsetMutator {
+    mutator: PropertyMutator,
+    offset: u8,
+}
+
+impl PropertyMutate for OffsetMutator {
+    fn mutate(&mut self, property_index: u8) {
+        self.mutator.mutate(self.offset + property_index);
+    }
+}
@@ -1,20 +1,26 @@
 use std::ops::{Deref, DerefMut};
 
-use naia_serde::{BitReader, BitWrite, BitWriter, Serde};
+use naia_serde::{
+    BitReader, BitWrite, BitWriter, Serde, SerdeErr, SerdeErrReason, UnsignedInteger,
+};
 
-use crate::protocol::property_mutate::PropertyMutator;
+use crate::protocol::{
+    property_baselines::{PropertyBaselines, BASELINE_DISTANCE_BITS},
+    property_codec::{PropertyCodec, SerdeCodec},
+    property_mutate::PropertyMutator,
+};
 
 /// A Property of an Component/Message, that contains data
 /// which must be tracked for updates
 #[derive(Clone)]
-pub struct Property<T: Serde> {
+pub struct Property<T: Clone + PartialEq> {
     inner: T,
     mutator: Option<PropertyMutator>,
     mutator_index: u8,
 }
 
 // should be shared
-impl<T: Serde> Property<T> {
+impl<T: Clone + PartialEq> Property<T> {
     /// Create a new Property
     pub fn new(value: T, mutator_index: u8) -> Property<T> {
         Property::<T> {
@@ -24,47 +30,132 @@ impl<T: Serde> Property<T> {
         }
     }
 
+    /// Set value, queues for update only if the new value is different from
+    /// the current one
+    pub fn set(&mut self, value: T) {
+        if self.inner != value {
+            **self = value;
+        }
+    }
+
     /// Set value to the value of another Property, queues for update if value
     /// changes
     pub fn mirror(&mut self, other: &Property<T>) {
         **self = (**other).clone();
     }
 
-    // Serialization / deserialization
+    // Serialization / deserialization with a PropertyCodec
 
-    /// Writes contained value into outgoing byte stream
-    pub fn write(&self, writer: &mut dyn BitWrite) {
-        self.inner.ser(writer);
+    /// Writes contained value into outgoing byte stream in full, using the
+    /// given PropertyCodec
+    pub fn write_with<C: PropertyCodec<T>>(&self, writer: &mut dyn BitWrite) {
+        C::encode(&self.inner, None, writer);
     }
 
-    /// Given a cursor into incoming packet data, initializes the Property with
-    /// the synced value
-    pub fn new_read(reader: &mut BitReader, mutator_index: u8) -> Self {
-        let inner = Self::read_inner(reader);
+    /// Writes contained value into an outgoing update, using the given
+    /// PropertyCodec. If it is a delta codec, the value is written against
+    /// the baseline the remote host holds, if there is one, and is recorded
+    /// as the value sent in the current packet
+    pub fn write_update_with<C: PropertyCodec<T>>(
+        &self,
+        property_index: u8,
+        baselines: &mut dyn PropertyBaselines,
+        writer: &mut dyn BitWrite,
+    ) {
+        if !C::DELTA {
+            self.write_with::<C>(writer);
+            return;
+        }
 
-        Property::<T> {
+        let baseline = baselines
+            .baseline(property_index)
+            .and_then(|(distance, value)| Some((distance, Self::decode_full::<C>(value)?)));
+
+        baseline.is_some().ser(writer);
+        if let Some((distance, _)) = &baseline {
+            UnsignedInteger::<BASELINE_DISTANCE_BITS>::new(distance - 1).ser(writer);
+        }
+        C::encode(
+            &self.inner,
+            baseline.as_ref().map(|(_, value)| value),
+            writer,
+        );
+
+        baselines.record(property_index, Self::encode_full::<C>(&self.inner));
+    }
+
+    /// Given a cursor into incoming packet data, initializes the Property with
+    /// the synced value, using the given PropertyCodec
+    pub fn new_read_with<C: PropertyCodec<T>>(
+        reader: &mut BitReader,
+        mutator_index: u8,
+    ) -> Result<Self, SerdeErr> {
+        let inner = C::decode(None, reader)?;
+
+        Ok(Property::<T> {
             inner,
             mutator: None,
             mutator_index,
-        }
+        })
     }
 
-    /// Reads from a stream and immediately writes to a stream
-    /// Used to buffer updates for later
-    pub fn read_write(bit_reader: &mut BitReader, bit_writer: &mut BitWriter) {
-        T::de(bit_reader)
-            .expect("Property read error.")
-            .ser(bit_writer);
+    /// Reads an update from a stream and immediately writes it to a stream,
+    /// using the given PropertyCodec. Used to buffer updates for later. If it
+    /// is a delta codec, the value is read against the baseline it was
+    /// written with, and buffered without one, so that it can be read
+    /// whatever value is held by then
+    pub fn read_write_update_with<C: PropertyCodec<T>>(
+        property_index: u8,
+        baselines: &mut dyn PropertyBaselines,
+        bit_reader: &mut BitReader,
+        bit_writer: &mut BitWriter,
+    ) -> Result<(), SerdeErr> {
+        if !C::DELTA {
+            C::encode(&C::decode(None, bit_reader)?, None, bit_writer);
+            return Ok(());
+        }
+
+        let baseline = if bool::de(bit_reader)? {
+            let distance = UnsignedInteger::<BASELINE_DISTANCE_BITS>::de(bit_reader)?.get() + 1;
+            let value = baselines
+                .baseline_at(property_index, distance as u16)
+                .and_then(Self::decode_full::<C>)
+                .ok_or_else(|| {
+                    SerdeErr::new(
+                        bit_reader.bit_offset(),
+                        "Property baseline",
+                        SerdeErrReason::InvalidValue,
+                    )
+                })?;
+            Some(value)
+        } else {
+            None
+        };
+        let value = C::decode(baseline.as_ref(), bit_reader)?;
+        C::encode(&value, None, bit_writer);
+
+        baselines.record(property_index, Self::encode_full::<C>(&value));
+        Ok(())
     }
 
     /// Given a cursor into incoming packet data, updates the Property with the
-    /// synced value
-    pub fn read(&mut self, reader: &mut BitReader) {
-        self.inner = Self::read_inner(reader);
+    /// synced value, using the given PropertyCodec
+    pub fn read_with<C: PropertyCodec<T>>(
+        &mut self,
+        reader: &mut BitReader,
+    ) -> Result<(), SerdeErr> {
+        self.inner = C::decode(None, reader)?;
+        Ok(())
     }
 
-    fn read_inner(reader: &mut BitReader) -> T {
-        T::de(reader).expect("Property read error.")
+    fn encode_full<C: PropertyCodec<T>>(value: &T) -> Box<[u8]> {
+        let mut writer = BitWriter::default();
+        C::encode(value, None, &mut writer);
+        writer.to_bytes()
+    }
+
+    fn decode_full<C: PropertyCodec<T>>(bytes: &[u8]) -> Option<T> {
+        C::decode(None, &mut BitReader::new(bytes)).ok()
     }
 
     // Comparison
@@ -82,9 +173,40 @@ impl<T: Serde> Property<T> {
     }
 }
 
+impl<T: Serde> Property<T> {
+    // Serialization / deserialization
+
+    /// Writes contained value into outgoing byte stream
+    pub fn write(&self, writer: &mut dyn BitWrite) {
+        self.write_with::<SerdeCodec>(writer);
+    }
+
+    /// Given a cursor into incoming packet data, initializes the Property with
+    /// the synced value
+    pub fn new_read(reader: &mut BitReader, mutator_index: u8) -> Result<Self, SerdeErr> {
+        Self::new_read_with::<SerdeCodec>(reader, mutator_index)
+    }
+
+    /// Reads from a stream and immediately writes to a stream
+    /// Used to buffer updates for later
+    pub fn read_write(
+        bit_reader: &mut BitReader,
+        bit_writer: &mut BitWriter,
+    ) -> Result<(), SerdeErr> {
+        T::de(bit_reader)?.ser(bit_writer);
+        Ok(())
+    }
+
+    /// Given a cursor into incoming packet data, updates the Property with the
+    /// synced value
+    pub fn read(&mut self, reader: &mut BitReader) -> Result<(), SerdeErr> {
+        self.read_with::<SerdeCodec>(reader)
+    }
+}
+
 // It could be argued that Property here is a type of smart-pointer,
 // but honestly this is mainly for the convenience of type coercion
-impl<T: Serde> Deref for Property<T> {
+impl<T: Clone + PartialEq> Deref for Property<T> {
     type Target = T;
 
     fn deref(&self) -> &Self::Target {
@@ -92,7 +214,7 @@ impl<T: Serde> Deref for Property<T> {
     }
 }
 
-impl<T: Serde> DerefMut for Property<T> {
+impl<T: Clone + PartialEq> DerefMut for Property<T> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         // Just assume inner value will be changed, queue for update
         if let Some(mutator) = &mut self.mutator {
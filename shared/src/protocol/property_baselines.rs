@@ -0,0 +1,37 @@
+/// The number of bits used to write how many packets before an update the
+/// baseline of a delta-encoded Property was sent
+pub const BASELINE_DISTANCE_BITS: u8 = 5;
+/// The most packets before an update that its baselines can have been sent
+pub const MAX_BASELINE_DISTANCE: u16 = 1 << BASELINE_DISTANCE_BITS;
+
+/// The values of a Replica's delta-encoded Properties which have been sent to
+/// or received from the remote host, in their full encoding. Properties are
+/// identified by their bit in the Replica's DiffMask
+pub trait PropertyBaselines {
+    /// Gets the value to write an update of the Property against, which the
+    /// remote host is known to hold, along with how many packets before the
+    /// current one it was sent
+    fn baseline(&self, property_index: u8) -> Option<(u16, &[u8])>;
+    /// Gets the value of the Property which was received the given number of
+    /// packets before the current one
+    fn baseline_at(&self, property_index: u8, distance: u16) -> Option<&[u8]>;
+    /// Records the value of the Property written to or read from the current
+    /// packet
+    fn record(&mut self, property_index: u8, value: Box<[u8]>);
+}
+
+/// PropertyBaselines which hold no values, so that every Property is written
+/// in full
+pub struct NoBaselines;
+
+impl PropertyBaselines for NoBaselines {
+    fn baseline(&self, _: u8) -> Option<(u16, &[u8])> {
+        None
+    }
+
+    fn baseline_at(&self, _: u8, _: u16) -> Option<&[u8]> {
+        None
+    }
+
+    fn record(&mut self, _: u8, _: Box<[u8]>) {}
+}
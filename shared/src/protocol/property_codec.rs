@@ -0,0 +1,42 @@
+use naia_serde::{BitReader, BitWrite, Serde, SerdeErr};
+
+/// A custom way of writing the value of a Property into an outgoing byte
+/// stream and reading it back, i.e. to quantize it, to use a more compact
+/// representation, or to write only its difference from a previous value.
+/// Used by marking a field of a Replicate struct with
+/// `#[property(codec = "path::to::Codec")]`.
+///
+/// Both methods may be given a baseline, a value the reader is known to hold,
+/// which lets a codec write only the difference from it. Replicas are always
+/// written in full, without one. Updates of a codec which sets `DELTA` are
+/// written against the last value the remote host has acknowledged receiving,
+/// when it is recent enough, and read against that same value. Other codecs
+/// are never given a baseline.
+///
+/// A value read by `decode` must be written the same way again by `encode`,
+/// as updates are buffered by writing them again once they have been read.
+pub trait PropertyCodec<T> {
+    /// Whether updates should be written against a baseline
+    const DELTA: bool = false;
+
+    /// Writes the value into an outgoing byte stream, optionally relative to
+    /// a baseline
+    fn encode(value: &T, baseline: Option<&T>, writer: &mut dyn BitWrite);
+    /// Reads a value from an incoming byte stream, given the baseline it was
+    /// written against, if any
+    fn decode(baseline: Option<&T>, reader: &mut BitReader) -> Result<T, SerdeErr>;
+}
+
+/// The PropertyCodec used by a Property when no other is given, which uses
+/// the value's Serde implementation
+pub struct SerdeCodec;
+
+impl<T: Serde> PropertyCodec<T> for SerdeCodec {
+    fn encode(value: &T, _baseline: Option<&T>, writer: &mut dyn BitWrite) {
+        value.ser(writer);
+    }
+
+    fn decode(_baseline: Option<&T>, reader: &mut BitReader) -> Result<T, SerdeErr> {
+        T::de(reader)
+    }
+}
@@ -0,0 +1,277 @@
+use naia_serde::{BitReader, BitWrite, BitWriter, Serde, SerdeErr, SerdeErrReason};
+
+use crate::{
+    protocol::{diff_mask::DiffMask, property_mutate::PropertyMutator},
+    wrapping_number::sequence_greater_than,
+};
+
+// The most operations kept in the log before it is cleared and the whole
+// collection is sent instead
+const OP_LOG_SIZE: usize = 16;
+
+// A PropertyLog takes up 2 bits in the DiffMask, the first marks the whole
+// collection for syncing, and the second marks the logged operations
+const FULL_BIT: u8 = 0;
+const OPS_BIT: u8 = 1;
+
+/// An operation on a collection, which can be logged and applied remotely
+pub(crate) trait CollectionOp: Serde + Send + Sync + 'static {
+    type Collection: Serde + Send + Sync + 'static;
+
+    /// Returns whether the operation can be applied to the collection as it
+    /// is, i.e. any index it refers to is in range
+    fn fits(&self, _collection: &Self::Collection) -> bool {
+        true
+    }
+
+    /// Applies the operation to the collection, an operation which does not
+    /// fit the collection is skipped
+    fn apply(self, collection: &mut Self::Collection);
+}
+
+/// The shared part of collection Properties, which records each operation on
+/// the collection so that only the operations need to be synced.
+///
+/// Every operation increments a wrapping version number, which is sent along
+/// with the operations so that a receiver skips any it has already applied.
+/// As a User may not have received every operation, each update carries the
+/// whole log, which is kept short by clearing it once it is full and syncing
+/// the whole collection once instead.
+///
+/// Updates may arrive out of order, so a receiver holds on to operations
+/// which follow a version it hasn't reached yet, and applies them once the
+/// whole collection they follow on from has arrived.
+#[derive(Clone)]
+pub(crate) struct PropertyLog<O: CollectionOp> {
+    inner: O::Collection,
+    // the version after every operation in the log has been applied
+    version: u16,
+    // the version before the first operation in the log was applied
+    log_version: u16,
+    ops: Vec<O>,
+    // received operations which can't be applied yet, along with the
+    // version before the first of them
+    pending_ops: Option<(u16, Vec<O>)>,
+    mutator: Option<PropertyMutator>,
+    mutator_index: u8,
+}
+
+impl<O: CollectionOp> PropertyLog<O> {
+    pub fn new(value: O::Collection, mutator_index: u8) -> Self {
+        Self::new_versioned(value, 0, mutator_index)
+    }
+
+    fn new_versioned(value: O::Collection, version: u16, mutator_index: u8) -> Self {
+        Self {
+            inner: value,
+            version,
+            log_version: version,
+            ops: Vec::new(),
+            pending_ops: None,
+            mutator: None,
+            mutator_index,
+        }
+    }
+
+    pub fn inner(&self) -> &O::Collection {
+        &self.inner
+    }
+
+    /// Performs an operation on the collection and records it, queueing it
+    /// for update
+    pub fn record(&mut self, op: O) {
+        self.log(op);
+        self.mutate(OPS_BIT);
+    }
+
+    /// Replaces the whole collection, and queues it for update
+    pub fn replace(&mut self, value: O::Collection) {
+        self.inner = value;
+        self.version = self.version.wrapping_add(1);
+        self.clear_log();
+        self.mutate(FULL_BIT);
+    }
+
+    /// Set value to the value of another collection Property. Takes on the
+    /// other's version if changes to this one are not being tracked, so that
+    /// the two compare as equal when written
+    pub fn mirror(&mut self, other: &PropertyLog<O>) {
+        if self.mutator.is_none() {
+            self.inner = other.inner.clone();
+            self.version = other.version;
+            self.clear_log();
+        } else {
+            self.replace(other.inner.clone());
+        }
+    }
+
+    // Serialization / deserialization
+
+    /// Writes the whole collection into outgoing byte stream
+    pub fn write(&self, writer: &mut dyn BitWrite) {
+        self.version.ser(writer);
+        self.inner.ser(writer);
+    }
+
+    /// Writes the whole collection if it has been marked in the DiffMask,
+    /// otherwise the logged operations
+    pub fn write_update(&self, diff_mask: &DiffMask, parent_offset: u8, writer: &mut dyn BitWrite) {
+        let full_index = parent_offset + self.mutator_index + FULL_BIT;
+        let is_full = diff_mask.bit(full_index) == Some(true);
+        is_full.ser(writer);
+        if is_full {
+            self.write(writer);
+        } else {
+            self.log_version.ser(writer);
+            self.ops.ser(writer);
+        }
+    }
+
+    /// Returns whether the collection or its operations are marked in the
+    /// DiffMask
+    pub fn is_mutated(&self, diff_mask: &DiffMask, parent_offset: u8) -> bool {
+        let start = parent_offset + self.mutator_index;
+        diff_mask.bit(start + FULL_BIT) == Some(true)
+            || diff_mask.bit(start + OPS_BIT) == Some(true)
+    }
+
+    /// Given a cursor into incoming packet data, initializes the Property with
+    /// the synced collection
+    pub fn new_read(reader: &mut BitReader, mutator_index: u8) -> Result<Self, SerdeErr> {
+        let version = u16::de(reader)?;
+        let inner = O::Collection::de(reader)?;
+        Ok(Self::new_versioned(inner, version, mutator_index))
+    }
+
+    /// Reads an update from a stream and immediately writes it to a stream,
+    /// marking it in the DiffMask at `bit_index`. Used to buffer updates for
+    /// later
+    pub fn read_write(
+        bit_reader: &mut BitReader,
+        bit_writer: &mut BitWriter,
+        diff_mask: &mut DiffMask,
+        bit_index: u8,
+    ) -> Result<(), SerdeErr> {
+        let is_full = bool::de(bit_reader)?;
+        is_full.ser(bit_writer);
+        u16::de(bit_reader)?.ser(bit_writer);
+        if is_full {
+            O::Collection::de(bit_reader)?.ser(bit_writer);
+            diff_mask.set_bit(bit_index + FULL_BIT, true);
+        } else {
+            Vec::<O>::de(bit_reader)?.ser(bit_writer);
+            diff_mask.set_bit(bit_index + OPS_BIT, true);
+        }
+        Ok(())
+    }
+
+    /// Given a cursor into incoming packet data, applies the synced collection
+    /// or operations which are newer than those already applied
+    pub fn read(&mut self, reader: &mut BitReader) -> Result<(), SerdeErr> {
+        let is_full = bool::de(reader)?;
+        if is_full {
+            let version = u16::de(reader)?;
+            let inner = O::Collection::de(reader)?;
+            if sequence_greater_than(version, self.version) {
+                self.inner = inner;
+                self.version = version;
+                self.clear_log();
+            }
+        } else {
+            let op_version = u16::de(reader)?;
+            let ops = Vec::<O>::de(reader)?;
+            if let Some(ops) = self.apply_ops(op_version, ops, reader)? {
+                // an earlier operation is missing, as the whole collection
+                // which they follow on from hasn't arrived yet
+                self.hold_ops(op_version, ops);
+            }
+        }
+
+        if let Some((op_version, ops)) = self.pending_ops.take() {
+            if let Some(ops) = self.apply_ops(op_version, ops, reader)? {
+                self.pending_ops = Some((op_version, ops));
+            }
+        }
+        Ok(())
+    }
+
+    /// Applies the operations which follow on from the current version,
+    /// returning them all if an earlier operation is missing
+    fn apply_ops(
+        &mut self,
+        mut op_version: u16,
+        ops: Vec<O>,
+        reader: &BitReader,
+    ) -> Result<Option<Vec<O>>, SerdeErr> {
+        let last_version = op_version.wrapping_add(ops.len() as u16);
+        if !sequence_greater_than(last_version, self.version) {
+            // already applied
+            return Ok(None);
+        }
+        if sequence_greater_than(op_version, self.version) {
+            return Ok(Some(ops));
+        }
+
+        for op in ops {
+            op_version = op_version.wrapping_add(1);
+            if !sequence_greater_than(op_version, self.version) {
+                // already applied
+                continue;
+            }
+            if !op.fits(&self.inner) {
+                // the sender's collection can never have been in this
+                // state, so the update is malformed
+                return Err(SerdeErr::new(
+                    reader.bit_offset(),
+                    "CollectionOp",
+                    SerdeErrReason::InvalidValue,
+                ));
+            }
+            // kept in the log, in case the update is passed on
+            self.log(op);
+        }
+        Ok(None)
+    }
+
+    /// Holds on to operations which can't be applied yet, keeping only the
+    /// newest of those received
+    fn hold_ops(&mut self, op_version: u16, ops: Vec<O>) {
+        let last_version = op_version.wrapping_add(ops.len() as u16);
+        if let Some((pending_version, pending_ops)) = &self.pending_ops {
+            let pending_last_version = pending_version.wrapping_add(pending_ops.len() as u16);
+            if !sequence_greater_than(last_version, pending_last_version) {
+                return;
+            }
+        }
+        self.pending_ops = Some((op_version, ops));
+    }
+
+    // Internal
+
+    /// Set an PropertyMutator to track changes to the Property
+    pub fn set_mutator(&mut self, mutator: &PropertyMutator) {
+        self.mutator = Some(mutator.clone_new());
+    }
+
+    fn log(&mut self, op: O) {
+        op.clone().apply(&mut self.inner);
+        self.version = self.version.wrapping_add(1);
+        if self.ops.len() >= OP_LOG_SIZE {
+            self.clear_log();
+            self.mutate(FULL_BIT);
+        } else {
+            self.ops.push(op);
+        }
+    }
+
+    fn clear_log(&mut self) {
+        self.ops.clear();
+        self.log_version = self.version;
+    }
+
+    fn mutate(&mut self, bit: u8) {
+        if let Some(mutator) = &mut self.mutator {
+            mutator.mutate(self.mutator_index + bit);
+        }
+    }
+}
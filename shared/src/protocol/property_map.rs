@@ -0,0 +1,196 @@
+use std::{borrow::Borrow, collections::HashMap, hash::Hash, ops::Deref};
+
+use naia_serde::{BitReader, BitWrite, BitWriter, Serde, SerdeErr};
+
+use crate::protocol::{
+    diff_mask::DiffMask,
+    property_log::{CollectionOp, PropertyLog},
+    property_mutate::PropertyMutator,
+};
+
+/// A Property of an Component/Message, that contains a HashMap which is synced
+/// one operation at a time, rather than as a whole whenever it changes
+#[derive(Clone)]
+pub struct PropertyMap<K, V>
+where
+    K: Serde + Eq + Hash + Send + Sync + 'static,
+    V: Serde + Send + Sync + 'static,
+{
+    log: PropertyLog<MapOp<K, V>>,
+}
+
+impl<K, V> PropertyMap<K, V>
+where
+    K: Serde + Eq + Hash + Send + Sync + 'static,
+    V: Serde + Send + Sync + 'static,
+{
+    /// Create a new PropertyMap
+    pub fn new(value: HashMap<K, V>, mutator_index: u8) -> Self {
+        Self {
+            log: PropertyLog::new(value, mutator_index),
+        }
+    }
+
+    /// Inserts a key-value pair into the map, returning the value previously
+    /// held for the key, if any
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let old_value = self.get(&key).cloned();
+        self.log.record(MapOp::Insert(key, value));
+        old_value
+    }
+
+    /// Removes a key from the map, returning the value held for it, if any
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let (key, value) = self
+            .log
+            .inner()
+            .get_key_value(key)
+            .map(|(key, value)| (key.clone(), value.clone()))?;
+        self.log.record(MapOp::Remove(key));
+        Some(value)
+    }
+
+    /// Removes every key-value pair
+    pub fn clear(&mut self) {
+        self.log.replace(HashMap::new());
+    }
+
+    /// Replaces the whole HashMap, which is then synced as a whole
+    pub fn replace(&mut self, value: HashMap<K, V>) {
+        self.log.replace(value);
+    }
+
+    /// Set value to the value of another PropertyMap, queues for update
+    pub fn mirror(&mut self, other: &PropertyMap<K, V>) {
+        self.log.mirror(&other.log);
+    }
+
+    // Serialization / deserialization
+
+    /// Writes contained value into outgoing byte stream
+    pub fn write(&self, writer: &mut dyn BitWrite) {
+        self.log.write(writer);
+    }
+
+    /// Writes the operations performed since the last update into outgoing
+    /// byte stream, or the whole HashMap if it has been replaced.
+    /// `parent_offset` is where the containing struct's Properties start in
+    /// the DiffMask
+    pub fn write_update(&self, diff_mask: &DiffMask, parent_offset: u8, writer: &mut dyn BitWrite) {
+        self.log.write_update(diff_mask, parent_offset, writer);
+    }
+
+    /// Returns whether the PropertyMap is marked in the DiffMask
+    pub fn is_mutated(&self, diff_mask: &DiffMask, parent_offset: u8) -> bool {
+        self.log.is_mutated(diff_mask, parent_offset)
+    }
+
+    /// Given a cursor into incoming packet data, initializes the PropertyMap
+    /// with the synced value
+    pub fn new_read(reader: &mut BitReader, mutator_index: u8) -> Result<Self, SerdeErr> {
+        Ok(Self {
+            log: PropertyLog::new_read(reader, mutator_index)?,
+        })
+    }
+
+    /// Reads an update from a stream and immediately writes it to a stream,
+    /// marking it in the DiffMask at `bit_index`. Used to buffer updates for
+    /// later
+    pub fn read_write(
+        bit_reader: &mut BitReader,
+        bit_writer: &mut BitWriter,
+        diff_mask: &mut DiffMask,
+        bit_index: u8,
+    ) -> Result<(), SerdeErr> {
+        PropertyLog::<MapOp<K, V>>::read_write(bit_reader, bit_writer, diff_mask, bit_index)
+    }
+
+    /// Given a cursor into incoming packet data, updates the PropertyMap with
+    /// the synced operations
+    pub fn read(&mut self, reader: &mut BitReader) -> Result<(), SerdeErr> {
+        self.log.read(reader)
+    }
+
+    // Comparison
+
+    /// Compare to another PropertyMap
+    pub fn equals(&self, other: &PropertyMap<K, V>) -> bool {
+        self.log.inner() == other.log.inner()
+    }
+
+    // Internal
+
+    /// Set an PropertyMutator to track changes to the PropertyMap
+    pub fn set_mutator(&mut self, mutator: &PropertyMutator) {
+        self.log.set_mutator(mutator);
+    }
+}
+
+// Changes must go through the PropertyMap's methods so that they can be
+// recorded, so there is no DerefMut
+impl<K, V> Deref for PropertyMap<K, V>
+where
+    K: Serde + Eq + Hash + Send + Sync + 'static,
+    V: Serde + Send + Sync + 'static,
+{
+    type Target = HashMap<K, V>;
+
+    fn deref(&self) -> &Self::Target {
+        self.log.inner()
+    }
+}
+
+#[derive(Clone, PartialEq)]
+enum MapOp<K: Serde, V: Serde> {
+    Insert(K, V),
+    Remove(K),
+}
+
+impl<K, V> CollectionOp for MapOp<K, V>
+where
+    K: Serde + Eq + Hash + Send + Sync + 'static,
+    V: Serde + Send + Sync + 'static,
+{
+    type Collection = HashMap<K, V>;
+
+    fn apply(self, collection: &mut HashMap<K, V>) {
+        match self {
+            MapOp::Insert(key, value) => {
+                collection.insert(key, value);
+            }
+            MapOp::Remove(key) => {
+                collection.remove(&key);
+            }
+        }
+    }
+}
+
+impl<K: Serde, V: Serde> Serde for MapOp<K, V> {
+    fn ser(&self, writer: &mut dyn BitWrite) {
+        match self {
+            MapOp::Insert(key, value) => {
+                true.ser(writer);
+                key.ser(writer);
+                value.ser(writer);
+            }
+            MapOp::Remove(key) => {
+                false.ser(writer);
+                key.ser(writer);
+            }
+        }
+    }
+
+    fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+        let is_insert = bool::de(reader)?;
+        let key = K::de(reader)?;
+        if is_insert {
+            Ok(MapOp::Insert(key, V::de(reader)?))
+        } else {
+            Ok(MapOp::Remove(key))
+        }
+    }
+}
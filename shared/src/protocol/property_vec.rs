@@ -0,0 +1,277 @@
+use std::ops::Deref;
+
+use naia_serde::{
+    BitReader, BitWrite, BitWriter, Serde, SerdeErr, SerdeErrReason, UnsignedInteger,
+    UnsignedVariableInteger,
+};
+
+use crate::protocol::{
+    diff_mask::DiffMask,
+    property_log::{CollectionOp, PropertyLog},
+    property_mutate::PropertyMutator,
+};
+
+/// A Property of an Component/Message, that contains a Vec which is synced
+/// one operation at a time, rather than as a whole whenever it changes
+#[derive(Clone)]
+pub struct PropertyVec<T: Serde + Send + Sync + 'static> {
+    log: PropertyLog<VecOp<T>>,
+}
+
+impl<T: Serde + Send + Sync + 'static> PropertyVec<T> {
+    /// Create a new PropertyVec
+    pub fn new(value: Vec<T>, mutator_index: u8) -> Self {
+        Self {
+            log: PropertyLog::new(value, mutator_index),
+        }
+    }
+
+    /// Appends an element to the back of the Vec
+    pub fn push(&mut self, value: T) {
+        self.log.record(VecOp::Push(value));
+    }
+
+    /// Inserts an element at the given index, shifting all elements after it
+    /// to the right. Panics if `index > len`
+    pub fn insert(&mut self, index: usize, value: T) {
+        if index > self.len() {
+            panic!(
+                "insertion index (is {}) should be <= len (is {})",
+                index,
+                self.len()
+            );
+        }
+        self.log.record(VecOp::Insert(index, value));
+    }
+
+    /// Removes and returns the element at the given index, shifting all
+    /// elements after it to the left. Panics if `index` is out of bounds
+    pub fn remove(&mut self, index: usize) -> T {
+        let value = self[index].clone();
+        self.log.record(VecOp::Remove(index));
+        value
+    }
+
+    /// Replaces the element at the given index. Panics if `index` is out of
+    /// bounds
+    pub fn set(&mut self, index: usize, value: T) {
+        if index >= self.len() {
+            panic!(
+                "index out of bounds: the len is {} but the index is {}",
+                self.len(),
+                index
+            );
+        }
+        self.log.record(VecOp::Set(index, value));
+    }
+
+    /// Removes every element
+    pub fn clear(&mut self) {
+        self.log.replace(Vec::new());
+    }
+
+    /// Replaces the whole Vec, which is then synced as a whole
+    pub fn replace(&mut self, value: Vec<T>) {
+        self.log.replace(value);
+    }
+
+    /// Set value to the value of another PropertyVec, queues for update
+    pub fn mirror(&mut self, other: &PropertyVec<T>) {
+        self.log.mirror(&other.log);
+    }
+
+    // Serialization / deserialization
+
+    /// Writes contained value into outgoing byte stream
+    pub fn write(&self, writer: &mut dyn BitWrite) {
+        self.log.write(writer);
+    }
+
+    /// Writes the operations performed since the last update into outgoing
+    /// byte stream, or the whole Vec if it has been replaced. `parent_offset`
+    /// is where the containing struct's Properties start in the DiffMask
+    pub fn write_update(&self, diff_mask: &DiffMask, parent_offset: u8, writer: &mut dyn BitWrite) {
+        self.log.write_update(diff_mask, parent_offset, writer);
+    }
+
+    /// Returns whether the PropertyVec is marked in the DiffMask
+    pub fn is_mutated(&self, diff_mask: &DiffMask, parent_offset: u8) -> bool {
+        self.log.is_mutated(diff_mask, parent_offset)
+    }
+
+    /// Given a cursor into incoming packet data, initializes the PropertyVec
+    /// with the synced value
+    pub fn new_read(reader: &mut BitReader, mutator_index: u8) -> Result<Self, SerdeErr> {
+        Ok(Self {
+            log: PropertyLog::new_read(reader, mutator_index)?,
+        })
+    }
+
+    /// Reads an update from a stream and immediately writes it to a stream,
+    /// marking it in the DiffMask at `bit_index`. Used to buffer updates for
+    /// later
+    pub fn read_write(
+        bit_reader: &mut BitReader,
+        bit_writer: &mut BitWriter,
+        diff_mask: &mut DiffMask,
+        bit_index: u8,
+    ) -> Result<(), SerdeErr> {
+        PropertyLog::<VecOp<T>>::read_write(bit_reader, bit_writer, diff_mask, bit_index)
+    }
+
+    /// Given a cursor into incoming packet data, updates the PropertyVec with
+    /// the synced operations
+    pub fn read(&mut self, reader: &mut BitReader) -> Result<(), SerdeErr> {
+        self.log.read(reader)
+    }
+
+    // Comparison
+
+    /// Compare to another PropertyVec
+    pub fn equals(&self, other: &PropertyVec<T>) -> bool {
+        self.log.inner() == other.log.inner()
+    }
+
+    // Internal
+
+    /// Set an PropertyMutator to track changes to the PropertyVec
+    pub fn set_mutator(&mut self, mutator: &PropertyMutator) {
+        self.log.set_mutator(mutator);
+    }
+}
+
+// Changes must go through the PropertyVec's methods so that they can be
+// recorded, so there is no DerefMut
+impl<T: Serde + Send + Sync + 'static> Deref for PropertyVec<T> {
+    type Target = Vec<T>;
+
+    fn deref(&self) -> &Self::Target {
+        self.log.inner()
+    }
+}
+
+#[derive(Clone, PartialEq)]
+enum VecOp<T: Serde> {
+    Push(T),
+    Insert(usize, T),
+    Remove(usize),
+    Set(usize, T),
+}
+
+impl<T: Serde + Send + Sync + 'static> CollectionOp for VecOp<T> {
+    type Collection = Vec<T>;
+
+    fn fits(&self, collection: &Vec<T>) -> bool {
+        match self {
+            VecOp::Push(_) => true,
+            VecOp::Insert(index, _) => *index <= collection.len(),
+            VecOp::Remove(index) | VecOp::Set(index, _) => *index < collection.len(),
+        }
+    }
+
+    fn apply(self, collection: &mut Vec<T>) {
+        match self {
+            VecOp::Push(value) => collection.push(value),
+            VecOp::Insert(index, value) => {
+                if index <= collection.len() {
+                    collection.insert(index, value);
+                }
+            }
+            VecOp::Remove(index) => {
+                if index < collection.len() {
+                    collection.remove(index);
+                }
+            }
+            VecOp::Set(index, value) => {
+                if let Some(element) = collection.get_mut(index) {
+                    *element = value;
+                }
+            }
+        }
+    }
+}
+
+impl<T: Serde> Serde for VecOp<T> {
+    fn ser(&self, writer: &mut dyn BitWrite) {
+        match self {
+            VecOp::Push(value) => {
+                UnsignedInteger::<2>::new(0).ser(writer);
+                value.ser(writer);
+            }
+            VecOp::Insert(index, value) => {
+                UnsignedInteger::<2>::new(1).ser(writer);
+                UnsignedVariableInteger::<5>::new(*index as u64).ser(writer);
+                value.ser(writer);
+            }
+            VecOp::Remove(index) => {
+                UnsignedInteger::<2>::new(2).ser(writer);
+                UnsignedVariableInteger::<5>::new(*index as u64).ser(writer);
+            }
+            VecOp::Set(index, value) => {
+                UnsignedInteger::<2>::new(3).ser(writer);
+                UnsignedVariableInteger::<5>::new(*index as u64).ser(writer);
+                value.ser(writer);
+            }
+        }
+    }
+
+    fn de(reader: &mut BitReader) -> Result<Self, SerdeErr> {
+        let op_index = UnsignedInteger::<2>::de(reader)?.get();
+        if op_index == 0 {
+            return Ok(VecOp::Push(T::de(reader)?));
+        }
+
+        let index =
+            usize::try_from(UnsignedVariableInteger::<5>::de(reader)?.get()).map_err(|_| {
+                SerdeErr::new(reader.bit_offset(), "VecOp", SerdeErrReason::InvalidValue)
+            })?;
+        match op_index {
+            1 => Ok(VecOp::Insert(index, T::de(reader)?)),
+            2 => Ok(VecOp::Remove(index)),
+            _ => Ok(VecOp::Set(index, T::de(reader)?)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use naia_serde::{BitReader, BitWriter, Serde};
+
+    use super::{PropertyVec, VecOp};
+
+    // An update carrying the given operations, as the first ones after
+    // version 0
+    fn ops_update(ops: Vec<VecOp<u8>>) -> Box<[u8]> {
+        let mut writer = BitWriter::default();
+        false.ser(&mut writer);
+        0u16.ser(&mut writer);
+        ops.ser(&mut writer);
+        writer.to_bytes()
+    }
+
+    #[test]
+    fn in_range_ops_are_applied() {
+        let mut property = PropertyVec::<u8>::new(vec![1, 2], 0);
+        let bytes = ops_update(vec![
+            VecOp::Set(1, 9),
+            VecOp::Insert(2, 3),
+            VecOp::Remove(0),
+        ]);
+        property.read(&mut BitReader::new(&bytes)).unwrap();
+        assert_eq!(*property, vec![9, 3]);
+    }
+
+    #[test]
+    fn out_of_range_ops_are_rejected() {
+        for op in [VecOp::Set(2, 9), VecOp::Insert(3, 9), VecOp::Remove(2)] {
+            let mut property = PropertyVec::<u8>::new(vec![1, 2], 0);
+            let bytes = ops_update(vec![op]);
+            assert!(property.read(&mut BitReader::new(&bytes)).is_err());
+        }
+
+        // checked against the length left by the earlier operations
+        let mut property = PropertyVec::<u8>::new(vec![1, 2], 0);
+        let bytes = ops_update(vec![VecOp::Remove(0), VecOp::Set(1, 9)]);
+        assert!(property.read(&mut BitReader::new(&bytes)).is_err());
+    }
+}
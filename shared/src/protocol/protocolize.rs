@@ -1,8 +1,14 @@
 use std::{any::TypeId, hash::Hash};
 
-use naia_serde::{BitReader, BitWrite, Serde};
+use naia_serde::{BitReader, BitWrite, Serde, SerdeErr};
 
-use crate::{protocol::component_update::ComponentUpdate, DiffMask, NetEntityHandleConverter};
+use crate::{
+    protocol::{
+        component_update::ComponentUpdate,
+        property_baselines::{NoBaselines, PropertyBaselines},
+    },
+    DiffMask, NetEntityHandleConverter,
+};
 
 use super::{
     replica_ref::{ReplicaDynMut, ReplicaDynRef},
@@ -18,10 +24,30 @@ pub trait Protocolize: Clone + Sized + Sync + Send + 'static {
     fn kind_of<R: ReplicateSafe<Self>>() -> Self::Kind;
     /// Get kind from a type_id
     fn type_to_kind(type_id: TypeId) -> Option<Self::Kind>;
+    /// Get a stable hash of the kind id of every variant, whether those ids
+    /// were given with `#[protocol(id = N)]`, & the names, types and codecs of
+    /// each variant's Properties
+    fn protocol_hash() -> u64;
     /// Read from a bit stream to create a new Replica
-    fn read(bit_reader: &mut BitReader, converter: &dyn NetEntityHandleConverter) -> Self;
+    fn read(
+        bit_reader: &mut BitReader,
+        converter: &dyn NetEntityHandleConverter,
+    ) -> Result<Self, SerdeErr>;
     /// Read from a bit stream to create a new Component Update
-    fn read_create_update(bit_reader: &mut BitReader) -> ComponentUpdate<Self::Kind>;
+    fn read_create_update(
+        bit_reader: &mut BitReader,
+    ) -> Result<ComponentUpdate<Self::Kind>, SerdeErr> {
+        let kind = Self::Kind::de(bit_reader)?;
+        Self::read_create_update_of(&kind, bit_reader, &mut NoBaselines)
+    }
+    /// Read from a bit stream to create a new Component Update of the given
+    /// kind, which has already been read, reading delta-encoded Properties
+    /// against the given PropertyBaselines
+    fn read_create_update_of(
+        kind: &Self::Kind,
+        bit_reader: &mut BitReader,
+        baselines: &mut dyn PropertyBaselines,
+    ) -> Result<ComponentUpdate<Self::Kind>, SerdeErr>;
     /// Get an immutable reference to the inner Component/Message as a
     /// Replicate trait object
     fn dyn_ref(&self) -> ReplicaDynRef<'_, Self>;
@@ -5,6 +5,7 @@ use super::{
     diff_mask::DiffMask,
     entity_handle::EntityHandle,
     entity_property::NetEntityHandleConverter,
+    property_baselines::{NoBaselines, PropertyBaselines},
     property_mutate::PropertyMutator,
     protocolize::Protocolize,
     replica_ref::{ReplicaDynMut, ReplicaDynRef},
@@ -49,6 +50,18 @@ pub trait ReplicateSafe<P: Protocolize>: ReplicateInner {
         diff_mask: &DiffMask,
         bit_writer: &mut dyn BitWrite,
         converter: &dyn NetEntityHandleConverter,
+    ) {
+        self.write_update_against(diff_mask, bit_writer, converter, &mut NoBaselines);
+    }
+    /// Write data into an outgoing byte stream, sufficient only to update the
+    /// mutated Properties of the Message/Component on the client, writing
+    /// delta-encoded Properties against the given PropertyBaselines
+    fn write_update_against(
+        &self,
+        diff_mask: &DiffMask,
+        bit_writer: &mut dyn BitWrite,
+        converter: &dyn NetEntityHandleConverter,
+        baselines: &mut dyn PropertyBaselines,
     );
     /// Reads data from an incoming packet, sufficient to sync the in-memory
     /// Component with it's replica on the Server
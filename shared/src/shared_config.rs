@@ -4,8 +4,9 @@ use naia_socket_shared::SocketConfig;
 
 use crate::{
     connection::compression_config::CompressionConfig,
+    fingerprint::FingerprintHasher,
     messages::channel_config::{ChannelConfig, ChannelIndex, DefaultChannels},
-    Channel,
+    Channel, Protocolize,
 };
 
 /// Contains Config properties which will be shared by Server and Client
@@ -37,6 +38,16 @@ impl<C: ChannelIndex> SharedConfig<C> {
             compression,
         }
     }
+
+    /// Gets a stable hash of the given Protocol's layout combined with the
+    /// registered Channels. Client & Server compare this during the handshake
+    /// so that mismatched builds are refused rather than misreading packets
+    pub fn fingerprint<P: Protocolize>(&self) -> u64 {
+        let mut hasher = FingerprintHasher::default();
+        hasher.write_u64(P::protocol_hash());
+        hasher.write_u64(self.channel.fingerprint());
+        hasher.finish()
+    }
 }
 
 impl SharedConfig<DefaultChannels> {
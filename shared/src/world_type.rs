@@ -2,6 +2,7 @@ use crate::{ComponentUpdate, Replicate};
 
 use crate::protocol::{
     entity_property::NetEntityHandleConverter,
+    property_mutate::PropertyMutator,
     protocolize::{ProtocolInserter, Protocolize},
     replica_ref::{ReplicaDynRefWrapper, ReplicaMutWrapper, ReplicaRefWrapper},
     replicate::ReplicateSafe,
@@ -42,6 +43,10 @@ pub trait WorldMutType<P: Protocolize, E>: WorldRefType<P, E> + ProtocolInserter
     // Entities
     /// spawn an entity
     fn spawn_entity(&mut self) -> E;
+    /// spawn the entity whose components are the Server's Resources, which
+    /// is left out of `entities()`, & out of the World's own queries where
+    /// the World allows it
+    fn spawn_resource_entity(&mut self) -> E;
     /// duplicate an entity
     fn duplicate_entity(&mut self, entity: &E) -> E;
     /// make it so one entity has all the same components as another
@@ -65,6 +70,14 @@ pub trait WorldMutType<P: Protocolize, E>: WorldRefType<P, E> + ProtocolInserter
         component_kind: &P::Kind,
         update: ComponentUpdate<P::Kind>,
     );
+    /// sets the PropertyMutator of a component, which tracks changes made to
+    /// its Properties from then on
+    fn component_set_mutator(
+        &mut self,
+        entity: &E,
+        component_kind: &P::Kind,
+        mutator: &PropertyMutator,
+    );
     /// mirrors the whole state of two different entities
     /// (setting 1st entity's component to 2nd entity's component's state)
     fn mirror_entities(&mut self, mutable_entity: &E, immutable_entity: &E);
@@ -0,0 +1,225 @@
+mod some_types {
+    use naia_shared::{derive_serde, serde};
+
+    #[derive(Debug)]
+    #[derive_serde]
+    pub struct SomeAttributeStruct {
+        #[serde(bits = 5)]
+        pub some_small: u8,
+        #[serde(bits = 6)]
+        pub some_signed: i16,
+        #[serde(varint)]
+        pub some_count: u64,
+        #[serde(skip)]
+        pub some_local: String,
+        #[serde(quantize(-100, 100, 10))]
+        pub some_float: f32,
+    }
+
+    #[derive(Debug)]
+    #[derive_serde]
+    pub struct SomeAttributeTupleStruct(#[serde(bits = 3)] pub u16, #[serde(skip)] pub bool);
+
+    #[derive(Debug)]
+    #[derive_serde]
+    pub struct SomeWideVarintStruct(#[serde(varint)] pub u64);
+
+    #[derive(Debug)]
+    #[derive_serde]
+    pub struct SomeNarrowVarintStruct(#[serde(varint)] pub u8);
+
+    #[derive(Debug)]
+    #[derive_serde]
+    pub struct SomeFractionalStruct(#[serde(quantize(-0.5, 1.25, 8))] pub f32);
+
+    #[derive(Debug)]
+    #[derive_serde]
+    pub enum SomeAttributeEnum {
+        Variant1 {
+            #[serde(varint = 3)]
+            some_int: i32,
+            #[serde(skip)]
+            some_local: u32,
+        },
+        Variant2(#[serde(quantize(0, 1, 4))] f64, #[serde(skip)] u8),
+    }
+}
+
+use naia_shared::serde::{BitReader, BitWriter, Serde};
+
+use some_types::{
+    SomeAttributeEnum, SomeAttributeStruct, SomeAttributeTupleStruct, SomeFractionalStruct,
+    SomeNarrowVarintStruct, SomeWideVarintStruct,
+};
+
+#[test]
+fn read_write_attribute_struct() {
+    // Write
+    let mut writer = BitWriter::default();
+
+    let in_1 = SomeAttributeStruct {
+        some_small: 31,
+        some_signed: -63,
+        some_count: 1000,
+        some_local: "not sent".to_string(),
+        some_float: 50.0,
+    };
+
+    in_1.ser(&mut writer);
+
+    let (buffer_length, buffer) = writer.flush();
+
+    // 5 + (1 + 6) + (2 * (1 + 7)) + 10 bits
+    assert_eq!(buffer_length, 5);
+
+    // Read
+
+    let mut reader = BitReader::new(&buffer[..buffer_length]);
+
+    let out_1: SomeAttributeStruct = Serde::de(&mut reader).unwrap();
+
+    assert_eq!(out_1.some_small, 31);
+    assert_eq!(out_1.some_signed, -63);
+    assert_eq!(out_1.some_count, 1000);
+    assert_eq!(out_1.some_local, String::new());
+    assert!((out_1.some_float - 50.0).abs() < 0.2);
+}
+
+fn out_of_range_struct() -> SomeAttributeStruct {
+    SomeAttributeStruct {
+        some_small: 200,
+        some_signed: -1000,
+        some_count: 0,
+        some_local: String::new(),
+        some_float: 0.0,
+    }
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "doesn't fit into 5 bits")]
+fn out_of_range_bits_panic_in_debug() {
+    out_of_range_struct().ser(&mut BitWriter::default());
+}
+
+#[test]
+#[cfg(not(debug_assertions))]
+fn out_of_range_bits_are_clamped() {
+    // Write
+    let mut writer = BitWriter::default();
+
+    out_of_range_struct().ser(&mut writer);
+
+    let (buffer_length, buffer) = writer.flush();
+
+    // Read
+
+    let mut reader = BitReader::new(&buffer[..buffer_length]);
+
+    let out_1: SomeAttributeStruct = Serde::de(&mut reader).unwrap();
+
+    assert_eq!(out_1.some_small, 31);
+    assert_eq!(out_1.some_signed, -63);
+}
+
+#[test]
+fn values_too_large_for_type_are_an_error() {
+    // Write
+    let mut writer = BitWriter::default();
+
+    SomeWideVarintStruct(1000).ser(&mut writer);
+
+    let (buffer_length, buffer) = writer.flush();
+
+    // Read
+
+    let mut reader = BitReader::new(&buffer[..buffer_length]);
+
+    let out_1: Result<SomeNarrowVarintStruct, _> = Serde::de(&mut reader);
+
+    assert!(out_1.is_err());
+}
+
+#[test]
+fn read_write_attribute_tuple_struct() {
+    // Write
+    let mut writer = BitWriter::default();
+
+    let in_1 = SomeAttributeTupleStruct(5, true);
+
+    in_1.ser(&mut writer);
+
+    let (buffer_length, buffer) = writer.flush();
+
+    assert_eq!(buffer_length, 1);
+
+    // Read
+
+    let mut reader = BitReader::new(&buffer[..buffer_length]);
+
+    let out_1: SomeAttributeTupleStruct = Serde::de(&mut reader).unwrap();
+
+    assert_eq!(out_1, SomeAttributeTupleStruct(5, false));
+}
+
+#[test]
+fn read_write_fractional_quantize() {
+    // Write
+    let mut writer = BitWriter::default();
+
+    let in_1 = SomeFractionalStruct(0.3);
+    let in_2 = SomeFractionalStruct(-3.0);
+
+    in_1.ser(&mut writer);
+    in_2.ser(&mut writer);
+
+    let (buffer_length, buffer) = writer.flush();
+
+    // 8 + 8 bits
+    assert_eq!(buffer_length, 2);
+
+    // Read
+
+    let mut reader = BitReader::new(&buffer[..buffer_length]);
+
+    let out_1: SomeFractionalStruct = Serde::de(&mut reader).unwrap();
+    let out_2: SomeFractionalStruct = Serde::de(&mut reader).unwrap();
+
+    // precision of 1.75 / (2^8 - 1)
+    assert!((out_1.0 - 0.3).abs() < 0.004);
+    // clamped to the lower bound
+    assert_eq!(out_2.0, -0.5);
+}
+
+#[test]
+fn read_write_attribute_enum() {
+    // Write
+    let mut writer = BitWriter::default();
+
+    let in_1 = SomeAttributeEnum::Variant1 {
+        some_int: -20,
+        some_local: 7,
+    };
+    let in_2 = SomeAttributeEnum::Variant2(1.0, 9);
+
+    in_1.ser(&mut writer);
+    in_2.ser(&mut writer);
+
+    let (buffer_length, buffer) = writer.flush();
+
+    // Read
+
+    let mut reader = BitReader::new(&buffer[..buffer_length]);
+
+    let out_1: SomeAttributeEnum = Serde::de(&mut reader).unwrap();
+    let out_2: SomeAttributeEnum = Serde::de(&mut reader).unwrap();
+
+    assert_eq!(
+        out_1,
+        SomeAttributeEnum::Variant1 {
+            some_int: -20,
+            some_local: 0,
+        }
+    );
+    assert_eq!(out_2, SomeAttributeEnum::Variant2(1.0, 0));
+}
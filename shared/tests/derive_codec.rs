@@ -0,0 +1,417 @@
+mod some_protocol {
+    use super::{
+        counter_replica::Counter, score_replica::Score, some_replica::Position,
+        uncoded_replica::UncodedPosition,
+    };
+    use naia_shared::Protocolize;
+
+    #[derive(Protocolize)]
+    pub enum SomeProtocol {
+        Position(Position),
+        Counter(Counter),
+        Score(Score),
+        UncodedPosition(UncodedPosition),
+    }
+}
+
+mod codecs {
+    use naia_shared::{
+        serde::{
+            BitReader, BitWrite, Serde, SerdeErr, SerdeErrReason, SignedInteger,
+            SignedVariableInteger, UnsignedInteger,
+        },
+        PropertyCodec,
+    };
+
+    /// Writes a value between 0 & 25.5 in a single byte, to the nearest 0.1
+    pub struct TenthsCodec;
+
+    impl PropertyCodec<f32> for TenthsCodec {
+        fn encode(value: &f32, _baseline: Option<&f32>, writer: &mut dyn BitWrite) {
+            let tenths = (value * 10.0).round().clamp(0.0, 255.0) as u8;
+            UnsignedInteger::<8>::new(tenths).ser(writer);
+        }
+
+        fn decode(_baseline: Option<&f32>, reader: &mut BitReader) -> Result<f32, SerdeErr> {
+            let tenths = UnsignedInteger::<8>::de(reader)?.get();
+            Ok(tenths as f32 / 10.0)
+        }
+    }
+
+    /// Writes a value with as few bits as its magnitude allows
+    pub struct VariableCodec;
+
+    impl PropertyCodec<i32> for VariableCodec {
+        fn encode(value: &i32, _baseline: Option<&i32>, writer: &mut dyn BitWrite) {
+            SignedVariableInteger::<7>::new(*value).ser(writer);
+        }
+
+        fn decode(_baseline: Option<&i32>, reader: &mut BitReader) -> Result<i32, SerdeErr> {
+            let value = SignedVariableInteger::<7>::de(reader)?.get();
+            i32::try_from(value).map_err(|_| {
+                SerdeErr::new(
+                    reader.bit_offset(),
+                    "VariableCodec",
+                    SerdeErrReason::InvalidValue,
+                )
+            })
+        }
+    }
+
+    /// Writes a value as its difference from the baseline when that fits in a
+    /// byte, and in full otherwise
+    pub struct DeltaCodec;
+
+    impl PropertyCodec<i32> for DeltaCodec {
+        const DELTA: bool = true;
+
+        fn encode(value: &i32, baseline: Option<&i32>, writer: &mut dyn BitWrite) {
+            let delta = baseline
+                .map(|baseline| value.wrapping_sub(*baseline))
+                .filter(|delta| (-127..128).contains(delta));
+            delta.is_some().ser(writer);
+            match delta {
+                Some(delta) => SignedInteger::<7>::new(delta).ser(writer),
+                None => value.ser(writer),
+            }
+        }
+
+        fn decode(baseline: Option<&i32>, reader: &mut BitReader) -> Result<i32, SerdeErr> {
+            if !bool::de(reader)? {
+                return i32::de(reader);
+            }
+            let delta = SignedInteger::<7>::de(reader)?.get() as i32;
+            match baseline {
+                Some(baseline) => Ok(baseline.wrapping_add(delta)),
+                None => Err(SerdeErr::new(
+                    reader.bit_offset(),
+                    "DeltaCodec",
+                    SerdeErrReason::InvalidValue,
+                )),
+            }
+        }
+    }
+}
+
+mod some_replica {
+    use naia_shared::{Property, Replicate};
+
+    #[derive(Replicate)]
+    #[protocol_path = "super::some_protocol::SomeProtocol"]
+    pub struct Position {
+        #[property(codec = "super::codecs::TenthsCodec")]
+        pub x: Property<f32>,
+        pub y: Property<f32>,
+    }
+
+    impl Position {
+        pub fn new(x: f32, y: f32) -> Self {
+            Position::new_complete(x, y)
+        }
+    }
+}
+
+// The same Properties as Position, without a codec
+mod uncoded_replica {
+    use naia_shared::{Property, Replicate};
+
+    #[derive(Replicate)]
+    #[protocol_path = "super::some_protocol::SomeProtocol"]
+    pub struct UncodedPosition {
+        pub x: Property<f32>,
+        pub y: Property<f32>,
+    }
+}
+
+mod counter_replica {
+    use naia_shared::{Property, Replicate};
+
+    #[derive(Replicate)]
+    #[protocol_path = "super::some_protocol::SomeProtocol"]
+    pub struct Counter {
+        #[property(codec = "super::codecs::VariableCodec")]
+        pub count: Property<i32>,
+    }
+
+    impl Counter {
+        pub fn new(count: i32) -> Self {
+            Counter::new_complete(count)
+        }
+    }
+}
+
+mod score_replica {
+    use naia_shared::{Property, Replicate};
+
+    #[derive(Replicate)]
+    #[protocol_path = "super::some_protocol::SomeProtocol"]
+    pub struct Score {
+        #[property(codec = "super::codecs::DeltaCodec")]
+        pub points: Property<i32>,
+    }
+
+    impl Score {
+        pub fn new(points: i32) -> Self {
+            Score::new_complete(points)
+        }
+    }
+}
+
+use naia_shared::{
+    serde::{BitReader, BitWrite, BitWriter},
+    DiffMask, FakeEntityConverter, NoBaselines, Property, PropertyBaselines, Protocolize,
+    ReplicateSafe,
+};
+
+use codecs::{DeltaCodec, VariableCodec};
+use counter_replica::Counter;
+use score_replica::Score;
+use some_protocol::SomeProtocol;
+use some_replica::Position;
+use uncoded_replica::UncodedPosition;
+
+#[test]
+fn codec_is_used_to_write_and_read() {
+    // Write
+    let mut writer = BitWriter::default();
+
+    let in_1 = SomeProtocol::Position(Position::new(1.23, 4.56));
+
+    in_1.write(&mut writer, &FakeEntityConverter);
+
+    let (buffer_length, buffer) = writer.flush();
+
+    // Read
+
+    let mut reader = BitReader::new(&buffer[..buffer_length]);
+
+    let out_1 = SomeProtocol::read(&mut reader, &FakeEntityConverter).unwrap();
+
+    let typed_out_1 = out_1.cast_ref::<Position>().unwrap();
+    assert_eq!(*typed_out_1.x, 1.2);
+    assert_eq!(*typed_out_1.y, 4.56);
+}
+
+#[test]
+fn codec_is_used_for_updates() {
+    // Write
+    let mut writer = BitWriter::default();
+
+    let in_1 = Position::new(7.77, 0.0);
+
+    let mut diff_mask = DiffMask::new(1);
+    diff_mask.set_bit(0, true);
+
+    in_1.write_update(&diff_mask, &mut writer, &FakeEntityConverter);
+
+    let (buffer_length, buffer) = writer.flush();
+
+    // Read
+
+    let mut reader = BitReader::new(&buffer[..buffer_length]);
+
+    let update = Position::read_create_update(&mut reader).unwrap();
+
+    let mut out_1 = Position::new(0.0, 0.0);
+    out_1.read_apply_update(&FakeEntityConverter, update);
+
+    assert_eq!(*out_1.x, 7.8);
+    assert_eq!(*out_1.y, 0.0);
+}
+
+#[test]
+fn codec_changes_layout_hash() {
+    assert_ne!(Position::layout_hash(), UncodedPosition::layout_hash());
+}
+
+#[test]
+fn variable_codec_writes_small_values_compactly() {
+    // Write
+    let mut writer = BitWriter::default();
+
+    let in_1 = Property::new(10, 0);
+    in_1.write_with::<VariableCodec>(&mut writer);
+
+    // a sign bit & a single group, rather than a whole i32
+    assert_eq!(writer.bit_count(), 9);
+
+    let (buffer_length, buffer) = writer.flush();
+
+    // Read
+
+    let mut reader = BitReader::new(&buffer[..buffer_length]);
+
+    let mut out_1 = Property::new(0, 0);
+    out_1.read_with::<VariableCodec>(&mut reader).unwrap();
+
+    assert_eq!(*out_1, 10);
+}
+
+#[test]
+fn codec_is_used_when_buffering() {
+    // Write
+    let mut writer = BitWriter::default();
+
+    let in_1 = Property::new(1000, 0);
+    in_1.write_with::<VariableCodec>(&mut writer);
+
+    let (buffer_length, buffer) = writer.flush();
+
+    // Buffer
+
+    let mut reader = BitReader::new(&buffer[..buffer_length]);
+    let mut buffer_writer = BitWriter::default();
+
+    Property::<i32>::read_write_update_with::<VariableCodec>(
+        0,
+        &mut NoBaselines,
+        &mut reader,
+        &mut buffer_writer,
+    )
+    .unwrap();
+
+    let (buffered_length, buffered) = buffer_writer.flush();
+
+    // Read
+
+    let mut reader = BitReader::new(&buffered[..buffered_length]);
+
+    let out_1 = Property::<i32>::new_read_with::<VariableCodec>(&mut reader, 0).unwrap();
+
+    assert_eq!(*out_1, 1000);
+}
+
+#[test]
+fn codec_is_used_for_counter_updates() {
+    // Write
+    let mut writer = BitWriter::default();
+
+    let in_1 = Counter::new(5000);
+
+    let mut diff_mask = DiffMask::new(1);
+    diff_mask.set_bit(0, true);
+
+    in_1.write_update(&diff_mask, &mut writer, &FakeEntityConverter);
+
+    let (buffer_length, buffer) = writer.flush();
+
+    // Read
+
+    let mut reader = BitReader::new(&buffer[..buffer_length]);
+
+    let update = Counter::read_create_update(&mut reader).unwrap();
+
+    let mut out_1 = Counter::new(0);
+    out_1.read_apply_update(&FakeEntityConverter, update);
+
+    assert_eq!(*out_1.count, 5000);
+}
+
+fn full_bytes(value: i32) -> Box<[u8]> {
+    let mut writer = BitWriter::default();
+    Property::new(value, 0).write_with::<DeltaCodec>(&mut writer);
+    writer.to_bytes()
+}
+
+/// Holds a single value of the first Property, sent two packets ago
+struct TwoPacketsAgo {
+    value: Box<[u8]>,
+    recorded: Option<Box<[u8]>>,
+}
+
+impl TwoPacketsAgo {
+    fn new(value: i32) -> Self {
+        Self {
+            value: full_bytes(value),
+            recorded: None,
+        }
+    }
+}
+
+impl PropertyBaselines for TwoPacketsAgo {
+    fn baseline(&self, property_index: u8) -> Option<(u16, &[u8])> {
+        (property_index == 0).then(|| (2, &self.value[..]))
+    }
+
+    fn baseline_at(&self, property_index: u8, distance: u16) -> Option<&[u8]> {
+        (property_index == 0 && distance == 2).then(|| &self.value[..])
+    }
+
+    fn record(&mut self, _: u8, value: Box<[u8]>) {
+        self.recorded = Some(value);
+    }
+}
+
+fn score_update(score: &Score, baselines: &mut dyn PropertyBaselines) -> BitWriter {
+    let mut writer = BitWriter::default();
+
+    let mut diff_mask = DiffMask::new(1);
+    diff_mask.set_bit(0, true);
+
+    score.write_update_against(&diff_mask, &mut writer, &FakeEntityConverter, baselines);
+
+    writer
+}
+
+#[test]
+fn delta_codec_update_is_written_against_baseline() {
+    // Write
+    let mut sent_baselines = TwoPacketsAgo::new(990);
+    let mut writer = score_update(&Score::new(1000), &mut sent_baselines);
+
+    // the update flag, the baseline flag & distance, then a flag & a byte,
+    // rather than a whole i32
+    assert_eq!(writer.bit_count(), 1 + 1 + 5 + 1 + 8);
+
+    // the value written is kept in full, to be written against once acked
+    assert_eq!(sent_baselines.recorded, Some(full_bytes(1000)));
+
+    let (buffer_length, buffer) = writer.flush();
+
+    // Read, against the same baseline
+
+    let mut reader = BitReader::new(&buffer[..buffer_length]);
+    let mut received_baselines = TwoPacketsAgo::new(990);
+
+    let update = Score::read_create_update_against(&mut reader, &mut received_baselines).unwrap();
+
+    // the update is buffered in full, so is applied whatever value is held
+    let mut out_1 = Score::new(0);
+    out_1.read_apply_update(&FakeEntityConverter, update);
+
+    assert_eq!(*out_1.points, 1000);
+    assert_eq!(received_baselines.recorded, sent_baselines.recorded);
+}
+
+#[test]
+fn delta_codec_update_without_baseline_is_written_in_full() {
+    // Write
+    let mut writer = score_update(&Score::new(1000), &mut NoBaselines);
+
+    let (buffer_length, buffer) = writer.flush();
+
+    // Read
+
+    let mut reader = BitReader::new(&buffer[..buffer_length]);
+
+    let update = Score::read_create_update(&mut reader).unwrap();
+
+    let mut out_1 = Score::new(0);
+    out_1.read_apply_update(&FakeEntityConverter, update);
+
+    assert_eq!(*out_1.points, 1000);
+}
+
+#[test]
+fn delta_codec_update_without_its_baseline_is_rejected() {
+    // Write
+    let mut writer = score_update(&Score::new(1000), &mut TwoPacketsAgo::new(990));
+
+    let (buffer_length, buffer) = writer.flush();
+
+    // Read, without the baseline it was written against
+
+    let mut reader = BitReader::new(&buffer[..buffer_length]);
+
+    assert!(Score::read_create_update(&mut reader).is_err());
+}
@@ -0,0 +1,192 @@
+mod some_protocol {
+    use super::some_replica::Inventory;
+    use naia_shared::Protocolize;
+
+    #[derive(Protocolize)]
+    pub enum SomeProtocol {
+        Inventory(Inventory),
+    }
+}
+
+mod some_replica {
+    use std::collections::HashMap;
+
+    use naia_shared::{Property, PropertyMap, PropertyVec, Replicate};
+
+    #[derive(Replicate)]
+    #[protocol_path = "super::some_protocol::SomeProtocol"]
+    pub struct Inventory {
+        pub gold: Property<u32>,
+        pub items: PropertyVec<u16>,
+        pub buffs: PropertyMap<u8, u32>,
+    }
+
+    impl Inventory {
+        pub fn new(items: Vec<u16>) -> Self {
+            Inventory::new_complete(0, items, HashMap::new())
+        }
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+use naia_shared::{
+    serde::{BitReader, BitWriter},
+    ComponentUpdate, DiffMask, FakeEntityConverter, PropertyMutate, PropertyMutator, Protocolize,
+    ReplicateSafe,
+};
+
+use some_protocol::{SomeProtocol, SomeProtocolKind};
+use some_replica::Inventory;
+
+#[derive(Clone)]
+struct MaskMutator {
+    diff_mask: Arc<Mutex<DiffMask>>,
+}
+
+impl PropertyMutate for MaskMutator {
+    fn mutate(&mut self, property_index: u8) {
+        self.diff_mask.lock().unwrap().set_bit(property_index, true);
+    }
+}
+
+// Creates an Inventory whose changes are recorded in the returned DiffMask
+fn tracked_inventory(items: Vec<u16>) -> (Inventory, Arc<Mutex<DiffMask>>) {
+    let mut inventory = Inventory::new(items);
+    let diff_mask = Arc::new(Mutex::new(DiffMask::new(inventory.diff_mask_size())));
+    inventory.set_mutator(&PropertyMutator::new(MaskMutator {
+        diff_mask: diff_mask.clone(),
+    }));
+    (inventory, diff_mask)
+}
+
+// Copies a replica as a receiver would when it enters scope
+fn receive_copy(inventory: &Inventory) -> Inventory {
+    let mut writer = BitWriter::default();
+    inventory.write(&mut writer, &FakeEntityConverter);
+    let (length, buffer) = writer.flush();
+    let mut reader = BitReader::new(&buffer[..length]);
+    let protocol = SomeProtocol::read(&mut reader, &FakeEntityConverter).unwrap();
+    match protocol {
+        SomeProtocol::Inventory(inventory) => inventory,
+    }
+}
+
+fn update(inventory: &Inventory, diff_mask: &DiffMask) -> ComponentUpdate<SomeProtocolKind> {
+    let mut writer = BitWriter::default();
+    inventory.write_update(diff_mask, &mut writer, &FakeEntityConverter);
+    let (length, buffer) = writer.flush();
+    let mut reader = BitReader::new(&buffer[..length]);
+    Inventory::read_create_update(&mut reader).unwrap()
+}
+
+#[test]
+fn collection_takes_two_bits() {
+    let (mut inventory, diff_mask) = tracked_inventory(vec![1, 2, 3]);
+
+    inventory.items.push(4);
+    inventory.buffs.insert(7, 100);
+
+    let diff_mask = diff_mask.lock().unwrap();
+    // gold is bit 0, items are bits 1 & 2, buffs are bits 3 & 4
+    assert_eq!(diff_mask.bit(0), Some(false));
+    assert_eq!(diff_mask.bit(1), Some(false));
+    assert_eq!(diff_mask.bit(2), Some(true));
+    assert_eq!(diff_mask.bit(3), Some(false));
+    assert_eq!(diff_mask.bit(4), Some(true));
+}
+
+#[test]
+fn operations_are_applied_remotely() {
+    let (mut inventory, diff_mask) = tracked_inventory(vec![1, 2, 3]);
+    let mut remote = receive_copy(&inventory);
+
+    inventory.items.push(4);
+    inventory.items.remove(0);
+    inventory.items.set(1, 9);
+    inventory.items.insert(0, 5);
+    inventory.buffs.insert(7, 100);
+    inventory.buffs.insert(8, 200);
+    inventory.buffs.remove(&7);
+
+    let update = update(&inventory, &diff_mask.lock().unwrap());
+    remote.read_apply_update(&FakeEntityConverter, update);
+
+    assert_eq!(*remote.items, vec![5, 2, 9, 4]);
+    assert_eq!(remote.buffs.len(), 1);
+    assert_eq!(remote.buffs.get(&8), Some(&200));
+}
+
+#[test]
+fn resent_operations_are_not_applied_twice() {
+    let (mut inventory, diff_mask) = tracked_inventory(vec![]);
+    let mut remote = receive_copy(&inventory);
+
+    inventory.items.push(1);
+    let first_update = update(&inventory, &diff_mask.lock().unwrap());
+
+    inventory.items.push(2);
+    let second_update = update(&inventory, &diff_mask.lock().unwrap());
+
+    // the second update carries both operations, and arrives first
+    remote.read_apply_update(&FakeEntityConverter, second_update);
+    remote.read_apply_update(&FakeEntityConverter, first_update);
+
+    assert_eq!(*remote.items, vec![1, 2]);
+}
+
+#[test]
+fn full_log_syncs_whole_collection() {
+    let (mut inventory, diff_mask) = tracked_inventory(vec![]);
+    let mut remote = receive_copy(&inventory);
+
+    for item in 0..40 {
+        inventory.items.push(item);
+    }
+    // the log was cleared, so the whole collection must be sent
+    assert_eq!(diff_mask.lock().unwrap().bit(1), Some(true));
+
+    let update = update(&inventory, &diff_mask.lock().unwrap());
+    remote.read_apply_update(&FakeEntityConverter, update);
+
+    assert_eq!(*remote.items, (0..40).collect::<Vec<u16>>());
+}
+
+#[test]
+fn missing_operations_are_skipped() {
+    let (mut inventory, _) = tracked_inventory(vec![]);
+    let mut remote = receive_copy(&inventory);
+
+    // a replace was never received, so the operations after it can't apply
+    inventory.items.replace(vec![1, 2]);
+    inventory.items.push(3);
+
+    let mut ops_only = DiffMask::new(inventory.diff_mask_size());
+    ops_only.set_bit(2, true);
+    let update = update(&inventory, &ops_only);
+    remote.read_apply_update(&FakeEntityConverter, update);
+
+    assert!(remote.items.is_empty());
+}
+
+#[test]
+fn operations_arriving_before_the_full_collection_are_applied() {
+    let (mut inventory, diff_mask) = tracked_inventory(vec![]);
+    let mut remote = receive_copy(&inventory);
+
+    // fills the log, so it is cleared & the whole collection sent
+    for item in 0..20 {
+        inventory.items.push(item);
+    }
+    let full_update = update(&inventory, &diff_mask.lock().unwrap());
+    *diff_mask.lock().unwrap() = DiffMask::new(inventory.diff_mask_size());
+
+    inventory.items.push(20);
+    let ops_update = update(&inventory, &diff_mask.lock().unwrap());
+
+    // the operations arrive first, and are never sent again once acked
+    remote.read_apply_update(&FakeEntityConverter, ops_update);
+    remote.read_apply_update(&FakeEntityConverter, full_update);
+
+    assert_eq!(*remote.items, (0..21).collect::<Vec<u16>>());
+}
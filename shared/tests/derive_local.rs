@@ -0,0 +1,125 @@
+mod some_protocol {
+    use super::some_replica::Unit;
+    use naia_shared::Protocolize;
+
+    #[derive(Protocolize)]
+    pub enum SomeProtocol {
+        Unit(Unit),
+    }
+}
+
+mod some_replica {
+    use naia_shared::{Property, Replicate};
+
+    #[derive(Replicate)]
+    #[protocol_path = "super::some_protocol::SomeProtocol"]
+    pub struct Unit {
+        pub health: Property<u8>,
+        #[replicate(skip)]
+        pub think_timer: f32,
+        pub name: Property<String>,
+        #[replicate(skip)]
+        pub mesh: Option<String>,
+    }
+
+    impl Unit {
+        pub fn new(health: u8, name: &str) -> Self {
+            Unit::new_complete(health, name.to_string())
+        }
+    }
+}
+
+use naia_shared::{
+    serde::{BitReader, BitWriter},
+    DiffMask, FakeEntityConverter, Protocolize, ReplicateSafe,
+};
+
+use some_protocol::SomeProtocol;
+use some_replica::Unit;
+
+fn local_unit() -> Unit {
+    let mut unit = Unit::new(100, "knight");
+    unit.think_timer = 2.5;
+    unit.mesh = Some("knight.glb".to_string());
+    unit
+}
+
+#[test]
+fn local_fields_are_not_written() {
+    let unit = local_unit();
+
+    // Write
+    let mut writer = BitWriter::default();
+
+    unit.write(&mut writer, &FakeEntityConverter);
+
+    let (buffer_length, buffer) = writer.flush();
+
+    // Read
+
+    let mut reader = BitReader::new(&buffer[..buffer_length]);
+
+    let out_1 = SomeProtocol::read(&mut reader, &FakeEntityConverter).unwrap();
+
+    let typed_out_1 = out_1.cast_ref::<Unit>().unwrap();
+    assert_eq!(*typed_out_1.health, 100);
+    assert_eq!(*typed_out_1.name, "knight".to_string());
+    assert_eq!(typed_out_1.think_timer, 0.0);
+    assert_eq!(typed_out_1.mesh, None);
+}
+
+#[test]
+fn local_fields_are_not_in_diff_mask() {
+    let unit = local_unit();
+
+    // health is bit 0, name is bit 1
+    let mut diff_mask = DiffMask::new(unit.diff_mask_size());
+    diff_mask.set_bit(1, true);
+
+    // Write
+    let mut writer = BitWriter::default();
+
+    unit.write_update(&diff_mask, &mut writer, &FakeEntityConverter);
+
+    let (buffer_length, buffer) = writer.flush();
+
+    // Read
+
+    let mut reader = BitReader::new(&buffer[..buffer_length]);
+
+    let update = Unit::read_create_update(&mut reader).unwrap();
+
+    let mut out_1 = Unit::new(50, "");
+    out_1.think_timer = 1.0;
+    out_1.read_apply_update(&FakeEntityConverter, update);
+
+    assert_eq!(*out_1.health, 50);
+    assert_eq!(*out_1.name, "knight".to_string());
+    assert_eq!(out_1.think_timer, 1.0);
+}
+
+#[test]
+fn mirror_preserves_local_fields() {
+    let unit = local_unit();
+
+    let mut other = Unit::new(10, "archer");
+    other.think_timer = 7.0;
+
+    other.mirror(&SomeProtocol::Unit(unit));
+
+    assert_eq!(*other.health, 100);
+    assert_eq!(*other.name, "knight".to_string());
+    assert_eq!(other.think_timer, 7.0);
+    assert_eq!(other.mesh, None);
+}
+
+#[test]
+fn clone_copies_local_fields() {
+    let unit = local_unit();
+
+    let clone = unit.clone();
+
+    assert_eq!(*clone.health, 100);
+    assert_eq!(clone.think_timer, 2.5);
+    assert_eq!(clone.mesh, Some("knight.glb".to_string()));
+}
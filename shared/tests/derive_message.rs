@@ -0,0 +1,130 @@
+mod some_protocol {
+    use super::some_message::{Chat, Follow, Ping};
+    use naia_shared::Protocolize;
+
+    #[derive(Protocolize)]
+    pub enum SomeProtocol {
+        Chat(Chat),
+        Follow(Follow),
+        Ping(Ping),
+    }
+}
+
+mod some_message {
+    use naia_shared::{EntityProperty, Message};
+
+    #[derive(Message)]
+    #[protocol_path = "super::some_protocol::SomeProtocol"]
+    pub struct Chat {
+        pub sender: String,
+        pub text: String,
+        pub color: Option<u8>,
+    }
+
+    #[derive(Message)]
+    #[protocol_path = "super::some_protocol::SomeProtocol"]
+    pub struct Follow {
+        pub distance: u16,
+        pub target: EntityProperty,
+    }
+
+    #[derive(Message)]
+    #[protocol_path = "super::some_protocol::SomeProtocol"]
+    pub struct Ping;
+}
+
+use naia_shared::{
+    serde::{BitReader, BitWriter},
+    BigMapKey, EntityHandle, EntityHandleConverter, FakeEntityConverter, NetEntity,
+    NetEntityHandleConverter, Protocolize, ReplicateSafe,
+};
+
+// Uses an Entity's id as its EntityHandle & NetEntity
+struct IdConverter;
+
+impl EntityHandleConverter<u16> for IdConverter {
+    fn handle_to_entity(&self, entity_handle: &EntityHandle) -> u16 {
+        entity_handle.to_u64() as u16
+    }
+
+    fn entity_to_handle(&self, entity: &u16) -> EntityHandle {
+        EntityHandle::from_u64(*entity as u64)
+    }
+}
+
+impl NetEntityHandleConverter for IdConverter {
+    fn handle_to_net_entity(&self, entity_handle: &EntityHandle) -> NetEntity {
+        NetEntity::from(entity_handle.to_u64() as u16)
+    }
+
+    fn net_entity_to_handle(&self, net_entity: &NetEntity) -> Option<EntityHandle> {
+        Some(EntityHandle::from_u64(u16::from(*net_entity) as u64))
+    }
+}
+
+use some_message::{Chat, Follow, Ping};
+use some_protocol::SomeProtocol;
+
+#[test]
+fn read_write_message() {
+    // Write
+    let mut writer = BitWriter::default();
+
+    let in_1 = Chat {
+        sender: "alice".to_string(),
+        text: "hello".to_string(),
+        color: Some(3),
+    };
+    let in_2 = Ping;
+
+    in_1.write(&mut writer, &FakeEntityConverter);
+    in_2.write(&mut writer, &FakeEntityConverter);
+
+    let (buffer_length, buffer) = writer.flush();
+
+    // Read
+
+    let mut reader = BitReader::new(&buffer[..buffer_length]);
+
+    let out_1 = SomeProtocol::read(&mut reader, &FakeEntityConverter).unwrap();
+    let out_2 = SomeProtocol::read(&mut reader, &FakeEntityConverter).unwrap();
+
+    let typed_out_1 = out_1.cast_ref::<Chat>().unwrap();
+    assert_eq!(typed_out_1.sender, "alice");
+    assert_eq!(typed_out_1.text, "hello");
+    assert_eq!(typed_out_1.color, Some(3));
+    assert!(out_2.cast_ref::<Ping>().is_some());
+}
+
+#[test]
+fn message_has_no_diff_mask() {
+    let message = Chat::new_complete("alice".to_string(), "hello".to_string(), None);
+
+    assert_eq!(message.diff_mask_size(), 0);
+    assert!(!message.has_entity_properties());
+}
+
+#[test]
+fn message_entity_properties() {
+    let mut message = Follow::new_complete(5);
+    message.target.set(&IdConverter, &42);
+    assert!(message.has_entity_properties());
+    assert!(message.entities() == vec![EntityHandle::from_u64(42)]);
+
+    // Write
+    let mut writer = BitWriter::default();
+
+    message.write(&mut writer, &IdConverter);
+
+    let (buffer_length, buffer) = writer.flush();
+
+    // Read
+
+    let mut reader = BitReader::new(&buffer[..buffer_length]);
+
+    let out_1 = SomeProtocol::read(&mut reader, &IdConverter).unwrap();
+
+    let typed_out_1 = out_1.cast_ref::<Follow>().unwrap();
+    assert_eq!(typed_out_1.distance, 5);
+    assert_eq!(typed_out_1.target.get(&IdConverter), Some(42));
+}
@@ -0,0 +1,132 @@
+mod some_protocol {
+    use super::some_replica::Transform;
+    use naia_shared::Protocolize;
+
+    #[derive(Protocolize)]
+    pub enum SomeProtocol {
+        Transform(Transform),
+    }
+}
+
+mod some_replica {
+    use naia_shared::{NestedProperty, NestedReplicate, Property, Replicate};
+
+    #[derive(NestedReplicate)]
+    pub struct Vector {
+        pub x: Property<f32>,
+        pub y: Property<f32>,
+    }
+
+    impl Vector {
+        pub fn new(x: f32, y: f32) -> Self {
+            Vector::new_complete(x, y)
+        }
+    }
+
+    #[derive(Replicate)]
+    #[protocol_path = "super::some_protocol::SomeProtocol"]
+    pub struct Transform {
+        pub position: NestedProperty<Vector>,
+        pub rotation: Property<f32>,
+    }
+
+    impl Transform {
+        pub fn new(x: f32, y: f32, rotation: f32) -> Self {
+            Transform::new_complete(Vector::new(x, y), rotation)
+        }
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+use naia_shared::{
+    serde::{BitReader, BitWriter, Serde},
+    DiffMask, FakeEntityConverter, PropertyMutate, PropertyMutator, Protocolize, ReplicateSafe,
+};
+
+use some_protocol::SomeProtocol;
+use some_replica::Transform;
+
+#[derive(Clone)]
+struct RecordingMutator {
+    mutated: Arc<Mutex<Vec<u8>>>,
+}
+
+impl PropertyMutate for RecordingMutator {
+    fn mutate(&mut self, property_index: u8) {
+        self.mutated.lock().unwrap().push(property_index);
+    }
+}
+
+#[test]
+fn read_write_nested() {
+    // Write
+    let mut writer = BitWriter::default();
+
+    let in_1 = SomeProtocol::Transform(Transform::new(1.0, 2.0, 3.0));
+
+    in_1.write(&mut writer, &FakeEntityConverter);
+
+    let (buffer_length, buffer) = writer.flush();
+
+    // Read
+
+    let mut reader = BitReader::new(&buffer[..buffer_length]);
+
+    let out_1 = SomeProtocol::read(&mut reader, &FakeEntityConverter).unwrap();
+
+    let typed_out_1 = out_1.cast_ref::<Transform>().unwrap();
+    assert_eq!(*typed_out_1.position.x, 1.0);
+    assert_eq!(*typed_out_1.position.y, 2.0);
+    assert_eq!(*typed_out_1.rotation, 3.0);
+}
+
+#[test]
+fn nested_properties_take_a_bit_each() {
+    let mutated = Arc::new(Mutex::new(Vec::new()));
+    let mut transform = Transform::new(1.0, 2.0, 3.0);
+    transform.set_mutator(&PropertyMutator::new(RecordingMutator {
+        mutated: mutated.clone(),
+    }));
+
+    *transform.position.y = 5.0;
+    *transform.rotation = 6.0;
+
+    assert_eq!(transform.diff_mask_size(), 1);
+    assert_eq!(*mutated.lock().unwrap(), vec![1, 2]);
+}
+
+#[test]
+fn update_only_changed_nested_property() {
+    // Write
+    let mut writer = BitWriter::default();
+
+    let in_1 = SomeProtocol::Transform(Transform::new(1.0, 2.0, 3.0));
+
+    let mut diff_mask = DiffMask::new(1);
+    diff_mask.set_bit(1, true);
+
+    in_1.dyn_ref().kind().ser(&mut writer);
+    in_1.write_update(&diff_mask, &mut writer, &FakeEntityConverter);
+
+    let (buffer_length, buffer) = writer.flush();
+
+    // Read
+
+    let mut reader = BitReader::new(&buffer[..buffer_length]);
+
+    let update = SomeProtocol::read_create_update(&mut reader).unwrap();
+
+    assert_eq!(update.diff_mask().bit(0), Some(false));
+    assert_eq!(update.diff_mask().bit(1), Some(true));
+    assert_eq!(update.diff_mask().bit(2), Some(false));
+
+    // Apply
+
+    let mut out_1 = Transform::new(0.0, 0.0, 0.0);
+    out_1.read_apply_update(&FakeEntityConverter, update);
+
+    assert_eq!(*out_1.position.x, 0.0);
+    assert_eq!(*out_1.position.y, 2.0);
+    assert_eq!(*out_1.rotation, 0.0);
+}
@@ -0,0 +1,209 @@
+// Builds of the same Protocol, where the second has reordered its variants,
+// & the third has added a new one
+mod v1 {
+    pub mod some_protocol {
+        use super::some_replica::{Health, Position};
+        use naia_shared::Protocolize;
+
+        #[derive(Protocolize)]
+        pub enum SomeProtocol {
+            #[protocol(id = 1)]
+            Position(Position),
+            #[protocol(id = 2)]
+            Health(Health),
+        }
+    }
+
+    pub mod some_replica {
+        pub use health::Health;
+        pub use position::Position;
+
+        mod position {
+            use naia_shared::{Property, Replicate};
+
+            #[derive(Replicate)]
+            #[protocol_path = "super::super::some_protocol::SomeProtocol"]
+            pub struct Position {
+                pub x: Property<i16>,
+            }
+        }
+
+        mod health {
+            use naia_shared::{Property, Replicate};
+
+            #[derive(Replicate)]
+            #[protocol_path = "super::super::some_protocol::SomeProtocol"]
+            pub struct Health {
+                pub value: Property<u8>,
+            }
+        }
+    }
+}
+
+mod v2 {
+    pub mod some_protocol {
+        use super::some_replica::{Health, Position};
+        use naia_shared::Protocolize;
+
+        #[derive(Protocolize)]
+        pub enum SomeProtocol {
+            #[protocol(id = 2)]
+            Health(Health),
+            #[protocol(id = 1)]
+            Position(Position),
+        }
+    }
+
+    pub mod some_replica {
+        pub use health::Health;
+        pub use position::Position;
+
+        mod health {
+            use naia_shared::{Property, Replicate};
+
+            #[derive(Replicate)]
+            #[protocol_path = "super::super::some_protocol::SomeProtocol"]
+            pub struct Health {
+                pub value: Property<u8>,
+            }
+        }
+
+        mod position {
+            use naia_shared::{Property, Replicate};
+
+            #[derive(Replicate)]
+            #[protocol_path = "super::super::some_protocol::SomeProtocol"]
+            pub struct Position {
+                pub x: Property<i16>,
+            }
+        }
+    }
+}
+
+mod v3 {
+    pub mod some_protocol {
+        use super::some_replica::{Name, Position};
+        use naia_shared::Protocolize;
+
+        #[derive(Protocolize)]
+        pub enum SomeProtocol {
+            #[protocol(id = 1)]
+            Position(Position),
+            #[protocol(id = 300)]
+            Name(Name),
+        }
+    }
+
+    pub mod some_replica {
+        pub use name::Name;
+        pub use position::Position;
+
+        mod position {
+            use naia_shared::{Property, Replicate};
+
+            #[derive(Replicate)]
+            #[protocol_path = "super::super::some_protocol::SomeProtocol"]
+            pub struct Position {
+                pub x: Property<i16>,
+            }
+        }
+
+        mod name {
+            use naia_shared::{Property, Replicate};
+
+            #[derive(Replicate)]
+            #[protocol_path = "super::super::some_protocol::SomeProtocol"]
+            pub struct Name {
+                pub value: Property<String>,
+            }
+        }
+    }
+}
+
+use naia_shared::{
+    serde::{BitReader, BitWriter, Serde},
+    FakeEntityConverter, Protocolize,
+};
+
+#[test]
+fn reordered_variants_are_read_by_id() {
+    // Write
+    let mut writer = BitWriter::default();
+
+    let in_1 = v1::some_protocol::SomeProtocol::Health(v1::some_replica::Health::new_complete(7));
+    let in_2 =
+        v1::some_protocol::SomeProtocol::Position(v1::some_replica::Position::new_complete(-3));
+
+    in_1.write(&mut writer, &FakeEntityConverter);
+    in_2.write(&mut writer, &FakeEntityConverter);
+
+    let (buffer_length, buffer) = writer.flush();
+
+    // Read
+
+    let mut reader = BitReader::new(&buffer[..buffer_length]);
+
+    let out_1 = v2::some_protocol::SomeProtocol::read(&mut reader, &FakeEntityConverter).unwrap();
+    let out_2 = v2::some_protocol::SomeProtocol::read(&mut reader, &FakeEntityConverter).unwrap();
+
+    let typed_out_1 = out_1.cast_ref::<v2::some_replica::Health>().unwrap();
+    assert_eq!(*typed_out_1.value, 7);
+    let typed_out_2 = out_2.cast_ref::<v2::some_replica::Position>().unwrap();
+    assert_eq!(*typed_out_2.x, -3);
+}
+
+#[test]
+fn large_id_round_trips() {
+    use v3::some_protocol::{SomeProtocol, SomeProtocolKind};
+
+    // Write
+    let mut writer = BitWriter::default();
+
+    let in_1 = SomeProtocol::Name(v3::some_replica::Name::new_complete("hi".to_string()));
+
+    in_1.dyn_ref().kind().ser(&mut writer);
+
+    let (buffer_length, buffer) = writer.flush();
+
+    // Read
+
+    let mut reader = BitReader::new(&buffer[..buffer_length]);
+
+    let out_1 = SomeProtocolKind::de(&mut reader).unwrap();
+
+    assert_eq!(out_1, SomeProtocolKind::Name);
+}
+
+#[test]
+fn unknown_id_is_an_error() {
+    use v1::some_protocol::SomeProtocolKind;
+
+    // Write
+    let mut writer = BitWriter::default();
+
+    v3::some_protocol::SomeProtocolKind::Name.ser(&mut writer);
+
+    let (buffer_length, buffer) = writer.flush();
+
+    // Read
+
+    let mut reader = BitReader::new(&buffer[..buffer_length]);
+
+    assert!(SomeProtocolKind::de(&mut reader).is_err());
+}
+
+#[test]
+fn variant_order_does_not_change_protocol_hash() {
+    assert_eq!(
+        v1::some_protocol::SomeProtocol::protocol_hash(),
+        v2::some_protocol::SomeProtocol::protocol_hash()
+    );
+}
+
+#[test]
+fn added_variant_changes_protocol_hash() {
+    assert_ne!(
+        v1::some_protocol::SomeProtocol::protocol_hash(),
+        v3::some_protocol::SomeProtocol::protocol_hash()
+    );
+}
@@ -0,0 +1,141 @@
+mod some_struct {
+    use naia_shared::{
+        derive_serde, serde,
+        serde::{SerdeBoundedInteger, SerdeFloat},
+    };
+
+    #[derive(Debug)]
+    #[derive_serde]
+    pub struct SomeQuantizedStruct {
+        pub some_float: SerdeFloat<-100, 100, 12>,
+        pub some_int: SerdeBoundedInteger<-50, 50>,
+        pub some_bool: bool,
+    }
+}
+
+mod some_protocol {
+    use super::some_replica::QuantizedHolder;
+    use naia_shared::Protocolize;
+
+    #[derive(Protocolize)]
+    pub enum SomeProtocol {
+        QuantizedHolder(QuantizedHolder),
+    }
+}
+
+mod some_replica {
+    use naia_shared::{
+        serde::{SerdeBoundedInteger, SerdeFloat},
+        Property, Replicate,
+    };
+
+    #[derive(Replicate)]
+    #[protocol_path = "super::some_protocol::SomeProtocol"]
+    pub struct QuantizedHolder {
+        pub some_float: Property<SerdeFloat<-100, 100, 12>>,
+        pub some_int: Property<SerdeBoundedInteger<-50, 50>>,
+    }
+
+    impl QuantizedHolder {
+        pub fn new(some_float: f32, some_int: i64) -> Self {
+            QuantizedHolder::new_complete(
+                SerdeFloat::new(some_float),
+                SerdeBoundedInteger::new(some_int),
+            )
+        }
+    }
+}
+
+use naia_shared::{
+    serde::{BitReader, BitWriter, Serde, SerdeBoundedInteger, SerdeFloat},
+    DiffMask, FakeEntityConverter, Protocolize, ReplicateSafe,
+};
+
+use some_protocol::SomeProtocol;
+use some_replica::QuantizedHolder;
+use some_struct::SomeQuantizedStruct;
+
+#[test]
+fn read_write_quantized_struct() {
+    // Write
+    let mut writer = BitWriter::default();
+
+    let in_1 = SomeQuantizedStruct {
+        some_float: SerdeFloat::new(42.42),
+        some_int: SerdeBoundedInteger::new(-42),
+        some_bool: true,
+    };
+    let in_2 = SomeQuantizedStruct {
+        some_float: SerdeFloat::new(-99.9),
+        some_int: SerdeBoundedInteger::new(50),
+        some_bool: false,
+    };
+
+    in_1.ser(&mut writer);
+    in_2.ser(&mut writer);
+
+    let (buffer_length, buffer) = writer.flush();
+
+    // (12 + 7 + 1) * 2 bits
+    assert_eq!(buffer_length, 5);
+
+    // Read
+
+    let mut reader = BitReader::new(&buffer[..buffer_length]);
+
+    let out_1 = Serde::de(&mut reader).unwrap();
+    let out_2 = Serde::de(&mut reader).unwrap();
+
+    assert_eq!(in_1, out_1);
+    assert_eq!(in_2, out_2);
+}
+
+#[test]
+fn read_write_quantized_properties() {
+    let holder = QuantizedHolder::new(42.42, -42);
+
+    // Write
+    let mut writer = BitWriter::default();
+
+    holder.write(&mut writer, &FakeEntityConverter);
+
+    let (buffer_length, buffer) = writer.flush();
+
+    // Read
+
+    let mut reader = BitReader::new(&buffer[..buffer_length]);
+
+    let out_1 = SomeProtocol::read(&mut reader, &FakeEntityConverter).unwrap();
+
+    let typed_out_1 = out_1.cast_ref::<QuantizedHolder>().unwrap();
+    assert!(holder.some_float.equals(&typed_out_1.some_float));
+    assert!(holder.some_int.equals(&typed_out_1.some_int));
+}
+
+#[test]
+fn read_apply_quantized_property_update() {
+    let holder = QuantizedHolder::new(-99.9, 50);
+
+    // some_float is bit 0, some_int is bit 1
+    let mut diff_mask = DiffMask::new(holder.diff_mask_size());
+    diff_mask.set_bit(1, true);
+
+    // Write
+    let mut writer = BitWriter::default();
+
+    holder.write_update(&diff_mask, &mut writer, &FakeEntityConverter);
+
+    let (buffer_length, buffer) = writer.flush();
+
+    // Read
+
+    let mut reader = BitReader::new(&buffer[..buffer_length]);
+
+    let update = QuantizedHolder::read_create_update(&mut reader).unwrap();
+
+    let mut out_1 = QuantizedHolder::new(0.0, 0);
+    out_1.read_apply_update(&FakeEntityConverter, update);
+
+    assert_eq!(*out_1.some_float, SerdeFloat::new(0.0));
+    assert_eq!(*out_1.some_int, SerdeBoundedInteger::new(50));
+}
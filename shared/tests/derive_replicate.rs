@@ -25,9 +25,11 @@ mod some_replica {
     }
 }
 
+use std::sync::{Arc, Mutex};
+
 use naia_shared::{
-    serde::{BitReader, BitWriter},
-    FakeEntityConverter, Protocolize,
+    serde::{BitReader, BitWriter, Serde},
+    DiffMask, FakeEntityConverter, PropertyMutate, PropertyMutator, Protocolize, ReplicateSafe,
 };
 
 use some_protocol::SomeProtocol;
@@ -48,7 +50,7 @@ fn read_write_protocol() {
 
     let mut reader = BitReader::new(&buffer[..buffer_length]);
 
-    let out_1 = SomeProtocol::read(&mut reader, &FakeEntityConverter);
+    let out_1 = SomeProtocol::read(&mut reader, &FakeEntityConverter).unwrap();
 
     let typed_in_1 = in_1.cast_ref::<StringHolder>().unwrap();
     let typed_out_1 = out_1.cast_ref::<StringHolder>().unwrap();
@@ -59,3 +61,55 @@ fn read_write_protocol() {
     assert_eq!(*typed_out_1.string_1, "hello world".to_string());
     assert_eq!(*typed_out_1.string_2, "goodbye world".to_string());
 }
+
+#[test]
+fn read_update_diff_mask() {
+    // Write
+    let mut writer = BitWriter::default();
+
+    let in_1 = SomeProtocol::StringHolder(StringHolder::new("hello world", "goodbye world"));
+
+    let mut diff_mask = DiffMask::new(1);
+    diff_mask.set_bit(1, true);
+
+    in_1.dyn_ref().kind().ser(&mut writer);
+    in_1.write_update(&diff_mask, &mut writer, &FakeEntityConverter);
+
+    let (buffer_length, buffer) = writer.flush();
+
+    // Read
+
+    let mut reader = BitReader::new(&buffer[..buffer_length]);
+
+    let update = SomeProtocol::read_create_update(&mut reader).unwrap();
+
+    assert_eq!(update.diff_mask().bit(0), Some(false));
+    assert_eq!(update.diff_mask().bit(1), Some(true));
+}
+
+#[derive(Clone)]
+struct CountingMutator {
+    count: Arc<Mutex<usize>>,
+}
+
+impl PropertyMutate for CountingMutator {
+    fn mutate(&mut self, _: u8) {
+        *self.count.lock().unwrap() += 1;
+    }
+}
+
+#[test]
+fn set_equal_value_does_not_mutate() {
+    let count = Arc::new(Mutex::new(0));
+    let mut holder = StringHolder::new("hello world", "goodbye world");
+    holder.set_mutator(&PropertyMutator::new(CountingMutator {
+        count: count.clone(),
+    }));
+
+    holder.string_1.set("hello world".to_string());
+    assert_eq!(*count.lock().unwrap(), 0);
+
+    holder.string_1.set("hello again".to_string());
+    assert_eq!(*count.lock().unwrap(), 1);
+    assert_eq!(*holder.string_1, "hello again".to_string());
+}
@@ -0,0 +1,14 @@
+use naia_shared::{Property, Replicate};
+
+#[derive(Replicate)]
+#[protocol_path = "crate::protocol::Protocol"]
+pub struct Counter {
+    #[property(codec = "crate::delta_codec::DeltaCodec")]
+    pub count: Property<i32>,
+}
+
+impl Counter {
+    pub fn new(count: i32) -> Self {
+        Counter::new_complete(count)
+    }
+}
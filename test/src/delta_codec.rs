@@ -0,0 +1,46 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use naia_shared::{
+    serde::{BitReader, BitWrite, Serde, SerdeErr, SerdeErrReason, SignedInteger},
+    PropertyCodec,
+};
+
+/// How many values `DeltaCodec` has read against a baseline
+pub static DELTAS_READ: AtomicUsize = AtomicUsize::new(0);
+
+/// Writes a value as its difference from the baseline when that fits in a
+/// byte, and in full otherwise
+pub struct DeltaCodec;
+
+impl PropertyCodec<i32> for DeltaCodec {
+    const DELTA: bool = true;
+
+    fn encode(value: &i32, baseline: Option<&i32>, writer: &mut dyn BitWrite) {
+        let delta = baseline
+            .map(|baseline| value.wrapping_sub(*baseline))
+            .filter(|delta| (-127..128).contains(delta));
+        delta.is_some().ser(writer);
+        match delta {
+            Some(delta) => SignedInteger::<7>::new(delta).ser(writer),
+            None => value.ser(writer),
+        }
+    }
+
+    fn decode(baseline: Option<&i32>, reader: &mut BitReader) -> Result<i32, SerdeErr> {
+        if !bool::de(reader)? {
+            return i32::de(reader);
+        }
+        let delta = SignedInteger::<7>::de(reader)?.get() as i32;
+        match baseline {
+            Some(baseline) => {
+                DELTAS_READ.fetch_add(1, Ordering::Relaxed);
+                Ok(baseline.wrapping_add(delta))
+            }
+            None => Err(SerdeErr::new(
+                reader.bit_offset(),
+                "DeltaCodec",
+                SerdeErrReason::InvalidValue,
+            )),
+        }
+    }
+}
@@ -1,5 +1,18 @@
 mod auth;
+mod counter;
+mod delta_codec;
+mod local;
+mod position;
 mod protocol;
+mod score;
 
 pub use auth::Auth;
+pub use counter::Counter;
+pub use delta_codec::{DeltaCodec, DELTAS_READ};
+pub use local::{
+    client_x, run_until, set_x, spawn_on_client, wait_for_acks, wait_for_entity_count, LocalClient,
+    LocalServer, TestClient, TestClientEvent, TestServer, TestServerEvent, TestWorld,
+};
+pub use position::Position;
 pub use protocol::{Protocol, ProtocolKind};
+pub use score::Score;
@@ -0,0 +1,269 @@
+use std::{
+    net::{SocketAddr, UdpSocket},
+    thread,
+    time::{Duration, Instant},
+};
+
+use naia_client::{Client, ClientConfig, Event as ClientEvent, NaiaClientError};
+use naia_demo_world::{Entity, World};
+use naia_server::{
+    Event as ServerEvent, NaiaServerError, RoomKey, Server, ServerAddrs, ServerConfig, UserKey,
+};
+use naia_shared::{ConnectionConfig, DefaultChannels, ReplicateSafe, SharedConfig, WorldRefType};
+
+use crate::{Auth, Position, Protocol};
+
+pub type TestServer = Server<Protocol, Entity, DefaultChannels>;
+pub type TestClient = Client<Protocol, Entity, DefaultChannels>;
+pub type TestWorld = World<Protocol>;
+pub type TestServerEvent = Result<ServerEvent<Protocol, DefaultChannels>, NaiaServerError>;
+pub type TestClientEvent = Result<ClientEvent<Protocol, Entity, DefaultChannels>, NaiaClientError>;
+
+// How long `run_until` waits before failing the test
+const RUN_TIMEOUT: Duration = Duration::from_secs(10);
+
+// How long `wait_for_acks` updates both ends for
+const ACK_WAIT: Duration = Duration::from_millis(100);
+
+/// Calls `step` until it returns true, panicking if that takes too long
+pub fn run_until<F: FnMut() -> bool>(mut step: F) {
+    let start = Instant::now();
+    while !step() {
+        if start.elapsed() > RUN_TIMEOUT {
+            panic!("condition was not met within {:?}", RUN_TIMEOUT);
+        }
+        thread::sleep(Duration::from_millis(1));
+    }
+}
+
+/// Spawns an Entity with a Position into the Room, returning it once the
+/// Client has spawned it too, and the Server has heard back that it has
+pub fn spawn_on_client(server: &mut LocalServer, client: &mut LocalClient) -> Entity {
+    let entity = server.spawn(Position::new(0, 0));
+    run_until(|| {
+        server.update();
+        client
+            .update()
+            .iter()
+            .any(|event| matches!(event, Ok(ClientEvent::SpawnEntity(_))))
+    });
+    wait_for_acks(server, client);
+    entity
+}
+
+/// Updates both ends for long enough that the Server hears back about the
+/// packets the Client has read. The Client only acks a packet once it has
+/// read it, which may be a little after it arrives
+pub fn wait_for_acks(server: &mut LocalServer, client: &mut LocalClient) {
+    let start = Instant::now();
+    run_until(|| {
+        server.update();
+        client.update();
+        start.elapsed() >= ACK_WAIT
+    });
+}
+
+/// Updates both ends until the Client holds the given number of Entities
+pub fn wait_for_entity_count(server: &mut LocalServer, client: &mut LocalClient, count: usize) {
+    run_until(|| {
+        server.update();
+        client.update();
+        client.client.entities(&client.world.proxy()).len() == count
+    });
+}
+
+/// Sets the x of a Server Entity's Position
+pub fn set_x(server: &mut LocalServer, entity: &Entity, x: i16) {
+    *server
+        .server
+        .entity_mut(server.world.proxy_mut(), entity)
+        .component::<Position>()
+        .unwrap()
+        .x = x;
+}
+
+/// Gets the x of the Position of the Client's first Entity, if it has one
+pub fn client_x(client: &LocalClient) -> Option<i16> {
+    let client_entity = client.client.entities(&client.world.proxy())[0];
+    client
+        .world
+        .proxy()
+        .component::<Position>(&client_entity)
+        .map(|position| *position.x)
+}
+
+/// A Server listening on a free local port, along with its World and a Room
+/// every connected User is put into
+pub struct LocalServer {
+    pub server: TestServer,
+    pub world: TestWorld,
+    pub room_key: RoomKey,
+    pub address: SocketAddr,
+    shared_config: SharedConfig<DefaultChannels>,
+}
+
+impl LocalServer {
+    /// Config used by both ends of a local connection, ticking quickly so
+    /// that tests don't spend long waiting
+    pub fn shared_config() -> SharedConfig<DefaultChannels> {
+        let mut shared_config = SharedConfig::default();
+        shared_config.tick_interval = Some(Duration::from_millis(10));
+        shared_config
+    }
+
+    pub fn server_config() -> ServerConfig {
+        ServerConfig {
+            require_auth: false,
+            connection: Self::connection_config(),
+            ..Default::default()
+        }
+    }
+
+    // Heartbeats carry acknowledgements, so send them often enough that the
+    // other end learns quickly what has been delivered
+    fn connection_config() -> ConnectionConfig {
+        ConnectionConfig {
+            heartbeat_interval: Duration::from_millis(10),
+            ..Default::default()
+        }
+    }
+
+    pub fn start() -> Self {
+        Self::start_with(&Self::server_config(), &Self::shared_config())
+    }
+
+    pub fn start_with(
+        server_config: &ServerConfig,
+        shared_config: &SharedConfig<DefaultChannels>,
+    ) -> Self {
+        // let the OS pick a free port
+        let address = UdpSocket::bind("127.0.0.1:0")
+            .and_then(|socket| socket.local_addr())
+            .expect("unable to find a free local port");
+
+        let mut server = TestServer::new(server_config, shared_config);
+        server.listen(&ServerAddrs::new(
+            address,
+            address,
+            &format!("http://{}", address),
+        ));
+        let room_key = server.make_room().key();
+
+        Self {
+            server,
+            world: TestWorld::default(),
+            room_key,
+            address,
+            shared_config: shared_config.clone(),
+        }
+    }
+
+    /// Connects a new Client, returning it once both sides have seen the
+    /// connection & its User has been put into the Room
+    pub fn connect(&mut self) -> (LocalClient, UserKey) {
+        self.connect_with(&self.shared_config.clone())
+    }
+
+    pub fn connect_with(
+        &mut self,
+        shared_config: &SharedConfig<DefaultChannels>,
+    ) -> (LocalClient, UserKey) {
+        let mut client = LocalClient::connect(self.address, shared_config);
+
+        let mut user_key_opt = None;
+        let mut client_connected = false;
+        run_until(|| {
+            for event in self.update() {
+                if let Ok(ServerEvent::Connection(user_key)) = event {
+                    user_key_opt = Some(user_key);
+                }
+            }
+            for event in client.update() {
+                if let Ok(ClientEvent::Connection(_)) = event {
+                    client_connected = true;
+                }
+            }
+            user_key_opt.is_some() && client_connected
+        });
+
+        let user_key = user_key_opt.unwrap();
+        self.server.room_mut(&self.room_key).add_user(&user_key);
+
+        (client, user_key)
+    }
+
+    /// Spawns an Entity with the given Component into the Room
+    pub fn spawn<R: ReplicateSafe<Protocol>>(&mut self, component: R) -> Entity {
+        let entity = self
+            .server
+            .spawn_entity(self.world.proxy_mut())
+            .insert_component(component)
+            .id();
+        self.server.room_mut(&self.room_key).add_entity(&entity);
+        entity
+    }
+
+    /// Receives from & sends to every Client, including every Entity the
+    /// Server asks about in the scope of the User it shares a Room with
+    pub fn update(&mut self) -> Vec<TestServerEvent> {
+        let events = self.server.receive().into_iter().collect();
+        for (_, user_key, entity) in self.server.pending_scope_checks() {
+            self.server.user_scope(&user_key).include(&entity);
+        }
+        self.server.send_all_updates(self.world.proxy());
+        events
+    }
+}
+
+/// A Client connected to a LocalServer, along with its World
+pub struct LocalClient {
+    pub client: TestClient,
+    pub world: TestWorld,
+}
+
+impl LocalClient {
+    /// Starts connecting to the given address, which needn't be a
+    /// LocalServer's, without waiting for the connection to be made
+    pub fn connect(address: SocketAddr, shared_config: &SharedConfig<DefaultChannels>) -> Self {
+        let mut client = Self::new_client(shared_config);
+        client.connect(&format!("http://{}", address));
+
+        Self {
+            client,
+            world: TestWorld::default(),
+        }
+    }
+
+    /// Starts connecting like `connect`, sending the given Auth message for
+    /// the Server to authorize
+    pub fn connect_with_auth(
+        address: SocketAddr,
+        shared_config: &SharedConfig<DefaultChannels>,
+        auth: Auth,
+    ) -> Self {
+        let mut client = Self::new_client(shared_config);
+        client.auth(auth);
+        client.connect(&format!("http://{}", address));
+
+        Self {
+            client,
+            world: TestWorld::default(),
+        }
+    }
+
+    fn new_client(shared_config: &SharedConfig<DefaultChannels>) -> TestClient {
+        let client_config = ClientConfig {
+            connection: LocalServer::connection_config(),
+            send_handshake_interval: Duration::from_millis(10),
+            ..Default::default()
+        };
+        TestClient::new(&client_config, shared_config)
+    }
+
+    pub fn update(&mut self) -> Vec<TestClientEvent> {
+        self.client
+            .receive(self.world.proxy_mut())
+            .into_iter()
+            .collect()
+    }
+}
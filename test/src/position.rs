@@ -0,0 +1,21 @@
+use naia_shared::{Lerp, Property, Replicate};
+
+#[derive(Replicate)]
+#[protocol_path = "crate::protocol::Protocol"]
+pub struct Position {
+    pub x: Property<i16>,
+    pub y: Property<i16>,
+}
+
+impl Position {
+    pub fn new(x: i16, y: i16) -> Self {
+        Position::new_complete(x, y)
+    }
+}
+
+impl Lerp for Position {
+    fn lerp(&self, other: &Self, fraction: f32) -> Self {
+        let lerp = |from: i16, to: i16| (f32::from(from).lerp(&f32::from(to), fraction)) as i16;
+        Position::new(lerp(*self.x, *other.x), lerp(*self.y, *other.y))
+    }
+}
@@ -1,8 +1,11 @@
 use naia_shared::Protocolize;
 
-use super::auth::Auth;
+use super::{auth::Auth, counter::Counter, position::Position, score::Score};
 
 #[derive(Protocolize)]
 pub enum Protocol {
     Auth(Auth),
+    Counter(Counter),
+    Position(Position),
+    Score(Score),
 }
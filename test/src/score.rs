@@ -0,0 +1,13 @@
+use naia_shared::{Property, Replicate};
+
+#[derive(Replicate)]
+#[protocol_path = "crate::protocol::Protocol"]
+pub struct Score {
+    pub points: Property<u32>,
+}
+
+impl Score {
+    pub fn new(points: u32) -> Self {
+        Score::new_complete(points)
+    }
+}
@@ -0,0 +1,98 @@
+use std::time::{Duration, Instant};
+
+use naia_client::Event as ClientEvent;
+use naia_demo_world::Entity;
+use naia_shared::{WorldMutType, WorldRefType};
+use naia_test::{client_x, run_until, LocalClient, LocalServer, Position};
+
+// Updates every end once, applying the changes of Users with authority
+fn update_all(server: &mut LocalServer, clients: &mut [&mut LocalClient]) -> Vec<bool> {
+    server.update();
+    server
+        .server
+        .apply_authority_updates(server.world.proxy_mut());
+    clients
+        .iter_mut()
+        .map(|client| {
+            client
+                .update()
+                .iter()
+                .any(|event| matches!(event, Ok(ClientEvent::SpawnEntity(_))))
+        })
+        .collect()
+}
+
+fn server_x(server: &LocalServer, entity: &Entity) -> i16 {
+    *server
+        .world
+        .proxy()
+        .component::<Position>(entity)
+        .unwrap()
+        .x
+}
+
+fn set_client_x(client: &mut LocalClient, x: i16) {
+    let client_entity = client.client.entities(&client.world.proxy())[0];
+    let mut world = client.world.proxy_mut();
+    *world.component_mut::<Position>(&client_entity).unwrap().x = x;
+}
+
+#[test]
+fn granted_changes_are_applied_until_revoked() {
+    let mut server = LocalServer::start();
+    let (mut holder, holder_key) = server.connect();
+    let (mut observer, _) = server.connect();
+    let entity = server.spawn(Position::new(0, 0));
+
+    let mut spawned = [false, false];
+    run_until(|| {
+        let received = update_all(&mut server, &mut [&mut holder, &mut observer]);
+        for (spawned, received) in spawned.iter_mut().zip(received) {
+            *spawned |= received;
+        }
+        spawned == [true, true]
+    });
+
+    // grant
+    server
+        .server
+        .entity_mut(server.world.proxy_mut(), &entity)
+        .give_authority(&holder_key);
+    run_until(|| {
+        update_all(&mut server, &mut [&mut observer]);
+        holder
+            .update()
+            .iter()
+            .any(|event| matches!(event, Ok(ClientEvent::AuthorityGranted(_))))
+    });
+
+    // the holder's change reaches the Server, and every other User
+    set_client_x(&mut holder, 5);
+    run_until(|| {
+        update_all(&mut server, &mut [&mut holder, &mut observer]);
+        server_x(&server, &entity) == 5 && client_x(&observer) == Some(5)
+    });
+
+    // revoke
+    server
+        .server
+        .entity_mut(server.world.proxy_mut(), &entity)
+        .take_authority();
+    run_until(|| {
+        update_all(&mut server, &mut [&mut observer]);
+        holder
+            .update()
+            .iter()
+            .any(|event| matches!(event, Ok(ClientEvent::AuthorityRevoked(_))))
+    });
+
+    // changes made after authority was taken away are ignored
+    set_client_x(&mut holder, 9);
+    let start = Instant::now();
+    run_until(|| {
+        update_all(&mut server, &mut [&mut holder, &mut observer]);
+        start.elapsed() >= Duration::from_millis(200)
+    });
+    assert_eq!(server_x(&server, &entity), 5);
+    assert_eq!(client_x(&observer), Some(5));
+}
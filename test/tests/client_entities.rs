@@ -0,0 +1,134 @@
+use naia_client::Event as ClientEvent;
+use naia_demo_world::Entity;
+use naia_server::{ClientEntityKey, Event as ServerEvent};
+use naia_shared::WorldRefType;
+use naia_test::{run_until, LocalClient, LocalServer, Position};
+
+fn spawn_client_entity(client: &mut LocalClient) -> Entity {
+    client
+        .client
+        .spawn_entity(client.world.proxy_mut())
+        .insert_component(Position::new(3, 4))
+        .id()
+}
+
+// Updates both ends until the Server receives the Client's spawn
+fn receive_spawn(server: &mut LocalServer, client: &mut LocalClient) -> ClientEntityKey {
+    let mut client_entity_key_opt = None;
+    run_until(|| {
+        client.update();
+        for event in server.update() {
+            if let Ok(ServerEvent::SpawnEntity(_, client_entity_key)) = event {
+                client_entity_key_opt = Some(client_entity_key);
+            }
+        }
+        client_entity_key_opt.is_some()
+    });
+    client_entity_key_opt.unwrap()
+}
+
+#[test]
+fn accepted_entity_is_replicated_to_other_clients() {
+    let mut server = LocalServer::start();
+    let (mut owner, _) = server.connect();
+    let (mut observer, _) = server.connect();
+
+    spawn_client_entity(&mut owner);
+    let client_entity_key = receive_spawn(&mut server, &mut owner);
+
+    // accept the spawn, then the Component spawned along with the Entity
+    for _ in 0..2 {
+        server
+            .server
+            .accept_client_entity_action(server.world.proxy_mut(), &client_entity_key);
+    }
+
+    let entity = server.server.client_entity(&client_entity_key).unwrap();
+    let server_world = server.world.proxy();
+    assert_eq!(*server_world.component::<Position>(&entity).unwrap().x, 3);
+    server.server.room_mut(&server.room_key).add_entity(&entity);
+
+    let mut observed_entity_opt = None;
+    run_until(|| {
+        owner.update();
+        server.update();
+        for event in observer.update() {
+            if let Ok(ClientEvent::SpawnEntity(observed_entity)) = event {
+                observed_entity_opt = Some(observed_entity);
+            }
+        }
+        observed_entity_opt.is_some()
+    });
+    let observer_world = observer.world.proxy();
+    let position = observer_world
+        .component::<Position>(&observed_entity_opt.unwrap())
+        .unwrap();
+    assert_eq!((*position.x, *position.y), (3, 4));
+}
+
+#[test]
+fn rejected_entity_is_despawned_on_client() {
+    let mut server = LocalServer::start();
+    let (mut owner, _) = server.connect();
+
+    let entity = spawn_client_entity(&mut owner);
+    let client_entity_key = receive_spawn(&mut server, &mut owner);
+    server
+        .server
+        .reject_client_entity_action(&client_entity_key);
+    assert!(server.server.client_entity(&client_entity_key).is_none());
+
+    let mut rejected = false;
+    run_until(|| {
+        server.update();
+        for event in owner.update() {
+            if let Ok(ClientEvent::EntityRejected(rejected_entity)) = event {
+                assert!(rejected_entity == entity);
+                rejected = true;
+            }
+        }
+        rejected
+    });
+
+    assert!(!owner.world.proxy().has_entity(&entity));
+    assert!(!owner.client.entity_is_owned(&entity));
+}
+
+#[test]
+fn undecided_changes_are_capped() {
+    let mut server = LocalServer::start();
+    let (mut owner, _) = server.connect();
+
+    let entity = spawn_client_entity(&mut owner);
+    let client_entity_key = receive_spawn(&mut server, &mut owner);
+    for _ in 0..2 {
+        server
+            .server
+            .accept_client_entity_action(server.world.proxy_mut(), &client_entity_key);
+    }
+    let server_entity = server.server.client_entity(&client_entity_key).unwrap();
+
+    // change the Entity far more often than the Server decides on the changes
+    let mut rejected = false;
+    run_until(|| {
+        if let Some(mut position) = owner
+            .client
+            .entity_mut(owner.world.proxy_mut(), &entity)
+            .component::<Position>()
+        {
+            *position.x += 1;
+        }
+        server.update();
+        for event in owner.update() {
+            if let Ok(ClientEvent::EntityRejected(_)) = event {
+                rejected = true;
+            }
+        }
+        rejected
+    });
+
+    // the accepted Entity stays on the Server, which owns it from then on
+    assert!(!owner.world.proxy().has_entity(&entity));
+    assert!(server.server.client_entity(&client_entity_key).is_none());
+    assert!(server.world.proxy().has_entity(&server_entity));
+}
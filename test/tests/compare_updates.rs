@@ -0,0 +1,104 @@
+use std::time::{Duration, Instant};
+
+use naia_client::Event as ClientEvent;
+use naia_test::{client_x, run_until, set_x, spawn_on_client, LocalClient, LocalServer, Position};
+
+// Starts a Server which compares the updates of Positions with the values
+// last sent
+fn start() -> LocalServer {
+    let mut server = LocalServer::start();
+    server.server.compare_component_updates::<Position>(true);
+    server
+}
+
+// Updates both ends for the given duration, counting the updates which reach
+// the Client
+fn count_updates(server: &mut LocalServer, client: &mut LocalClient, duration: Duration) -> usize {
+    let mut update_count = 0;
+    let start = Instant::now();
+    run_until(|| {
+        server.update();
+        for event in client.update() {
+            if let Ok(ClientEvent::UpdateComponent(..)) = event {
+                update_count += 1;
+            }
+        }
+        start.elapsed() >= duration
+    });
+    update_count
+}
+
+#[test]
+fn value_written_at_spawn_is_not_sent_again() {
+    let mut server = start();
+    let (mut client, _) = server.connect();
+    let entity = spawn_on_client(&mut server, &mut client);
+
+    // changed, but back to the value the Client was spawned with
+    set_x(&mut server, &entity, 5);
+    set_x(&mut server, &entity, 0);
+    assert_eq!(
+        count_updates(&mut server, &mut client, Duration::from_millis(300)),
+        0
+    );
+}
+
+#[test]
+fn changed_values_are_sent() {
+    let mut server = start();
+    let (mut client, _) = server.connect();
+    let entity = spawn_on_client(&mut server, &mut client);
+
+    set_x(&mut server, &entity, 5);
+    run_until(|| {
+        server.update();
+        client.update();
+        client_x(&client) == Some(5)
+    });
+
+    // the value last sent is now 5, so going back to the spawned value is a
+    // change
+    set_x(&mut server, &entity, 0);
+    run_until(|| {
+        server.update();
+        client.update();
+        client_x(&client) == Some(0)
+    });
+
+    // and going back to the value last sent is not
+    set_x(&mut server, &entity, 7);
+    set_x(&mut server, &entity, 0);
+    assert_eq!(
+        count_updates(&mut server, &mut client, Duration::from_millis(300)),
+        0
+    );
+}
+
+#[test]
+fn reinserted_component_is_compared_with_its_new_value() {
+    let mut server = start();
+    let (mut client, _) = server.connect();
+    let entity = spawn_on_client(&mut server, &mut client);
+
+    server
+        .server
+        .entity_mut(server.world.proxy_mut(), &entity)
+        .remove_component::<Position>();
+    server
+        .server
+        .entity_mut(server.world.proxy_mut(), &entity)
+        .insert_component(Position::new(3, 0));
+    run_until(|| {
+        server.update();
+        client.update();
+        client_x(&client) == Some(3)
+    });
+
+    // the value written by the insert is the one compared with
+    set_x(&mut server, &entity, 4);
+    set_x(&mut server, &entity, 3);
+    assert_eq!(
+        count_updates(&mut server, &mut client, Duration::from_millis(300)),
+        0
+    );
+}
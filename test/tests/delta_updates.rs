@@ -0,0 +1,49 @@
+use std::sync::atomic::Ordering;
+
+use naia_shared::WorldRefType;
+
+use naia_test::{run_until, wait_for_acks, Counter, LocalClient, LocalServer, DELTAS_READ};
+
+fn client_count(client: &LocalClient) -> Option<i32> {
+    let client_entity = *client.client.entities(&client.world.proxy()).first()?;
+    client
+        .world
+        .proxy()
+        .component::<Counter>(&client_entity)
+        .map(|counter| *counter.count)
+}
+
+#[test]
+fn updates_are_written_against_acked_values() {
+    let mut server = LocalServer::start();
+    let (mut client, _) = server.connect();
+    let entity = server.spawn(Counter::new(0));
+
+    run_until(|| {
+        server.update();
+        client.update();
+        client_count(&client) == Some(0)
+    });
+    wait_for_acks(&mut server, &mut client);
+
+    let deltas_before = DELTAS_READ.load(Ordering::Relaxed);
+
+    // small changes, each of which the Client should receive as a difference
+    // from a value it has acked, then one too large to be written that way
+    for count in (1..=20).chain([100_000]) {
+        *server
+            .server
+            .entity_mut(server.world.proxy_mut(), &entity)
+            .component::<Counter>()
+            .unwrap()
+            .count = count;
+
+        run_until(|| {
+            server.update();
+            client.update();
+            client_count(&client) == Some(count)
+        });
+    }
+
+    assert!(DELTAS_READ.load(Ordering::Relaxed) > deltas_before);
+}
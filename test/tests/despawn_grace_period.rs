@@ -0,0 +1,100 @@
+use std::time::{Duration, Instant};
+
+use naia_client::Event as ClientEvent;
+use naia_demo_world::Entity;
+use naia_server::{DespawnGracePeriod, UserKey};
+use naia_shared::WorldRefType;
+use naia_test::{run_until, spawn_on_client, LocalClient, LocalServer, Score};
+
+fn start(despawn_grace_period: DespawnGracePeriod) -> LocalServer {
+    let mut server_config = LocalServer::server_config();
+    server_config.despawn_grace_period = despawn_grace_period;
+    LocalServer::start_with(&server_config, &LocalServer::shared_config())
+}
+
+fn exclude(server: &mut LocalServer, user_key: &UserKey, entity: &Entity) {
+    server.server.user_scope(user_key).exclude(entity);
+}
+
+// Updates both ends until the Client despawns the Entity
+fn wait_for_despawn(server: &mut LocalServer, client: &mut LocalClient) {
+    run_until(|| {
+        server.update();
+        client
+            .update()
+            .iter()
+            .any(|event| matches!(event, Ok(ClientEvent::DespawnEntity(_))))
+    });
+}
+
+#[test]
+fn entity_is_despawned_after_grace_period() {
+    let grace_period = Duration::from_millis(200);
+    let mut server = start(DespawnGracePeriod::Duration(grace_period));
+    let (mut client, user_key) = server.connect();
+    let entity = spawn_on_client(&mut server, &mut client);
+
+    let excluded_at = Instant::now();
+    exclude(&mut server, &user_key, &entity);
+    wait_for_despawn(&mut server, &mut client);
+
+    assert!(excluded_at.elapsed() >= grace_period);
+    assert!(client.client.entities(&client.world.proxy()).is_empty());
+}
+
+#[test]
+fn entity_is_despawned_after_grace_period_in_ticks() {
+    let mut server = start(DespawnGracePeriod::Ticks(20));
+    let (mut client, user_key) = server.connect();
+    let entity = spawn_on_client(&mut server, &mut client);
+
+    let excluded_tick = server.server.server_tick().unwrap();
+    exclude(&mut server, &user_key, &entity);
+    wait_for_despawn(&mut server, &mut client);
+
+    let despawned_tick = server.server.server_tick().unwrap();
+    assert!(despawned_tick.wrapping_sub(excluded_tick) >= 20);
+}
+
+#[test]
+fn re_entering_scope_cancels_despawn() {
+    let grace_period = Duration::from_millis(200);
+    let mut server = start(DespawnGracePeriod::Duration(grace_period));
+    let (mut client, user_key) = server.connect();
+    let entity = spawn_on_client(&mut server, &mut client);
+
+    exclude(&mut server, &user_key, &entity);
+    server.update();
+
+    // a Component inserted while the Entity lingers still reaches the Client
+    server
+        .server
+        .entity_mut(server.world.proxy_mut(), &entity)
+        .insert_component(Score::new(3));
+    server.server.user_scope(&user_key).include(&entity);
+
+    let mut respawned = false;
+    let mut despawned = false;
+    let start = Instant::now();
+    run_until(|| {
+        server.update();
+        for event in client.update() {
+            match event {
+                Ok(ClientEvent::SpawnEntity(_)) => respawned = true,
+                Ok(ClientEvent::DespawnEntity(_)) => despawned = true,
+                _ => {}
+            }
+        }
+        start.elapsed() >= grace_period * 2
+    });
+    assert!(!respawned);
+    assert!(!despawned);
+
+    let client_entity = client.client.entities(&client.world.proxy())[0];
+    let points = client
+        .world
+        .proxy()
+        .component::<Score>(&client_entity)
+        .map(|score| *score.points);
+    assert_eq!(points, Some(3));
+}
@@ -0,0 +1,195 @@
+use std::{
+    net::{SocketAddr, UdpSocket},
+    time::{Duration, Instant},
+};
+
+use naia_client::Event as ClientEvent;
+use naia_server::Event as ServerEvent;
+use naia_test::{run_until, LocalClient, LocalServer, Position, Score};
+
+// Small deterministic generator, so that a failing run can be reproduced
+struct Xorshift(u64);
+
+impl Xorshift {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, max: usize) -> usize {
+        (self.next() % max as u64) as usize
+    }
+
+    fn bytes(&mut self, length: usize) -> Vec<u8> {
+        (0..length).map(|_| self.next() as u8).collect()
+    }
+
+    // Truncates, corrupts, replaces or passes along the given packet
+    fn mangle(&mut self, packet: &[u8]) -> Vec<u8> {
+        let mut packet = packet.to_vec();
+        match self.below(4) {
+            0 => {
+                packet.truncate(self.below(packet.len() + 1));
+            }
+            1 => {
+                for _ in 0..=self.below(4) {
+                    if !packet.is_empty() {
+                        let index = self.below(packet.len());
+                        packet[index] ^= 1 << self.below(8);
+                    }
+                }
+            }
+            2 => {
+                let length = self.below(64);
+                packet = self.bytes(length);
+            }
+            _ => {}
+        }
+        packet
+    }
+}
+
+// Forwards packets between a Client & a Server, so that the packets of an
+// established connection can be tampered with
+struct Proxy {
+    socket: UdpSocket,
+    server_address: SocketAddr,
+    client_address: Option<SocketAddr>,
+}
+
+impl Proxy {
+    fn new(server_address: SocketAddr) -> Self {
+        let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        socket.set_nonblocking(true).unwrap();
+        Self {
+            socket,
+            server_address,
+            client_address: None,
+        }
+    }
+
+    fn address(&self) -> SocketAddr {
+        self.socket.local_addr().unwrap()
+    }
+
+    fn pump<F: FnMut(&[u8]) -> Vec<u8>>(&mut self, mut tamper: F) {
+        let mut buffer = [0; 1500];
+        while let Ok((length, from)) = self.socket.recv_from(&mut buffer) {
+            let to = if from == self.server_address {
+                match self.client_address {
+                    Some(client_address) => client_address,
+                    None => continue,
+                }
+            } else {
+                self.client_address = Some(from);
+                self.server_address
+            };
+            let packet = tamper(&buffer[..length]);
+            let _ = self.socket.send_to(&packet, to);
+        }
+    }
+
+    // Sends a packet to one end, as if it came from the other
+    fn inject(&self, to_server: bool, packet: &[u8]) {
+        let to = if to_server {
+            Some(self.server_address)
+        } else {
+            self.client_address
+        };
+        if let Some(to) = to {
+            let _ = self.socket.send_to(packet, to);
+        }
+    }
+}
+
+// Connects a Client to the Server through the Proxy, adding its User to the
+// Server's Room
+fn connect_through(server: &mut LocalServer, proxy: &mut Proxy) -> LocalClient {
+    let mut client = LocalClient::connect(proxy.address(), &LocalServer::shared_config());
+
+    let mut user_key_opt = None;
+    let mut client_connected = false;
+    run_until(|| {
+        proxy.pump(|packet| packet.to_vec());
+        for event in server.update() {
+            if let Ok(ServerEvent::Connection(user_key)) = event {
+                user_key_opt = Some(user_key);
+            }
+        }
+        for event in client.update() {
+            if let Ok(ClientEvent::Connection(_)) = event {
+                client_connected = true;
+            }
+        }
+        user_key_opt.is_some() && client_connected
+    });
+    let room_key = server.room_key;
+    server
+        .server
+        .room_mut(&room_key)
+        .add_user(&user_key_opt.unwrap());
+
+    client
+}
+
+#[test]
+fn garbage_packets_are_survived() {
+    let mut server = LocalServer::start();
+    let mut proxy = Proxy::new(server.address);
+    let mut client = connect_through(&mut server, &mut proxy);
+
+    // keep traffic flowing in both directions while packets are tampered with
+    let entity = server.spawn(Position::new(0, 0));
+    server.spawn(Score::new(0));
+
+    let stranger = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let mut random = Xorshift(0x2545_f491_4f6c_dd1d);
+    let start = Instant::now();
+    while start.elapsed() < Duration::from_secs(2) {
+        if let Some(mut position) = server
+            .server
+            .entity_mut(server.world.proxy_mut(), &entity)
+            .component::<Position>()
+        {
+            *position.x = position.x.wrapping_add(1);
+        }
+
+        proxy.pump(|packet| random.mangle(packet));
+
+        let length = random.below(64);
+        let garbage = random.bytes(length);
+        proxy.inject(random.below(2) == 0, &garbage);
+        let _ = stranger.send_to(&garbage, server.address);
+
+        // neither end may panic, whatever they are sent
+        server.update();
+        client.update();
+    }
+}
+
+#[test]
+fn entity_in_malformed_packet_is_received() {
+    let mut server = LocalServer::start();
+    let mut proxy = Proxy::new(server.address);
+    let mut client = connect_through(&mut server, &mut proxy);
+
+    // cut off the payload of every packet carrying the Entity's spawn, keeping
+    // their headers intact
+    server.spawn(Position::new(0, 0));
+    let start = Instant::now();
+    while start.elapsed() < Duration::from_millis(500) {
+        proxy.pump(|packet| packet[..packet.len().min(12)].to_vec());
+        server.update();
+        client.update();
+    }
+
+    // the malformed packets were never acked, so the spawn is sent again
+    run_until(|| {
+        proxy.pump(|packet| packet.to_vec());
+        server.update();
+        client.update();
+        client.client.entities(&client.world.proxy()).len() == 1
+    });
+}
@@ -0,0 +1,74 @@
+use std::time::{Duration, Instant};
+
+use naia_client::Event as ClientEvent;
+use naia_demo_world::Entity;
+use naia_test::{run_until, wait_for_entity_count, LocalServer, Position};
+
+// Spawns an Entity outside of any Room
+fn spawn_outside_room(server: &mut LocalServer) -> Entity {
+    server
+        .server
+        .spawn_entity(server.world.proxy_mut())
+        .insert_component(Position::new(0, 0))
+        .id()
+}
+
+fn set_global(server: &mut LocalServer, entity: &Entity, is_global: bool) {
+    server
+        .server
+        .entity_mut(server.world.proxy_mut(), entity)
+        .set_global(is_global);
+}
+
+#[test]
+fn global_entity_is_in_scope_for_new_user() {
+    let mut server = LocalServer::start();
+    let entity = spawn_outside_room(&mut server);
+    set_global(&mut server, &entity, true);
+
+    let (mut client, _) = server.connect();
+    wait_for_entity_count(&mut server, &mut client, 1);
+
+    set_global(&mut server, &entity, false);
+    wait_for_entity_count(&mut server, &mut client, 0);
+}
+
+#[test]
+fn entity_set_global_is_in_scope_for_connected_user() {
+    let mut server = LocalServer::start();
+    let (mut client, _) = server.connect();
+    let entity = spawn_outside_room(&mut server);
+
+    set_global(&mut server, &entity, true);
+    wait_for_entity_count(&mut server, &mut client, 1);
+}
+
+#[test]
+fn global_entity_removed_from_room_stays_spawned() {
+    let mut server = LocalServer::start();
+    let (mut client, _) = server.connect();
+    let entity = server.spawn(Position::new(0, 0));
+    set_global(&mut server, &entity, true);
+    wait_for_entity_count(&mut server, &mut client, 1);
+    let client_entities = client.client.entities(&client.world.proxy());
+
+    server
+        .server
+        .room_mut(&server.room_key)
+        .remove_entity(&entity);
+
+    // the User is still in the Room, but the Entity is global, so the Client
+    // is never told to despawn it & spawn it again
+    let mut events = Vec::new();
+    let removed_at = Instant::now();
+    run_until(|| {
+        server.update();
+        events.extend(client.update());
+        removed_at.elapsed() > Duration::from_millis(200)
+    });
+    assert!(!events.iter().any(|event| matches!(
+        event,
+        Ok(ClientEvent::DespawnEntity(_)) | Ok(ClientEvent::SpawnEntity(_))
+    )));
+    assert!(client.client.entities(&client.world.proxy()) == client_entities);
+}
@@ -1,17 +1,146 @@
 use std::time::Duration;
 
-use naia_client::internal::{HandshakeManager as ClientHandshakeManager, HandshakeState};
+use naia_client::internal::{
+    HandshakeManager as ClientHandshakeManager, HandshakeResult as ClientHandshakeResult,
+    HandshakeState,
+};
 use naia_server::internal::{HandshakeManager as ServerHandshakeManager, HandshakeResult};
 use naia_shared::{
     serde::{BitReader, BitWriter, Serde},
-    PacketType, Protocolize, StandardHeader,
+    PacketType, Protocolize, RejectReason, SharedConfig, StandardHeader,
 };
 use naia_test::{Auth, Protocol};
 
+// Builds of the same Protocol, where the newer one has added a variant
+mod older {
+    pub mod protocol {
+        use super::ping::Ping;
+        use naia_shared::Protocolize;
+
+        #[derive(Protocolize)]
+        pub enum Protocol {
+            #[protocol(id = 1)]
+            Ping(Ping),
+        }
+    }
+
+    pub mod ping {
+        use naia_shared::{Property, Replicate};
+
+        #[derive(Replicate)]
+        #[protocol_path = "super::protocol::Protocol"]
+        pub struct Ping {
+            pub index: Property<u16>,
+        }
+    }
+}
+
+mod newer {
+    pub mod protocol {
+        use super::{ping::Ping, pong::Pong};
+        use naia_shared::Protocolize;
+
+        #[derive(Protocolize)]
+        pub enum Protocol {
+            #[protocol(id = 1)]
+            Ping(Ping),
+            #[protocol(id = 2)]
+            Pong(Pong),
+        }
+    }
+
+    pub mod ping {
+        use naia_shared::{Property, Replicate};
+
+        #[derive(Replicate)]
+        #[protocol_path = "super::protocol::Protocol"]
+        pub struct Ping {
+            pub index: Property<u16>,
+        }
+    }
+
+    pub mod pong {
+        use naia_shared::{Property, Replicate};
+
+        #[derive(Replicate)]
+        #[protocol_path = "super::protocol::Protocol"]
+        pub struct Pong {
+            pub index: Property<u16>,
+        }
+    }
+}
+
+// A build of the newer Protocol, where a Property's type has changed
+mod retyped {
+    pub mod protocol {
+        use super::{ping::Ping, pong::Pong};
+        use naia_shared::Protocolize;
+
+        #[derive(Protocolize)]
+        pub enum Protocol {
+            #[protocol(id = 1)]
+            Ping(Ping),
+            #[protocol(id = 2)]
+            Pong(Pong),
+        }
+    }
+
+    pub mod ping {
+        use naia_shared::{Property, Replicate};
+
+        #[derive(Replicate)]
+        #[protocol_path = "super::protocol::Protocol"]
+        pub struct Ping {
+            pub index: Property<u32>,
+        }
+    }
+
+    pub mod pong {
+        use naia_shared::{Property, Replicate};
+
+        #[derive(Replicate)]
+        #[protocol_path = "super::protocol::Protocol"]
+        pub struct Pong {
+            pub index: Property<u16>,
+        }
+    }
+}
+
+// Runs the handshake up to the Server receiving the connect request, and
+// returns how the Server handled it
+fn request_connect<PC: Protocolize, PS: Protocolize>() -> HandshakeResult<PS> {
+    let shared_config = SharedConfig::default();
+    let mut client =
+        ClientHandshakeManager::<PC>::new(Duration::new(0, 0), shared_config.fingerprint::<PC>());
+    let mut server = ServerHandshakeManager::<PS>::new(false, shared_config.fingerprint::<PS>());
+    let mut writer: BitWriter;
+    let mut reader: BitReader;
+
+    // Client & Server exchange challenge
+    writer = client.write_challenge_request();
+    let (length, buffer) = writer.flush();
+    reader = BitReader::new(&buffer[..length]);
+    StandardHeader::de(&mut reader).unwrap();
+    writer = server.recv_challenge_request(&mut reader).unwrap();
+
+    let (length, buffer) = writer.flush();
+    reader = BitReader::new(&buffer[..length]);
+    StandardHeader::de(&mut reader).unwrap();
+    client.recv_challenge_response(&mut reader).unwrap();
+
+    // Server receives connect request
+    writer = client.write_connect_request();
+    let (length, buffer) = writer.flush();
+    reader = BitReader::new(&buffer[..length]);
+    StandardHeader::de(&mut reader).unwrap();
+    server.recv_connect_request(&mut reader).unwrap()
+}
+
 #[test]
 fn end_to_end_handshake_w_auth() {
-    let mut client = ClientHandshakeManager::<Protocol>::new(Duration::new(0, 0));
-    let mut server = ServerHandshakeManager::<Protocol>::new(true);
+    let fingerprint = SharedConfig::default().fingerprint::<Protocol>();
+    let mut client = ClientHandshakeManager::<Protocol>::new(Duration::new(0, 0), fingerprint);
+    let mut server = ServerHandshakeManager::<Protocol>::new(true, fingerprint);
     let mut message_length: usize;
     let mut message_buffer: [u8; 508];
     let mut writer: BitWriter;
@@ -34,7 +163,7 @@ fn end_to_end_handshake_w_auth() {
     {
         reader = BitReader::new(&message_buffer[..message_length]);
         StandardHeader::de(&mut reader).unwrap();
-        writer = server.recv_challenge_request(&mut reader);
+        writer = server.recv_challenge_request(&mut reader).unwrap();
     }
 
     // 3. Server send challenge response
@@ -48,7 +177,7 @@ fn end_to_end_handshake_w_auth() {
     {
         reader = BitReader::new(&message_buffer[..message_length]);
         StandardHeader::de(&mut reader).unwrap();
-        client.recv_challenge_response(&mut reader);
+        client.recv_challenge_response(&mut reader).unwrap();
         assert_eq!(
             client.connection_state,
             HandshakeState::AwaitingConnectResponse
@@ -67,7 +196,7 @@ fn end_to_end_handshake_w_auth() {
     {
         reader = BitReader::new(&message_buffer[..message_length]);
         StandardHeader::de(&mut reader).unwrap();
-        let result = server.recv_connect_request(&mut reader);
+        let result = server.recv_connect_request(&mut reader).unwrap();
         if let HandshakeResult::Success(Some(auth_message)) = result {
             let auth_replica = auth_message
                 .cast_ref::<Auth>()
@@ -104,3 +233,101 @@ fn end_to_end_handshake_w_auth() {
         client.recv_connect_response();
     }
 }
+
+#[test]
+fn handshake_rejects_protocol_mismatch() {
+    let mut client = ClientHandshakeManager::<Protocol>::new(Duration::new(0, 0), 1);
+    let mut server = ServerHandshakeManager::<Protocol>::new(false, 2);
+    let message_length: usize;
+    let message_buffer: [u8; 508];
+    let mut writer: BitWriter;
+    let mut reader: BitReader;
+
+    // 1. Client & Server exchange challenge
+    {
+        writer = client.write_challenge_request();
+        let (length, buffer) = writer.flush();
+        reader = BitReader::new(&buffer[..length]);
+        StandardHeader::de(&mut reader).unwrap();
+        writer = server.recv_challenge_request(&mut reader).unwrap();
+
+        let (length, buffer) = writer.flush();
+        reader = BitReader::new(&buffer[..length]);
+        StandardHeader::de(&mut reader).unwrap();
+        client.recv_challenge_response(&mut reader).unwrap();
+    }
+
+    // 2. Server refuses connect request
+    {
+        writer = client.write_connect_request();
+        let (length, buffer) = writer.flush();
+        reader = BitReader::new(&buffer[..length]);
+        StandardHeader::de(&mut reader).unwrap();
+        let result = server.recv_connect_request(&mut reader).unwrap();
+        if let HandshakeResult::Rejected(reason) = result {
+            assert_eq!(reason, RejectReason::ProtocolMismatch);
+            writer = server.write_reject_response(&reason);
+            let (length, buffer) = writer.flush();
+            message_length = length;
+            message_buffer = buffer;
+        } else {
+            panic!("handshake result from server was not a rejection");
+        }
+    }
+
+    // 3. Client receives reject response
+    {
+        reader = BitReader::new(&message_buffer[..message_length]);
+        let result = client.recv(&mut reader).unwrap();
+        assert!(matches!(
+            result,
+            ClientHandshakeResult::Rejected(RejectReason::ProtocolMismatch)
+        ));
+        assert_eq!(
+            client.connection_state,
+            HandshakeState::Rejected(RejectReason::ProtocolMismatch)
+        );
+    }
+}
+
+#[test]
+fn handshake_rejects_subset_protocol() {
+    // a kind which only the newer Server has can't be read by the older
+    // Client
+    let mut writer = BitWriter::default();
+    newer::protocol::Protocol::kind_of::<newer::pong::Pong>().ser(&mut writer);
+    let (length, buffer) = writer.flush();
+    let mut reader = BitReader::new(&buffer[..length]);
+    assert!(<older::protocol::Protocol as Protocolize>::Kind::de(&mut reader).is_err());
+
+    // so the older Client is refused
+    let result = request_connect::<older::protocol::Protocol, newer::protocol::Protocol>();
+    assert!(matches!(
+        result,
+        HandshakeResult::Rejected(RejectReason::ProtocolMismatch)
+    ));
+}
+
+#[test]
+fn handshake_accepts_same_protocol() {
+    let result = request_connect::<newer::protocol::Protocol, newer::protocol::Protocol>();
+    assert!(matches!(result, HandshakeResult::Success(None)));
+}
+
+#[test]
+fn handshake_rejects_superset_protocol() {
+    let result = request_connect::<newer::protocol::Protocol, older::protocol::Protocol>();
+    assert!(matches!(
+        result,
+        HandshakeResult::Rejected(RejectReason::ProtocolMismatch)
+    ));
+}
+
+#[test]
+fn handshake_rejects_retyped_property() {
+    let result = request_connect::<retyped::protocol::Protocol, newer::protocol::Protocol>();
+    assert!(matches!(
+        result,
+        HandshakeResult::Rejected(RejectReason::ProtocolMismatch)
+    ));
+}
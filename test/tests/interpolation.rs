@@ -0,0 +1,99 @@
+use std::{thread, time::Duration};
+
+use naia_client::{Event as ClientEvent, InterpolationBuffer};
+use naia_demo_world::Entity;
+use naia_shared::{sequence_greater_than, Tick};
+use naia_test::{run_until, wait_for_acks, LocalClient, LocalServer, Position, TestClientEvent};
+
+// Passes every event of one call to `receive` to the buffer, and returns them
+fn receive(
+    client: &mut LocalClient,
+    buffer: &mut InterpolationBuffer<Entity, Position>,
+) -> Vec<TestClientEvent> {
+    let events = client.update();
+    for event in events.iter().flatten() {
+        buffer.receive(&client.client, event);
+    }
+    events
+}
+
+fn receive_spawn(
+    server: &mut LocalServer,
+    client: &mut LocalClient,
+    buffer: &mut InterpolationBuffer<Entity, Position>,
+) -> Entity {
+    let mut client_entity_opt = None;
+    run_until(|| {
+        server.update();
+        for event in receive(client, buffer) {
+            if let Ok(ClientEvent::SpawnEntity(entity)) = event {
+                client_entity_opt = Some(entity);
+            }
+        }
+        client_entity_opt.is_some()
+    });
+    wait_for_acks(server, client);
+    client_entity_opt.unwrap()
+}
+
+#[test]
+fn inserted_component_is_sampled() {
+    let mut server = LocalServer::start();
+    let (mut client, _) = server.connect();
+    server.spawn(Position::new(7, 0));
+
+    let mut buffer = InterpolationBuffer::new(8, 0.0);
+    let client_entity = receive_spawn(&mut server, &mut client, &mut buffer);
+
+    let position = buffer.value_at(&client_entity, 0, 0.0).unwrap();
+    assert_eq!(*position.x, 7);
+}
+
+#[test]
+fn each_update_is_sampled() {
+    let mut server = LocalServer::start();
+    let (mut client, _) = server.connect();
+    let entity = server.spawn(Position::new(0, 0));
+
+    let mut buffer = InterpolationBuffer::new(64, 0.0);
+    let client_entity = receive_spawn(&mut server, &mut client, &mut buffer);
+
+    // let several updates arrive before the Client handles any of them
+    for _ in 0..100 {
+        if let Some(mut position) = server
+            .server
+            .entity_mut(server.world.proxy_mut(), &entity)
+            .component::<Position>()
+        {
+            *position.x += 1;
+        }
+        server.update();
+        thread::sleep(Duration::from_millis(1));
+    }
+    let mut update_ticks: Vec<Tick> = Vec::new();
+    run_until(|| {
+        server.update();
+        for event in receive(&mut client, &mut buffer) {
+            if let Ok(ClientEvent::UpdateComponent(tick, _, _)) = event {
+                update_ticks.push(tick);
+            }
+        }
+        !update_ticks.is_empty()
+    });
+
+    // each Tick holds the state of its own update, rather than the latest
+    update_ticks.sort_by(|a, b| {
+        if sequence_greater_than(*a, *b) {
+            std::cmp::Ordering::Greater
+        } else {
+            std::cmp::Ordering::Less
+        }
+    });
+    update_ticks.dedup();
+    let xs: Vec<i16> = update_ticks
+        .iter()
+        .map(|tick| *buffer.value_at(&client_entity, *tick, 0.0).unwrap().x)
+        .collect();
+    assert!(xs.len() >= 2);
+    assert!(xs.windows(2).all(|pair| pair[0] < pair[1]));
+}
@@ -0,0 +1,127 @@
+use std::{collections::HashMap, time::Duration};
+
+use naia_demo_world::Entity;
+use naia_server::ServerConfig;
+use naia_shared::{sequence_greater_than, Tick, WorldRefType};
+use naia_test::{run_until, set_x, LocalServer, Position};
+
+// Updates the Server until it has recorded the given number of new Ticks,
+// calling `on_tick` with each new Tick before anything is changed for it.
+// Returns the x of the Entity's Position as it was recorded at each Tick
+fn run_ticks<F: FnMut(&mut LocalServer, Tick)>(
+    server: &mut LocalServer,
+    entity: &Entity,
+    tick_count: usize,
+    mut on_tick: F,
+) -> HashMap<Tick, i16> {
+    let mut recorded = HashMap::new();
+    let mut last_tick = server.server.server_tick();
+    run_until(|| {
+        // the World is recorded as it is when the first update of a Tick is sent
+        let x = server
+            .world
+            .proxy()
+            .component::<Position>(entity)
+            .map(|position| *position.x);
+        server.update();
+        let tick = server.server.server_tick().unwrap();
+        if Some(tick) != last_tick {
+            last_tick = Some(tick);
+            if let Some(x) = x {
+                recorded.insert(tick, x);
+            }
+            on_tick(server, tick);
+        }
+        recorded.len() >= tick_count
+    });
+    recorded
+}
+
+#[test]
+fn history_holds_the_state_at_each_tick() {
+    let mut server = LocalServer::start();
+    server.server.record_component_history::<Position>();
+    let entity = server.spawn(Position::new(0, 0));
+
+    // only change the Position every other Tick
+    let recorded = run_ticks(&mut server, &entity, 20, |server, tick| {
+        if tick % 2 == 0 {
+            set_x(server, &entity, tick as i16);
+        }
+    });
+
+    for (tick, x) in &recorded {
+        let position = server
+            .server
+            .component_at::<Position>(&entity, *tick)
+            .unwrap();
+        assert_eq!(*position.x, *x);
+    }
+
+    // a removed Component has no state from then on
+    let earlier_tick = *recorded.keys().max().unwrap();
+    server
+        .server
+        .entity_mut(server.world.proxy_mut(), &entity)
+        .remove_component::<Position>();
+    let start_tick = server.server.server_tick().unwrap();
+    run_until(|| {
+        server.update();
+        server.server.server_tick().unwrap() != start_tick
+    });
+    let removed_tick = server.server.server_tick().unwrap();
+    assert!(server
+        .server
+        .component_at::<Position>(&entity, removed_tick)
+        .is_none());
+    assert!(server
+        .server
+        .component_at::<Position>(&entity, earlier_tick)
+        .is_some());
+}
+
+#[test]
+fn unchanged_state_outlives_the_history_duration() {
+    let server_config = ServerConfig {
+        history_duration: Duration::from_millis(50),
+        ..LocalServer::server_config()
+    };
+    let mut server = LocalServer::start_with(&server_config, &LocalServer::shared_config());
+    server.server.record_component_history::<Position>();
+    let entity = server.spawn(Position::new(3, 0));
+
+    let recorded = run_ticks(&mut server, &entity, 20, |_, _| {});
+    let first_tick = *recorded.keys().min().unwrap();
+    let last_tick = server.server.server_tick().unwrap();
+
+    // older than the history kept
+    assert!(server
+        .server
+        .component_at::<Position>(&entity, first_tick)
+        .is_none());
+    // recorded once, long ago, but still the state at the latest Tick
+    let position = server
+        .server
+        .component_at::<Position>(&entity, last_tick)
+        .unwrap();
+    assert_eq!(*position.x, 3);
+}
+
+#[test]
+fn user_view_tick_is_behind_the_server() {
+    let mut server = LocalServer::start();
+    let (mut client, user_key) = server.connect();
+    let start_tick = server.server.server_tick().unwrap();
+    run_until(|| {
+        server.update();
+        client.update();
+        sequence_greater_than(
+            server.server.server_tick().unwrap(),
+            start_tick.wrapping_add(10),
+        )
+    });
+
+    let server_tick = server.server.server_tick().unwrap();
+    let view_tick = server.server.user_view_tick(&user_key).unwrap();
+    assert!(!sequence_greater_than(view_tick, server_tick));
+}
@@ -0,0 +1,99 @@
+use naia_shared::{
+    serde::{BitReader, BitWriter},
+    ChannelConfig, DefaultChannels, FakeEntityConverter, HostType, Instant, MessageManager,
+    ProtocolIo,
+};
+
+use naia_test::{Auth, Protocol};
+
+type TestMessageManager = MessageManager<Protocol, DefaultChannels>;
+
+fn new_manager(host_type: HostType) -> TestMessageManager {
+    MessageManager::new(host_type, &ChannelConfig::new(ChannelConfig::default()))
+}
+
+// An Auth Message whose username takes up the given number of bytes
+fn auth_message(length: usize) -> Protocol {
+    let username: String = (0..length).map(|_| 'a').collect();
+    Protocol::Auth(Auth::new(&username, ""))
+}
+
+// Writes every packet the sender has Messages for
+fn write_packets(sender: &mut TestMessageManager) -> Vec<Vec<u8>> {
+    let io = ProtocolIo::new(&FakeEntityConverter);
+    sender.collect_outgoing_messages(&Instant::now(), &0.0);
+
+    let mut packets = Vec::new();
+    while sender.has_outgoing_messages() {
+        let mut writer = BitWriter::default();
+        sender.write_messages(&io, &mut writer, packets.len() as u16);
+        let (length, buffer) = writer.flush();
+        packets.push(buffer[..length].to_vec());
+    }
+    packets
+}
+
+fn read_packet(receiver: &mut TestMessageManager, packet: &[u8]) -> bool {
+    let io = ProtocolIo::new(&FakeEntityConverter);
+    receiver
+        .read_messages(&io, &mut BitReader::new(packet))
+        .is_ok()
+}
+
+#[test]
+fn message_after_bad_fragment_is_received() {
+    let io = ProtocolIo::new(&FakeEntityConverter);
+    let mut receiver = new_manager(HostType::Server);
+
+    // start reassembling a Message of 3 Fragments
+    let mut first_sender = new_manager(HostType::Client);
+    first_sender.send_message(&io, DefaultChannels::UnorderedReliable, auth_message(1000));
+    let first_packets = write_packets(&mut first_sender);
+    assert!(read_packet(&mut receiver, &first_packets[0]));
+
+    // a Message of 2 Fragments with the same Fragment id, followed by a
+    // whole Message
+    let mut second_sender = new_manager(HostType::Client);
+    second_sender.send_message(&io, DefaultChannels::UnorderedReliable, auth_message(500));
+    second_sender.send_message(&io, DefaultChannels::UnorderedReliable, auth_message(10));
+    let second_packets = write_packets(&mut second_sender);
+    let last_packet = second_packets.last().unwrap();
+    assert!(!read_packet(&mut receiver, last_packet));
+
+    // the Fragment is rejected, but the Message after it is still received
+    let messages = receiver.receive_messages();
+    assert_eq!(messages.len(), 1);
+    match &messages[0].1 {
+        Protocol::Auth(auth) => assert_eq!(auth.username.len(), 10),
+        _ => panic!("expected an Auth Message"),
+    }
+}
+
+#[test]
+fn unreliable_message_up_to_mtu_is_received() {
+    let io = ProtocolIo::new(&FakeEntityConverter);
+    let mut receiver = new_manager(HostType::Server);
+
+    // larger than the fragmentation limit, which only applies to reliable
+    // Channels
+    let mut sender = new_manager(HostType::Client);
+    sender.send_message(&io, DefaultChannels::UnorderedUnreliable, auth_message(450));
+    let packets = write_packets(&mut sender);
+    assert_eq!(packets.len(), 1);
+    assert!(read_packet(&mut receiver, &packets[0]));
+
+    let messages = receiver.receive_messages();
+    assert_eq!(messages.len(), 1);
+    match &messages[0].1 {
+        Protocol::Auth(auth) => assert_eq!(auth.username.len(), 450),
+        _ => panic!("expected an Auth Message"),
+    }
+}
+
+#[test]
+fn unreliable_message_over_mtu_is_dropped() {
+    let io = ProtocolIo::new(&FakeEntityConverter);
+    let mut sender = new_manager(HostType::Client);
+    sender.send_message(&io, DefaultChannels::UnorderedUnreliable, auth_message(500));
+    assert!(write_packets(&mut sender).is_empty());
+}
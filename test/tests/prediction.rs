@@ -0,0 +1,169 @@
+use std::time::{Duration, Instant};
+
+use naia_client::{CommandHistory, Event as ClientEvent};
+use naia_demo_world::Entity;
+use naia_shared::{sequence_greater_than, Tick, WorldMutType, WorldRefType};
+use naia_test::{run_until, LocalClient, LocalServer, Position};
+
+// Spawns an Entity on the Server, and a local prediction of it on the Client.
+// Returns the Server's Entity, then the Client's confirmed & predicted ones
+fn predict(server: &mut LocalServer, client: &mut LocalClient) -> (Entity, Entity, Entity) {
+    let server_entity = server.spawn(Position::new(0, 0));
+
+    let mut confirmed_entity_opt = None;
+    run_until(|| {
+        server.update();
+        for event in client.update() {
+            if let Ok(ClientEvent::SpawnEntity(entity)) = event {
+                confirmed_entity_opt = Some(entity);
+            }
+        }
+        confirmed_entity_opt.is_some()
+    });
+    let confirmed_entity = confirmed_entity_opt.unwrap();
+
+    let mut world = client.world.proxy_mut();
+    let predicted_entity = world.spawn_entity();
+    world.insert_component(&predicted_entity, Position::new(0, 0));
+    client
+        .client
+        .predict_entity(&confirmed_entity, &predicted_entity);
+
+    (server_entity, confirmed_entity, predicted_entity)
+}
+
+// Moves the Entity down on the Server, while the Client predicts it moving
+// right. Each of the Client's Ticks issues a command & records a prediction.
+// Returns the first Rollback event
+fn run_diverging(
+    server: &mut LocalServer,
+    client: &mut LocalClient,
+    server_entity: &Entity,
+    confirmed_entity: &Entity,
+    predicted_entity: &Entity,
+    command_history: &mut CommandHistory<i16>,
+    duration: Duration,
+) -> Option<Tick> {
+    let mut rollback_opt = None;
+    let start = Instant::now();
+    run_until(|| {
+        if let Some(mut position) = server
+            .server
+            .entity_mut(server.world.proxy_mut(), server_entity)
+            .component::<Position>()
+        {
+            *position.y += 1;
+        }
+        server.update();
+
+        for event in client.update() {
+            match event {
+                Ok(ClientEvent::Tick) => {
+                    let client_tick = client.client.client_tick().unwrap();
+                    if command_history.can_insert(&client_tick) {
+                        command_history.insert(client_tick, 1);
+                        let mut world = client.world.proxy_mut();
+                        *world.component_mut::<Position>(predicted_entity).unwrap().x += 1;
+                        client.client.record_prediction(
+                            &client.world.proxy(),
+                            confirmed_entity,
+                            client_tick,
+                        );
+                    }
+                }
+                Ok(ClientEvent::Rollback(tick, entity)) => {
+                    assert!(entity == *confirmed_entity);
+                    rollback_opt.get_or_insert(tick);
+                }
+                _ => {}
+            }
+        }
+        rollback_opt.is_some() || start.elapsed() >= duration
+    });
+    rollback_opt
+}
+
+#[test]
+fn misprediction_rolls_back_and_replays() {
+    let mut server = LocalServer::start();
+    let (mut client, _) = server.connect();
+    let (server_entity, confirmed_entity, predicted_entity) = predict(&mut server, &mut client);
+
+    let mut command_history = CommandHistory::default();
+    let rollback_tick = run_diverging(
+        &mut server,
+        &mut client,
+        &server_entity,
+        &confirmed_entity,
+        &predicted_entity,
+        &mut command_history,
+        Duration::from_secs(5),
+    )
+    .expect("the misprediction should cause a Rollback");
+
+    let replays = client.client.rollback_entity(
+        client.world.proxy_mut(),
+        &confirmed_entity,
+        &rollback_tick,
+        &mut command_history,
+    );
+
+    // the prediction is reset to the confirmed state
+    let position = |entity: &Entity| {
+        let world = client.world.proxy();
+        let position = world.component::<Position>(entity).unwrap();
+        (*position.x, *position.y)
+    };
+    let (confirmed_x, confirmed_y) = position(&confirmed_entity);
+    assert_eq!(position(&predicted_entity), (confirmed_x, confirmed_y));
+
+    // only the commands issued after the corrected Tick are replayed
+    assert!(replays
+        .iter()
+        .all(|(tick, _)| sequence_greater_than(*tick, rollback_tick)));
+    let mut world = client.world.proxy_mut();
+    let mut predicted = world.component_mut::<Position>(&predicted_entity).unwrap();
+    for (_, dx) in &replays {
+        *predicted.x += *dx;
+    }
+    assert_eq!(*predicted.x, confirmed_x + replays.len() as i16);
+}
+
+#[test]
+fn comparer_tolerates_differences() {
+    let mut server = LocalServer::start();
+    let (mut client, _) = server.connect();
+    let (server_entity, confirmed_entity, predicted_entity) = predict(&mut server, &mut client);
+
+    client.client.set_prediction_comparer::<Position>(Some(
+        |predicted: &Position, confirmed: &Position| {
+            (*predicted.x - *confirmed.x).abs() < 10_000
+                && (*predicted.y - *confirmed.y).abs() < 10_000
+        },
+    ));
+
+    let mut command_history = CommandHistory::default();
+    let rollback_tick = run_diverging(
+        &mut server,
+        &mut client,
+        &server_entity,
+        &confirmed_entity,
+        &predicted_entity,
+        &mut command_history,
+        Duration::from_millis(500),
+    );
+    assert!(rollback_tick.is_none());
+
+    // an exact comparison is required again once the comparer is cleared
+    client.client.set_prediction_comparer::<Position>(None);
+    let rollback_tick = run_diverging(
+        &mut server,
+        &mut client,
+        &server_entity,
+        &confirmed_entity,
+        &predicted_entity,
+        &mut command_history,
+        Duration::from_secs(5),
+    );
+    assert!(rollback_tick.is_some());
+}
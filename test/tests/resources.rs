@@ -0,0 +1,73 @@
+use naia_client::Event as ClientEvent;
+use naia_test::{run_until, LocalClient, LocalServer, Score, TestClientEvent};
+
+fn client_points(client: &LocalClient) -> Option<u32> {
+    client
+        .client
+        .resource::<Score, _>(&client.world.proxy())
+        .map(|score| *score.points)
+}
+
+// Updates both ends until the Client sees an event matching the predicate
+fn wait_for_event(
+    server: &mut LocalServer,
+    client: &mut LocalClient,
+    predicate: fn(&TestClientEvent) -> bool,
+) {
+    run_until(|| {
+        server.update();
+        client.update().iter().any(predicate)
+    });
+}
+
+#[test]
+fn resource_is_inserted_updated_and_removed() {
+    let mut server = LocalServer::start();
+    let (mut client, _) = server.connect();
+
+    server
+        .server
+        .insert_resource(server.world.proxy_mut(), Score::new(1));
+    wait_for_event(&mut server, &mut client, |event| {
+        matches!(event, Ok(ClientEvent::InsertResource(_)))
+    });
+    assert_eq!(client_points(&client), Some(1));
+    // the Resource Entity isn't one of the Client's Entities
+    assert!(client.client.entities(&client.world.proxy()).is_empty());
+
+    *server
+        .server
+        .resource_mut::<Score, _>(&mut server.world.proxy_mut())
+        .unwrap()
+        .points = 2;
+    wait_for_event(&mut server, &mut client, |event| {
+        matches!(event, Ok(ClientEvent::UpdateResource(..)))
+    });
+    assert_eq!(client_points(&client), Some(2));
+
+    server
+        .server
+        .remove_resource::<Score, _>(server.world.proxy_mut());
+    wait_for_event(&mut server, &mut client, |event| {
+        matches!(event, Ok(ClientEvent::RemoveResource(_)))
+    });
+    assert!(!client
+        .client
+        .has_resource::<Score, _>(&client.world.proxy()));
+}
+
+#[test]
+fn resource_reaches_late_joining_user() {
+    let mut server = LocalServer::start();
+    server
+        .server
+        .insert_resource(server.world.proxy_mut(), Score::new(3));
+    server.update();
+
+    let (mut client, _) = server.connect();
+    wait_for_event(&mut server, &mut client, |event| {
+        matches!(event, Ok(ClientEvent::InsertResource(_)))
+    });
+    assert_eq!(client_points(&client), Some(3));
+    assert!(client.client.entities(&client.world.proxy()).is_empty());
+}
@@ -0,0 +1,50 @@
+use naia_server::{Event as ServerEvent, ServerConfig};
+use naia_test::{run_until, wait_for_entity_count, Auth, LocalClient, LocalServer, Position};
+
+#[test]
+fn user_added_to_room_before_accepted_receives_room_entities() {
+    let server_config = ServerConfig {
+        require_auth: true,
+        ..LocalServer::server_config()
+    };
+    let mut server = LocalServer::start_with(&server_config, &LocalServer::shared_config());
+    server.spawn(Position::new(0, 0));
+
+    let mut client = LocalClient::connect_with_auth(
+        server.address,
+        &LocalServer::shared_config(),
+        Auth::new("charlie", "12345"),
+    );
+
+    let mut user_key_opt = None;
+    run_until(|| {
+        for event in server.update() {
+            if let Ok(ServerEvent::Authorization(user_key, _)) = event {
+                user_key_opt = Some(user_key);
+            }
+        }
+        client.update();
+        user_key_opt.is_some()
+    });
+    let user_key = user_key_opt.unwrap();
+
+    // the User's scope is evaluated while they are still being authorized
+    server.server.room_mut(&server.room_key).add_user(&user_key);
+    server.update();
+
+    server.server.accept_connection(&user_key);
+    wait_for_entity_count(&mut server, &mut client, 1);
+}
+
+#[test]
+#[allow(deprecated)]
+fn scope_checks_returns_every_scope_set() {
+    let mut server = LocalServer::start();
+    let (_client, user_key) = server.connect();
+    let entity = server.spawn(Position::new(0, 0));
+
+    // unlike pending_scope_checks, unchanged Scope Sets are returned again
+    for _ in 0..2 {
+        assert!(server.server.scope_checks() == vec![(server.room_key, user_key, entity)]);
+    }
+}
@@ -0,0 +1,99 @@
+use std::time::{Duration, Instant};
+
+use naia_demo_world::Entity;
+use naia_server::UserKey;
+use naia_test::{run_until, wait_for_entity_count, LocalServer, Position, TestServer, TestWorld};
+
+fn start() -> LocalServer {
+    let mut server_config = LocalServer::server_config();
+    server_config.spatial_cell_size = Some(10.0);
+    LocalServer::start_with(&server_config, &LocalServer::shared_config())
+}
+
+// Spawns an Entity at the given position, which the User's view is centered
+// on with a radius of one cell
+fn spawn_in_view(server: &mut LocalServer, user_key: &UserKey, x: f32) -> Entity {
+    server.server.user_mut(user_key).set_view(x, 0.0, 10.0);
+    let entity = server.spawn(Position::new(0, 0));
+    set_position(server, &entity, x);
+    entity
+}
+
+fn set_position(server: &mut LocalServer, entity: &Entity, x: f32) {
+    server
+        .server
+        .entity_mut(server.world.proxy_mut(), entity)
+        .set_position(x, 0.0);
+}
+
+#[test]
+fn entity_is_replicated_while_in_view() {
+    let mut server = start();
+    let (mut client, user_key) = server.connect();
+    let entity = spawn_in_view(&mut server, &user_key, 0.0);
+    wait_for_entity_count(&mut server, &mut client, 1);
+
+    set_position(&mut server, &entity, 500.0);
+    wait_for_entity_count(&mut server, &mut client, 0);
+
+    set_position(&mut server, &entity, 0.0);
+    wait_for_entity_count(&mut server, &mut client, 1);
+
+    // the spatial grid scopes Entities which aren't in any Room too
+    let roomless_entity = server
+        .server
+        .spawn_entity(server.world.proxy_mut())
+        .insert_component(Position::new(0, 0))
+        .id();
+    set_position(&mut server, &roomless_entity, 0.0);
+    wait_for_entity_count(&mut server, &mut client, 2);
+
+    set_position(&mut server, &roomless_entity, 500.0);
+    wait_for_entity_count(&mut server, &mut client, 1);
+}
+
+#[test]
+fn excluded_entity_in_view_is_not_replicated() {
+    let mut server = start();
+    let (mut client, user_key) = server.connect();
+    let entity = spawn_in_view(&mut server, &user_key, 0.0);
+    wait_for_entity_count(&mut server, &mut client, 1);
+
+    server.server.user_scope(&user_key).exclude(&entity);
+    wait_for_entity_count(&mut server, &mut client, 0);
+
+    // moving within view doesn't bring it back
+    set_position(&mut server, &entity, 1.0);
+    set_position(&mut server, &entity, 15.0);
+    let start = Instant::now();
+    run_until(|| {
+        server.update();
+        client.update();
+        start.elapsed() >= Duration::from_millis(300)
+    });
+    assert!(client.client.entities(&client.world.proxy()).is_empty());
+}
+
+#[test]
+fn included_entity_out_of_view_is_not_replicated() {
+    let mut server = start();
+    let (mut client, user_key) = server.connect();
+    let entity = spawn_in_view(&mut server, &user_key, 0.0);
+    wait_for_entity_count(&mut server, &mut client, 1);
+
+    set_position(&mut server, &entity, 500.0);
+    server.server.user_scope(&user_key).include(&entity);
+    wait_for_entity_count(&mut server, &mut client, 0);
+}
+
+#[test]
+#[should_panic]
+fn positioning_without_a_grid_panics() {
+    // not listening, as each listening Server holds onto a socket thread
+    let mut server = TestServer::new(&LocalServer::server_config(), &LocalServer::shared_config());
+    let mut world = TestWorld::default();
+    let entity = server.spawn_entity(world.proxy_mut()).id();
+    server
+        .entity_mut(world.proxy_mut(), &entity)
+        .set_position(0.0, 0.0);
+}
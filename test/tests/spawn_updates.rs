@@ -0,0 +1,19 @@
+use naia_test::{client_x, run_until, set_x, LocalServer, Position};
+
+#[test]
+fn change_made_before_spawn_is_acked_is_sent() {
+    let mut server = LocalServer::start();
+    let (mut client, _) = server.connect();
+    let entity = server.spawn(Position::new(0, 0));
+
+    // the spawn is written with the old value, and changed before the Client
+    // can have acked it
+    server.update();
+    set_x(&mut server, &entity, 5);
+
+    run_until(|| {
+        server.update();
+        client.update();
+        !client.client.entities(&client.world.proxy()).is_empty() && client_x(&client) == Some(5)
+    });
+}
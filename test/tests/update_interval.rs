@@ -0,0 +1,127 @@
+use std::time::{Duration, Instant};
+
+use naia_client::Event as ClientEvent;
+use naia_demo_world::Entity;
+use naia_shared::WorldRefType;
+use naia_test::{run_until, spawn_on_client, LocalClient, LocalServer, Position};
+
+// Moves the Entity every update for the given duration, counting the updates
+// which reach the Client
+fn count_updates(
+    server: &mut LocalServer,
+    client: &mut LocalClient,
+    entity: &Entity,
+    duration: Duration,
+) -> usize {
+    let mut update_count = 0;
+    let start = Instant::now();
+    run_until(|| {
+        if let Some(mut position) = server
+            .server
+            .entity_mut(server.world.proxy_mut(), entity)
+            .component::<Position>()
+        {
+            *position.x += 1;
+        }
+        server.update();
+        for event in client.update() {
+            if let Ok(ClientEvent::UpdateComponent(..)) = event {
+                update_count += 1;
+            }
+        }
+        start.elapsed() >= duration
+    });
+    update_count
+}
+
+#[test]
+fn kind_update_interval_throttles_updates() {
+    let mut server = LocalServer::start();
+    let (mut client, _) = server.connect();
+    let entity = spawn_on_client(&mut server, &mut client);
+
+    let unthrottled = count_updates(
+        &mut server,
+        &mut client,
+        &entity,
+        Duration::from_millis(300),
+    );
+    assert!(unthrottled >= 5);
+
+    server
+        .server
+        .set_component_update_interval::<Position>(Some(Duration::from_secs(1)));
+    // let any update already in flight arrive
+    count_updates(
+        &mut server,
+        &mut client,
+        &entity,
+        Duration::from_millis(100),
+    );
+
+    let throttled = count_updates(
+        &mut server,
+        &mut client,
+        &entity,
+        Duration::from_millis(300),
+    );
+    assert!(throttled <= 1);
+
+    // clearing the interval sends updates as soon as possible again
+    server
+        .server
+        .set_component_update_interval::<Position>(None);
+    let cleared = count_updates(
+        &mut server,
+        &mut client,
+        &entity,
+        Duration::from_millis(300),
+    );
+    assert!(cleared >= 5);
+}
+
+#[test]
+fn throttled_changes_are_accumulated() {
+    let mut server = LocalServer::start();
+    let (mut client, _) = server.connect();
+    let entity = spawn_on_client(&mut server, &mut client);
+
+    server
+        .server
+        .entity_mut(server.world.proxy_mut(), &entity)
+        .set_component_update_interval::<Position>(Some(Duration::from_millis(200)));
+    count_updates(
+        &mut server,
+        &mut client,
+        &entity,
+        Duration::from_millis(300),
+    );
+
+    // the latest value arrives once the interval has elapsed
+    let server_world = server.world.proxy();
+    let server_x = *server_world.component::<Position>(&entity).unwrap().x;
+    let client_entity = client.client.entities(&client.world.proxy())[0];
+    run_until(|| {
+        server.update();
+        client.update();
+        let client_world = client.world.proxy();
+        let client_x = *client_world
+            .component::<Position>(&client_entity)
+            .unwrap()
+            .x;
+        client_x == server_x
+    });
+
+    // clearing the Entity's own interval falls back to the kind's interval
+    server
+        .server
+        .entity_mut(server.world.proxy_mut(), &entity)
+        .set_component_update_interval::<Position>(None);
+    let cleared = count_updates(
+        &mut server,
+        &mut client,
+        &entity,
+        Duration::from_millis(300),
+    );
+    assert!(cleared >= 5);
+}